@@ -20,4 +20,12 @@ reason: string | null,
 /**
  * When set, the agent is asking the user to allow writes under this root for the remainder of the session.
  */
-grant_root: string | null, };
+grant_root: string | null, 
+/**
+ * When set, `changes` were made by a command running in the copy-on-write
+ * workspace overlay rather than proposed by `apply_patch`. `Approved`
+ * copies them into the workspace and `Denied` discards them. When more
+ * than one file changed, `ReviewEachFile` asks for each file to be
+ * reviewed in its own request instead.
+ */
+overlay_review: boolean, };
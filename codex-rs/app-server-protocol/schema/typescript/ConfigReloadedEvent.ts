@@ -0,0 +1,28 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AskForApproval } from "./AskForApproval";
+import type { Personality } from "./Personality";
+import type { ReasoningEffort } from "./ReasoningEffort";
+import type { SandboxPolicy } from "./SandboxPolicy";
+
+export type ConfigReloadedEvent = { 
+/**
+ * Changed config keys that take effect on the next turn.
+ */
+applied: Array<string>, 
+/**
+ * Changed config keys that only take effect after a restart.
+ */
+requires_restart: Array<string>, 
+/**
+ * Changed config keys that would loosen the approval policy or the
+ * sandbox. They are not applied; the user has to select the looser mode
+ * explicitly.
+ */
+needs_confirmation: Array<string>, 
+/**
+ * Settings the session uses from the next turn on. Clients that send
+ * them with every turn should adopt these values.
+ */
+model: string, reasoning_effort: ReasoningEffort | null, personality: Personality | null, approval_policy: AskForApproval, sandbox_policy: SandboxPolicy, };
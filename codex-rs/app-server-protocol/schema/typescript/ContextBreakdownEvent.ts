@@ -0,0 +1,23 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContextCategoryUsage } from "./ContextCategoryUsage";
+import type { ContextItemUsage } from "./ContextItemUsage";
+
+/**
+ * Response payload for `Op::GetContextBreakdown`.
+ */
+export type ContextBreakdownEvent = { 
+/**
+ * Estimated tokens for everything sent with the next request.
+ */
+total_tokens: number, model_context_window: number | null, 
+/**
+ * Token totals per category, largest first. Tool outputs are reported
+ * once per tool.
+ */
+categories: Array<ContextCategoryUsage>, 
+/**
+ * The largest individual items in the history, largest first.
+ */
+largest_items: Array<ContextItemUsage>, };
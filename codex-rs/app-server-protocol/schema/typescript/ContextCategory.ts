@@ -0,0 +1,5 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ContextCategory = "base_instructions" | "project_docs" | "skills" | "developer_context" | "user_messages" | "assistant_messages" | "reasoning" | "tool_calls" | "tool_outputs" | "images" | "compaction";
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContextCategory } from "./ContextCategory";
+
+export type ContextCategoryUsage = { category: ContextCategory, 
+/**
+ * Tool name, for `tool_outputs` entries.
+ */
+tool_name: string | null, tokens: number, item_count: number, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ContextItemsDroppedEvent = { 
+/**
+ * Ids of the items that were replaced with placeholders.
+ */
+item_ids: Array<string>, 
+/**
+ * Estimated tokens freed by the replacement.
+ */
+tokens_freed: number, };
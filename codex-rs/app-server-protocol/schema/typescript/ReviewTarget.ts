@@ -6,4 +6,9 @@ export type ReviewTarget = { "type": "uncommittedChanges" } | { "type": "baseBra
 /**
  * Optional human-readable label (e.g., commit subject) for UIs.
  */
-title: string | null, } | { "type": "custom", instructions: string, };
+title: string | null, } | { "type": "commitRange", base: string, head: string, } | { "type": "paths", paths: Array<string>, 
+/**
+ * Branch or commit to compare against. When omitted, uncommitted
+ * changes in the working tree are reviewed.
+ */
+base: string | null, } | { "type": "patchFile", path: string, } | { "type": "custom", instructions: string, };
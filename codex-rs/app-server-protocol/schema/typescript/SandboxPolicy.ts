@@ -32,4 +32,9 @@ exclude_tmpdir_env_var: boolean,
  * When set to `true`, will NOT include the `/tmp` among the default
  * writable roots on UNIX. Defaults to `false`.
  */
-exclude_slash_tmp: boolean, };
+exclude_slash_tmp: boolean, } | { "type": "workspace-overlay", 
+/**
+ * When set to `true`, outbound network access is allowed. `false` by
+ * default.
+ */
+network_access: boolean, };
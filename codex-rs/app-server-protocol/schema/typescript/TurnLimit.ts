@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A configured limit that can stop a turn before the model is done.
+ */
+export type TurnLimit = "max_turns" | "max_tool_calls" | "timeout" | "max_consecutive_failures";
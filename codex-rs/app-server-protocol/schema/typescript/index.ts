@@ -41,9 +41,15 @@ export type { CollabWaitingBeginEvent } from "./CollabWaitingBeginEvent";
 export type { CollabWaitingEndEvent } from "./CollabWaitingEndEvent";
 export type { CollaborationMode } from "./CollaborationMode";
 export type { CollaborationModeMask } from "./CollaborationModeMask";
+export type { ConfigReloadedEvent } from "./ConfigReloadedEvent";
 export type { ContentItem } from "./ContentItem";
+export type { ContextBreakdownEvent } from "./ContextBreakdownEvent";
+export type { ContextCategory } from "./ContextCategory";
+export type { ContextCategoryUsage } from "./ContextCategoryUsage";
 export type { ContextCompactedEvent } from "./ContextCompactedEvent";
 export type { ContextCompactionItem } from "./ContextCompactionItem";
+export type { ContextItemUsage } from "./ContextItemUsage";
+export type { ContextItemsDroppedEvent } from "./ContextItemsDroppedEvent";
 export type { ConversationGitInfo } from "./ConversationGitInfo";
 export type { ConversationSummary } from "./ConversationSummary";
 export type { CreditsSnapshot } from "./CreditsSnapshot";
@@ -202,6 +208,7 @@ export type { TurnAbortedEvent } from "./TurnAbortedEvent";
 export type { TurnCompleteEvent } from "./TurnCompleteEvent";
 export type { TurnDiffEvent } from "./TurnDiffEvent";
 export type { TurnItem } from "./TurnItem";
+export type { TurnLimit } from "./TurnLimit";
 export type { TurnStartedEvent } from "./TurnStartedEvent";
 export type { UndoCompletedEvent } from "./UndoCompletedEvent";
 export type { UndoStartedEvent } from "./UndoStartedEvent";
@@ -1,6 +1,12 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AbsolutePathBuf } from "../AbsolutePathBuf";
 import type { ConfigLayerSource } from "./ConfigLayerSource";
 
-export type ConfigLayerMetadata = { name: ConfigLayerSource, version: string, };
+export type ConfigLayerMetadata = { name: ConfigLayerSource, version: string, 
+/**
+ * File that supplied the value when it came from a file pulled in via
+ * `include` rather than from the layer's own config file.
+ */
+file: AbsolutePathBuf | null, };
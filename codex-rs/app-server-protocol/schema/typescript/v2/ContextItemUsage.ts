@@ -0,0 +1,15 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContextCategory } from "./ContextCategory";
+
+export type ContextItemUsage = { 
+/**
+ * Identifier accepted by `thread/context/drop`; only set for tool outputs
+ * and images.
+ */
+id: string | null, category: ContextCategory, toolName: string | null, 
+/**
+ * Short single-line preview of the item.
+ */
+preview: string, tokens: number, };
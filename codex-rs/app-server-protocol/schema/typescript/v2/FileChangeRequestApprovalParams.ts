@@ -11,4 +11,11 @@ reason?: string | null,
  * [UNSTABLE] When set, the agent is asking the user to allow writes under this root
  * for the remainder of the session (unclear if this is honored today).
  */
-grantRoot?: string | null, };
+grantRoot?: string | null, 
+/**
+ * When set, the changes were made by a command running in the copy-on-write
+ * workspace overlay. `accept` copies them into the workspace and `decline`
+ * discards them. When more than one file changed, `reviewEachFile` asks
+ * for each file to be reviewed in its own request instead.
+ */
+overlayReview: boolean, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadContextDropParams = { threadId: string, 
+/**
+ * Ids from `ThreadContextReadResponse.largestItems`. Tool outputs and
+ * images are replaced with a short placeholder; unknown ids are ignored.
+ */
+itemIds: Array<string>, };
@@ -0,0 +1,20 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ContextCategoryUsage } from "./ContextCategoryUsage";
+import type { ContextItemUsage } from "./ContextItemUsage";
+
+export type ThreadContextReadResponse = { 
+/**
+ * Estimated tokens for everything sent with the next request.
+ */
+totalTokens: number, modelContextWindow: number | null, 
+/**
+ * Token totals per category, largest first. Tool outputs are reported
+ * once per tool.
+ */
+categories: Array<ContextCategoryUsage>, 
+/**
+ * The largest individual items in the thread's context, largest first.
+ */
+largestItems: Array<ContextItemUsage>, };
@@ -0,0 +1,31 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadSourceKind } from "./ThreadSourceKind";
+
+export type ThreadSearchParams = { 
+/**
+ * Free-text query. Every term must appear in the same message, command or
+ * file path of a session; terms shorter than three characters are matched
+ * as plain substrings.
+ */
+query: string, 
+/**
+ * Optional maximum number of results; defaults to a reasonable server-side value.
+ */
+limit?: number | null, 
+/**
+ * Optional provider filter; when set, only sessions recorded under these
+ * providers are returned. When present but empty, includes all providers.
+ */
+modelProviders?: Array<string> | null, 
+/**
+ * Optional source filter; when set, only sessions from these source kinds
+ * are returned. When omitted or empty, defaults to interactive sources.
+ */
+sourceKinds?: Array<ThreadSourceKind> | null, 
+/**
+ * Optional archived filter; when set to true, only archived threads are searched.
+ * If false or null, only non-archived threads are searched.
+ */
+archived?: boolean | null, };
@@ -0,0 +1,20 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ByteRange } from "./ByteRange";
+import type { Thread } from "./Thread";
+import type { ThreadSearchMatchKind } from "./ThreadSearchMatchKind";
+
+export type ThreadSearchResult = { thread: Thread, 
+/**
+ * What the best match in this thread was found in.
+ */
+matchKind: ThreadSearchMatchKind, 
+/**
+ * Excerpt around the best match.
+ */
+snippet: string, 
+/**
+ * Byte ranges of the matched text within `snippet`.
+ */
+highlights: Array<ByteRange>, };
@@ -43,7 +43,10 @@ export type { ConfigRequirementsReadResponse } from "./ConfigRequirementsReadRes
 export type { ConfigValueWriteParams } from "./ConfigValueWriteParams";
 export type { ConfigWarningNotification } from "./ConfigWarningNotification";
 export type { ConfigWriteResponse } from "./ConfigWriteResponse";
+export type { ContextCategory } from "./ContextCategory";
+export type { ContextCategoryUsage } from "./ContextCategoryUsage";
 export type { ContextCompactedNotification } from "./ContextCompactedNotification";
+export type { ContextItemUsage } from "./ContextItemUsage";
 export type { CreditsSnapshot } from "./CreditsSnapshot";
 export type { DeprecationNoticeNotification } from "./DeprecationNoticeNotification";
 export type { DynamicToolCallOutputContentItem } from "./DynamicToolCallOutputContentItem";
@@ -135,6 +138,10 @@ export type { ThreadArchiveParams } from "./ThreadArchiveParams";
 export type { ThreadArchiveResponse } from "./ThreadArchiveResponse";
 export type { ThreadCompactStartParams } from "./ThreadCompactStartParams";
 export type { ThreadCompactStartResponse } from "./ThreadCompactStartResponse";
+export type { ThreadContextDropParams } from "./ThreadContextDropParams";
+export type { ThreadContextDropResponse } from "./ThreadContextDropResponse";
+export type { ThreadContextReadParams } from "./ThreadContextReadParams";
+export type { ThreadContextReadResponse } from "./ThreadContextReadResponse";
 export type { ThreadForkParams } from "./ThreadForkParams";
 export type { ThreadForkResponse } from "./ThreadForkResponse";
 export type { ThreadItem } from "./ThreadItem";
@@ -149,6 +156,10 @@ export type { ThreadResumeParams } from "./ThreadResumeParams";
 export type { ThreadResumeResponse } from "./ThreadResumeResponse";
 export type { ThreadRollbackParams } from "./ThreadRollbackParams";
 export type { ThreadRollbackResponse } from "./ThreadRollbackResponse";
+export type { ThreadSearchMatchKind } from "./ThreadSearchMatchKind";
+export type { ThreadSearchParams } from "./ThreadSearchParams";
+export type { ThreadSearchResponse } from "./ThreadSearchResponse";
+export type { ThreadSearchResult } from "./ThreadSearchResult";
 export type { ThreadSetNameParams } from "./ThreadSetNameParams";
 export type { ThreadSetNameResponse } from "./ThreadSetNameResponse";
 export type { ThreadSortKey } from "./ThreadSortKey";
@@ -173,6 +184,7 @@ export type { TurnDiffUpdatedNotification } from "./TurnDiffUpdatedNotification"
 export type { TurnError } from "./TurnError";
 export type { TurnInterruptParams } from "./TurnInterruptParams";
 export type { TurnInterruptResponse } from "./TurnInterruptResponse";
+export type { TurnLimit } from "./TurnLimit";
 export type { TurnPlanStep } from "./TurnPlanStep";
 export type { TurnPlanStepStatus } from "./TurnPlanStepStatus";
 export type { TurnPlanUpdatedNotification } from "./TurnPlanUpdatedNotification";
@@ -216,10 +216,22 @@ client_request_definitions! {
         params: v2::ThreadRollbackParams,
         response: v2::ThreadRollbackResponse,
     },
+    ThreadContextRead => "thread/context/read" {
+        params: v2::ThreadContextReadParams,
+        response: v2::ThreadContextReadResponse,
+    },
+    ThreadContextDrop => "thread/context/drop" {
+        params: v2::ThreadContextDropParams,
+        response: v2::ThreadContextDropResponse,
+    },
     ThreadList => "thread/list" {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
     },
+    ThreadSearch => "thread/search" {
+        params: v2::ThreadSearchParams,
+        response: v2::ThreadSearchResponse,
+    },
     ThreadLoadedList => "thread/loaded/list" {
         params: v2::ThreadLoadedListParams,
         response: v2::ThreadLoadedListResponse,
@@ -120,9 +120,22 @@ pub enum CodexErrorInfo {
         #[ts(rename = "httpStatusCode")]
         http_status_code: Option<u16>,
     },
+    /// The turn was stopped by one of the configured turn limits.
+    TurnLimitReached {
+        limit: TurnLimit,
+    },
     Other,
 }
 
+v2_enum_from_core!(
+    pub enum TurnLimit from codex_protocol::protocol::TurnLimit {
+        MaxTurns,
+        MaxToolCalls,
+        Timeout,
+        MaxConsecutiveFailures
+    }
+);
+
 impl From<CoreCodexErrorInfo> for CodexErrorInfo {
     fn from(value: CoreCodexErrorInfo) -> Self {
         match value {
@@ -152,6 +165,9 @@ impl From<CoreCodexErrorInfo> for CodexErrorInfo {
             CoreCodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code } => {
                 CodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code }
             }
+            CoreCodexErrorInfo::TurnLimitReached { limit } => CodexErrorInfo::TurnLimitReached {
+                limit: limit.into(),
+            },
             CoreCodexErrorInfo::Other => CodexErrorInfo::Other,
         }
     }
@@ -197,6 +213,7 @@ impl From<CoreAskForApproval> for AskForApproval {
 pub enum SandboxMode {
     ReadOnly,
     WorkspaceWrite,
+    WorkspaceOverlay,
     DangerFullAccess,
 }
 
@@ -205,6 +222,7 @@ impl SandboxMode {
         match self {
             SandboxMode::ReadOnly => CoreSandboxMode::ReadOnly,
             SandboxMode::WorkspaceWrite => CoreSandboxMode::WorkspaceWrite,
+            SandboxMode::WorkspaceOverlay => CoreSandboxMode::WorkspaceOverlay,
             SandboxMode::DangerFullAccess => CoreSandboxMode::DangerFullAccess,
         }
     }
@@ -215,6 +233,7 @@ impl From<CoreSandboxMode> for SandboxMode {
         match value {
             CoreSandboxMode::ReadOnly => SandboxMode::ReadOnly,
             CoreSandboxMode::WorkspaceWrite => SandboxMode::WorkspaceWrite,
+            CoreSandboxMode::WorkspaceOverlay => SandboxMode::WorkspaceOverlay,
             CoreSandboxMode::DangerFullAccess => SandboxMode::DangerFullAccess,
         }
     }
@@ -410,6 +429,10 @@ pub struct Config {
 pub struct ConfigLayerMetadata {
     pub name: ConfigLayerSource,
     pub version: String,
+    /// File that supplied the value when it came from a file pulled in via
+    /// `include` rather than from the layer's own config file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<AbsolutePathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -580,6 +603,8 @@ pub enum FileChangeApprovalDecision {
     Accept,
     /// User approved the file changes and future changes to the same files should run without prompting.
     AcceptForSession,
+    /// Only for overlay reviews of several files: review each file in its own request.
+    ReviewEachFile,
     /// User denied the file changes. The agent will continue the turn.
     Decline,
     /// User denied the file changes. The turn will also be immediately interrupted.
@@ -620,6 +645,12 @@ pub enum SandboxPolicy {
         #[serde(default)]
         exclude_slash_tmp: bool,
     },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    WorkspaceOverlay {
+        #[serde(default)]
+        network_access: bool,
+    },
 }
 
 impl SandboxPolicy {
@@ -648,6 +679,11 @@ impl SandboxPolicy {
                 exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                 exclude_slash_tmp: *exclude_slash_tmp,
             },
+            SandboxPolicy::WorkspaceOverlay { network_access } => {
+                codex_protocol::protocol::SandboxPolicy::WorkspaceOverlay {
+                    network_access: *network_access,
+                }
+            }
         }
     }
 }
@@ -678,6 +714,9 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
             },
+            codex_protocol::protocol::SandboxPolicy::WorkspaceOverlay { network_access } => {
+                SandboxPolicy::WorkspaceOverlay { network_access }
+            }
         }
     }
 }
@@ -1505,6 +1544,126 @@ pub struct ThreadRollbackResponse {
     pub thread: Thread,
 }
 
+v2_enum_from_core!(
+    pub enum ContextCategory from codex_protocol::protocol::ContextCategory {
+        BaseInstructions,
+        ProjectDocs,
+        Skills,
+        DeveloperContext,
+        UserMessages,
+        AssistantMessages,
+        Reasoning,
+        ToolCalls,
+        ToolOutputs,
+        Images,
+        Compaction
+    }
+);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadContextReadParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadContextReadResponse {
+    /// Estimated tokens for everything sent with the next request.
+    #[ts(type = "number")]
+    pub total_tokens: i64,
+    #[ts(type = "number | null")]
+    pub model_context_window: Option<i64>,
+    /// Token totals per category, largest first. Tool outputs are reported
+    /// once per tool.
+    pub categories: Vec<ContextCategoryUsage>,
+    /// The largest individual items in the thread's context, largest first.
+    pub largest_items: Vec<ContextItemUsage>,
+}
+
+impl From<codex_protocol::protocol::ContextBreakdownEvent> for ThreadContextReadResponse {
+    fn from(value: codex_protocol::protocol::ContextBreakdownEvent) -> Self {
+        Self {
+            total_tokens: value.total_tokens,
+            model_context_window: value.model_context_window,
+            categories: value.categories.into_iter().map(Into::into).collect(),
+            largest_items: value.largest_items.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ContextCategoryUsage {
+    pub category: ContextCategory,
+    /// Tool name, for `toolOutputs` entries.
+    pub tool_name: Option<String>,
+    #[ts(type = "number")]
+    pub tokens: i64,
+    pub item_count: u32,
+}
+
+impl From<codex_protocol::protocol::ContextCategoryUsage> for ContextCategoryUsage {
+    fn from(value: codex_protocol::protocol::ContextCategoryUsage) -> Self {
+        Self {
+            category: value.category.into(),
+            tool_name: value.tool_name,
+            tokens: value.tokens,
+            item_count: value.item_count,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ContextItemUsage {
+    /// Identifier accepted by `thread/context/drop`; only set for tool outputs
+    /// and images.
+    pub id: Option<String>,
+    pub category: ContextCategory,
+    pub tool_name: Option<String>,
+    /// Short single-line preview of the item.
+    pub preview: String,
+    #[ts(type = "number")]
+    pub tokens: i64,
+}
+
+impl From<codex_protocol::protocol::ContextItemUsage> for ContextItemUsage {
+    fn from(value: codex_protocol::protocol::ContextItemUsage) -> Self {
+        Self {
+            id: value.id,
+            category: value.category.into(),
+            tool_name: value.tool_name,
+            preview: value.preview,
+            tokens: value.tokens,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadContextDropParams {
+    pub thread_id: String,
+    /// Ids from `ThreadContextReadResponse.largestItems`. Tool outputs and
+    /// images are replaced with a short placeholder; unknown ids are ignored.
+    pub item_ids: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadContextDropResponse {
+    /// Ids that were replaced.
+    pub item_ids: Vec<String>,
+    #[ts(type = "number")]
+    pub tokens_freed: i64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1568,6 +1727,62 @@ pub struct ThreadListResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchParams {
+    /// Free-text query. Every term must appear in the same message, command or
+    /// file path of a session; terms shorter than three characters are matched
+    /// as plain substrings.
+    pub query: String,
+    /// Optional maximum number of results; defaults to a reasonable server-side value.
+    #[ts(optional = nullable)]
+    pub limit: Option<u32>,
+    /// Optional provider filter; when set, only sessions recorded under these
+    /// providers are returned. When present but empty, includes all providers.
+    #[ts(optional = nullable)]
+    pub model_providers: Option<Vec<String>>,
+    /// Optional source filter; when set, only sessions from these source kinds
+    /// are returned. When omitted or empty, defaults to interactive sources.
+    #[ts(optional = nullable)]
+    pub source_kinds: Option<Vec<ThreadSourceKind>>,
+    /// Optional archived filter; when set to true, only archived threads are searched.
+    /// If false or null, only non-archived threads are searched.
+    #[ts(optional = nullable)]
+    pub archived: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResponse {
+    /// Matching threads, best match first.
+    pub data: Vec<ThreadSearchResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResult {
+    pub thread: Thread,
+    /// What the best match in this thread was found in.
+    pub match_kind: ThreadSearchMatchKind,
+    /// Excerpt around the best match.
+    pub snippet: String,
+    /// Byte ranges of the matched text within `snippet`.
+    pub highlights: Vec<ByteRange>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum ThreadSearchMatchKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    FilePath,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2079,6 +2294,26 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the combined changes of a commit range, as in `git diff base..head`.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { base: String, head: String },
+
+    /// Review only the changes to the given repository-relative paths.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Paths {
+        paths: Vec<String>,
+        /// Branch or commit to compare against. When omitted, uncommitted
+        /// changes in the working tree are reviewed.
+        base: Option<String>,
+    },
+
+    /// Review a patch file, such as one received by email, without applying it.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
     /// Arbitrary instructions, equivalent to the old free-form prompt.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -2882,6 +3117,12 @@ pub struct FileChangeRequestApprovalParams {
     /// for the remainder of the session (unclear if this is honored today).
     #[ts(optional = nullable)]
     pub grant_root: Option<PathBuf>,
+    /// When set, the changes were made by a command running in the copy-on-write
+    /// workspace overlay. `accept` copies them into the workspace and `decline`
+    /// discards them. When more than one file changed, `reviewEachFile` asks
+    /// for each file to be reviewed in its own request instead.
+    #[serde(default)]
+    pub overlay_review: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
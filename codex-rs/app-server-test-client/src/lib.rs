@@ -981,6 +981,7 @@ impl CodexClient {
             item_id,
             reason,
             grant_root,
+            overlay_review,
         } = params;
 
         println!(
@@ -992,6 +993,9 @@ impl CodexClient {
         if let Some(grant_root) = grant_root.as_deref() {
             println!("< grant root: {}", grant_root.display());
         }
+        if overlay_review {
+            println!("< changes made in the workspace overlay");
+        }
 
         let response = FileChangeRequestApprovalResponse {
             decision: FileChangeApprovalDecision::Accept,
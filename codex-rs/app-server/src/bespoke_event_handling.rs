@@ -136,6 +136,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             changes,
             reason,
             grant_root,
+            overlay_review,
         }) => match api_version {
             ApiVersion::V1 => {
                 let params = ApplyPatchApprovalParams {
@@ -185,6 +186,7 @@ pub(crate) async fn apply_bespoke_event_handling(
                     item_id: item_id.clone(),
                     reason,
                     grant_root,
+                    overlay_review,
                 };
                 let rx = outgoing
                     .send_request(ServerRequestPayload::FileChangeRequestApproval(params))
@@ -1610,6 +1612,7 @@ fn map_file_change_approval_decision(
     match decision {
         FileChangeApprovalDecision::Accept => (ReviewDecision::Approved, None),
         FileChangeApprovalDecision::AcceptForSession => (ReviewDecision::ApprovedForSession, None),
+        FileChangeApprovalDecision::ReviewEachFile => (ReviewDecision::ReviewEachFile, None),
         FileChangeApprovalDecision::Decline => {
             (ReviewDecision::Denied, Some(PatchApplyStatus::Declined))
         }
@@ -22,6 +22,7 @@ use codex_app_server_protocol::ArchiveConversationResponse;
 use codex_app_server_protocol::AskForApproval;
 use codex_app_server_protocol::AuthMode;
 use codex_app_server_protocol::AuthStatusChangeNotification;
+use codex_app_server_protocol::ByteRange;
 use codex_app_server_protocol::CancelLoginAccountParams;
 use codex_app_server_protocol::CancelLoginAccountResponse;
 use codex_app_server_protocol::CancelLoginAccountStatus;
@@ -111,6 +112,10 @@ use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadArchiveResponse;
 use codex_app_server_protocol::ThreadCompactStartParams;
 use codex_app_server_protocol::ThreadCompactStartResponse;
+use codex_app_server_protocol::ThreadContextDropParams;
+use codex_app_server_protocol::ThreadContextDropResponse;
+use codex_app_server_protocol::ThreadContextReadParams;
+use codex_app_server_protocol::ThreadContextReadResponse;
 use codex_app_server_protocol::ThreadForkParams;
 use codex_app_server_protocol::ThreadForkResponse;
 use codex_app_server_protocol::ThreadItem;
@@ -123,6 +128,10 @@ use codex_app_server_protocol::ThreadReadResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchMatchKind;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use codex_app_server_protocol::ThreadSearchResult;
 use codex_app_server_protocol::ThreadSetNameParams;
 use codex_app_server_protocol::ThreadSetNameResponse;
 use codex_app_server_protocol::ThreadSortKey;
@@ -197,8 +206,10 @@ use codex_core::rollout_date_parts;
 use codex_core::sandboxing::SandboxPermissions;
 use codex_core::skills::remote::download_remote_skill;
 use codex_core::skills::remote::list_remote_skills;
+use codex_core::state_db::SearchDocumentKind;
 use codex_core::state_db::StateDbHandle;
 use codex_core::state_db::open_if_present;
+use codex_core::state_db::search_threads_db;
 use codex_core::token_data::parse_id_token;
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_feedback::CodexFeedback;
@@ -427,6 +438,34 @@ impl CodexMessageProcessor {
                     .filter(|t| !t.is_empty());
                 ApiReviewTarget::Commit { sha, title }
             }
+            ApiReviewTarget::CommitRange { base, head } => {
+                let base = base.trim().to_string();
+                let head = head.trim().to_string();
+                if base.is_empty() || head.is_empty() {
+                    return Err(invalid_request(
+                        "base and head must not be empty".to_string(),
+                    ));
+                }
+                ApiReviewTarget::CommitRange { base, head }
+            }
+            ApiReviewTarget::Paths { paths, base } => {
+                let paths: Vec<String> = paths
+                    .iter()
+                    .map(|path| path.trim().to_string())
+                    .filter(|path| !path.is_empty())
+                    .collect();
+                if paths.is_empty() {
+                    return Err(invalid_request("paths must not be empty".to_string()));
+                }
+                let base = base.map(|b| b.trim().to_string()).filter(|b| !b.is_empty());
+                ApiReviewTarget::Paths { paths, base }
+            }
+            ApiReviewTarget::PatchFile { path } => {
+                if path.as_os_str().is_empty() {
+                    return Err(invalid_request("path must not be empty".to_string()));
+                }
+                ApiReviewTarget::PatchFile { path }
+            }
             ApiReviewTarget::Custom { instructions } => {
                 let trimmed = instructions.trim().to_string();
                 if trimmed.is_empty() {
@@ -444,6 +483,11 @@ impl CodexMessageProcessor {
             ApiReviewTarget::UncommittedChanges => CoreReviewTarget::UncommittedChanges,
             ApiReviewTarget::BaseBranch { branch } => CoreReviewTarget::BaseBranch { branch },
             ApiReviewTarget::Commit { sha, title } => CoreReviewTarget::Commit { sha, title },
+            ApiReviewTarget::CommitRange { base, head } => {
+                CoreReviewTarget::CommitRange { base, head }
+            }
+            ApiReviewTarget::Paths { paths, base } => CoreReviewTarget::Paths { paths, base },
+            ApiReviewTarget::PatchFile { path } => CoreReviewTarget::PatchFile { path },
             ApiReviewTarget::Custom { instructions } => CoreReviewTarget::Custom { instructions },
         };
 
@@ -499,10 +543,22 @@ impl CodexMessageProcessor {
                 self.thread_rollback(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadContextRead { request_id, params } => {
+                self.thread_context_read(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadContextDrop { request_id, params } => {
+                self.thread_context_drop(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::ThreadList { request_id, params } => {
                 self.thread_list(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadSearch { request_id, params } => {
+                self.thread_search(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::ThreadLoadedList { request_id, params } => {
                 self.thread_loaded_list(to_connection_request_id(request_id), params)
                     .await;
@@ -1610,6 +1666,7 @@ impl CodexMessageProcessor {
             windows_sandbox_level,
             justification: None,
             arg0: None,
+            stdin: None,
         };
 
         let requested_policy = params.sandbox_policy.map(|policy| policy.to_core());
@@ -2298,6 +2355,62 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn thread_context_read(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadContextReadParams,
+    ) {
+        let ThreadContextReadParams { thread_id } = params;
+
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let response = ThreadContextReadResponse::from(thread.context_breakdown().await);
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    async fn thread_context_drop(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadContextDropParams,
+    ) {
+        let ThreadContextDropParams {
+            thread_id,
+            item_ids,
+        } = params;
+
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        match thread.drop_context_items(&item_ids).await {
+            Ok(dropped) => {
+                self.outgoing
+                    .send_response(
+                        request_id,
+                        ThreadContextDropResponse {
+                            item_ids: dropped.item_ids,
+                            tokens_freed: dropped.tokens_freed,
+                        },
+                    )
+                    .await;
+            }
+            Err(err) => {
+                self.send_invalid_request_error(request_id, err.to_string())
+                    .await;
+            }
+        }
+    }
+
     async fn thread_list(&self, request_id: ConnectionRequestId, params: ThreadListParams) {
         let ThreadListParams {
             cursor,
@@ -2339,6 +2452,116 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn thread_search(&self, request_id: ConnectionRequestId, params: ThreadSearchParams) {
+        let ThreadSearchParams {
+            query,
+            limit,
+            model_providers,
+            source_kinds,
+            archived,
+        } = params;
+
+        if query.trim().is_empty() {
+            self.send_invalid_request_error(request_id, "query must not be empty".to_string())
+                .await;
+            return;
+        }
+        let limit = limit
+            .map(|value| value as usize)
+            .unwrap_or(THREAD_LIST_DEFAULT_LIMIT)
+            .clamp(1, THREAD_LIST_MAX_LIMIT);
+        let model_provider_filter = match model_providers {
+            Some(providers) if providers.is_empty() => None,
+            Some(providers) => Some(providers),
+            None => Some(vec![self.config.model_provider_id.clone()]),
+        };
+        let (allowed_sources, source_kind_filter) = compute_source_filters(source_kinds);
+        // Source kinds without a stored equivalent are filtered after the query,
+        // so fetch extra candidates to fill the page.
+        let fetch_limit = if source_kind_filter.is_some() {
+            THREAD_LIST_MAX_LIMIT
+        } else {
+            limit
+        };
+
+        let state_db_ctx = open_if_present(
+            &self.config.codex_home,
+            self.config.model_provider_id.as_str(),
+        )
+        .await;
+        let Some(hits) = search_threads_db(
+            state_db_ctx.as_deref(),
+            query.as_str(),
+            fetch_limit,
+            allowed_sources.as_slice(),
+            model_provider_filter.as_deref(),
+            archived.unwrap_or(false),
+        )
+        .await
+        else {
+            let error = JSONRPCErrorError {
+                code: INTERNAL_ERROR_CODE,
+                message: "session search index is not available".to_string(),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        };
+
+        let data = hits
+            .into_iter()
+            .filter_map(|hit| {
+                let metadata = hit.thread;
+                let summary = summary_from_state_db_metadata(
+                    metadata.id,
+                    metadata.rollout_path,
+                    metadata.first_user_message,
+                    metadata
+                        .created_at
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                    metadata
+                        .updated_at
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                    metadata.model_provider,
+                    metadata.cwd,
+                    metadata.cli_version,
+                    metadata.source,
+                    metadata.git_sha,
+                    metadata.git_branch,
+                    metadata.git_origin_url,
+                );
+                if source_kind_filter
+                    .as_ref()
+                    .is_some_and(|filter| !source_kind_matches(&summary.source, filter))
+                {
+                    return None;
+                }
+                Some(ThreadSearchResult {
+                    thread: summary_to_thread(summary),
+                    match_kind: match hit.kind {
+                        SearchDocumentKind::UserMessage => ThreadSearchMatchKind::UserMessage,
+                        SearchDocumentKind::AgentMessage => ThreadSearchMatchKind::AgentMessage,
+                        SearchDocumentKind::Command => ThreadSearchMatchKind::Command,
+                        SearchDocumentKind::FilePath => ThreadSearchMatchKind::FilePath,
+                    },
+                    highlights: hit
+                        .snippet
+                        .highlights
+                        .into_iter()
+                        .map(|range| ByteRange {
+                            start: range.start,
+                            end: range.end,
+                        })
+                        .collect(),
+                    snippet: hit.snippet.text,
+                })
+            })
+            .take(limit)
+            .collect();
+        let response = ThreadSearchResponse { data };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
     async fn thread_loaded_list(
         &self,
         request_id: ConnectionRequestId,
@@ -125,6 +125,7 @@ fn map_sandbox_mode_requirement_to_api(mode: CoreSandboxModeRequirement) -> Opti
     match mode {
         CoreSandboxModeRequirement::ReadOnly => Some(SandboxMode::ReadOnly),
         CoreSandboxModeRequirement::WorkspaceWrite => Some(SandboxMode::WorkspaceWrite),
+        CoreSandboxModeRequirement::WorkspaceOverlay => Some(SandboxMode::WorkspaceOverlay),
         CoreSandboxModeRequirement::DangerFullAccess => Some(SandboxMode::DangerFullAccess),
         CoreSandboxModeRequirement::ExternalSandbox => None,
     }
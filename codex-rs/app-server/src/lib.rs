@@ -297,7 +297,7 @@ pub async fn run_main_with_transport(
 
     let feedback = CodexFeedback::new();
 
-    let otel = codex_core::otel_init::build_provider(
+    let otel = codex_core::otel_init::build_provider_with_prometheus(
         &config,
         env!("CARGO_PKG_VERSION"),
         Some("codex_app_server"),
@@ -52,12 +52,15 @@ use codex_app_server_protocol::ServerRequest;
 use codex_app_server_protocol::SetDefaultModelParams;
 use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadCompactStartParams;
+use codex_app_server_protocol::ThreadContextDropParams;
+use codex_app_server_protocol::ThreadContextReadParams;
 use codex_app_server_protocol::ThreadForkParams;
 use codex_app_server_protocol::ThreadListParams;
 use codex_app_server_protocol::ThreadLoadedListParams;
 use codex_app_server_protocol::ThreadReadParams;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchParams;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadUnarchiveParams;
 use codex_app_server_protocol::TurnInterruptParams;
@@ -439,6 +442,24 @@ impl McpProcess {
         self.send_request("thread/rollback", params).await
     }
 
+    /// Send a `thread/context/read` JSON-RPC request.
+    pub async fn send_thread_context_read_request(
+        &mut self,
+        params: ThreadContextReadParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/context/read", params).await
+    }
+
+    /// Send a `thread/context/drop` JSON-RPC request.
+    pub async fn send_thread_context_drop_request(
+        &mut self,
+        params: ThreadContextDropParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/context/drop", params).await
+    }
+
     /// Send a `thread/list` JSON-RPC request.
     pub async fn send_thread_list_request(
         &mut self,
@@ -448,6 +469,15 @@ impl McpProcess {
         self.send_request("thread/list", params).await
     }
 
+    /// Send a `thread/search` JSON-RPC request.
+    pub async fn send_thread_search_request(
+        &mut self,
+        params: ThreadSearchParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/search", params).await
+    }
+
     /// Send a `thread/loaded/list` JSON-RPC request.
     pub async fn send_thread_loaded_list_request(
         &mut self,
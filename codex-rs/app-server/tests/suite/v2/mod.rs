@@ -16,12 +16,14 @@ mod rate_limits;
 mod request_user_input;
 mod review;
 mod thread_archive;
+mod thread_context;
 mod thread_fork;
 mod thread_list;
 mod thread_loaded_list;
 mod thread_read;
 mod thread_resume;
 mod thread_rollback;
+mod thread_search;
 mod thread_start;
 mod thread_unarchive;
 mod turn_interrupt;
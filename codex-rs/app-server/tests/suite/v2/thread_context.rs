@@ -0,0 +1,149 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_final_assistant_message_sse_response;
+use app_test_support::create_mock_responses_server_sequence_unchecked;
+use app_test_support::to_response;
+use codex_app_server_protocol::ContextCategory;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadContextDropParams;
+use codex_app_server_protocol::ThreadContextDropResponse;
+use codex_app_server_protocol::ThreadContextReadParams;
+use codex_app_server_protocol::ThreadContextReadResponse;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn thread_context_read_reports_categories_after_turn() -> Result<()> {
+    let responses = vec![create_final_assistant_message_sse_response("Done")?];
+    let server = create_mock_responses_server_sequence_unchecked(responses).await;
+
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let start_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let start_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(start_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(start_resp)?;
+
+    let turn_id = mcp
+        .send_turn_start_request(TurnStartParams {
+            thread_id: thread.id.clone(),
+            input: vec![V2UserInput::Text {
+                text: "Hello".to_string(),
+                text_elements: Vec::new(),
+            }],
+            ..Default::default()
+        })
+        .await?;
+    let _turn_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(turn_id)),
+    )
+    .await??;
+    let _completed = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("turn/completed"),
+    )
+    .await??;
+
+    let read_id = mcp
+        .send_thread_context_read_request(ThreadContextReadParams {
+            thread_id: thread.id.clone(),
+        })
+        .await?;
+    let read_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(read_id)),
+    )
+    .await??;
+    let context = to_response::<ThreadContextReadResponse>(read_resp)?;
+
+    let categories = context
+        .categories
+        .iter()
+        .map(|usage| usage.category)
+        .collect::<Vec<_>>();
+    for expected in [
+        ContextCategory::BaseInstructions,
+        ContextCategory::UserMessages,
+        ContextCategory::AssistantMessages,
+    ] {
+        assert!(
+            categories.contains(&expected),
+            "missing {expected:?} in {categories:?}"
+        );
+    }
+    assert_eq!(
+        context
+            .categories
+            .iter()
+            .map(|usage| usage.tokens)
+            .sum::<i64>(),
+        context.total_tokens
+    );
+
+    // Messages cannot be dropped, and unknown ids are ignored.
+    let drop_id = mcp
+        .send_thread_context_drop_request(ThreadContextDropParams {
+            thread_id: thread.id,
+            item_ids: vec!["call-missing".to_string()],
+        })
+        .await?;
+    let drop_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(drop_id)),
+    )
+    .await??;
+    let dropped = to_response::<ThreadContextDropResponse>(drop_resp)?;
+    assert_eq!(
+        dropped,
+        ThreadContextDropResponse {
+            item_ids: Vec::new(),
+            tokens_freed: 0,
+        }
+    );
+
+    Ok(())
+}
+
+fn create_config_toml(codex_home: &std::path::Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "responses"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
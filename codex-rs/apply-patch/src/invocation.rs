@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::LazyLock;
 
+use codex_utils_text_encoding::bytes_to_string_smart;
 use tree_sitter::Parser;
 use tree_sitter::Query;
 use tree_sitter::QueryCursor;
@@ -168,8 +169,8 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         changes.insert(path, ApplyPatchFileChange::Add { content: contents });
                     }
                     Hunk::DeleteFile { .. } => {
-                        let content = match std::fs::read_to_string(&path) {
-                            Ok(content) => content,
+                        let content = match std::fs::read(&path) {
+                            Ok(bytes) => bytes_to_string_smart(&bytes),
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(
                                     ApplyPatchError::IoError(IoError {
@@ -810,4 +811,27 @@ PATCH"#,
             other => panic!("expected update change, got {other:?}"),
         }
     }
+
+    #[test]
+    fn test_git_diff_is_verified_like_an_envelope_patch() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("greeting.txt"), "hello\n").unwrap();
+
+        let diff = "diff --git a/greeting.txt b/greeting.txt\n--- a/greeting.txt\n+++ b/greeting.txt\n@@ -1 +1 @@\n-hello\n+goodbye\n";
+        let shell_script = format!("apply_patch <<'EOF'\n{diff}EOF");
+        let argv = vec!["bash".into(), "-lc".into(), shell_script];
+
+        let action = match maybe_parse_apply_patch_verified(&argv, dir.path()) {
+            MaybeApplyPatchVerified::Body(action) => action,
+            other => panic!("expected verified body, got {other:?}"),
+        };
+        assert_eq!(
+            action.changes().get(&dir.path().join("greeting.txt")),
+            Some(&ApplyPatchFileChange::Update {
+                unified_diff: "@@ -1 +1 @@\n-hello\n+goodbye\n".to_string(),
+                move_path: None,
+                new_content: "goodbye\n".to_string(),
+            })
+        );
+    }
 }
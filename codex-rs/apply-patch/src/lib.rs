@@ -2,12 +2,14 @@ mod invocation;
 mod parser;
 mod seek_sequence;
 mod standalone_executable;
+mod text_file;
+mod transaction;
+mod unified_diff;
 
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
-use anyhow::Context;
 use anyhow::Result;
 pub use parser::Hunk;
 pub use parser::ParseError;
@@ -34,6 +36,15 @@ pub enum ApplyPatchError {
     /// Error that occurs while computing replacements when applying patch chunks
     #[error("{0}")]
     ComputeReplacements(String),
+    /// The file to update is not text that can be written back unchanged.
+    #[error("Failed to read file to update {}: it looks like a binary file", .0.display())]
+    BinaryFile(PathBuf),
+    /// The patched contents contain characters the file's encoding cannot represent.
+    #[error("Failed to write file {}: the new contents cannot be represented in {encoding}", path.display())]
+    UnencodableContent {
+        path: PathBuf,
+        encoding: &'static str,
+    },
     /// A raw patch body was provided without an explicit `apply_patch` invocation.
     #[error(
         "patch detected without explicit call to apply_patch. Rerun as [\"apply_patch\", \"<patch>\"]"
@@ -266,67 +277,13 @@ pub struct AffectedPaths {
 }
 
 /// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
-/// Returns an error if the patch could not be applied.
+/// Returns an error if the patch could not be applied, in which case no file was changed.
 fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
     if hunks.is_empty() {
         anyhow::bail!("No files were modified.");
     }
 
-    let mut added: Vec<PathBuf> = Vec::new();
-    let mut modified: Vec<PathBuf> = Vec::new();
-    let mut deleted: Vec<PathBuf> = Vec::new();
-    for hunk in hunks {
-        match hunk {
-            Hunk::AddFile { path, contents } => {
-                if let Some(parent) = path.parent()
-                    && !parent.as_os_str().is_empty()
-                {
-                    std::fs::create_dir_all(parent).with_context(|| {
-                        format!("Failed to create parent directories for {}", path.display())
-                    })?;
-                }
-                std::fs::write(path, contents)
-                    .with_context(|| format!("Failed to write file {}", path.display()))?;
-                added.push(path.clone());
-            }
-            Hunk::DeleteFile { path } => {
-                std::fs::remove_file(path)
-                    .with_context(|| format!("Failed to delete file {}", path.display()))?;
-                deleted.push(path.clone());
-            }
-            Hunk::UpdateFile {
-                path,
-                move_path,
-                chunks,
-            } => {
-                let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
-                if let Some(dest) = move_path {
-                    if let Some(parent) = dest.parent()
-                        && !parent.as_os_str().is_empty()
-                    {
-                        std::fs::create_dir_all(parent).with_context(|| {
-                            format!("Failed to create parent directories for {}", dest.display())
-                        })?;
-                    }
-                    std::fs::write(dest, new_contents)
-                        .with_context(|| format!("Failed to write file {}", dest.display()))?;
-                    std::fs::remove_file(path)
-                        .with_context(|| format!("Failed to remove original {}", path.display()))?;
-                    modified.push(dest.clone());
-                } else {
-                    std::fs::write(path, new_contents)
-                        .with_context(|| format!("Failed to write file {}", path.display()))?;
-                    modified.push(path.clone());
-                }
-            }
-        }
-    }
-    Ok(AffectedPaths {
-        added,
-        modified,
-        deleted,
-    })
+    transaction::apply_hunks_atomically(hunks)
 }
 
 struct AppliedPatch {
@@ -340,16 +297,24 @@ fn derive_new_contents_from_chunks(
     path: &Path,
     chunks: &[UpdateFileChunk],
 ) -> std::result::Result<AppliedPatch, ApplyPatchError> {
-    let original_contents = match std::fs::read_to_string(path) {
-        Ok(contents) => contents,
-        Err(err) => {
-            return Err(ApplyPatchError::IoError(IoError {
-                context: format!("Failed to read file to update {}", path.display()),
-                source: err,
-            }));
-        }
-    };
+    let original_contents = text_file::read_file_to_update(path)?.contents;
+    let new_contents = apply_chunks_to_contents(&original_contents, path, chunks)?;
+    Ok(AppliedPatch {
+        original_contents,
+        new_contents,
+    })
+}
 
+/// Applies the chunks to `original_contents`, the current contents of `path`.
+fn apply_chunks_to_contents(
+    original_contents: &str,
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<String, ApplyPatchError> {
+    if chunks.is_empty() {
+        // A pure rename keeps the contents exactly as they are.
+        return Ok(original_contents.to_string());
+    }
     let mut original_lines: Vec<String> = original_contents.split('\n').map(String::from).collect();
 
     // Drop the trailing empty element that results from the final newline so
@@ -361,14 +326,47 @@ fn derive_new_contents_from_chunks(
     let replacements = compute_replacements(&original_lines, path, chunks)?;
     let new_lines = apply_replacements(original_lines, &replacements);
     let mut new_lines = new_lines;
-    if !new_lines.last().is_some_and(String::is_empty) {
+    if chunks
+        .last()
+        .is_some_and(|chunk| chunk.missing_final_newline)
+    {
+        if new_lines.last().is_some_and(String::is_empty) {
+            new_lines.pop();
+        }
+    } else if !new_lines.last().is_some_and(String::is_empty) {
         new_lines.push(String::new());
     }
-    let new_contents = new_lines.join("\n");
-    Ok(AppliedPatch {
-        original_contents,
-        new_contents,
-    })
+    Ok(new_lines.join("\n"))
+}
+
+/// Finds `pattern` at or after `start`. When the chunk carries the line it
+/// was generated against, the occurrence closest to that line wins, so a
+/// block that repeats in the file is still placed correctly after the file
+/// has drifted.
+fn seek_old_lines(
+    lines: &[String],
+    pattern: &[String],
+    start: usize,
+    chunk: &UpdateFileChunk,
+) -> Option<usize> {
+    let Some(hint) = chunk.original_line.map(|line| line.saturating_sub(1)) else {
+        return seek_sequence::seek_sequence(lines, pattern, start, chunk.is_end_of_file);
+    };
+    let mut best: Option<usize> = None;
+    let mut from = start;
+    while let Some(index) = seek_sequence::seek_sequence(lines, pattern, from, false) {
+        if index < from {
+            break;
+        }
+        if best.is_none_or(|best| index.abs_diff(hint) < best.abs_diff(hint)) {
+            best = Some(index);
+        }
+        if index >= hint {
+            break;
+        }
+        from = index + 1;
+    }
+    best
 }
 
 /// Compute a list of replacements needed to transform `original_lines` into the
@@ -426,8 +424,7 @@ fn compute_replacements(
         // located reliably.
 
         let mut pattern: &[String] = &chunk.old_lines;
-        let mut found =
-            seek_sequence::seek_sequence(original_lines, pattern, line_index, chunk.is_end_of_file);
+        let mut found = seek_old_lines(original_lines, pattern, line_index, chunk);
 
         let mut new_slice: &[String] = &chunk.new_lines;
 
@@ -439,12 +436,7 @@ fn compute_replacements(
                 new_slice = &new_slice[..new_slice.len() - 1];
             }
 
-            found = seek_sequence::seek_sequence(
-                original_lines,
-                pattern,
-                line_index,
-                chunk.is_end_of_file,
-            );
+            found = seek_old_lines(original_lines, pattern, line_index, chunk);
         }
 
         if let Some(start_idx) = found {
@@ -1062,4 +1054,203 @@ g
         let result = apply_patch(&patch, &mut stdout, &mut stderr);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_apply_patch_failure_leaves_earlier_hunks_unapplied() {
+        let dir = tempdir().unwrap();
+        let created = dir.path().join("created.txt");
+        let existing = dir.path().join("existing.txt");
+        let missing = dir.path().join("missing.txt");
+        fs::write(&existing, "old\n").unwrap();
+
+        let patch = wrap_patch(&format!(
+            "*** Add File: {}\n+hello\n*** Update File: {}\n@@\n-old\n+new\n*** Update File: {}\n@@\n-old\n+new",
+            created.display(),
+            existing.display(),
+            missing.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(&patch, &mut stdout, &mut stderr);
+
+        assert!(result.is_err());
+        assert!(!created.exists());
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "old\n");
+        let stderr = String::from_utf8(stderr).unwrap();
+        assert!(stderr.ends_with(&format!(
+            "\nHunk 3 (Update File: {}) failed; no files were changed.\n",
+            missing.display()
+        )));
+    }
+
+    #[test]
+    fn test_apply_patch_restores_files_when_rename_fails() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("existing.txt");
+        let nested = dir.path().join("new/dir/file.txt");
+        let blocked = dir.path().join("blocked");
+        fs::write(&existing, "old\n").unwrap();
+        fs::create_dir_all(blocked.join("child")).unwrap();
+
+        // The contents of every hunk can be computed, but the new file cannot
+        // be renamed over the non-empty `blocked` directory.
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n@@\n-old\n+new\n*** Add File: {}\n+nested\n*** Add File: {}\n+blocked",
+            existing.display(),
+            nested.display(),
+            blocked.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(&patch, &mut stdout, &mut stderr);
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "old\n");
+        assert!(!dir.path().join("new").exists());
+        assert!(blocked.join("child").is_dir());
+        let mut leftovers = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        leftovers.sort();
+        assert_eq!(leftovers, vec!["blocked", "existing.txt"]);
+        let stderr = String::from_utf8(stderr).unwrap();
+        assert!(stderr.ends_with(&format!(
+            "\nHunk 3 (Add File: {}) failed; no files were changed.\n",
+            blocked.display()
+        )));
+    }
+
+    #[test]
+    fn test_apply_patch_later_hunks_see_earlier_hunks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+
+        let patch = wrap_patch(&format!(
+            "*** Add File: {0}\n+one\n*** Update File: {0}\n@@\n-one\n+two",
+            path.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two\n");
+    }
+
+    #[test]
+    fn test_apply_patch_preserves_line_endings_and_encoding() {
+        let dir = tempdir().unwrap();
+        let script = dir.path().join("build.bat");
+        fs::write(&script, "@echo off\r\necho old\r\n").unwrap();
+        let fixture = dir.path().join("greeting.txt");
+        let (shift_jis, _, _) =
+            encoding_rs::SHIFT_JIS.encode("こんにちは、世界。\r\nこれは古い行です。\r\n");
+        fs::write(&fixture, &shift_jis).unwrap();
+
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n@@\n-echo old\n+echo new\n*** Update File: {}\n@@\n-これは古い行です。\n+これは新しい行です。",
+            script.display(),
+            fixture.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(fs::read(&script).unwrap(), b"@echo off\r\necho new\r\n");
+        let (expected, _, _) =
+            encoding_rs::SHIFT_JIS.encode("こんにちは、世界。\r\nこれは新しい行です。\r\n");
+        assert_eq!(fs::read(&fixture).unwrap(), expected.as_ref());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_apply_patch_keeps_permissions_when_moving() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("run.sh");
+        let dest = dir.path().join("bin/run.sh");
+        fs::write(&src, "echo old\n").unwrap();
+        fs::set_permissions(&src, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n*** Move to: {}\n@@\n-echo old\n+echo new",
+            src.display(),
+            dest.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert!(!src.exists());
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "echo new\n");
+        assert_eq!(
+            fs::metadata(&dest).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_refuses_binary_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("blob.bin");
+        fs::write(&path, b"old\n\0\x01\x02").unwrap();
+
+        let patch = wrap_patch(&format!(
+            "*** Update File: {}\n@@\n-old\n+new",
+            path.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(&patch, &mut stdout, &mut stderr);
+
+        assert!(result.is_err());
+        assert_eq!(
+            String::from_utf8(stderr).unwrap(),
+            format!(
+                "Failed to read file to update {0}: it looks like a binary file\nHunk 1 (Update File: {0}) failed; no files were changed.\n",
+                path.display()
+            )
+        );
+        assert_eq!(fs::read(&path).unwrap(), b"old\n\0\x01\x02");
+    }
+
+    #[test]
+    fn test_apply_unified_diff_uses_line_numbers_to_place_hunks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.txt");
+        // The same block appears twice and the file has drifted by one line
+        // since the diff was made.
+        fs::write(
+            &path,
+            "header\nextra\n[a]\nenabled = false\n\n[b]\nenabled = false\n",
+        )
+        .unwrap();
+        let moved = dir.path().join("renamed.txt");
+
+        let patch = format!(
+            "--- a/{0}\n+++ b/{1}\n@@ -6 +6 @@\n-enabled = false\n+enabled = true\n\\ No newline at end of file\n",
+            path.display(),
+            path.display()
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "header\nextra\n[a]\nenabled = false\n\n[b]\nenabled = true"
+        );
+
+        let rename = format!(
+            "diff --git a/{0} b/{1}\nsimilarity index 100%\nrename from {0}\nrename to {1}\n",
+            path.display(),
+            moved.display()
+        );
+        apply_patch(&rename, &mut stdout, &mut stderr).unwrap();
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(&moved).unwrap(),
+            "header\nextra\n[a]\nenabled = false\n\n[b]\nenabled = true"
+        );
+    }
 }
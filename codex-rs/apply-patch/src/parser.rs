@@ -22,7 +22,11 @@
 //!
 //! The parser below is a little more lenient than the explicit spec and allows for
 //! leading/trailing whitespace around patch markers.
+//!
+//! Standard unified diffs (`diff -u`, `git diff`) are accepted as well and are
+//! parsed into the same hunks by [`crate::unified_diff`].
 use crate::ApplyPatchArgs;
+use crate::unified_diff;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -101,6 +105,15 @@ pub struct UpdateFileChunk {
     /// If set to true, `old_lines` must occur at the end of the source file.
     /// (Tolerance around trailing newlines should be encouraged.)
     pub is_end_of_file: bool,
+
+    /// 1-based line of the original file where `old_lines` is expected to
+    /// start, when the patch says so (unified diffs). Used to pick the closest
+    /// match when `old_lines` occurs more than once.
+    pub original_line: Option<usize>,
+
+    /// Set when the file must end without a trailing newline after this chunk
+    /// (`\ No newline at end of file` in a unified diff).
+    pub missing_final_newline: bool,
 }
 
 pub fn parse_patch(patch: &str) -> Result<ApplyPatchArgs, ParseError> {
@@ -153,6 +166,13 @@ enum ParseMode {
 
 fn parse_patch_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, ParseError> {
     let lines: Vec<&str> = patch.trim().lines().collect();
+    if let Some(diff_lines) = unified_diff_lines(&lines, &mode) {
+        return Ok(ApplyPatchArgs {
+            hunks: unified_diff::parse_unified_diff(diff_lines)?,
+            patch: diff_lines.join("\n"),
+            workdir: None,
+        });
+    }
     let lines: &[&str] = match check_patch_boundaries_strict(&lines) {
         Ok(()) => &lines,
         Err(e) => match mode {
@@ -182,6 +202,24 @@ fn parse_patch_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, Pars
     })
 }
 
+/// Returns the lines of a unified diff, unwrapping a heredoc in lenient mode,
+/// or `None` if the patch is not a unified diff.
+fn unified_diff_lines<'a>(lines: &'a [&'a str], mode: &ParseMode) -> Option<&'a [&'a str]> {
+    if unified_diff::is_unified_diff(lines) {
+        return Some(lines);
+    }
+    match (mode, lines) {
+        (ParseMode::Lenient, [first, .., last])
+            if (first == &"<<EOF" || first == &"<<'EOF'" || first == &"<<\"EOF\"")
+                && last.ends_with("EOF") =>
+        {
+            let inner_lines = &lines[1..lines.len() - 1];
+            unified_diff::is_unified_diff(inner_lines).then_some(inner_lines)
+        }
+        _ => None,
+    }
+}
+
 /// Checks the start and end lines of the patch text for `apply_patch`,
 /// returning an error if they do not match the expected markers.
 fn check_patch_boundaries_strict(lines: &[&str]) -> Result<(), ParseError> {
@@ -380,6 +418,8 @@ fn parse_update_file_chunk(
         old_lines: Vec::new(),
         new_lines: Vec::new(),
         is_end_of_file: false,
+        original_line: None,
+        missing_final_newline: false,
     };
     let mut parsed_lines = 0;
     for line in &lines[start_index..] {
@@ -520,7 +560,9 @@ fn test_parse_patch() {
                     change_context: Some("def f():".to_string()),
                     old_lines: vec!["    pass".to_string()],
                     new_lines: vec!["    return 123".to_string()],
-                    is_end_of_file: false
+                    is_end_of_file: false,
+                    original_line: None,
+                    missing_final_newline: false
                 }]
             }
         ]
@@ -547,7 +589,9 @@ fn test_parse_patch() {
                     change_context: None,
                     old_lines: vec![],
                     new_lines: vec!["line".to_string()],
-                    is_end_of_file: false
+                    is_end_of_file: false,
+                    original_line: None,
+                    missing_final_newline: false
                 }],
             },
             AddFile {
@@ -578,6 +622,8 @@ fn test_parse_patch() {
                 old_lines: vec!["import foo".to_string()],
                 new_lines: vec!["import foo".to_string(), "bar".to_string()],
                 is_end_of_file: false,
+                original_line: None,
+                missing_final_newline: false,
             }],
         }]
     );
@@ -598,6 +644,8 @@ fn test_parse_patch_lenient() {
             old_lines: vec!["import foo".to_string()],
             new_lines: vec!["import foo".to_string(), "bar".to_string()],
             is_end_of_file: false,
+            original_line: None,
+            missing_final_newline: false,
         }],
     }];
     let expected_error =
@@ -743,7 +791,9 @@ fn test_update_file_chunk() {
                     "add".to_string(),
                     "context2".to_string()
                 ],
-                is_end_of_file: false
+                is_end_of_file: false,
+                original_line: None,
+                missing_final_newline: false
             }),
             6
         ))
@@ -755,7 +805,9 @@ fn test_update_file_chunk() {
                 change_context: None,
                 old_lines: vec![],
                 new_lines: vec!["line".to_string()],
-                is_end_of_file: true
+                is_end_of_file: true,
+                original_line: None,
+                missing_final_newline: false
             }),
             3
         ))
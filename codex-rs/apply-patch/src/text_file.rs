@@ -0,0 +1,120 @@
+//! Reading files to patch and writing them back in their original format.
+//!
+//! Patches are always expressed as UTF-8 with `\n` line endings. Files are
+//! decoded into that form before the chunks are applied, and the new contents
+//! are encoded with the file's original encoding, byte order mark and line
+//! endings when written back.
+
+use std::path::Path;
+
+use codex_utils_text_encoding::FileEncoding;
+use codex_utils_text_encoding::decode_text_file;
+
+use crate::ApplyPatchError;
+use crate::IoError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TextFormat {
+    encoding: FileEncoding,
+    crlf: bool,
+}
+
+impl Default for TextFormat {
+    /// UTF-8 with `\n` line endings, used for new files.
+    fn default() -> Self {
+        Self {
+            encoding: FileEncoding::UTF8,
+            crlf: false,
+        }
+    }
+}
+
+impl TextFormat {
+    pub(crate) fn encode(&self, path: &Path, contents: &str) -> Result<Vec<u8>, ApplyPatchError> {
+        let encoded = if self.crlf {
+            self.encoding.encode(&contents.replace('\n', "\r\n"))
+        } else {
+            self.encoding.encode(contents)
+        };
+        encoded.ok_or_else(|| ApplyPatchError::UnencodableContent {
+            path: path.to_path_buf(),
+            encoding: self.encoding.name(),
+        })
+    }
+}
+
+pub(crate) struct TextFile {
+    /// Decoded contents with `\r\n` line endings normalized to `\n`.
+    pub(crate) contents: String,
+    pub(crate) format: TextFormat,
+}
+
+/// Reads the file at `path` so it can be updated.
+pub(crate) fn read_file_to_update(path: &Path) -> Result<TextFile, ApplyPatchError> {
+    let bytes = std::fs::read(path).map_err(|source| {
+        ApplyPatchError::IoError(IoError {
+            context: format!("Failed to read file to update {}", path.display()),
+            source,
+        })
+    })?;
+    let (contents, encoding) =
+        decode_text_file(&bytes).ok_or_else(|| ApplyPatchError::BinaryFile(path.to_path_buf()))?;
+
+    // Only files that consistently use `\r\n` are normalized; mixed line
+    // endings are left alone so untouched lines keep theirs.
+    let crlf_count = contents.matches("\r\n").count();
+    let crlf = crlf_count > 0 && crlf_count == contents.matches('\n').count();
+    let contents = if crlf {
+        contents.replace("\r\n", "\n")
+    } else {
+        contents
+    };
+    Ok(TextFile {
+        contents,
+        format: TextFormat { encoding, crlf },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn crlf_files_are_normalized_and_restored() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("build.bat");
+        std::fs::write(&path, "@echo off\r\necho hi\r\n").unwrap();
+
+        let file = read_file_to_update(&path).unwrap();
+        assert_eq!(file.contents, "@echo off\necho hi\n");
+        assert_eq!(
+            file.format.encode(&path, "@echo off\necho bye\n").unwrap(),
+            b"@echo off\r\necho bye\r\n"
+        );
+    }
+
+    #[test]
+    fn mixed_line_endings_are_left_alone() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mixed.txt");
+        std::fs::write(&path, "a\r\nb\n").unwrap();
+
+        let file = read_file_to_update(&path).unwrap();
+        assert_eq!(file.contents, "a\r\nb\n");
+        assert_eq!(file.format, TextFormat::default());
+    }
+
+    #[test]
+    fn binary_files_are_refused() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        std::fs::write(&path, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+
+        assert_eq!(
+            read_file_to_update(&path).err(),
+            Some(ApplyPatchError::BinaryFile(path))
+        );
+    }
+}
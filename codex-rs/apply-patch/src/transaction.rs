@@ -0,0 +1,385 @@
+//! All-or-nothing application of parsed hunks.
+//!
+//! A patch is applied in three steps. First every hunk is replayed in memory
+//! to compute the final contents of each path it touches, so a hunk that does
+//! not apply is reported before anything is written. The new contents are then
+//! staged in temporary files next to their targets, and finally each staged
+//! file is renamed into place and deleted paths are removed. If staging or
+//! committing fails, the original contents are written back, created files and
+//! directories are removed, and the error names the hunk that failed.
+//!
+//! Updated files keep their encoding, byte order mark, line endings and
+//! permissions, including when they are moved.
+
+use std::fs::OpenOptions;
+use std::fs::Permissions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::AffectedPaths;
+use crate::ApplyPatchError;
+use crate::IoError;
+use crate::apply_chunks_to_contents;
+use crate::parser::Hunk;
+use crate::text_file::TextFormat;
+use crate::text_file::read_file_to_update;
+
+/// Applies `hunks` to the filesystem, leaving it untouched if any hunk fails.
+pub(crate) fn apply_hunks_atomically(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
+    let plan = PatchPlan::new(hunks)?;
+    let mut transaction = Transaction::default();
+    match transaction.run(&plan.changes) {
+        Ok(()) => Ok(plan.affected),
+        Err((hunk_index, err)) => {
+            let hunk = describe_hunk(&hunks[hunk_index]);
+            match transaction.rollback() {
+                Ok(()) => Err(hunk_failed(err, hunk_index, hunks)),
+                Err(rollback_errors) => Err(anyhow::anyhow!(
+                    "{err}\nHunk {} ({hunk}) failed and the following files could not be restored:\n{}",
+                    hunk_index + 1,
+                    rollback_errors.join("\n")
+                )),
+            }
+        }
+    }
+}
+
+fn hunk_failed(err: anyhow::Error, hunk_index: usize, hunks: &[Hunk]) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{err}\nHunk {} ({}) failed; no files were changed.",
+        hunk_index + 1,
+        describe_hunk(&hunks[hunk_index])
+    )
+}
+
+fn describe_hunk(hunk: &Hunk) -> String {
+    match hunk {
+        Hunk::AddFile { path, .. } => format!("Add File: {}", path.display()),
+        Hunk::DeleteFile { path } => format!("Delete File: {}", path.display()),
+        Hunk::UpdateFile { path, .. } => format!("Update File: {}", path.display()),
+    }
+}
+
+/// Final state of one path touched by the patch.
+struct PlannedChange {
+    path: PathBuf,
+    /// The file after the patch, or `None` when the path ends up deleted.
+    file: Option<PlannedFile>,
+    /// Index of the last hunk that touched the path.
+    hunk_index: usize,
+}
+
+struct PlannedFile {
+    contents: String,
+    format: TextFormat,
+    /// Permissions of the file the contents came from, carried over on moves.
+    permissions: Option<Permissions>,
+}
+
+/// The outcome of every hunk, computed without writing anything.
+struct PatchPlan {
+    /// Touched paths in the order they were first touched.
+    changes: Vec<PlannedChange>,
+    affected: AffectedPaths,
+}
+
+impl PatchPlan {
+    fn new(hunks: &[Hunk]) -> anyhow::Result<Self> {
+        let mut plan = Self {
+            changes: Vec::new(),
+            affected: AffectedPaths {
+                added: Vec::new(),
+                modified: Vec::new(),
+                deleted: Vec::new(),
+            },
+        };
+        for (hunk_index, hunk) in hunks.iter().enumerate() {
+            plan.add_hunk(hunk, hunk_index)
+                .map_err(|err| hunk_failed(err, hunk_index, hunks))?;
+        }
+        Ok(plan)
+    }
+
+    fn add_hunk(&mut self, hunk: &Hunk, hunk_index: usize) -> anyhow::Result<()> {
+        match hunk {
+            Hunk::AddFile { path, contents } => {
+                let file = PlannedFile {
+                    contents: contents.clone(),
+                    format: TextFormat::default(),
+                    permissions: None,
+                };
+                self.set(path, Some(file), hunk_index);
+                self.affected.added.push(path.clone());
+            }
+            Hunk::DeleteFile { path } => {
+                let exists = match self.planned(path) {
+                    Some(file) => file.is_some(),
+                    None => std::fs::symlink_metadata(path).is_ok_and(|meta| !meta.is_dir()),
+                };
+                if !exists {
+                    anyhow::bail!("Failed to delete file {}", path.display());
+                }
+                self.set(path, None, hunk_index);
+                self.affected.deleted.push(path.clone());
+            }
+            Hunk::UpdateFile {
+                path,
+                move_path,
+                chunks,
+            } => {
+                let file = match self.planned(path) {
+                    Some(Some(file)) => PlannedFile {
+                        contents: apply_chunks_to_contents(&file.contents, path, chunks)?,
+                        format: file.format,
+                        permissions: file.permissions.clone(),
+                    },
+                    Some(None) => {
+                        return Err(ApplyPatchError::IoError(IoError {
+                            context: format!("Failed to read file to update {}", path.display()),
+                            source: std::io::Error::new(
+                                std::io::ErrorKind::NotFound,
+                                "deleted earlier in this patch",
+                            ),
+                        })
+                        .into());
+                    }
+                    None => {
+                        let original = read_file_to_update(path)?;
+                        PlannedFile {
+                            contents: apply_chunks_to_contents(&original.contents, path, chunks)?,
+                            format: original.format,
+                            permissions: std::fs::metadata(path)
+                                .ok()
+                                .map(|meta| meta.permissions()),
+                        }
+                    }
+                };
+                match move_path {
+                    Some(dest) if dest != path => {
+                        self.set(path, None, hunk_index);
+                        self.set(dest, Some(file), hunk_index);
+                        self.affected.modified.push(dest.clone());
+                    }
+                    _ => {
+                        self.set(path, Some(file), hunk_index);
+                        self.affected.modified.push(path.clone());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The file an earlier hunk left at `path`, if any hunk touched it.
+    fn planned(&self, path: &Path) -> Option<Option<&PlannedFile>> {
+        self.changes
+            .iter()
+            .find(|change| change.path == path)
+            .map(|change| change.file.as_ref())
+    }
+
+    fn set(&mut self, path: &Path, file: Option<PlannedFile>, hunk_index: usize) {
+        if let Some(change) = self.changes.iter_mut().find(|change| change.path == path) {
+            change.file = file;
+            change.hunk_index = hunk_index;
+        } else {
+            self.changes.push(PlannedChange {
+                path: path.to_path_buf(),
+                file,
+                hunk_index,
+            });
+        }
+    }
+}
+
+/// Contents and permissions of a file before the patch touched it.
+struct Original {
+    bytes: Vec<u8>,
+    permissions: Permissions,
+}
+
+struct StagedChange {
+    /// File that is replaced or removed. For writes through a symlink this is
+    /// the link's target.
+    target: PathBuf,
+    /// Temporary file holding the new contents, or `None` for deletions.
+    temp: Option<PathBuf>,
+    original: Option<Original>,
+    applied: bool,
+}
+
+#[derive(Default)]
+struct Transaction {
+    staged: Vec<StagedChange>,
+    /// Directories created for new files, parents first.
+    created_dirs: Vec<PathBuf>,
+}
+
+impl Transaction {
+    /// Stages every change, then commits them in order. On failure, returns
+    /// the index of the hunk responsible.
+    fn run(&mut self, changes: &[PlannedChange]) -> Result<(), (usize, anyhow::Error)> {
+        for change in changes {
+            self.stage(change).map_err(|err| (change.hunk_index, err))?;
+        }
+        for (index, change) in changes.iter().enumerate() {
+            self.commit(index, &change.path)
+                .map_err(|err| (change.hunk_index, err))?;
+        }
+        Ok(())
+    }
+
+    fn stage(&mut self, change: &PlannedChange) -> anyhow::Result<()> {
+        let path = &change.path;
+        let metadata = std::fs::symlink_metadata(path).ok();
+        let target = match &metadata {
+            Some(meta) if meta.file_type().is_symlink() && change.file.is_some() => {
+                std::fs::canonicalize(path).unwrap_or_else(|_| path.clone())
+            }
+            _ => path.clone(),
+        };
+        let original = match std::fs::metadata(&target) {
+            Ok(meta) if meta.is_file() => Some(Original {
+                bytes: std::fs::read(&target)
+                    .with_context(|| format!("Failed to read file {}", path.display()))?,
+                permissions: meta.permissions(),
+            }),
+            _ => None,
+        };
+
+        let temp = match &change.file {
+            Some(file) => {
+                let bytes = file.format.encode(path, &file.contents)?;
+                let permissions = file
+                    .permissions
+                    .as_ref()
+                    .or(original.as_ref().map(|original| &original.permissions));
+                self.create_parent_dirs(&target).with_context(|| {
+                    format!("Failed to create parent directories for {}", path.display())
+                })?;
+                if original.is_some() {
+                    // Renaming over a read-only file would succeed, so check
+                    // that it could be written in place.
+                    OpenOptions::new()
+                        .write(true)
+                        .open(&target)
+                        .with_context(|| format!("Failed to write file {}", path.display()))?;
+                }
+                let temp = temp_path_for(&target, self.staged.len());
+                let staged = write_temp_file(&temp, &bytes, permissions);
+                if staged.is_err() {
+                    let _ = std::fs::remove_file(&temp);
+                }
+                staged.with_context(|| format!("Failed to write file {}", path.display()))?;
+                Some(temp)
+            }
+            None => None,
+        };
+
+        self.staged.push(StagedChange {
+            target,
+            temp,
+            original,
+            applied: false,
+        });
+        Ok(())
+    }
+
+    fn commit(&mut self, index: usize, path: &Path) -> anyhow::Result<()> {
+        let staged = &mut self.staged[index];
+        match &staged.temp {
+            Some(temp) => std::fs::rename(temp, &staged.target)
+                .with_context(|| format!("Failed to write file {}", path.display()))?,
+            None if staged.original.is_some() => std::fs::remove_file(&staged.target)
+                .with_context(|| format!("Failed to delete file {}", path.display()))?,
+            // Added and deleted again within the same patch.
+            None => {}
+        }
+        staged.applied = true;
+        Ok(())
+    }
+
+    fn create_parent_dirs(&mut self, path: &Path) -> std::io::Result<()> {
+        let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        else {
+            return Ok(());
+        };
+        let mut missing = parent
+            .ancestors()
+            .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        missing.reverse();
+        std::fs::create_dir_all(parent)?;
+        self.created_dirs.extend(missing);
+        Ok(())
+    }
+
+    /// Undoes every committed change and removes staged files and created
+    /// directories. Returns a description of each file that could not be
+    /// restored.
+    fn rollback(self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        for staged in self.staged.iter().rev() {
+            if let Some(temp) = &staged.temp
+                && !staged.applied
+            {
+                let _ = std::fs::remove_file(temp);
+            }
+            if !staged.applied {
+                continue;
+            }
+            let restored = match &staged.original {
+                Some(original) => std::fs::write(&staged.target, &original.bytes).and_then(|()| {
+                    std::fs::set_permissions(&staged.target, original.permissions.clone())
+                }),
+                None if staged.temp.is_some() => std::fs::remove_file(&staged.target),
+                None => Ok(()),
+            };
+            if let Err(err) = restored {
+                errors.push(format!("{}: {err}", staged.target.display()));
+            }
+        }
+        for dir in self.created_dirs.iter().rev() {
+            // Only succeeds if the directory is empty again.
+            let _ = std::fs::remove_dir(dir);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn temp_path_for(target: &Path, index: usize) -> PathBuf {
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target.with_file_name(format!(
+        ".{file_name}.apply_patch.{}.{index}.tmp",
+        std::process::id()
+    ))
+}
+
+fn write_temp_file(
+    temp: &Path,
+    bytes: &[u8],
+    permissions: Option<&Permissions>,
+) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(temp)?;
+    file.write_all(bytes)?;
+    if let Some(permissions) = permissions {
+        file.set_permissions(permissions.clone())?;
+    }
+    file.sync_all()
+}
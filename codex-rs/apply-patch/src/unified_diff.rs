@@ -0,0 +1,562 @@
+//! Parsing of standard unified diffs (`diff -u`, `git diff`) into [`Hunk`]s.
+//!
+//! Models and users often produce unified diffs instead of the `*** Begin
+//! Patch` envelope. They are converted into the same hunks so both formats go
+//! through the same verification, approval and application path:
+//!
+//! - `--- /dev/null` (or `new file mode`) becomes an [`Hunk::AddFile`],
+//! - `+++ /dev/null` (or `deleted file mode`) becomes a [`Hunk::DeleteFile`],
+//! - `rename from`/`rename to` becomes an [`Hunk::UpdateFile`] with a move,
+//! - every `@@ -l,s +l,s @@` hunk becomes an [`UpdateFileChunk`] that
+//!   remembers its line number, so placement can prefer the closest match
+//!   while still tolerating line drift.
+//!
+//! Paths are taken relative to the working directory, with git's `a/` and
+//! `b/` prefixes removed. Mode-only changes are ignored; copies and binary
+//! patches are rejected.
+
+use std::path::PathBuf;
+
+use crate::parser::Hunk;
+use crate::parser::ParseError;
+use crate::parser::ParseError::InvalidHunkError;
+use crate::parser::UpdateFileChunk;
+
+const GIT_HEADER_PREFIX: &str = "diff --git ";
+const OLD_FILE_PREFIX: &str = "--- ";
+const NEW_FILE_PREFIX: &str = "+++ ";
+const HUNK_HEADER_PREFIX: &str = "@@ ";
+const DEV_NULL: &str = "/dev/null";
+
+/// Whether `lines` look like a unified diff rather than an apply_patch
+/// envelope.
+pub(crate) fn is_unified_diff(lines: &[&str]) -> bool {
+    match lines {
+        [first, ..] if first.starts_with(GIT_HEADER_PREFIX) => true,
+        [first, second, ..] => {
+            first.starts_with(OLD_FILE_PREFIX) && second.starts_with(NEW_FILE_PREFIX)
+        }
+        _ => false,
+    }
+}
+
+pub(crate) fn parse_unified_diff(lines: &[&str]) -> Result<Vec<Hunk>, ParseError> {
+    let mut parser = DiffParser { lines, index: 0 };
+    let mut hunks = Vec::new();
+    while let Some(line) = parser.peek() {
+        if line.trim().is_empty() {
+            parser.index += 1;
+        } else if line.starts_with(GIT_HEADER_PREFIX) || line.starts_with(OLD_FILE_PREFIX) {
+            if let Some(hunk) = parser.parse_file()? {
+                hunks.push(hunk);
+            }
+        } else {
+            return Err(parser.error(format!("Unexpected line in unified diff: '{line}'")));
+        }
+    }
+    Ok(hunks)
+}
+
+#[derive(Default)]
+struct FileDiff {
+    is_git: bool,
+    old_path: Option<String>,
+    new_path: Option<String>,
+    new_file: bool,
+    deleted_file: bool,
+    chunks: Vec<UpdateFileChunk>,
+}
+
+struct DiffParser<'a> {
+    lines: &'a [&'a str],
+    index: usize,
+}
+
+impl<'a> DiffParser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.lines.get(self.index).copied()
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        InvalidHunkError {
+            message,
+            line_number: self.index + 1,
+        }
+    }
+
+    /// Parses the headers and hunks of one file, returning `None` for diffs
+    /// that do not change any content (such as mode changes).
+    fn parse_file(&mut self) -> Result<Option<Hunk>, ParseError> {
+        let mut file = FileDiff::default();
+        if let Some(paths) = self
+            .peek()
+            .and_then(|line| line.strip_prefix(GIT_HEADER_PREFIX))
+        {
+            file.is_git = true;
+            if let Some((old, new)) = split_git_header_paths(paths) {
+                file.old_path = Some(old);
+                file.new_path = Some(new);
+            }
+            self.index += 1;
+            self.parse_extended_headers(&mut file)?;
+        }
+
+        if let Some(old) = self
+            .peek()
+            .and_then(|line| line.strip_prefix(OLD_FILE_PREFIX))
+        {
+            self.index += 1;
+            let Some(new) = self
+                .peek()
+                .and_then(|line| line.strip_prefix(NEW_FILE_PREFIX))
+            else {
+                return Err(self.error(format!(
+                    "Expected a '+++ ' line after '--- {old}' in unified diff"
+                )));
+            };
+            self.index += 1;
+            file.new_file |= is_dev_null(old);
+            file.deleted_file |= is_dev_null(new);
+            if !is_dev_null(old) {
+                file.old_path = Some(parse_header_path(old, "a/"));
+            }
+            if !is_dev_null(new) {
+                file.new_path = Some(parse_header_path(new, "b/"));
+            }
+        }
+
+        while self
+            .peek()
+            .is_some_and(|line| line.starts_with(HUNK_HEADER_PREFIX))
+        {
+            let chunk = self.parse_hunk()?;
+            file.chunks.push(chunk);
+        }
+
+        self.finish_file(file)
+    }
+
+    fn parse_extended_headers(&mut self, file: &mut FileDiff) -> Result<(), ParseError> {
+        while let Some(line) = self.peek() {
+            if line.starts_with(OLD_FILE_PREFIX)
+                || line.starts_with(GIT_HEADER_PREFIX)
+                || line.starts_with(HUNK_HEADER_PREFIX)
+            {
+                break;
+            }
+            if line.starts_with("new file mode") {
+                file.new_file = true;
+            } else if line.starts_with("deleted file mode") {
+                file.deleted_file = true;
+            } else if let Some(path) = line.strip_prefix("rename from ") {
+                file.old_path = Some(unquote(path));
+            } else if let Some(path) = line.strip_prefix("rename to ") {
+                file.new_path = Some(unquote(path));
+            } else if line.starts_with("copy from ") || line.starts_with("copy to ") {
+                return Err(self.error("Copies are not supported in unified diffs".to_string()));
+            } else if line.starts_with("Binary files ") || line == "GIT binary patch" {
+                return Err(self.error("Binary patches are not supported".to_string()));
+            } else if ![
+                "old mode ",
+                "new mode ",
+                "index ",
+                "similarity index ",
+                "dissimilarity index ",
+            ]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+            {
+                return Err(self.error(format!("Unexpected line in git diff header: '{line}'")));
+            }
+            self.index += 1;
+        }
+        Ok(())
+    }
+
+    fn parse_hunk(&mut self) -> Result<UpdateFileChunk, ParseError> {
+        let header = self.peek().unwrap_or_default();
+        let Some((old_start, mut old_remaining, mut new_remaining)) = parse_hunk_header(header)
+        else {
+            return Err(self.error(format!(
+                "Invalid hunk header '{header}'. Expected '@@ -start,count +start,count @@'"
+            )));
+        };
+        self.index += 1;
+
+        let mut chunk = UpdateFileChunk {
+            change_context: None,
+            old_lines: Vec::new(),
+            new_lines: Vec::new(),
+            is_end_of_file: false,
+            original_line: (old_start > 0).then_some(old_start),
+            missing_final_newline: false,
+        };
+        let mut last_kind = ' ';
+        while let Some(line) = self.peek() {
+            if let Some(marker) = line.strip_prefix('\\') {
+                // `\ No newline at end of file` applies to the previous line.
+                if marker.trim_start().starts_with("No newline") {
+                    chunk.is_end_of_file = true;
+                    if last_kind != '-' {
+                        chunk.missing_final_newline = true;
+                    }
+                }
+                self.index += 1;
+                continue;
+            }
+            if (old_remaining == 0 && new_remaining == 0)
+                || line.starts_with(HUNK_HEADER_PREFIX)
+                || line.starts_with(GIT_HEADER_PREFIX)
+            {
+                break;
+            }
+            let (kind, text) = match line.chars().next() {
+                // Some tools strip the single space from empty context lines.
+                None => (' ', ""),
+                Some(kind @ (' ' | '-' | '+')) => (kind, &line[1..]),
+                Some(_) => {
+                    return Err(self.error(format!(
+                        "Unexpected line in unified diff hunk: '{line}'. Every line should start with ' ', '+' or '-'"
+                    )));
+                }
+            };
+            if kind != '+' {
+                chunk.old_lines.push(text.to_string());
+                old_remaining = old_remaining.saturating_sub(1);
+            }
+            if kind != '-' {
+                chunk.new_lines.push(text.to_string());
+                new_remaining = new_remaining.saturating_sub(1);
+            }
+            last_kind = kind;
+            self.index += 1;
+        }
+        Ok(chunk)
+    }
+
+    fn finish_file(&self, file: FileDiff) -> Result<Option<Hunk>, ParseError> {
+        if file.new_file {
+            let Some(path) = file.new_path else {
+                return Err(self.error("New file in unified diff has no path".to_string()));
+            };
+            let mut contents = String::new();
+            let mut missing_final_newline = false;
+            for chunk in file.chunks {
+                for line in chunk.new_lines {
+                    contents.push_str(&line);
+                    contents.push('\n');
+                }
+                missing_final_newline = chunk.missing_final_newline;
+            }
+            if missing_final_newline {
+                contents.pop();
+            }
+            return Ok(Some(Hunk::AddFile {
+                path: PathBuf::from(path),
+                contents,
+            }));
+        }
+
+        if file.deleted_file {
+            let Some(path) = file.old_path else {
+                return Err(self.error("Deleted file in unified diff has no path".to_string()));
+            };
+            return Ok(Some(Hunk::DeleteFile {
+                path: PathBuf::from(path),
+            }));
+        }
+
+        let (path, move_path) = match (file.old_path, file.new_path) {
+            (Some(old), Some(new)) if file.is_git && old != new => (old, Some(new)),
+            // Plain `diff -u` output often names a backup copy as the old
+            // file, so the new name is the one to update.
+            (_, Some(new)) => (new, None),
+            (Some(old), None) => (old, None),
+            (None, None) => {
+                return Err(self.error("File in unified diff has no path".to_string()));
+            }
+        };
+        if file.chunks.is_empty() && move_path.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Hunk::UpdateFile {
+            path: PathBuf::from(path),
+            move_path: move_path.map(PathBuf::from),
+            chunks: file.chunks,
+        }))
+    }
+}
+
+/// Parses `@@ -l,s +l,s @@`, returning the old start line and the old and
+/// new line counts.
+fn parse_hunk_header(header: &str) -> Option<(usize, usize, usize)> {
+    let ranges = header.strip_prefix(HUNK_HEADER_PREFIX)?;
+    let (ranges, _section) = ranges.split_once(" @@")?;
+    let (old, new) = ranges.split_once(' ')?;
+    let (old_start, old_count) = parse_range(old.strip_prefix('-')?)?;
+    let (_, new_count) = parse_range(new.strip_prefix('+')?)?;
+    Some((old_start, old_count, new_count))
+}
+
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+fn is_dev_null(path: &str) -> bool {
+    path.split('\t').next().map(str::trim_end) == Some(DEV_NULL)
+}
+
+/// Extracts the path from a `---`/`+++` line, dropping any timestamp and the
+/// git `a/`/`b/` prefix.
+fn parse_header_path(path: &str, git_prefix: &str) -> String {
+    let path = path.split('\t').next().unwrap_or_default().trim_end();
+    let path = unquote(path);
+    match path.strip_prefix(git_prefix) {
+        Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+        _ => path,
+    }
+}
+
+/// Splits the paths of a `diff --git a/<old> b/<new>` header. Paths with
+/// spaces are ambiguous here; later `---`/`+++` or rename lines take
+/// precedence when present.
+fn split_git_header_paths(paths: &str) -> Option<(String, String)> {
+    if let Some(quoted) = paths.strip_prefix('"') {
+        let (old, new) = quoted.split_once("\" ")?;
+        let old = unquote(&format!("\"{old}\""));
+        let new = unquote(new.trim_start());
+        return Some((
+            old.strip_prefix("a/").unwrap_or(&old).to_string(),
+            new.strip_prefix("b/").unwrap_or(&new).to_string(),
+        ));
+    }
+    let rest = paths.strip_prefix("a/")?;
+    // When old and new are the same, the header is `a/<path> b/<path>`.
+    let half = rest.len().saturating_sub(3) / 2;
+    if rest.len() == half * 2 + 3
+        && rest.is_char_boundary(half)
+        && rest[half..].starts_with(" b/")
+        && rest[..half] == rest[half + 3..]
+    {
+        return Some((rest[..half].to_string(), rest[half + 3..].to_string()));
+    }
+    let (old, new) = rest.split_once(" b/")?;
+    Some((old.to_string(), new.to_string()))
+}
+
+/// Removes git's C-style quoting from a path, if present.
+fn unquote(path: &str) -> String {
+    let Some(inner) = path
+        .strip_prefix('"')
+        .and_then(|path| path.strip_suffix('"'))
+    else {
+        return path.to_string();
+    };
+    let mut bytes = Vec::with_capacity(inner.len());
+    let mut chars = inner.bytes().peekable();
+    while let Some(byte) = chars.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        match chars.next() {
+            Some(b'n') => bytes.push(b'\n'),
+            Some(b't') => bytes.push(b'\t'),
+            Some(digit @ b'0'..=b'7') => {
+                // Octal escapes encode raw UTF-8 bytes.
+                let mut value = u32::from(digit - b'0');
+                for _ in 0..2 {
+                    if let Some(next @ b'0'..=b'7') = chars.peek().copied() {
+                        value = value * 8 + u32::from(next - b'0');
+                        chars.next();
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some(other) => bytes.push(other),
+            None => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse(diff: &str) -> Result<Vec<Hunk>, ParseError> {
+        let lines = diff.lines().collect::<Vec<_>>();
+        assert!(is_unified_diff(&lines));
+        parse_unified_diff(&lines)
+    }
+
+    fn chunk(
+        old_lines: &[&str],
+        new_lines: &[&str],
+        original_line: usize,
+        eof: (bool, bool),
+    ) -> UpdateFileChunk {
+        UpdateFileChunk {
+            change_context: None,
+            old_lines: old_lines.iter().map(ToString::to_string).collect(),
+            new_lines: new_lines.iter().map(ToString::to_string).collect(),
+            is_end_of_file: eof.0,
+            original_line: Some(original_line),
+            missing_final_newline: eof.1,
+        }
+    }
+
+    #[test]
+    fn parses_git_diff_with_update_add_delete_and_rename() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 83db48f..bf269f4 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@ mod parser;
+ fn one() {}
+-fn two() {}
++fn zwei() {}
+
+diff --git a/notes.txt b/notes.txt
+new file mode 100644
+index 0000000..e69de29
+--- /dev/null
++++ b/notes.txt
+@@ -0,0 +1,2 @@
++first
++second
+\\ No newline at end of file
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+index e69de29..0000000
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+diff --git a/from.rs b/to.rs
+similarity index 90%
+rename from from.rs
+rename to to.rs
+index 1111111..2222222 100644
+--- a/from.rs
++++ b/to.rs
+@@ -10,2 +10,2 @@
+-old
++new
+ tail
+";
+        assert_eq!(
+            parse(diff),
+            Ok(vec![
+                Hunk::UpdateFile {
+                    path: PathBuf::from("src/lib.rs"),
+                    move_path: None,
+                    chunks: vec![chunk(
+                        &["fn one() {}", "fn two() {}", ""],
+                        &["fn one() {}", "fn zwei() {}", ""],
+                        1,
+                        (false, false),
+                    )],
+                },
+                Hunk::AddFile {
+                    path: PathBuf::from("notes.txt"),
+                    contents: "first\nsecond".to_string(),
+                },
+                Hunk::DeleteFile {
+                    path: PathBuf::from("old.txt"),
+                },
+                Hunk::UpdateFile {
+                    path: PathBuf::from("from.rs"),
+                    move_path: Some(PathBuf::from("to.rs")),
+                    chunks: vec![chunk(
+                        &["old", "tail"],
+                        &["new", "tail"],
+                        10,
+                        (false, false)
+                    )],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_plain_unified_diff_and_newline_markers() {
+        let diff = "\
+--- config.ini.orig\t2024-01-01 00:00:00.000000000 +0000
++++ config.ini\t2024-01-02 00:00:00.000000000 +0000
+@@ -2,2 +2,2 @@
+ [core]
+-debug = false
+\\ No newline at end of file
++debug = true
+";
+        assert_eq!(
+            parse(diff),
+            Ok(vec![Hunk::UpdateFile {
+                path: PathBuf::from("config.ini"),
+                move_path: None,
+                chunks: vec![chunk(
+                    &["[core]", "debug = false"],
+                    &["[core]", "debug = true"],
+                    2,
+                    (true, false),
+                )],
+            }])
+        );
+    }
+
+    #[test]
+    fn pure_renames_and_mode_changes() {
+        let diff = "\
+diff --git a/a.sh b/a.sh
+old mode 100644
+new mode 100755
+diff --git \"a/with space.txt\" \"b/moved here.txt\"
+similarity index 100%
+rename from with space.txt
+rename to moved here.txt
+";
+        assert_eq!(
+            parse(diff),
+            Ok(vec![Hunk::UpdateFile {
+                path: PathBuf::from("with space.txt"),
+                move_path: Some(PathBuf::from("moved here.txt")),
+                chunks: Vec::new(),
+            }])
+        );
+    }
+
+    #[test]
+    fn rejects_binary_patches_and_bad_hunk_headers() {
+        assert_eq!(
+            parse("diff --git a/x.png b/x.png\nBinary files a/x.png and b/x.png differ\n"),
+            Err(InvalidHunkError {
+                message: "Binary patches are not supported".to_string(),
+                line_number: 2,
+            })
+        );
+        assert_eq!(
+            parse("--- a/x\n+++ b/x\n@@ bogus @@\n-a\n+b\n"),
+            Err(InvalidHunkError {
+                message:
+                    "Invalid hunk header '@@ bogus @@'. Expected '@@ -start,count +start,count @@'"
+                        .to_string(),
+                line_number: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn split_git_header_paths_handles_spaces() {
+        assert_eq!(
+            split_git_header_paths("a/my file.txt b/my file.txt"),
+            Some(("my file.txt".to_string(), "my file.txt".to_string()))
+        );
+        assert_eq!(unquote("\"na\\303\\257ve.txt\""), "naïve.txt");
+    }
+}
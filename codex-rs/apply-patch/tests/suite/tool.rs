@@ -104,7 +104,7 @@ fn test_apply_patch_cli_reports_missing_context() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Update File: modify.txt\n@@\n-missing\n+changed\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Failed to find expected lines in modify.txt:\nmissing\n");
+        .stderr("Failed to find expected lines in modify.txt:\nmissing\nHunk 1 (Update File: modify.txt) failed; no files were changed.\n");
     assert_eq!(fs::read_to_string(&target_path)?, "line1\nline2\n");
 
     Ok(())
@@ -118,7 +118,9 @@ fn test_apply_patch_cli_rejects_missing_file_delete() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Delete File: missing.txt\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Failed to delete file missing.txt\n");
+        .stderr(
+            "Failed to delete file missing.txt\nHunk 1 (Delete File: missing.txt) failed; no files were changed.\n",
+        );
 
     Ok(())
 }
@@ -145,7 +147,7 @@ fn test_apply_patch_cli_requires_existing_file_for_update() -> anyhow::Result<()
         .assert()
         .failure()
         .stderr(
-            "Failed to read file to update missing.txt: No such file or directory (os error 2)\n",
+            "Failed to read file to update missing.txt: No such file or directory (os error 2)\nHunk 1 (Update File: missing.txt) failed; no files were changed.\n",
         );
 
     Ok(())
@@ -201,7 +203,9 @@ fn test_apply_patch_cli_delete_directory_fails() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Delete File: dir\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Failed to delete file dir\n");
+        .stderr(
+            "Failed to delete file dir\nHunk 1 (Delete File: dir) failed; no files were changed.\n",
+        );
 
     Ok(())
 }
@@ -240,7 +244,7 @@ fn test_apply_patch_cli_updates_file_appends_trailing_newline() -> anyhow::Resul
 }
 
 #[test]
-fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow::Result<()> {
+fn test_apply_patch_cli_failure_after_partial_success_changes_nothing() -> anyhow::Result<()> {
     let tmp = tempdir()?;
     let new_file = tmp.path().join("created.txt");
 
@@ -249,9 +253,9 @@ fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow
         .assert()
         .failure()
         .stdout("")
-        .stderr("Failed to read file to update missing.txt: No such file or directory (os error 2)\n");
+        .stderr("Failed to read file to update missing.txt: No such file or directory (os error 2)\nHunk 2 (Update File: missing.txt) failed; no files were changed.\n");
 
-    assert_eq!(fs::read_to_string(&new_file)?, "hello\n");
+    assert!(!new_file.exists());
 
     Ok(())
 }
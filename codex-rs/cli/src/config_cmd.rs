@@ -0,0 +1,599 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_app_server_protocol::ConfigLayerMetadata;
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_app_server_protocol::ConfigReadParams;
+use codex_app_server_protocol::ConfigReadResponse;
+use codex_app_server_protocol::ConfigValueWriteParams;
+use codex_app_server_protocol::MergeStrategy;
+use codex_app_server_protocol::WriteStatus;
+use codex_common::CliConfigOverrides;
+use codex_common::parse_override_value;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::find_codex_home;
+use codex_core::config::schema::config_key_schema;
+use codex_core::config::service::ConfigService;
+use codex_core::config_loader::CloudRequirementsLoader;
+use codex_core::config_loader::ConfigDiagnostic;
+use codex_core::config_loader::ConfigDiagnosticSeverity;
+use codex_core::config_loader::LoaderOverrides;
+use codex_core::config_loader::format_config_error_with_source;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+
+/// 子命令：
+/// - `get`      — 查看某个配置项的生效值
+/// - `set`      — 在 `~/.codex/config.toml` 中设置配置项
+/// - `unset`    — 从 `~/.codex/config.toml` 中删除配置项
+/// - `list`     — 列出所有生效的配置项（可加 `--origins` 显示来源层）
+/// - `validate` — 校验配置文件（语法、类型、未知键与语义检查）
+/// - `explain`  — 说明配置项的含义、类型、取值及各层的设置
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    /// 查看配置项的生效值。
+    Get(GetArgs),
+    /// 在 config.toml 中设置配置项。
+    Set(SetArgs),
+    /// 从 config.toml 中删除配置项。
+    Unset(UnsetArgs),
+    /// 列出所有生效的配置项。
+    List(ListArgs),
+    /// 校验配置文件，并报告带行列号的问题。
+    Validate(ValidateArgs),
+    /// 说明配置项的含义、类型与来源。
+    Explain(ExplainArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct GetArgs {
+    /// 以点分隔的配置键（例如：tools.web_search）。
+    #[arg(value_name = "键")]
+    pub key: String,
+
+    /// 以 JSON 输出。
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    /// 以点分隔的配置键（例如：profiles.work.model）。
+    #[arg(value_name = "键")]
+    pub key: String,
+
+    /// 新值，按 TOML 解析（例如：true、42、["a", "b"]）；无法解析时按字符串处理。
+    #[arg(value_name = "值")]
+    pub value: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct UnsetArgs {
+    /// 以点分隔的配置键。
+    #[arg(value_name = "键")]
+    pub key: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// 显示每个配置项来自哪一层（系统、用户、项目、命令行等）。
+    #[arg(long)]
+    pub origins: bool,
+
+    /// 以 JSON 输出。
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ValidateArgs {
+    /// 以 JSON 输出诊断结果。
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExplainArgs {
+    /// 以点分隔的配置键。
+    #[arg(value_name = "键")]
+    pub key: String,
+
+    /// 以 JSON 输出。
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl ConfigCli {
+    pub async fn run(self) -> Result<()> {
+        let ConfigCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        let service = config_service(&config_overrides)?;
+        match subcommand {
+            ConfigSubcommand::Get(args) => run_get(&service, args).await?,
+            ConfigSubcommand::Set(args) => run_set(&service, args).await?,
+            ConfigSubcommand::Unset(args) => run_unset(&service, args).await?,
+            ConfigSubcommand::List(args) => run_list(&service, args).await?,
+            ConfigSubcommand::Validate(args) => run_validate(&service, args).await?,
+            ConfigSubcommand::Explain(args) => run_explain(&service, args).await?,
+        }
+
+        Ok(())
+    }
+}
+
+fn config_service(config_overrides: &CliConfigOverrides) -> Result<ConfigService> {
+    let cli_overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let codex_home = find_codex_home().context("无法确定 CODEX_HOME")?;
+    Ok(ConfigService::new(
+        codex_home,
+        cli_overrides,
+        LoaderOverrides::default(),
+        CloudRequirementsLoader::default(),
+    ))
+}
+
+async fn read_config(service: &ConfigService, include_layers: bool) -> Result<ConfigReadResponse> {
+    let cwd = std::env::current_dir().context("无法获取当前目录")?;
+    service
+        .read(ConfigReadParams {
+            include_layers,
+            cwd: Some(cwd.display().to_string()),
+        })
+        .await
+        .context("读取配置失败")
+}
+
+async fn run_get(service: &ConfigService, args: GetArgs) -> Result<()> {
+    let GetArgs { key, json } = args;
+    let segments = split_key(&key)?;
+    let response = read_config(service, false).await?;
+    let config = serde_json::to_value(&response.config)?;
+    let Some(value) = value_at(&config, &segments).filter(|value| !value.is_null()) else {
+        bail!("配置项 `{key}` 未设置");
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    } else {
+        println!("{}", format_value(value));
+    }
+    Ok(())
+}
+
+async fn run_set(service: &ConfigService, args: SetArgs) -> Result<()> {
+    let SetArgs { key, value } = args;
+    split_key(&key)?;
+    let value = serde_json::to_value(parse_override_value(&value))?;
+    let response = service
+        .write_value(ConfigValueWriteParams {
+            key_path: key.clone(),
+            value: value.clone(),
+            merge_strategy: MergeStrategy::Replace,
+            file_path: None,
+            expected_version: None,
+        })
+        .await
+        .with_context(|| format!("设置 `{key}` 失败"))?;
+
+    println!(
+        "已在 {} 中设置 {key} = {}",
+        response.file_path.display(),
+        format_value(&value)
+    );
+    if matches!(response.status, WriteStatus::OkOverridden)
+        && let Some(overridden) = response.overridden_metadata
+    {
+        eprintln!(
+            "注意：该值被 {} 覆盖，生效值仍为 {}。",
+            layer_label(&overridden.overriding_layer.name),
+            format_value(&overridden.effective_value)
+        );
+    }
+    Ok(())
+}
+
+async fn run_unset(service: &ConfigService, args: UnsetArgs) -> Result<()> {
+    let UnsetArgs { key } = args;
+    split_key(&key)?;
+    let before = read_config(service, false).await?;
+    let user_origins: Vec<&ConfigLayerMetadata> = origins_under(&before.origins, &key)
+        .into_iter()
+        .filter(|origin| matches!(origin.name, ConfigLayerSource::User { .. }))
+        .collect();
+    let included_files = included_files(&user_origins);
+    if !included_files.is_empty() && user_origins.iter().all(|origin| origin.file.is_some()) {
+        bail!(
+            "配置项 `{key}` 来自 include 引入的文件 {}，而不是 config.toml；请在该文件中删除，或从 include 列表中移除该文件",
+            included_files.join("、")
+        );
+    }
+
+    let response = service
+        .write_value(ConfigValueWriteParams {
+            key_path: key.clone(),
+            value: JsonValue::Null,
+            merge_strategy: MergeStrategy::Replace,
+            file_path: None,
+            expected_version: None,
+        })
+        .await
+        .with_context(|| format!("删除 `{key}` 失败"))?;
+
+    println!("已从 {} 中删除 {key}", response.file_path.display());
+    if let Some(overridden) = response.overridden_metadata {
+        eprintln!(
+            "注意：{} 仍设置了该项，生效值为 {}。",
+            layer_label(&overridden.overriding_layer.name),
+            format_value(&overridden.effective_value)
+        );
+    } else if !included_files.is_empty() {
+        eprintln!(
+            "注意：include 引入的文件 {} 仍设置了该项，它现在会生效。",
+            included_files.join("、")
+        );
+    }
+    Ok(())
+}
+
+/// Origins of `key` itself and of every key nested under it.
+fn origins_under<'a>(
+    origins: &'a HashMap<String, ConfigLayerMetadata>,
+    key: &str,
+) -> Vec<&'a ConfigLayerMetadata> {
+    let prefix = format!("{key}.");
+    origins
+        .iter()
+        .filter(|(origin_key, _)| origin_key.as_str() == key || origin_key.starts_with(&prefix))
+        .map(|(_, origin)| origin)
+        .collect()
+}
+
+/// Distinct included files among `origins`, sorted for stable output.
+fn included_files(origins: &[&ConfigLayerMetadata]) -> Vec<String> {
+    let mut files: Vec<String> = origins
+        .iter()
+        .filter_map(|origin| origin.file.as_ref())
+        .map(|file| file.display().to_string())
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+async fn run_list(service: &ConfigService, args: ListArgs) -> Result<()> {
+    let ListArgs { origins, json } = args;
+    let response = read_config(service, false).await?;
+    let config = serde_json::to_value(&response.config)?;
+    let mut entries = Vec::new();
+    flatten_leaves(&config, &mut Vec::new(), &mut entries);
+    entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+    if json {
+        let rows: Vec<JsonValue> = entries
+            .iter()
+            .map(|(key, value)| {
+                let mut row = json!({ "key": key, "value": value });
+                if origins && let Some(origin) = origin_for(&response.origins, key) {
+                    row["origin"] = serde_json::to_value(&origin.name).unwrap_or(JsonValue::Null);
+                    if let Some(file) = origin.file.as_ref() {
+                        row["originFile"] = json!(file.display().to_string());
+                    }
+                }
+                row
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("当前没有生效的配置项。");
+        return Ok(());
+    }
+
+    let lines: Vec<(String, Option<String>)> = entries
+        .iter()
+        .map(|(key, value)| {
+            let origin = origins
+                .then(|| origin_for(&response.origins, key))
+                .flatten()
+                .map(origin_label);
+            (format!("{key} = {}", format_inline_value(value)), origin)
+        })
+        .collect();
+    let width = lines
+        .iter()
+        .map(|(line, _)| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (line, origin) in lines {
+        match origin {
+            Some(origin) => println!("{line:<width$}  # {origin}"),
+            None => println!("{line}"),
+        }
+    }
+    Ok(())
+}
+
+async fn run_validate(service: &ConfigService, args: ValidateArgs) -> Result<()> {
+    let ValidateArgs { json } = args;
+    let cwd = std::env::current_dir().context("无法获取当前目录")?;
+    let diagnostics = service.validate(Some(cwd)).await.context("校验配置失败")?;
+    let has_errors = diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == ConfigDiagnosticSeverity::Error);
+
+    if json {
+        let rows: Vec<JsonValue> = diagnostics.iter().map(diagnostic_to_json).collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else if diagnostics.is_empty() {
+        println!("配置有效。");
+    } else {
+        for diagnostic in &diagnostics {
+            eprintln!("{}\n", format_diagnostic(diagnostic));
+        }
+        let errors = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == ConfigDiagnosticSeverity::Error)
+            .count();
+        let warnings = diagnostics.len() - errors;
+        eprintln!("{errors} 个错误，{warnings} 个警告。");
+    }
+
+    if has_errors {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run_explain(service: &ConfigService, args: ExplainArgs) -> Result<()> {
+    let ExplainArgs { key, json } = args;
+    let segments = split_key(&key)?;
+    let schema = config_key_schema(&segments);
+    let response = read_config(service, true).await?;
+    let config = serde_json::to_value(&response.config)?;
+    let effective = value_at(&config, &segments)
+        .filter(|value| !value.is_null())
+        .cloned();
+    let origin = origin_for(&response.origins, &key);
+
+    // Layers are listed from highest to lowest precedence.
+    let layer_values: Vec<(ConfigLayerSource, JsonValue, bool)> = response
+        .layers
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|layer| {
+            let value = value_at(&layer.config, &segments)?.clone();
+            Some((layer.name, value, layer.disabled_reason.is_some()))
+        })
+        .collect();
+
+    if schema.is_none() && effective.is_none() && layer_values.is_empty() {
+        bail!("未知的配置项 `{key}`");
+    }
+
+    if json {
+        let output = json!({
+            "key": key,
+            "known": schema.is_some(),
+            "description": schema.as_ref().and_then(|schema| schema.description.clone()),
+            "types": schema.as_ref().map(|schema| schema.types.clone()).unwrap_or_default(),
+            "allowedValues": schema
+                .as_ref()
+                .map(|schema| schema.allowed_values.clone())
+                .unwrap_or_default(),
+            "default": schema.as_ref().and_then(|schema| schema.default.clone()),
+            "effectiveValue": effective,
+            "origin": origin.map(|origin| origin.name.clone()),
+            "originFile": origin
+                .and_then(|origin| origin.file.as_ref())
+                .map(|file| file.display().to_string()),
+            "layers": layer_values
+                .iter()
+                .map(|(name, value, disabled)| json!({
+                    "name": name,
+                    "value": value,
+                    "disabled": disabled,
+                }))
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!("{key}");
+    match schema.as_ref() {
+        Some(schema) => {
+            if let Some(description) = schema.description.as_deref() {
+                println!();
+                for line in description.lines() {
+                    println!("  {line}");
+                }
+            }
+            println!();
+            if !schema.types.is_empty() {
+                println!("  类型：{}", schema.types.join(" | "));
+            }
+            if !schema.allowed_values.is_empty() {
+                let values: Vec<String> = schema
+                    .allowed_values
+                    .iter()
+                    .map(format_inline_value)
+                    .collect();
+                println!("  可选值：{}", values.join("、"));
+            }
+            if let Some(default) = schema.default.as_ref() {
+                println!("  默认值：{}", format_inline_value(default));
+            }
+        }
+        None => {
+            println!();
+            println!("  配置结构中没有该键，Codex 会忽略它。");
+        }
+    }
+
+    match (&effective, origin) {
+        (Some(value), Some(origin)) => println!(
+            "  生效值：{}（来自 {}）",
+            format_inline_value(value),
+            origin_label(origin)
+        ),
+        (Some(value), None) => println!("  生效值：{}", format_inline_value(value)),
+        (None, _) => println!("  生效值：未设置"),
+    }
+
+    if !layer_values.is_empty() {
+        println!();
+        println!("  各层设置（优先级从高到低）：");
+        for (name, value, disabled) in &layer_values {
+            let suffix = if *disabled { "（已停用）" } else { "" };
+            println!(
+                "    {}：{}{suffix}",
+                layer_label(name),
+                format_inline_value(value)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn split_key(key: &str) -> Result<Vec<String>> {
+    let segments: Vec<String> = key.split('.').map(str::to_string).collect();
+    if segments.iter().any(String::is_empty) {
+        bail!("无效的配置键 `{key}`");
+    }
+    Ok(segments)
+}
+
+fn value_at<'a>(root: &'a JsonValue, segments: &[String]) -> Option<&'a JsonValue> {
+    segments
+        .iter()
+        .try_fold(root, |current, segment| match current {
+            JsonValue::Object(map) => map.get(segment),
+            JsonValue::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+/// Collects `(dotted key, value)` pairs for every non-null leaf. Arrays are
+/// treated as leaves so they print on one line.
+fn flatten_leaves(value: &JsonValue, path: &mut Vec<String>, out: &mut Vec<(String, JsonValue)>) {
+    match value {
+        JsonValue::Null => {}
+        JsonValue::Object(map) => {
+            for (key, child) in map {
+                path.push(key.clone());
+                flatten_leaves(child, path, out);
+                path.pop();
+            }
+        }
+        other => {
+            if !path.is_empty() {
+                out.push((path.join("."), other.clone()));
+            }
+        }
+    }
+}
+
+/// Origins are recorded per scalar, so arrays are attributed to the layer
+/// that set their first element.
+fn origin_for<'a>(
+    origins: &'a HashMap<String, ConfigLayerMetadata>,
+    key: &str,
+) -> Option<&'a ConfigLayerMetadata> {
+    origins
+        .get(key)
+        .or_else(|| origins.get(&format!("{key}.0")))
+}
+
+fn origin_label(origin: &ConfigLayerMetadata) -> String {
+    match origin.file.as_ref() {
+        Some(file) => format!(
+            "{}，经 include 引入：{}",
+            layer_label(&origin.name),
+            file.display()
+        ),
+        None => layer_label(&origin.name),
+    }
+}
+
+fn layer_label(source: &ConfigLayerSource) -> String {
+    match source {
+        ConfigLayerSource::Mdm { domain, key } => format!("MDM（{domain}:{key}）"),
+        ConfigLayerSource::System { file } => format!("系统配置（{}）", file.display()),
+        ConfigLayerSource::User { file } => format!("用户配置（{}）", file.display()),
+        ConfigLayerSource::Project { dot_codex_folder } => format!(
+            "项目配置（{}）",
+            dot_codex_folder.as_path().join(CONFIG_TOML_FILE).display()
+        ),
+        ConfigLayerSource::SessionFlags => "命令行 -c 覆盖".to_string(),
+        ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => {
+            format!("托管配置（{}）", file.display())
+        }
+        ConfigLayerSource::LegacyManagedConfigTomlFromMdm => "托管配置（MDM）".to_string(),
+    }
+}
+
+/// Strings print bare; tables print as TOML; everything else inline.
+fn format_value(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(text) => text.clone(),
+        JsonValue::Object(_) => serde_json::from_value::<toml::Table>(value.clone())
+            .ok()
+            .and_then(|table| toml::to_string_pretty(&table).ok())
+            .map(|text| text.trim_end().to_string())
+            .unwrap_or_else(|| value.to_string()),
+        _ => format_inline_value(value),
+    }
+}
+
+fn format_inline_value(value: &JsonValue) -> String {
+    serde_json::from_value::<toml::Value>(value.clone())
+        .map(|value| value.to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+fn format_diagnostic(diagnostic: &ConfigDiagnostic) -> String {
+    let severity = match diagnostic.severity {
+        ConfigDiagnosticSeverity::Error => "错误",
+        ConfigDiagnosticSeverity::Warning => "警告",
+    };
+    let body = match (diagnostic.config_error(), diagnostic.path.as_ref()) {
+        (Some(error), _) => format_config_error_with_source(&error),
+        (None, Some(path)) => format!("{}: {}", path.display(), diagnostic.message),
+        (None, None) => diagnostic.message.clone(),
+    };
+    format!("{severity}：{body}")
+}
+
+fn diagnostic_to_json(diagnostic: &ConfigDiagnostic) -> JsonValue {
+    let severity = match diagnostic.severity {
+        ConfigDiagnosticSeverity::Error => "error",
+        ConfigDiagnosticSeverity::Warning => "warning",
+    };
+    json!({
+        "severity": severity,
+        "file": diagnostic.path.as_ref().map(|path| path.display().to_string()),
+        "line": diagnostic.range.map(|range| range.start.line),
+        "column": diagnostic.range.map(|range| range.start.column),
+        "endLine": diagnostic.range.map(|range| range.end.line),
+        "endColumn": diagnostic.range.map(|range| range.end.column),
+        "message": diagnostic.message,
+    })
+}
@@ -33,12 +33,14 @@ use supports_color::Stream;
 
 #[cfg(target_os = "macos")]
 mod app_cmd;
+mod config_cmd;
 #[cfg(target_os = "macos")]
 mod desktop_app;
 mod mcp_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::config_cmd::ConfigCli;
 use crate::mcp_cmd::McpCli;
 
 use codex_core::config::Config;
@@ -114,6 +116,9 @@ enum Subcommand {
     /// 【实验】运行 Codex MCP 服务器（stdio 传输）。
     McpServer,
 
+    /// 查看、修改和校验 config.toml 配置。
+    Config(ConfigCli),
+
     /// 【实验】运行 app server 或相关工具。
     AppServer(AppServerCommand),
 
@@ -212,6 +217,11 @@ struct ResumeCommand {
     #[arg(long = "all", default_value_t = false)]
     all: bool,
 
+    /// 打开选择器并按会话内容全文搜索（消息、命令与改动过的文件路径）。
+    /// 所有关键词须出现在同一条消息、命令或路径中。
+    #[arg(long = "search", value_name = "查询", conflicts_with_all = ["session_id", "last"])]
+    search: Option<String>,
+
     #[clap(flatten)]
     config_overrides: TuiCli,
 }
@@ -608,6 +618,13 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Config(mut config_cli)) => {
+            prepend_config_flags(
+                &mut config_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            config_cli.run().await?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 let transport = app_server_cli.listen;
@@ -646,6 +663,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             session_id,
             last,
             all,
+            search,
             config_overrides,
         })) => {
             interactive = finalize_resume_interactive(
@@ -654,6 +672,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 session_id,
                 last,
                 all,
+                search,
                 config_overrides,
             );
             let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
@@ -943,6 +962,7 @@ fn finalize_resume_interactive(
     session_id: Option<String>,
     last: bool,
     show_all: bool,
+    search: Option<String>,
     resume_cli: TuiCli,
 ) -> TuiCli {
     // Start with the parsed interactive CLI so resume shares the same
@@ -952,6 +972,7 @@ fn finalize_resume_interactive(
     interactive.resume_last = last;
     interactive.resume_session_id = resume_session_id;
     interactive.resume_show_all = show_all;
+    interactive.resume_search = search;
 
     // Merge resume-scoped flags and overrides with highest precedence.
     merge_interactive_cli_flags(&mut interactive, resume_cli);
@@ -1064,6 +1085,7 @@ mod tests {
             session_id,
             last,
             all,
+            search,
             config_overrides: resume_cli,
         }) = subcommand.expect("resume present")
         else {
@@ -1076,6 +1098,7 @@ mod tests {
             session_id,
             last,
             all,
+            search,
             resume_cli,
         )
     }
@@ -1242,6 +1265,17 @@ mod tests {
         assert!(interactive.resume_show_all);
     }
 
+    #[test]
+    fn resume_search_flag_opens_picker_with_query() {
+        let interactive =
+            finalize_resume_from_args(["codex2", "resume", "--search", "flaky migration"].as_ref());
+        assert!(interactive.resume_picker);
+        assert_eq!(
+            interactive.resume_search.as_deref(),
+            Some("flaky migration")
+        );
+    }
+
     #[test]
     fn resume_merges_option_flags_and_full_auto() {
         let interactive = finalize_resume_from_args(
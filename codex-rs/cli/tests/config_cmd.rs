@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex2")?);
+    cmd.env("CODEX_HOME", codex_home);
+    cmd.current_dir(codex_home);
+    Ok(cmd)
+}
+
+#[tokio::test]
+async fn config_set_get_and_unset_round_trip() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut set_cmd = codex_command(codex_home.path())?;
+    set_cmd
+        .args(["config", "set", "tools.web_search", "true"])
+        .assert()
+        .success()
+        .stdout(contains("tools.web_search = true"));
+
+    let config = std::fs::read_to_string(codex_home.path().join("config.toml"))?;
+    assert!(config.contains("[tools]"));
+    assert!(config.contains("web_search = true"));
+
+    let mut get_cmd = codex_command(codex_home.path())?;
+    get_cmd
+        .args(["config", "get", "tools.web_search"])
+        .assert()
+        .success()
+        .stdout("true\n");
+
+    let mut unset_cmd = codex_command(codex_home.path())?;
+    unset_cmd
+        .args(["config", "unset", "tools.web_search"])
+        .assert()
+        .success();
+
+    let mut get_cmd = codex_command(codex_home.path())?;
+    get_cmd
+        .args(["config", "get", "tools.web_search"])
+        .assert()
+        .failure()
+        .stderr(contains("未设置"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_unset_rejects_key_that_comes_from_an_include() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "include = [\"team.toml\"]\n",
+    )?;
+    std::fs::write(codex_home.path().join("team.toml"), "model = \"gpt-5\"\n")?;
+
+    let mut unset_cmd = codex_command(codex_home.path())?;
+    unset_cmd
+        .args(["config", "unset", "model"])
+        .assert()
+        .failure()
+        .stderr(contains("team.toml"));
+
+    let mut get_cmd = codex_command(codex_home.path())?;
+    get_cmd
+        .args(["config", "get", "model"])
+        .assert()
+        .success()
+        .stdout("gpt-5\n");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_list_origins_labels_session_flags() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(codex_home.path().join("config.toml"), "model = \"gpt-5\"\n")?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args([
+        "-c",
+        "model_reasoning_effort=\"high\"",
+        "config",
+        "list",
+        "--origins",
+    ])
+    .assert()
+    .success()
+    .stdout(contains("model = \"gpt-5\""))
+    .stdout(contains("用户配置"))
+    .stdout(contains("命令行 -c 覆盖"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_validate_reports_location_and_fails() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "model = \"gpt-5\"\nmodel_context_window = \"large\"\n",
+    )?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["config", "validate"])
+        .assert()
+        .failure()
+        .stderr(contains("错误："))
+        .stderr(contains("config.toml:2:"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_explain_describes_known_key() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["config", "explain", "approval_policy"])
+        .assert()
+        .success()
+        .stdout(contains("可选值："))
+        .stdout(contains("生效值："));
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use codex_client::Request;
 use http::HeaderMap;
 use http::HeaderValue;
+use std::future::Future;
 
 /// Provides bearer and account identity information for API requests.
 ///
@@ -12,6 +13,12 @@ pub trait AuthProvider: Send + Sync {
     fn account_id(&self) -> Option<String> {
         None
     }
+    /// Called when a request is rejected with `401 Unauthorized`, before the
+    /// retry policy decides whether to send it again. Providers that can
+    /// obtain a fresh token should do so here so the retry picks it up.
+    fn refresh_after_unauthorized(&self) -> impl Future<Output = ()> + Send {
+        async {}
+    }
 }
 
 pub(crate) fn add_auth_headers_to_header_map<A: AuthProvider>(auth: &A, headers: &mut HeaderMap) {
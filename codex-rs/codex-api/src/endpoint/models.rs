@@ -143,6 +143,7 @@ mod tests {
                 retry_429: false,
                 retry_5xx: true,
                 retry_transport: true,
+                retry_401: false,
             },
             stream_idle_timeout: Duration::from_secs(1),
         }
@@ -8,8 +8,10 @@ use codex_client::Request;
 use codex_client::RequestTelemetry;
 use codex_client::Response;
 use codex_client::StreamResponse;
+use codex_client::TransportError;
 use http::HeaderMap;
 use http::Method;
+use http::StatusCode;
 use serde_json::Value;
 use std::sync::Arc;
 
@@ -57,6 +59,14 @@ impl<T: HttpTransport, A: AuthProvider> EndpointSession<T, A> {
         add_auth_headers(&self.auth, req)
     }
 
+    async fn refresh_auth_if_unauthorized<R>(&self, result: &Result<R, TransportError>) {
+        if let Err(TransportError::Http { status, .. }) = result
+            && *status == StatusCode::UNAUTHORIZED
+        {
+            self.auth.refresh_after_unauthorized().await;
+        }
+    }
+
     pub(crate) async fn execute(
         &self,
         method: Method,
@@ -89,7 +99,11 @@ impl<T: HttpTransport, A: AuthProvider> EndpointSession<T, A> {
             self.provider.retry.to_policy(),
             self.request_telemetry.clone(),
             make_request,
-            |req| self.transport.execute(req),
+            |req| async move {
+                let result = self.transport.execute(req).await;
+                self.refresh_auth_if_unauthorized(&result).await;
+                result
+            },
         )
         .await?;
 
@@ -117,7 +131,11 @@ impl<T: HttpTransport, A: AuthProvider> EndpointSession<T, A> {
             self.provider.retry.to_policy(),
             self.request_telemetry.clone(),
             make_request,
-            |req| self.transport.stream(req),
+            |req| async move {
+                let result = self.transport.stream(req).await;
+                self.refresh_auth_if_unauthorized(&result).await;
+                result
+            },
         )
         .await?;
 
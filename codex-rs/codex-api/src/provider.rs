@@ -19,6 +19,9 @@ pub struct RetryConfig {
     pub retry_429: bool,
     pub retry_5xx: bool,
     pub retry_transport: bool,
+    /// Retry once after a `401 Unauthorized`, giving the auth provider a
+    /// chance to refresh its token first.
+    pub retry_401: bool,
 }
 
 impl RetryConfig {
@@ -30,6 +33,7 @@ impl RetryConfig {
                 retry_429: self.retry_429,
                 retry_5xx: self.retry_5xx,
                 retry_transport: self.retry_transport,
+                retry_401: self.retry_401,
             },
         }
     }
@@ -40,6 +40,7 @@ fn provider(base_url: &str) -> Provider {
             retry_429: false,
             retry_5xx: true,
             retry_transport: true,
+            retry_401: false,
         },
         stream_idle_timeout: std::time::Duration::from_secs(1),
     }
@@ -72,6 +72,7 @@ fn provider(name: &str) -> Provider {
             retry_429: false,
             retry_5xx: false,
             retry_transport: true,
+            retry_401: false,
         },
         stream_idle_timeout: Duration::from_millis(50),
     }
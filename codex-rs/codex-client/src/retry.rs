@@ -17,6 +17,7 @@ pub struct RetryOn {
     pub retry_429: bool,
     pub retry_5xx: bool,
     pub retry_transport: bool,
+    pub retry_401: bool,
 }
 
 impl RetryOn {
@@ -28,6 +29,7 @@ impl RetryOn {
             TransportError::Http { status, .. } => {
                 (self.retry_429 && status.as_u16() == 429)
                     || (self.retry_5xx && status.is_server_error())
+                    || (self.retry_401 && status.as_u16() == 401 && attempt == 0)
             }
             TransportError::Timeout | TransportError::Network(_) => self.retry_transport,
             _ => false,
@@ -58,19 +58,10 @@ impl CliConfigOverrides {
                     return Err(format!("覆盖项的键为空：{s}"));
                 }
 
-                // Attempt to parse as TOML. If that fails, treat it as a raw
-                // string. This allows convenient usage such as
-                // `-c model=o3` without the quotes.
-                let value: Value = match parse_toml_value(value_str) {
-                    Ok(v) => v,
-                    Err(_) => {
-                        // Strip leading/trailing quotes if present
-                        let trimmed = value_str.trim().trim_matches(|c| c == '"' || c == '\'');
-                        Value::String(trimmed.to_string())
-                    }
-                };
-
-                Ok((canonicalize_override_key(key), value))
+                Ok((
+                    canonicalize_override_key(key),
+                    parse_override_value(value_str),
+                ))
             })
             .collect()
     }
@@ -87,6 +78,20 @@ impl CliConfigOverrides {
     }
 }
 
+/// Parse the value half of a `key=value` override. Valid TOML is used as-is;
+/// anything else is treated as a raw string, which allows convenient usage
+/// such as `-c model=o3` without the quotes.
+pub fn parse_override_value(raw: &str) -> Value {
+    match parse_toml_value(raw) {
+        Ok(v) => v,
+        Err(_) => {
+            // Strip leading/trailing quotes if present
+            let trimmed = raw.trim().trim_matches(|c| c == '"' || c == '\'');
+            Value::String(trimmed.to_string())
+        }
+    }
+}
+
 fn canonicalize_override_key(key: &str) -> String {
     if key == "use_linux_sandbox_bwrap" {
         "features.use_linux_sandbox_bwrap".to_string()
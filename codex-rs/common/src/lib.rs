@@ -22,6 +22,9 @@ mod config_override;
 #[cfg(feature = "cli")]
 pub use config_override::CliConfigOverrides;
 
+#[cfg(feature = "cli")]
+pub use config_override::parse_override_value;
+
 mod sandbox_summary;
 
 #[cfg(feature = "sandbox_summary")]
@@ -14,6 +14,7 @@ use codex_protocol::config_types::SandboxMode;
 pub enum SandboxModeCliArg {
     ReadOnly,
     WorkspaceWrite,
+    WorkspaceOverlay,
     DangerFullAccess,
 }
 
@@ -22,6 +23,7 @@ impl From<SandboxModeCliArg> for SandboxMode {
         match value {
             SandboxModeCliArg::ReadOnly => SandboxMode::ReadOnly,
             SandboxModeCliArg::WorkspaceWrite => SandboxMode::WorkspaceWrite,
+            SandboxModeCliArg::WorkspaceOverlay => SandboxMode::WorkspaceOverlay,
             SandboxModeCliArg::DangerFullAccess => SandboxMode::DangerFullAccess,
         }
     }
@@ -39,6 +41,10 @@ mod tests {
             SandboxMode::WorkspaceWrite,
             SandboxModeCliArg::WorkspaceWrite.into()
         );
+        assert_eq!(
+            SandboxMode::WorkspaceOverlay,
+            SandboxModeCliArg::WorkspaceOverlay.into()
+        );
         assert_eq!(
             SandboxMode::DangerFullAccess,
             SandboxModeCliArg::DangerFullAccess.into()
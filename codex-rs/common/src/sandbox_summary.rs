@@ -40,6 +40,13 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             }
             summary
         }
+        SandboxPolicy::WorkspaceOverlay { network_access } => {
+            let mut summary = "workspace-overlay".to_string();
+            if *network_access {
+                summary.push_str(" (network access enabled)");
+            }
+            summary
+        }
     }
 }
 
@@ -7,8 +7,11 @@ use codex_api::rate_limits::parse_promo_message;
 use codex_api::rate_limits::parse_rate_limit;
 use http::HeaderMap;
 use serde::Deserialize;
+use std::sync::Arc;
+use tracing::warn;
 
 use crate::auth::CodexAuth;
+use crate::auth::command::AuthCommand;
 use crate::error::CodexErr;
 use crate::error::ModelCapError;
 use crate::error::RetryLimitReachedError;
@@ -171,14 +174,25 @@ fn extract_header(headers: Option<&HeaderMap>, name: &str) -> Option<String> {
     })
 }
 
-pub(crate) fn auth_provider_from_auth(
+pub(crate) async fn auth_provider_from_auth(
     auth: Option<CodexAuth>,
     provider: &ModelProviderInfo,
 ) -> crate::error::Result<CoreAuthProvider> {
+    if let Some(argv) = provider.auth_command.as_deref() {
+        let command = AuthCommand::for_argv(argv);
+        let token = command.token().await?;
+        return Ok(CoreAuthProvider {
+            token: Some(token),
+            account_id: None,
+            auth_command: Some(command),
+        });
+    }
+
     if let Some(api_key) = provider.api_key()? {
         return Ok(CoreAuthProvider {
             token: Some(api_key),
             account_id: None,
+            auth_command: None,
         });
     }
 
@@ -186,6 +200,7 @@ pub(crate) fn auth_provider_from_auth(
         return Ok(CoreAuthProvider {
             token: Some(token),
             account_id: None,
+            auth_command: None,
         });
     }
 
@@ -194,12 +209,10 @@ pub(crate) fn auth_provider_from_auth(
         Ok(CoreAuthProvider {
             token: Some(token),
             account_id: auth.get_account_id(),
+            auth_command: None,
         })
     } else {
-        Ok(CoreAuthProvider {
-            token: None,
-            account_id: None,
-        })
+        Ok(CoreAuthProvider::default())
     }
 }
 
@@ -220,14 +233,28 @@ struct UsageErrorBody {
 pub(crate) struct CoreAuthProvider {
     token: Option<String>,
     account_id: Option<String>,
+    /// Set when the token comes from the provider's `auth_command`, so a
+    /// refreshed token replaces `token` for later requests.
+    auth_command: Option<Arc<AuthCommand>>,
 }
 
 impl ApiAuthProvider for CoreAuthProvider {
     fn bearer_token(&self) -> Option<String> {
-        self.token.clone()
+        self.auth_command
+            .as_ref()
+            .and_then(|command| command.cached_token())
+            .or_else(|| self.token.clone())
     }
 
     fn account_id(&self) -> Option<String> {
         self.account_id.clone()
     }
+
+    async fn refresh_after_unauthorized(&self) {
+        if let Some(command) = &self.auth_command
+            && let Err(err) = command.refresh_after_unauthorized().await
+        {
+            warn!("failed to refresh provider token after 401: {err}");
+        }
+    }
 }
@@ -1,3 +1,4 @@
+pub(crate) mod command;
 mod storage;
 
 use async_trait::async_trait;
@@ -0,0 +1,294 @@
+//! Bearer tokens obtained from a provider's `auth_command` helper.
+//!
+//! The helper is run on demand, its token is kept in memory only, and it is
+//! run again shortly before the token expires or after the provider rejects
+//! it with `401 Unauthorized`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use tokio::process::Command;
+
+/// Tokens this close to expiry are treated as expired so in-flight requests
+/// do not race the deadline.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// Upper bound on how long the helper may run before it is killed.
+const AUTH_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// One cache per distinct command so every client for a provider shares it.
+static AUTH_COMMANDS: LazyLock<Mutex<HashMap<Vec<String>, Arc<AuthCommand>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Clone)]
+struct CachedToken {
+    token: String,
+    expires_at: Option<DateTime<Utc>>,
+    fetched_at: Instant,
+}
+
+impl CachedToken {
+    fn is_fresh(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => {
+                let margin = chrono::Duration::from_std(EXPIRY_MARGIN).unwrap_or_default();
+                Utc::now() + margin < expires_at
+            }
+            None => true,
+        }
+    }
+}
+
+/// JSON form of the helper's output. Plain-text output is the token itself.
+#[derive(Deserialize)]
+struct AuthCommandOutput {
+    token: String,
+    #[serde(default)]
+    expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+pub(crate) struct AuthCommand {
+    argv: Vec<String>,
+    cached: Mutex<Option<CachedToken>>,
+    /// Serializes helper runs so concurrent requests share one refresh.
+    refresh: tokio::sync::Mutex<()>,
+}
+
+impl fmt::Debug for AuthCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthCommand")
+            .field("argv", &self.argv)
+            .field("token", &"<redacted>")
+            .finish()
+    }
+}
+
+impl AuthCommand {
+    /// Returns the shared cache for `argv`.
+    pub(crate) fn for_argv(argv: &[String]) -> Arc<Self> {
+        let mut commands = AUTH_COMMANDS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        commands
+            .entry(argv.to_vec())
+            .or_insert_with(|| {
+                Arc::new(Self {
+                    argv: argv.to_vec(),
+                    cached: Mutex::new(None),
+                    refresh: tokio::sync::Mutex::new(()),
+                })
+            })
+            .clone()
+    }
+
+    /// The cached token, if one has been fetched. May be stale; callers
+    /// that can await should use [`AuthCommand::token`] instead.
+    pub(crate) fn cached_token(&self) -> Option<String> {
+        self.cached_entry().map(|cached| cached.token)
+    }
+
+    /// Returns a token that is not about to expire, running the helper if
+    /// needed.
+    pub(crate) async fn token(&self) -> io::Result<String> {
+        if let Some(cached) = self.cached_entry().filter(CachedToken::is_fresh) {
+            return Ok(cached.token);
+        }
+        let _guard = self.refresh.lock().await;
+        if let Some(cached) = self.cached_entry().filter(CachedToken::is_fresh) {
+            return Ok(cached.token);
+        }
+        self.run().await
+    }
+
+    /// Discards the cached token and fetches a new one, unless another
+    /// caller already did so after this one was rejected.
+    pub(crate) async fn refresh_after_unauthorized(&self) -> io::Result<String> {
+        let rejected_at = Instant::now();
+        let _guard = self.refresh.lock().await;
+        if let Some(cached) = self
+            .cached_entry()
+            .filter(|cached| cached.fetched_at > rejected_at)
+        {
+            return Ok(cached.token);
+        }
+        self.run().await
+    }
+
+    fn cached_entry(&self) -> Option<CachedToken> {
+        self.cached
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    async fn run(&self) -> io::Result<String> {
+        let (program, args) = self
+            .argv
+            .split_first()
+            .filter(|(program, _)| !program.is_empty())
+            .ok_or_else(|| io::Error::other("`auth_command` must name a program to run"))?;
+        let child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("failed to run auth_command `{program}`: {err}"),
+                )
+            })?;
+        let output = tokio::time::timeout(AUTH_COMMAND_TIMEOUT, child.wait_with_output())
+            .await
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!(
+                        "auth_command `{program}` did not finish within {}s",
+                        AUTH_COMMAND_TIMEOUT.as_secs()
+                    ),
+                )
+            })??;
+        if !output.status.success() {
+            // Only stderr is surfaced; stdout may contain a partial token.
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!(
+                "auth_command `{program}` exited with {}: {}",
+                output.status,
+                stderr.trim()
+            )));
+        }
+
+        let stdout = String::from_utf8(output.stdout).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("auth_command `{program}` printed invalid UTF-8"),
+            )
+        })?;
+        let cached = parse_output(&stdout, Utc::now()).map_err(|message| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("auth_command `{program}` {message}"),
+            )
+        })?;
+        let token = cached.token.clone();
+        *self
+            .cached
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(cached);
+        Ok(token)
+    }
+}
+
+/// Parses the helper's stdout. Error messages never include the output
+/// itself because it may contain the token.
+fn parse_output(stdout: &str, now: DateTime<Utc>) -> Result<CachedToken, &'static str> {
+    let stdout = stdout.trim();
+    let (token, expires_at) = if stdout.starts_with('{') {
+        let output: AuthCommandOutput = serde_json::from_str(stdout)
+            .map_err(|_| "printed JSON without a string `token` field")?;
+        let expires_at = output.expires_at.or_else(|| {
+            output
+                .expires_in
+                .and_then(|secs| i64::try_from(secs).ok())
+                .and_then(chrono::Duration::try_seconds)
+                .and_then(|expires_in| now.checked_add_signed(expires_in))
+        });
+        (output.token.trim().to_string(), expires_at)
+    } else {
+        (stdout.to_string(), None)
+    };
+    if token.is_empty() {
+        return Err("printed an empty token");
+    }
+    Ok(CachedToken {
+        token,
+        expires_at,
+        fetched_at: Instant::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_plain_and_json_output() {
+        let now = Utc::now();
+
+        let plain = parse_output("secret-token\n", now).expect("plain token");
+        assert_eq!(plain.token, "secret-token");
+        assert_eq!(plain.expires_at, None);
+
+        let json = parse_output(
+            r#"{"token":"abc","expires_at":"2030-01-01T00:00:00Z"}"#,
+            now,
+        )
+        .expect("json token");
+        assert_eq!(json.token, "abc");
+        assert_eq!(
+            json.expires_at,
+            Some("2030-01-01T00:00:00Z".parse().expect("timestamp"))
+        );
+
+        let relative =
+            parse_output(r#"{"token":"abc","expires_in":3600}"#, now).expect("json token");
+        assert_eq!(
+            relative.expires_at,
+            Some(now + chrono::Duration::seconds(3600))
+        );
+
+        assert!(parse_output("  \n", now).is_err());
+        assert!(parse_output(r#"{"access_token":"abc"}"#, now).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn caches_until_expiry_or_unauthorized() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let log = tmp.path().join("calls");
+        let script = format!(
+            "echo call >> '{}'; printf '{{\"token\":\"tok\",\"expires_in\":3600}}'",
+            log.display()
+        );
+        let command = AuthCommand::for_argv(&["sh".to_string(), "-c".to_string(), script]);
+        let calls = || {
+            std::fs::read_to_string(&log)
+                .map(|calls| calls.lines().count())
+                .unwrap_or(0)
+        };
+
+        assert_eq!(command.cached_token(), None);
+        assert_eq!(command.token().await.expect("token"), "tok");
+        assert_eq!(command.token().await.expect("token"), "tok");
+        assert_eq!(calls(), 1);
+
+        command.refresh_after_unauthorized().await.expect("refresh");
+        assert_eq!(calls(), 2);
+
+        let expiring = format!(
+            "echo call >> '{}'; printf '{{\"token\":\"tok\",\"expires_in\":1}}'",
+            log.display()
+        );
+        let command = AuthCommand::for_argv(&["sh".to_string(), "-c".to_string(), expiring]);
+        command.token().await.expect("token");
+        command.token().await.expect("token");
+        assert_eq!(calls(), 4);
+        assert!(format!("{command:?}").contains("<redacted>"));
+    }
+}
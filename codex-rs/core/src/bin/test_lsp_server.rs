@@ -0,0 +1,200 @@
+//! Tiny language server used by the LSP integration tests.
+//!
+//! It speaks just enough LSP over stdio for Codex's client:
+//! - every line containing `ERROR` is published as an error diagnostic;
+//! - definition resolves the word under the cursor to its `fn <word>` line;
+//! - references lists every occurrence of the word in the document;
+//! - hover returns `word: <word>`.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::io::Read;
+use std::io::Write;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde_json::Value;
+use serde_json::json;
+
+fn main() -> Result<()> {
+    let stdin = std::io::stdin();
+    let mut reader = stdin.lock();
+    let mut stdout = std::io::stdout().lock();
+    let mut documents: HashMap<String, String> = HashMap::new();
+
+    while let Some(message) = read_message(&mut reader)? {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let id = message.get("id").cloned();
+
+        match method {
+            "initialize" => respond(
+                &mut stdout,
+                id,
+                json!({
+                    "capabilities": {
+                        "textDocumentSync": 1,
+                        "definitionProvider": true,
+                        "referencesProvider": true,
+                        "hoverProvider": true,
+                    },
+                }),
+            )?,
+            "textDocument/didOpen" => {
+                let uri = string_at(&params, "/textDocument/uri");
+                let text = string_at(&params, "/textDocument/text");
+                publish_diagnostics(&mut stdout, &uri, &text)?;
+                documents.insert(uri, text);
+            }
+            "textDocument/didChange" => {
+                let uri = string_at(&params, "/textDocument/uri");
+                let text = string_at(&params, "/contentChanges/0/text");
+                publish_diagnostics(&mut stdout, &uri, &text)?;
+                documents.insert(uri, text);
+            }
+            "textDocument/definition" | "textDocument/references" | "textDocument/hover" => {
+                let uri = string_at(&params, "/textDocument/uri");
+                let text = documents.get(&uri).cloned().unwrap_or_default();
+                let line = params
+                    .pointer("/position/line")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0) as usize;
+                let character = params
+                    .pointer("/position/character")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0) as usize;
+                let word = word_at(&text, line, character);
+                let result = match (method, word) {
+                    (_, None) => Value::Null,
+                    ("textDocument/hover", Some(word)) => json!({
+                        "contents": { "kind": "plaintext", "value": format!("word: {word}") },
+                    }),
+                    ("textDocument/definition", Some(word)) => {
+                        let definition = format!("fn {word}");
+                        occurrences(&text, &definition)
+                            .first()
+                            .map(|(line, character)| location(&uri, *line, character + 3))
+                            .unwrap_or(Value::Null)
+                    }
+                    (_, Some(word)) => Value::Array(
+                        occurrences(&text, &word)
+                            .into_iter()
+                            .map(|(line, character)| location(&uri, line, character))
+                            .collect(),
+                    ),
+                };
+                respond(&mut stdout, id, result)?;
+            }
+            "shutdown" => respond(&mut stdout, id, Value::Null)?,
+            "exit" => break,
+            _ => {
+                if id.is_some() && !method.is_empty() {
+                    respond(&mut stdout, id, Value::Null)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+    let content_length = content_length.context("missing Content-Length header")?;
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+fn write_message(stdout: &mut impl Write, message: &Value) -> Result<()> {
+    let body = serde_json::to_vec(message)?;
+    write!(stdout, "Content-Length: {}\r\n\r\n", body.len())?;
+    stdout.write_all(&body)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn respond(stdout: &mut impl Write, id: Option<Value>, result: Value) -> Result<()> {
+    write_message(
+        stdout,
+        &json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+    )
+}
+
+fn publish_diagnostics(stdout: &mut impl Write, uri: &str, text: &str) -> Result<()> {
+    let diagnostics: Vec<Value> = occurrences(text, "ERROR")
+        .into_iter()
+        .map(|(line, character)| {
+            json!({
+                "range": {
+                    "start": { "line": line, "character": character },
+                    "end": { "line": line, "character": character + 5 },
+                },
+                "severity": 1,
+                "source": "test-lsp",
+                "message": "found ERROR marker",
+            })
+        })
+        .collect();
+    write_message(
+        stdout,
+        &json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        }),
+    )
+}
+
+fn string_at(value: &Value, pointer: &str) -> String {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn location(uri: &str, line: usize, character: usize) -> Value {
+    json!({
+        "uri": uri,
+        "range": {
+            "start": { "line": line, "character": character },
+            "end": { "line": line, "character": character },
+        },
+    })
+}
+
+fn occurrences(text: &str, needle: &str) -> Vec<(usize, usize)> {
+    text.lines()
+        .enumerate()
+        .flat_map(|(line, content)| {
+            content
+                .match_indices(needle)
+                .map(move |(character, _)| (line, character))
+        })
+        .collect()
+}
+
+fn word_at(text: &str, line: usize, character: usize) -> Option<String> {
+    let content = text.lines().nth(line)?;
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let start = content[..character.min(content.len())]
+        .rfind(|c: char| !is_word(c))
+        .map_or(0, |index| index + 1);
+    let end = content[start..]
+        .find(|c: char| !is_word(c))
+        .map_or(content.len(), |index| start + index);
+    (start < end).then(|| content[start..end].to_string())
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
@@ -27,7 +28,9 @@ use crate::features::maybe_push_unstable_features_warning;
 use crate::hooks::HookEvent;
 use crate::hooks::HookEventAfterAgent;
 use crate::hooks::Hooks;
+use crate::lsp::LspManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::models_manager::model_info::token_counter_for_model;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
 use crate::rollout::session_index;
@@ -36,6 +39,7 @@ use crate::stream_events_utils::handle_non_tool_response_item;
 use crate::stream_events_utils::handle_output_item_done;
 use crate::stream_events_utils::last_assistant_message_from_item;
 use crate::terminal;
+use crate::truncate::TokenCounter;
 use crate::truncate::TruncationPolicy;
 use crate::turn_metadata::build_turn_metadata_header;
 use crate::util::error_or_panic;
@@ -66,6 +70,7 @@ use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::protocol::TurnAbortReason;
 use codex_protocol::protocol::TurnContextItem;
+use codex_protocol::protocol::TurnLimit;
 use codex_protocol::protocol::TurnStartedEvent;
 use codex_protocol::request_user_input::RequestUserInputArgs;
 use codex_protocol::request_user_input::RequestUserInputResponse;
@@ -112,6 +117,8 @@ use crate::config::GhostSnapshotConfig;
 use crate::config::resolve_web_search_mode_for_turn;
 use crate::config::types::McpServerConfig;
 use crate::config::types::ShellEnvironmentPolicy;
+use crate::config_reload;
+use crate::config_reload::ConfigReload;
 use crate::context_manager::ContextManager;
 use crate::environment_context::EnvironmentContext;
 use crate::error::CodexErr;
@@ -126,6 +133,7 @@ pub enum SteerInputError {
     EmptyInput,
 }
 use crate::exec_policy::ExecPolicyUpdateError;
+use crate::external_edits;
 use crate::feedback_tags;
 use crate::file_watcher::FileWatcher;
 use crate::file_watcher::FileWatcherEvent;
@@ -150,6 +158,10 @@ use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::CompactedItem;
+use crate::protocol::ConfigReloadedEvent;
+use crate::protocol::ContextBreakdownEvent;
+use crate::protocol::ContextItemsDroppedEvent;
 use crate::protocol::DeprecationNoticeEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
@@ -198,6 +210,7 @@ use crate::state::ActiveTurn;
 use crate::state::SessionServices;
 use crate::state::SessionState;
 use crate::state_db;
+use crate::symbol_index::SymbolIndex;
 use crate::tasks::GhostSnapshotTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
@@ -209,6 +222,8 @@ use crate::tools::sandboxing::ApprovalStore;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
+use crate::turn_limits::ToolCallBudget;
+use crate::turn_limits::TurnLimitTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::util::backoff;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
@@ -254,6 +269,9 @@ pub struct CodexSpawnOk {
 
 pub(crate) const INITIAL_SUBMIT_ID: &str = "";
 pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
+/// Tool-output pruning aims for this share of the auto-compact limit, so the
+/// next few turns fit before pruning (or compaction) is needed again.
+const TOOL_OUTPUT_PRUNING_TARGET_PERCENT: i64 = 70;
 
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
@@ -479,6 +497,21 @@ impl Codex {
         state.session_configuration.thread_config_snapshot()
     }
 
+    pub(crate) async fn context_breakdown(&self) -> ContextBreakdownEvent {
+        let turn_context = self.session.new_default_turn().await;
+        self.session.context_breakdown(turn_context.as_ref()).await
+    }
+
+    pub(crate) async fn drop_context_items(
+        &self,
+        item_ids: &[String],
+    ) -> CodexResult<ContextItemsDroppedEvent> {
+        let turn_context = self.session.new_default_turn().await;
+        self.session
+            .drop_context_items(turn_context.as_ref(), item_ids)
+            .await
+    }
+
     pub(crate) fn state_db(&self) -> Option<state_db::StateDbHandle> {
         self.session.state_db()
     }
@@ -533,8 +566,11 @@ pub(crate) struct TurnContext {
     pub(crate) codex_linux_sandbox_exe: Option<PathBuf>,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) truncation_policy: TruncationPolicy,
+    /// Counts tokens for this turn's model when estimating context usage.
+    pub(crate) token_counter: TokenCounter,
     pub(crate) dynamic_tools: Vec<DynamicToolSpec>,
     turn_metadata_header: OnceCell<Option<String>>,
+    sandbox_deny_read: std::sync::OnceLock<Vec<PathBuf>>,
 }
 impl TurnContext {
     pub(crate) fn model_context_window(&self) -> Option<i64> {
@@ -550,6 +586,23 @@ impl TurnContext {
             .map_or_else(|| self.cwd.clone(), |p| self.cwd.join(p))
     }
 
+    /// The `sandbox_deny_read` paths the sandbox has to mask for this turn.
+    /// They are looked up on first use and reused by every command of the
+    /// turn, since finding name entries walks the whole cwd.
+    pub(crate) fn sandbox_deny_read(&self) -> &[PathBuf] {
+        self.sandbox_deny_read.get_or_init(|| {
+            if self.features.enabled(Feature::UseLinuxSandboxBwrap)
+                || self.config.sandbox_deny_read.is_configured()
+            {
+                self.config.sandbox_deny_read.existing(&self.cwd)
+            } else {
+                // Landlock cannot subtract paths from a read rule, so the
+                // legacy pipeline only enforces lists the user opted into.
+                Vec::new()
+            }
+        })
+    }
+
     pub(crate) fn compact_prompt(&self) -> &str {
         self.compact_prompt
             .as_deref()
@@ -681,6 +734,52 @@ impl SessionConfiguration {
         }
         Ok(next_configuration)
     }
+
+    /// Switches to a config reloaded from disk. A setting follows the reloaded
+    /// config only while the session still uses the value from the previous
+    /// config, so changes made during the session (`/model`, `/approvals`, ...)
+    /// are kept.
+    fn with_reloaded_config(&self, reloaded: Arc<Config>) -> Self {
+        let previous = Arc::clone(&self.original_config_do_not_use);
+        let mut next_configuration = self.clone();
+
+        let model_unchanged = previous
+            .model
+            .as_deref()
+            .is_none_or(|model| model == self.collaboration_mode.model());
+        let model = (reloaded.model != previous.model && model_unchanged)
+            .then(|| reloaded.model.clone())
+            .flatten();
+        let effort = (reloaded.model_reasoning_effort != previous.model_reasoning_effort
+            && self.collaboration_mode.reasoning_effort() == previous.model_reasoning_effort)
+            .then_some(reloaded.model_reasoning_effort);
+        next_configuration.collaboration_mode =
+            self.collaboration_mode.with_updates(model, effort, None);
+
+        if self.model_reasoning_summary == previous.model_reasoning_summary {
+            next_configuration.model_reasoning_summary = reloaded.model_reasoning_summary;
+        }
+        if self.personality == previous.personality {
+            next_configuration.personality = reloaded.personality;
+        }
+        if self.approval_policy.value() == previous.approval_policy.value()
+            && let Err(err) = next_configuration
+                .approval_policy
+                .set(reloaded.approval_policy.value())
+        {
+            warn!("ignoring reloaded approval_policy: {err}");
+        }
+        if self.sandbox_policy.get() == previous.sandbox_policy.get()
+            && let Err(err) = next_configuration
+                .sandbox_policy
+                .set(reloaded.sandbox_policy.get().clone())
+        {
+            warn!("ignoring reloaded sandbox policy: {err}");
+        }
+
+        next_configuration.original_config_do_not_use = reloaded;
+        next_configuration
+    }
 }
 
 #[derive(Default, Clone)]
@@ -761,6 +860,19 @@ impl Session {
                         };
                         sess.send_event_raw(event).await;
                     }
+                    Ok(FileWatcherEvent::TrackedFilesChanged { paths }) => {
+                        let Some(sess) = weak_sess.upgrade() else {
+                            break;
+                        };
+                        let mut state = sess.state.lock().await;
+                        state.external_edits.mark_changed(paths);
+                    }
+                    Ok(FileWatcherEvent::ConfigChanged { .. }) => {
+                        let Some(sess) = weak_sess.upgrade() else {
+                            break;
+                        };
+                        sess.reload_config().await;
+                    }
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                 }
@@ -768,6 +880,201 @@ impl Session {
         });
     }
 
+    /// Re-reads config files after they changed on disk. Settings that can
+    /// change mid-session are staged for the next turn, and clients are told
+    /// which changed keys were applied, which need a restart, and which were
+    /// held back because they would loosen approvals or the sandbox.
+    async fn reload_config(&self) {
+        let current = {
+            let state = self.state.lock().await;
+            state.pending_config_reload.clone().unwrap_or_else(|| {
+                Arc::clone(&state.session_configuration.original_config_do_not_use)
+            })
+        };
+        let ConfigReload {
+            config,
+            applied,
+            requires_restart,
+            needs_confirmation,
+        } = match config_reload::reload_config(&current).await {
+            Ok(Some(reload)) => reload,
+            Ok(None) => return,
+            Err(err) => {
+                warn!("failed to reload config: {err}");
+                self.send_event_raw(Event {
+                    id: self.next_internal_sub_id(),
+                    msg: EventMsg::Warning(WarningEvent {
+                        message: format!(
+                            "Failed to reload config; keeping the previous settings: {err}"
+                        ),
+                    }),
+                })
+                .await;
+                return;
+            }
+        };
+
+        if config.mcp_servers != current.mcp_servers
+            || config.mcp_oauth_credentials_store_mode != current.mcp_oauth_credentials_store_mode
+        {
+            match (
+                serde_json::to_value(config.mcp_servers.get()),
+                serde_json::to_value(config.mcp_oauth_credentials_store_mode),
+            ) {
+                (Ok(mcp_servers), Ok(mcp_oauth_credentials_store_mode)) => {
+                    *self.pending_mcp_server_refresh_config.lock().await =
+                        Some(McpServerRefreshConfig {
+                            mcp_servers,
+                            mcp_oauth_credentials_store_mode,
+                        });
+                }
+                (Err(err), _) | (_, Err(err)) => {
+                    warn!("failed to serialize reloaded MCP server config: {err}");
+                }
+            }
+        }
+
+        let next = {
+            let mut state = self.state.lock().await;
+            let config = Arc::new(config);
+            state.pending_config_reload = Some(Arc::clone(&config));
+            state.session_configuration.with_reloaded_config(config)
+        };
+        self.send_event_raw(Event {
+            id: self.next_internal_sub_id(),
+            msg: EventMsg::ConfigReloaded(ConfigReloadedEvent {
+                applied,
+                requires_restart,
+                needs_confirmation,
+                model: next.collaboration_mode.model().to_string(),
+                reasoning_effort: next.collaboration_mode.reasoning_effort(),
+                personality: next.personality,
+                approval_policy: next.approval_policy.value(),
+                sandbox_policy: next.sandbox_policy.get().clone(),
+            }),
+        })
+        .await;
+    }
+
+    /// Snapshots files the agent has just read or written so that edits made
+    /// to them outside the conversation can be reported later.
+    pub(crate) async fn track_agent_files(&self, paths: impl IntoIterator<Item = PathBuf>) {
+        if !self.enabled(Feature::ExternalEditDetection) {
+            return;
+        }
+        for path in paths {
+            let Some(path) = external_edits::tracked_path(&path) else {
+                continue;
+            };
+            let already_tracked = {
+                let state = self.state.lock().await;
+                state.external_edits.is_tracked(&path)
+            };
+            if !already_tracked && external_edits::is_gitignored(&path) {
+                continue;
+            }
+            let content = external_edits::read_tracked_file(&path).await;
+            {
+                let mut state = self.state.lock().await;
+                state.external_edits.record(path.clone(), content);
+            }
+            if !already_tracked {
+                self.services.file_watcher.watch_file(path);
+            }
+        }
+    }
+
+    /// Called before an agent command runs: edits the user already made to
+    /// tracked files are described now, before the command's own changes are
+    /// folded into the snapshots by [`Self::resnapshot_tracked_files`].
+    pub(crate) async fn collect_external_edits_before_agent_command(&self) {
+        if !self.enabled(Feature::ExternalEditDetection) {
+            return;
+        }
+        let tracked = {
+            let state = self.state.lock().await;
+            state.external_edits.tracked_paths()
+        };
+        for path in tracked {
+            let after = external_edits::read_tracked_file(&path).await;
+            let mut state = self.state.lock().await;
+            let description = external_edits::describe_external_edit(
+                &path,
+                state.external_edits.snapshot(&path),
+                after.as_deref(),
+            );
+            if let Some(description) = description {
+                state.external_edits.push_pending_description(description);
+                state.external_edits.record(path, after);
+            }
+        }
+    }
+
+    /// Called after an agent command ends: whatever it changed in tracked
+    /// files becomes the new snapshot, so it is not reported as a user edit.
+    /// Files the command deleted stop being tracked.
+    pub(crate) async fn resnapshot_tracked_files(&self) {
+        if !self.enabled(Feature::ExternalEditDetection) {
+            return;
+        }
+        let tracked = {
+            let state = self.state.lock().await;
+            state.external_edits.tracked_paths()
+        };
+        for path in tracked {
+            let after = external_edits::read_tracked_file(&path).await;
+            if after.is_none() && !path.exists() {
+                self.untrack_file(&path).await;
+                continue;
+            }
+            let mut state = self.state.lock().await;
+            state.external_edits.record(path, after);
+        }
+    }
+
+    async fn untrack_file(&self, path: &Path) {
+        let was_tracked = {
+            let mut state = self.state.lock().await;
+            state.external_edits.forget(path)
+        };
+        if was_tracked {
+            self.services.file_watcher.unwatch_file(path);
+        }
+    }
+
+    /// Returns a developer message listing tracked files that changed on disk
+    /// since the agent last saw them, if any did.
+    async fn take_external_edits_message(&self) -> Option<ResponseItem> {
+        let (changed, mut descriptions) = {
+            let mut state = self.state.lock().await;
+            (
+                state.external_edits.take_changed(),
+                state.external_edits.take_pending_descriptions(),
+            )
+        };
+        for (path, before) in changed {
+            let after = external_edits::read_tracked_file(&path).await;
+            if let Some(description) =
+                external_edits::describe_external_edit(&path, before.as_deref(), after.as_deref())
+            {
+                descriptions.push(description);
+            }
+            if after.is_none() && !path.exists() {
+                self.untrack_file(&path).await;
+                continue;
+            }
+            let mut state = self.state.lock().await;
+            state.external_edits.record(path, after);
+        }
+        if descriptions.is_empty() {
+            return None;
+        }
+        Some(
+            DeveloperInstructions::new(external_edits::external_edits_message(&descriptions))
+                .into(),
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn make_turn_context(
         auth_manager: Option<Arc<AuthManager>>,
@@ -794,7 +1101,9 @@ impl Session {
             model_info: &model_info,
             features: &per_turn_config.features,
             web_search_mode: per_turn_config.web_search_mode,
-        });
+        })
+        .with_command_tools(per_turn_config.command_tools.clone())
+        .with_lsp_tools(!per_turn_config.lsp_servers.is_empty());
 
         let cwd = session_configuration.cwd.clone();
         TurnContext {
@@ -824,8 +1133,10 @@ impl Session {
             codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
             truncation_policy: model_info.truncation_policy.into(),
+            token_counter: token_counter_for_model(&model_info, &per_turn_config.features),
             dynamic_tools: session_configuration.dynamic_tools.clone(),
             turn_metadata_header: OnceCell::new(),
+            sandbox_deny_read: std::sync::OnceLock::new(),
         }
     }
 
@@ -1051,6 +1362,8 @@ impl Session {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             file_watcher,
+            lsp_manager: LspManager::new(config.lsp_servers.clone()),
+            symbol_index: Arc::new(SymbolIndex::new()),
             agent_control,
             state_db: state_db_ctx.clone(),
             model_client: ModelClient::new(
@@ -1331,11 +1644,16 @@ impl Session {
     ) -> ConstraintResult<Arc<TurnContext>> {
         let (session_configuration, sandbox_policy_changed) = {
             let mut state = self.state.lock().await;
-            match state.session_configuration.clone().apply(&updates) {
+            let current = match state.pending_config_reload.clone() {
+                Some(reloaded) => state.session_configuration.with_reloaded_config(reloaded),
+                None => state.session_configuration.clone(),
+            };
+            match current.apply(&updates) {
                 Ok(next) => {
                     let sandbox_policy_changed =
                         state.session_configuration.sandbox_policy != next.sandbox_policy;
                     state.session_configuration = next.clone();
+                    state.pending_config_reload = None;
                     (next, sandbox_policy_changed)
                 }
                 Err(err) => {
@@ -1806,6 +2124,49 @@ impl Session {
         changes: HashMap<PathBuf, FileChange>,
         reason: Option<String>,
         grant_root: Option<PathBuf>,
+    ) -> oneshot::Receiver<ReviewDecision> {
+        self.request_file_change_approval(
+            turn_context,
+            ApplyPatchApprovalRequestEvent {
+                call_id,
+                turn_id: turn_context.sub_id.clone(),
+                changes,
+                reason,
+                grant_root,
+                overlay_review: false,
+            },
+        )
+        .await
+    }
+
+    /// Asks the user to review changes a command made in the workspace
+    /// overlay. See [`ApplyPatchApprovalRequestEvent::overlay_review`] for how
+    /// the decision is interpreted.
+    pub async fn request_overlay_review(
+        &self,
+        turn_context: &TurnContext,
+        call_id: String,
+        changes: HashMap<PathBuf, FileChange>,
+        reason: Option<String>,
+    ) -> oneshot::Receiver<ReviewDecision> {
+        self.request_file_change_approval(
+            turn_context,
+            ApplyPatchApprovalRequestEvent {
+                call_id,
+                turn_id: turn_context.sub_id.clone(),
+                changes,
+                reason,
+                grant_root: None,
+                overlay_review: true,
+            },
+        )
+        .await
+    }
+
+    pub(crate) async fn request_file_change_approval(
+        &self,
+        turn_context: &TurnContext,
+        request: ApplyPatchApprovalRequestEvent,
     ) -> oneshot::Receiver<ReviewDecision> {
         let sub_id = turn_context.sub_id.clone();
         // Add the tx_approve callback to the map before sending the request.
@@ -1825,13 +2186,7 @@ impl Session {
             warn!("Overwriting existing pending approval for sub_id: {event_id}");
         }
 
-        let event = EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
-            call_id,
-            turn_id: turn_context.sub_id.clone(),
-            changes,
-            reason,
-            grant_root,
-        });
+        let event = EventMsg::ApplyPatchApprovalRequest(request);
         self.send_event(turn_context, event).await;
         rx_approve
     }
@@ -1967,6 +2322,7 @@ impl Session {
         rollout_items: &[RolloutItem],
     ) -> Vec<ResponseItem> {
         let mut history = ContextManager::new();
+        history.set_token_counter(turn_context.token_counter);
         for item in rollout_items {
             match item {
                 RolloutItem::ResponseItem(response_item) => {
@@ -2004,6 +2360,7 @@ impl Session {
         turn_context: &TurnContext,
     ) {
         let mut state = self.state.lock().await;
+        state.history.set_token_counter(turn_context.token_counter);
         state.record_items(items.iter(), turn_context.truncation_policy);
     }
 
@@ -2204,6 +2561,101 @@ impl Session {
         self.send_token_count_event(turn_context).await;
     }
 
+    /// Breaks the current history down by category for context inspection.
+    pub(crate) async fn context_breakdown(
+        &self,
+        turn_context: &TurnContext,
+    ) -> ContextBreakdownEvent {
+        let history = self.clone_history().await;
+        let base_instructions = self.get_base_instructions().await;
+        history.breakdown(&base_instructions, turn_context.model_context_window())
+    }
+
+    /// Replaces the given tool outputs and images with placeholders. The
+    /// rewritten history is persisted so resumed threads see the same context.
+    ///
+    /// Fails while a turn is running. The `active_turn` lock is held until the
+    /// history has been rewritten so a turn cannot start in between.
+    pub(crate) async fn drop_context_items(
+        &self,
+        turn_context: &TurnContext,
+        item_ids: &[String],
+    ) -> CodexResult<ContextItemsDroppedEvent> {
+        let active = self.active_turn.lock().await;
+        if active.is_some() {
+            return Err(CodexErr::InvalidRequest(
+                "Cannot drop context items while a turn is in progress.".to_string(),
+            ));
+        }
+        let base_instructions = self.get_base_instructions().await;
+        let mut history = self.clone_history().await;
+        let tokens_before = history
+            .estimate_token_count_with_base_instructions(&base_instructions)
+            .unwrap_or_default();
+        let item_ids = history.drop_items(item_ids);
+        if item_ids.is_empty() {
+            return Ok(ContextItemsDroppedEvent {
+                item_ids,
+                tokens_freed: 0,
+            });
+        }
+        let tokens_after = history
+            .estimate_token_count_with_base_instructions(&base_instructions)
+            .unwrap_or_default();
+
+        let items = history.raw_items().to_vec();
+        self.replace_history(items.clone()).await;
+        self.persist_rollout_items(&[RolloutItem::Compacted(CompactedItem {
+            message: String::new(),
+            replacement_history: Some(items),
+        })])
+        .await;
+        self.recompute_token_usage(turn_context).await;
+        drop(active);
+
+        Ok(ContextItemsDroppedEvent {
+            item_ids,
+            tokens_freed: tokens_before.saturating_sub(tokens_after).max(0),
+        })
+    }
+
+    /// Cheaper first stage of auto-compaction: shrinks outputs of older tool
+    /// calls until usage is back under a fraction of `auto_compact_limit`.
+    /// Returns true when that was enough to skip summarization.
+    pub(crate) async fn prune_tool_outputs(
+        &self,
+        turn_context: &TurnContext,
+        auto_compact_limit: i64,
+    ) -> bool {
+        if !self.enabled(Feature::ToolOutputPruning) {
+            return false;
+        }
+        let base_instructions = self.get_base_instructions().await;
+        let mut history = self.clone_history().await;
+        let target_tokens =
+            auto_compact_limit.saturating_mul(TOOL_OUTPUT_PRUNING_TARGET_PERCENT) / 100;
+        let pruned = history.prune_tool_outputs(&base_instructions, target_tokens);
+        if pruned == 0 {
+            return false;
+        }
+
+        let items = history.raw_items().to_vec();
+        self.replace_history(items.clone()).await;
+        self.persist_rollout_items(&[RolloutItem::Compacted(CompactedItem {
+            message: String::new(),
+            replacement_history: Some(items),
+        })])
+        .await;
+        self.recompute_token_usage(turn_context).await;
+
+        let total_usage_tokens = self.get_total_token_usage().await;
+        info!(
+            pruned,
+            total_usage_tokens, auto_compact_limit, "pruned old tool outputs before compaction"
+        );
+        total_usage_tokens < auto_compact_limit
+    }
+
     pub(crate) async fn update_rate_limits(
         &self,
         turn_context: &TurnContext,
@@ -2724,6 +3176,12 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ThreadRollback { num_turns } => {
                 handlers::thread_rollback(&sess, sub.id.clone(), num_turns).await;
             }
+            Op::GetContextBreakdown => {
+                handlers::get_context_breakdown(&sess, sub.id.clone()).await;
+            }
+            Op::DropContextItems { item_ids } => {
+                handlers::drop_context_items(&sess, sub.id.clone(), item_ids).await;
+            }
             Op::SetThreadName { name } => {
                 handlers::set_thread_name(&sess, sub.id.clone(), name).await;
             }
@@ -3300,6 +3758,35 @@ mod handlers {
         .await;
     }
 
+    pub async fn get_context_breakdown(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let breakdown = sess.context_breakdown(turn_context.as_ref()).await;
+        sess.send_event_raw(Event {
+            id: turn_context.sub_id.clone(),
+            msg: EventMsg::ContextBreakdown(breakdown),
+        })
+        .await;
+    }
+
+    pub async fn drop_context_items(sess: &Arc<Session>, sub_id: String, item_ids: Vec<String>) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let msg = match sess
+            .drop_context_items(turn_context.as_ref(), &item_ids)
+            .await
+        {
+            Ok(dropped) => EventMsg::ContextItemsDropped(dropped),
+            Err(err) => EventMsg::Error(ErrorEvent {
+                message: err.to_string(),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+            }),
+        };
+        sess.send_event_raw(Event {
+            id: turn_context.sub_id.clone(),
+            msg,
+        })
+        .await;
+    }
+
     /// Persists the thread name in the session index, updates in-memory state, and emits
     /// a `ThreadNameUpdated` event on success.
     ///
@@ -3372,6 +3859,13 @@ mod handlers {
             .unified_exec_manager
             .terminate_all_processes()
             .await;
+        let tracked = {
+            let state = sess.state.lock().await;
+            state.external_edits.tracked_paths()
+        };
+        for path in tracked {
+            sess.untrack_file(&path).await;
+        }
         info!("Shutting down Codex instance");
         let history = sess.clone_history().await;
         let turn_count = history
@@ -3527,7 +4021,9 @@ async fn spawn_review_thread(
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         dynamic_tools: parent_turn_context.dynamic_tools.clone(),
         truncation_policy: model_info.truncation_policy.into(),
+        token_counter: token_counter_for_model(&model_info, &review_features),
         turn_metadata_header: parent_turn_context.turn_metadata_header.clone(),
+        sandbox_deny_read: std::sync::OnceLock::new(),
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -3629,14 +4125,22 @@ pub(crate) async fn run_turn(
     let model_info = turn_context.model_info.clone();
     let auto_compact_limit = model_info.auto_compact_token_limit().unwrap_or(i64::MAX);
     let total_usage_tokens = sess.get_total_token_usage().await;
+    let mut turn_limits = TurnLimitTracker::new(turn_context.config.turn_limits);
 
     let event = EventMsg::TurnStarted(TurnStartedEvent {
         model_context_window: turn_context.model_context_window(),
         collaboration_mode_kind: turn_context.collaboration_mode.mode,
     });
     sess.send_event(&turn_context, event).await;
-    if total_usage_tokens >= auto_compact_limit {
-        run_auto_compact(&sess, &turn_context).await;
+    if total_usage_tokens >= auto_compact_limit
+        && turn_limits
+            .within_deadline(run_auto_compact(&sess, &turn_context))
+            .await
+            .is_none()
+    {
+        let event = turn_limits.error_event(TurnLimit::Timeout);
+        sess.send_event(&turn_context, EventMsg::Error(event)).await;
+        return None;
     }
 
     let skills_outcome = Some(
@@ -3728,6 +4232,9 @@ pub(crate) async fn run_turn(
     sess.maybe_start_ghost_snapshot(Arc::clone(&turn_context), cancellation_token.child_token())
         .await;
     let mut last_agent_message: Option<String> = None;
+    // The latest assistant message, reported as the turn's result when a
+    // turn limit stops it early.
+    let mut partial_agent_message: Option<String> = None;
     // Although from the perspective of codex.rs, TurnDiffTracker has the lifecycle of a Task which contains
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
@@ -3768,6 +4275,12 @@ pub(crate) async fn run_turn(
             }
         }
 
+        // Let the model know about files the user changed since it last saw them.
+        if let Some(external_edits) = sess.take_external_edits_message().await {
+            sess.record_conversation_items(&turn_context, std::slice::from_ref(&external_edits))
+                .await;
+        }
+
         // Construct the input that we will send to the model.
         let sampling_request_input: Vec<ResponseItem> = { sess.clone_history().await.for_prompt() };
 
@@ -3783,7 +4296,8 @@ pub(crate) async fn run_turn(
             explicit_app_paths: &explicit_app_paths,
             skill_name_counts_lower: &skill_name_counts_lower,
         };
-        match run_sampling_request(
+        let sampling_cancellation_token = cancellation_token.child_token();
+        let sampling_request = run_sampling_request(
             Arc::clone(&sess),
             Arc::clone(&turn_context),
             Arc::clone(&turn_diff_tracker),
@@ -3791,15 +4305,36 @@ pub(crate) async fn run_turn(
             turn_metadata_header.as_deref(),
             sampling_request_input,
             tool_selection,
-            cancellation_token.child_token(),
-        )
-        .await
-        {
+            turn_limits.tool_call_budget(),
+            sampling_cancellation_token.clone(),
+        );
+        let Some(sampling_request_result) = turn_limits.within_deadline(sampling_request).await
+        else {
+            // Stop tool calls that are still running.
+            sampling_cancellation_token.cancel();
+            let event = turn_limits.error_event(TurnLimit::Timeout);
+            sess.send_event(&turn_context, EventMsg::Error(event)).await;
+            last_agent_message = partial_agent_message;
+            break;
+        };
+        match sampling_request_result {
             Ok(sampling_request_output) => {
                 let SamplingRequestResult {
                     needs_follow_up,
                     last_agent_message: sampling_request_last_agent_message,
+                    tool_outputs,
                 } = sampling_request_output;
+                if sampling_request_last_agent_message.is_some() {
+                    partial_agent_message = sampling_request_last_agent_message.clone();
+                }
+                if needs_follow_up
+                    && let Some(limit) = turn_limits.record_model_request(&tool_outputs)
+                {
+                    let event = turn_limits.error_event(limit);
+                    sess.send_event(&turn_context, EventMsg::Error(event)).await;
+                    last_agent_message = partial_agent_message;
+                    break;
+                }
                 let total_usage_tokens = sess.get_total_token_usage().await;
                 let token_limit_reached = total_usage_tokens >= auto_compact_limit;
 
@@ -3818,7 +4353,16 @@ pub(crate) async fn run_turn(
 
                 // as long as compaction works well in getting us way below the token limit, we shouldn't worry about being in an infinite loop.
                 if token_limit_reached && needs_follow_up {
-                    run_auto_compact(&sess, &turn_context).await;
+                    if turn_limits
+                        .within_deadline(run_auto_compact(&sess, &turn_context))
+                        .await
+                        .is_none()
+                    {
+                        let event = turn_limits.error_event(TurnLimit::Timeout);
+                        sess.send_event(&turn_context, EventMsg::Error(event)).await;
+                        last_agent_message = partial_agent_message;
+                        break;
+                    }
                     continue;
                 }
 
@@ -3877,6 +4421,13 @@ pub(crate) async fn run_turn(
 }
 
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
+    if let Some(auto_compact_limit) = turn_context.model_info.auto_compact_token_limit()
+        && sess
+            .prune_tool_outputs(turn_context, auto_compact_limit)
+            .await
+    {
+        return;
+    }
     if should_use_remote_compact_task(sess.as_ref(), &turn_context.provider) {
         run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
     } else {
@@ -3999,6 +4550,7 @@ async fn run_sampling_request(
     turn_metadata_header: Option<&str>,
     input: Vec<ResponseItem>,
     tool_selection: SamplingRequestToolSelection<'_>,
+    tool_call_budget: ToolCallBudget,
     cancellation_token: CancellationToken,
 ) -> CodexResult<SamplingRequestResult> {
     let mut mcp_tools = sess
@@ -4057,6 +4609,7 @@ async fn run_sampling_request(
             turn_metadata_header,
             Arc::clone(&turn_diff_tracker),
             &prompt,
+            tool_call_budget.clone(),
             cancellation_token.child_token(),
         )
         .await
@@ -4130,6 +4683,8 @@ async fn run_sampling_request(
 struct SamplingRequestResult {
     needs_follow_up: bool,
     last_agent_message: Option<String>,
+    /// Outputs of the tool calls made in this request, in call order.
+    tool_outputs: Vec<ResponseInputItem>,
 }
 
 /// Ephemeral per-response state for streaming a single proposed plan.
@@ -4511,19 +5066,21 @@ async fn drain_in_flight(
     in_flight: &mut FuturesOrdered<BoxFuture<'static, CodexResult<ResponseInputItem>>>,
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
-) -> CodexResult<()> {
+) -> CodexResult<Vec<ResponseInputItem>> {
+    let mut outputs = Vec::new();
     while let Some(res) = in_flight.next().await {
         match res {
             Ok(response_input) => {
-                sess.record_conversation_items(&turn_context, &[response_input.into()])
+                sess.record_conversation_items(&turn_context, &[response_input.clone().into()])
                     .await;
+                outputs.push(response_input);
             }
             Err(err) => {
                 error_or_panic(format!("in-flight tool future failed during drain: {err}"));
             }
         }
     }
-    Ok(())
+    Ok(outputs)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -4542,6 +5099,7 @@ async fn try_run_sampling_request(
     turn_metadata_header: Option<&str>,
     turn_diff_tracker: SharedTurnDiffTracker,
     prompt: &Prompt,
+    tool_call_budget: ToolCallBudget,
     cancellation_token: CancellationToken,
 ) -> CodexResult<SamplingRequestResult> {
     let collaboration_mode = sess.current_collaboration_mode().await;
@@ -4588,6 +5146,7 @@ async fn try_run_sampling_request(
         Arc::clone(&sess),
         Arc::clone(&turn_context),
         Arc::clone(&turn_diff_tracker),
+        tool_call_budget,
     );
     let mut in_flight: FuturesOrdered<BoxFuture<'static, CodexResult<ResponseInputItem>>> =
         FuturesOrdered::new();
@@ -4727,6 +5286,7 @@ async fn try_run_sampling_request(
                 break Ok(SamplingRequestResult {
                     needs_follow_up,
                     last_agent_message,
+                    tool_outputs: Vec::new(),
                 });
             }
             ResponseEvent::OutputTextDelta(delta) => {
@@ -4807,7 +5367,7 @@ async fn try_run_sampling_request(
         }
     };
 
-    drain_in_flight(&mut in_flight, sess.clone(), turn_context.clone()).await?;
+    let tool_outputs = drain_in_flight(&mut in_flight, sess.clone(), turn_context.clone()).await?;
 
     if should_emit_turn_diff {
         let unified_diff = {
@@ -4820,7 +5380,10 @@ async fn try_run_sampling_request(
         }
     }
 
-    outcome
+    outcome.map(|result| SamplingRequestResult {
+        tool_outputs,
+        ..result
+    })
 }
 
 pub(super) fn get_last_assistant_message_from_turn(responses: &[ResponseItem]) -> Option<String> {
@@ -5166,6 +5729,84 @@ mod tests {
         assert_eq!(actual, Some(info2));
     }
 
+    #[tokio::test]
+    async fn external_edits_message_reports_only_edits_made_outside_the_agent() {
+        let (session, _turn_context) = make_session_and_context().await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "one\ntwo\n").expect("write file");
+        let tracked = external_edits::tracked_path(&file).expect("tracked path");
+
+        session.track_agent_files([file.clone()]).await;
+        std::fs::write(&file, "one\nzwei\n").expect("external edit");
+        {
+            let mut state = session.state.lock().await;
+            state.external_edits.mark_changed([tracked.clone()]);
+        }
+
+        let Some(ResponseItem::Message { role, content, .. }) =
+            session.take_external_edits_message().await
+        else {
+            panic!("expected a developer message");
+        };
+        assert_eq!(role, "developer");
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            panic!("expected a single text item");
+        };
+        assert!(text.starts_with("<external_edits>"));
+        assert!(text.contains("-two\n+zwei"));
+
+        // Edits the agent makes itself refresh the snapshot and are not reported.
+        std::fs::write(&file, "one\ndrei\n").expect("agent edit");
+        session.track_agent_files([file]).await;
+        {
+            let mut state = session.state.lock().await;
+            state.external_edits.mark_changed([tracked]);
+        }
+        assert_eq!(session.take_external_edits_message().await, None);
+    }
+
+    #[tokio::test]
+    async fn agent_command_edits_are_not_reported_as_external_edits() {
+        let (session, _turn_context) = make_session_and_context().await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "one\ntwo\n").expect("write file");
+        let tracked = external_edits::tracked_path(&file).expect("tracked path");
+        session.track_agent_files([file.clone()]).await;
+
+        // The user edits the file, then the agent runs `sed -i` on it.
+        std::fs::write(&file, "one\nzwei\n").expect("external edit");
+        session.collect_external_edits_before_agent_command().await;
+        std::fs::write(&file, "one\ndrei\n").expect("agent command edit");
+        session.resnapshot_tracked_files().await;
+        {
+            let mut state = session.state.lock().await;
+            state.external_edits.mark_changed([tracked]);
+        }
+
+        let Some(ResponseItem::Message { content, .. }) =
+            session.take_external_edits_message().await
+        else {
+            panic!("expected a developer message");
+        };
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            panic!("expected a single text item");
+        };
+        assert!(text.contains("-two\n+zwei"));
+        assert!(!text.contains("drei"));
+
+        // A file the agent deletes stops being tracked.
+        session.collect_external_edits_before_agent_command().await;
+        std::fs::remove_file(&file).expect("agent command delete");
+        session.resnapshot_tracked_files().await;
+        assert_eq!(session.take_external_edits_message().await, None);
+        assert_eq!(
+            session.state.lock().await.external_edits.tracked_paths(),
+            Vec::<PathBuf>::new()
+        );
+    }
+
     #[tokio::test]
     async fn recompute_token_usage_uses_session_base_instructions() {
         let (session, turn_context) = make_session_and_context().await;
@@ -5785,6 +6426,8 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             file_watcher,
+            lsp_manager: LspManager::new(config.lsp_servers.clone()),
+            symbol_index: Arc::new(SymbolIndex::new()),
             agent_control,
             state_db: None,
             model_client: ModelClient::new(
@@ -5915,6 +6558,8 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             file_watcher,
+            lsp_manager: LspManager::new(config.lsp_servers.clone()),
+            symbol_index: Arc::new(SymbolIndex::new()),
             agent_control,
             state_db: None,
             model_client: ModelClient::new(
@@ -6534,6 +7179,7 @@ mod tests {
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: Some("test".to_string()),
             arg0: None,
+            stdin: None,
         };
 
         let params2 = ExecParams {
@@ -6545,6 +7191,7 @@ mod tests {
             windows_sandbox_level: turn_context.windows_sandbox_level,
             justification: params.justification.clone(),
             arg0: None,
+            stdin: None,
         };
 
         let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
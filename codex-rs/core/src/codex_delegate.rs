@@ -350,15 +350,12 @@ async fn handle_patch_approval(
     event: ApplyPatchApprovalRequestEvent,
     cancel_token: &CancellationToken,
 ) {
-    let ApplyPatchApprovalRequestEvent {
-        call_id,
-        changes,
-        reason,
-        grant_root,
-        ..
-    } = event;
+    let request = ApplyPatchApprovalRequestEvent {
+        turn_id: parent_ctx.sub_id.clone(),
+        ..event
+    };
     let decision_rx = parent_session
-        .request_patch_approval(parent_ctx, call_id, changes, reason, grant_root)
+        .request_file_change_approval(parent_ctx, request)
         .await;
     let decision = await_approval_with_cancel(
         async move { decision_rx.await.unwrap_or_default() },
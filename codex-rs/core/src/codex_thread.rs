@@ -2,6 +2,8 @@ use crate::agent::AgentStatus;
 use crate::codex::Codex;
 use crate::codex::SteerInputError;
 use crate::error::Result as CodexResult;
+use crate::protocol::ContextBreakdownEvent;
+use crate::protocol::ContextItemsDroppedEvent;
 use crate::protocol::Event;
 use crate::protocol::Op;
 use crate::protocol::Submission;
@@ -83,4 +85,18 @@ impl CodexThread {
     pub async fn config_snapshot(&self) -> ThreadConfigSnapshot {
         self.codex.thread_config_snapshot().await
     }
+
+    /// Breaks the thread's current context down by category.
+    pub async fn context_breakdown(&self) -> ContextBreakdownEvent {
+        self.codex.context_breakdown().await
+    }
+
+    /// Replaces tool outputs or images with placeholders before the next turn.
+    /// Fails while a turn is running.
+    pub async fn drop_context_items(
+        &self,
+        item_ids: &[String],
+    ) -> CodexResult<ContextItemsDroppedEvent> {
+        self.codex.drop_context_items(item_ids).await
+    }
 }
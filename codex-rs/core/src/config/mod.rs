@@ -1,8 +1,10 @@
 use crate::auth::AuthCredentialsStoreMode;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::types::CommandToolConfig;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::LspServerConfig;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
@@ -16,10 +18,13 @@ use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
+use crate::config::types::StatusLineSegment;
 use crate::config::types::Tui;
+use crate::config::types::TurnLimits;
 use crate::config::types::UriBasedFileOpener;
 use crate::config_loader::CloudRequirementsLoader;
 use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::ConstrainedWithSource;
 use crate::config_loader::LoaderOverrides;
@@ -43,6 +48,7 @@ use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::sandboxing::deny_read::DenyReadPaths;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
@@ -120,6 +126,10 @@ pub struct Config {
     /// Warnings collected during config load that should be shown on startup.
     pub startup_warnings: Vec<String>,
 
+    /// Harness overrides this config was built with, kept so the config can
+    /// be rebuilt with the same overrides when its files change on disk.
+    pub harness_overrides: ConfigOverrides,
+
     /// Optional override of model selection.
     pub model: Option<String>,
 
@@ -146,6 +156,9 @@ pub struct Config {
 
     pub sandbox_policy: Constrained<SandboxPolicy>,
 
+    /// Paths that sandboxed commands and the built-in file tools may not read.
+    pub sandbox_deny_read: DenyReadPaths,
+
     /// enforce_residency means web traffic cannot be routed outside of a
     /// particular geography. HTTP clients should direct their requests
     /// using backend-specific headers or URLs to enforce this.
@@ -231,6 +244,9 @@ pub struct Config {
     /// Ordered list of status line item identifiers for the TUI.
     pub tui_status_line: Option<Vec<String>>,
 
+    /// Custom status line segments that can be referenced from `tui_status_line`.
+    pub tui_status_line_segments: BTreeMap<String, StatusLineSegment>,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -259,6 +275,10 @@ pub struct Config {
     /// When unset, Codex will bind to an ephemeral port chosen by the OS.
     pub mcp_oauth_callback_port: Option<u16>,
 
+    /// Language servers launched on demand for diagnostics and code
+    /// navigation, keyed by name.
+    pub lsp_servers: BTreeMap<String, LspServerConfig>,
+
     /// Combined provider map (defaults merged with user-defined overrides).
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
@@ -271,6 +291,9 @@ pub struct Config {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Limits that stop a turn before the model is done.
+    pub turn_limits: TurnLimits,
+
     /// Maximum number of agent threads that can be open concurrently.
     pub agent_max_threads: Option<usize>,
 
@@ -330,6 +353,10 @@ pub struct Config {
     /// Explicit or feature-derived web search mode.
     pub web_search_mode: Option<WebSearchMode>,
 
+    /// Command-backed function tools declared under `[tools.commands.<name>]`,
+    /// keyed by tool name.
+    pub command_tools: BTreeMap<String, CommandToolConfig>,
+
     /// If set to `true`, used only the experimental unified exec tool.
     pub use_experimental_unified_exec_tool: bool,
 
@@ -445,6 +472,29 @@ impl ConfigBuilder {
             cloud_requirements,
         )
         .await?;
+        Config::load_from_config_layer_stack(config_layer_stack, harness_overrides, codex_home)
+            .await
+    }
+}
+
+impl Config {
+    /// This is the preferred way to create an instance of [Config].
+    pub async fn load_with_cli_overrides(
+        cli_overrides: Vec<(String, TomlValue)>,
+    ) -> std::io::Result<Self> {
+        ConfigBuilder::default()
+            .cli_overrides(cli_overrides)
+            .build()
+            .await
+    }
+
+    /// Builds a [Config] from an already-loaded layer stack, e.g. one whose
+    /// files were re-read after changing on disk.
+    pub(crate) async fn load_from_config_layer_stack(
+        config_layer_stack: ConfigLayerStack,
+        harness_overrides: ConfigOverrides,
+        codex_home: PathBuf,
+    ) -> std::io::Result<Self> {
         let merged_toml = config_layer_stack.effective_config();
 
         // Note that each layer in ConfigLayerStack should have resolved
@@ -466,25 +516,13 @@ impl ConfigBuilder {
                 return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
             }
         };
-        Config::load_config_with_layer_stack(
+        Self::load_config_with_layer_stack(
             config_toml,
             harness_overrides,
             codex_home,
             config_layer_stack,
         )
     }
-}
-
-impl Config {
-    /// This is the preferred way to create an instance of [Config].
-    pub async fn load_with_cli_overrides(
-        cli_overrides: Vec<(String, TomlValue)>,
-    ) -> std::io::Result<Self> {
-        ConfigBuilder::default()
-            .cli_overrides(cli_overrides)
-            .build()
-            .await
-    }
 
     /// Load a default configuration when user config files are invalid.
     pub fn load_default_with_cli_overrides(
@@ -799,6 +837,15 @@ pub fn set_project_trust_level(
         .apply_blocking()
 }
 
+/// Whether `name` is accepted as a function tool name by the Responses API,
+/// i.e. matches `^[a-zA-Z0-9_-]{1,64}$`.
+fn is_valid_tool_name(name: &str) -> bool {
+    (1..=64).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
 /// Save the default OSS provider preference to config.toml
 pub fn set_default_oss_provider(codex_home: &Path, provider: &str) -> std::io::Result<()> {
     // Validate that the provider is one of the known OSS providers
@@ -864,6 +911,16 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Paths that sandboxed commands and the built-in file tools may not read.
+    /// `~/` expands to the home directory, entries without a `/` (such as
+    /// `.env`) match a file or directory of that name at any depth, and other
+    /// relative entries are resolved against the session cwd. Defaults to
+    /// `~/.ssh`, `~/.aws`, `~/.config/gh`, `~/.gnupg`, `~/.netrc` and `.env`;
+    /// the credential files in `CODEX_HOME` are always denied. The legacy
+    /// Landlock sandbox only enforces an explicitly set list, and cannot hide
+    /// paths inside writable roots.
+    pub sandbox_deny_read: Option<Vec<String>>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
@@ -917,6 +974,10 @@ pub struct ConfigToml {
     /// When unset, Codex will bind to an ephemeral port chosen by the OS.
     pub mcp_oauth_callback_port: Option<u16>,
 
+    /// Language servers for diagnostics and code navigation, keyed by name.
+    #[serde(default)]
+    pub lsp_servers: BTreeMap<String, LspServerConfig>,
+
     /// User-defined provider entries that extend/override the built-in list.
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderInfo>,
@@ -930,6 +991,9 @@ pub struct ConfigToml {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Limits that stop a turn before the model is done.
+    pub limits: Option<TurnLimits>,
+
     /// Profile to use from the `profiles` map.
     pub profile: Option<String>,
 
@@ -979,7 +1043,7 @@ pub struct ConfigToml {
     /// Controls the web search tool mode: disabled, cached, or live.
     pub web_search: Option<WebSearchMode>,
 
-    /// Nested tools section for feature toggles
+    /// Nested tools section for feature toggles and command tools
     pub tools: Option<ToolsToml>,
 
     /// Agent-related settings (thread limits, etc.).
@@ -1095,6 +1159,11 @@ pub struct ToolsToml {
     /// Enable the `view_image` tool that lets the agent attach local images.
     #[serde(default)]
     pub view_image: Option<bool>,
+
+    /// Command-backed function tools, one `[tools.commands.<name>]` table per
+    /// tool.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, CommandToolConfig>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
@@ -1178,6 +1247,12 @@ impl ConfigToml {
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
+            SandboxMode::WorkspaceOverlay => SandboxPolicy::WorkspaceOverlay {
+                network_access: self
+                    .sandbox_workspace_write
+                    .as_ref()
+                    .is_some_and(|settings| settings.network_access),
+            },
             SandboxMode::DangerFullAccess => SandboxPolicy::DangerFullAccess,
         };
         let mut forced_auto_mode_downgraded_on_windows = false;
@@ -1257,7 +1332,7 @@ impl ConfigToml {
 }
 
 /// Optional overrides for user configuration (e.g., from CLI flags).
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct ConfigOverrides {
     pub model: Option<String>,
     pub review_model: Option<String>,
@@ -1277,6 +1352,8 @@ pub struct ConfigOverrides {
     pub ephemeral: Option<bool>,
     /// Additional directories that should be treated as writable roots for this session.
     pub additional_writable_roots: Vec<PathBuf>,
+    /// Turn limits that take precedence over the `limits` tables in config.toml.
+    pub turn_limits: TurnLimits,
 }
 
 /// Resolves the OSS provider from CLI override, profile config, or global config.
@@ -1359,8 +1436,13 @@ impl Config {
     ) -> std::io::Result<Self> {
         let requirements = config_layer_stack.requirements().clone();
         let user_instructions = Self::load_instructions(Some(&codex_home));
-        let mut startup_warnings = Vec::new();
+        let mut startup_warnings: Vec<String> = config_layer_stack
+            .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false)
+            .into_iter()
+            .flat_map(|layer| layer.warnings.iter().cloned())
+            .collect();
 
+        let harness_overrides = overrides.clone();
         // Destructure ConfigOverrides fully to ensure all overrides are applied.
         let ConfigOverrides {
             model,
@@ -1380,6 +1462,7 @@ impl Config {
             tools_web_search_request: override_tools_web_search_request,
             ephemeral,
             additional_writable_roots,
+            turn_limits: turn_limits_override,
         } = overrides;
 
         let active_profile_name = config_profile_key
@@ -1521,6 +1604,20 @@ impl Config {
             ));
         }
 
+        let command_tools = cfg
+            .tools
+            .as_ref()
+            .map(|tools| tools.commands.clone())
+            .unwrap_or_default();
+        if let Some(name) = command_tools.keys().find(|name| !is_valid_tool_name(name)) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid command tool name {name:?} in [tools.commands]: tool names must be 1 to 64 letters, digits, `_` or `-`"
+                ),
+            ));
+        }
+
         let ghost_snapshot = {
             let mut config = GhostSnapshotConfig::default();
             if let Some(ghost_snapshot) = cfg.ghost_snapshot.as_ref()
@@ -1651,8 +1748,13 @@ impl Config {
             model_provider,
             cwd: resolved_cwd,
             startup_warnings,
+            harness_overrides,
             approval_policy: constrained_approval_policy.value,
             sandbox_policy: constrained_sandbox_policy.value,
+            sandbox_deny_read: DenyReadPaths::from_config(
+                cfg.sandbox_deny_read.as_deref(),
+                &codex_home,
+            ),
             enforce_residency: enforce_residency.value,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             forced_auto_mode_downgraded_on_windows,
@@ -1671,6 +1773,7 @@ impl Config {
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
             mcp_oauth_callback_port: cfg.mcp_oauth_callback_port,
+            lsp_servers: cfg.lsp_servers.clone(),
             model_providers,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
             project_doc_fallback_filenames: cfg
@@ -1687,6 +1790,9 @@ impl Config {
                 })
                 .collect(),
             tool_output_token_limit: cfg.tool_output_token_limit,
+            turn_limits: turn_limits_override
+                .or(config_profile.limits.unwrap_or_default())
+                .or(cfg.limits.unwrap_or_default()),
             agent_max_threads,
             codex_home,
             log_dir,
@@ -1718,6 +1824,7 @@ impl Config {
             forced_login_method,
             include_apply_patch_tool: include_apply_patch_tool_flag,
             web_search_mode,
+            command_tools,
             use_experimental_unified_exec_tool,
             ghost_snapshot,
             features,
@@ -1759,6 +1866,11 @@ impl Config {
                 .map(|t| t.alternate_screen)
                 .unwrap_or_default(),
             tui_status_line: cfg.tui.as_ref().and_then(|t| t.status_line.clone()),
+            tui_status_line_segments: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.status_line_segments.clone())
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1767,12 +1879,14 @@ impl Config {
                     .unwrap_or(DEFAULT_OTEL_ENVIRONMENT.to_string());
                 let exporter = t.exporter.unwrap_or(OtelExporterKind::None);
                 let trace_exporter = t.trace_exporter.unwrap_or_else(|| exporter.clone());
+                let metrics_exporter = t.metrics_exporter.unwrap_or(OtelExporterKind::Statsig);
                 OtelConfig {
                     log_user_prompt,
                     environment,
                     exporter,
                     trace_exporter,
-                    metrics_exporter: OtelExporterKind::Statsig,
+                    metrics_exporter,
+                    prometheus_listen: t.prometheus_listen,
                 }
             },
         };
@@ -1995,10 +2109,119 @@ persistence = "none"
                 experimental_mode: None,
                 alternate_screen: AltScreenMode::Auto,
                 status_line: None,
+                status_line_segments: BTreeMap::new(),
             }
         );
     }
 
+    #[test]
+    fn tui_config_parses_status_line_segments() {
+        let cfg = r#"
+[tui]
+status_line = ["model-name", "kube"]
+
+[tui.status_line_segments.kube]
+command = ["kubectl", "config", "current-context"]
+refresh_interval_secs = 30
+
+[tui.status_line_segments.ticket]
+template = "{branch}"
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("status line segments should parse");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        let kube = &tui.status_line_segments["kube"];
+        assert_eq!(
+            kube.command,
+            Some(vec![
+                "kubectl".to_string(),
+                "config".to_string(),
+                "current-context".to_string(),
+            ])
+        );
+        assert_eq!(kube.refresh_interval(), Duration::from_secs(30));
+        assert_eq!(kube.timeout(), StatusLineSegment::DEFAULT_TIMEOUT);
+        assert_eq!(
+            tui.status_line_segments["ticket"].template.as_deref(),
+            Some("{branch}")
+        );
+
+        let zero_interval = StatusLineSegment {
+            refresh_interval_secs: Some(0),
+            ..kube.clone()
+        };
+        assert_eq!(
+            zero_interval.refresh_interval(),
+            StatusLineSegment::MIN_REFRESH_INTERVAL
+        );
+    }
+
+    #[test]
+    fn tools_config_parses_command_tools_next_to_toggles() {
+        let cfg = r#"
+[tools]
+web_search = true
+some_future_toggle = true
+
+[tools.commands.run_migrations]
+description = "Apply pending database migrations."
+command = "./scripts/migrate.sh"
+timeout_ms = 60000
+
+[tools.commands.run_migrations.input_schema]
+type = "object"
+properties = { target = { type = "string" } }
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("command tools should parse");
+        let tools = parsed.tools.expect("config should include tools section");
+
+        assert_eq!(tools.web_search, Some(true));
+        assert_eq!(
+            tools.commands,
+            BTreeMap::from([(
+                "run_migrations".to_string(),
+                CommandToolConfig {
+                    description: "Apply pending database migrations.".to_string(),
+                    command: "./scripts/migrate.sh".to_string(),
+                    input_schema: Some(serde_json::json!({
+                        "type": "object",
+                        "properties": { "target": { "type": "string" } },
+                    })),
+                    timeout_ms: Some(60_000),
+                },
+            )])
+        );
+    }
+
+    #[test]
+    fn command_tool_names_must_be_valid_tool_names() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = |name: &str| {
+            toml::from_str::<ConfigToml>(&format!(
+                "[tools.commands.{name:?}]\ndescription = \"d\"\ncommand = \"true\"\n"
+            ))
+            .expect("command tools should parse")
+        };
+
+        Config::load_from_base_config_with_overrides(
+            cfg("run-migrations_2"),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        for name in ["run migrations", "deploy.prod", "", &"a".repeat(65)] {
+            let error = Config::load_from_base_config_with_overrides(
+                cfg(name),
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+            .expect_err("invalid command tool name should be rejected");
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -2147,6 +2370,32 @@ trust_level = "trusted"
                 }
             );
         }
+
+        let sandbox_workspace_overlay = r#"
+sandbox_mode = "workspace-overlay"
+
+[sandbox_workspace_write]
+network_access = true
+"#;
+
+        let sandbox_workspace_overlay_cfg = toml::from_str::<ConfigToml>(sandbox_workspace_overlay)
+            .expect("TOML deserialization should succeed");
+        let resolution = sandbox_workspace_overlay_cfg.derive_sandbox_policy(
+            None,
+            None,
+            WindowsSandboxLevel::Disabled,
+            &PathBuf::from("/tmp/test"),
+            None,
+        );
+        assert_eq!(
+            resolution,
+            SandboxPolicyResolution {
+                policy: SandboxPolicy::WorkspaceOverlay {
+                    network_access: true,
+                },
+                forced_auto_mode_downgraded_on_windows: false,
+            }
+        );
     }
 
     #[test]
@@ -3815,6 +4064,7 @@ model_verbosity = "high"
             wire_api: crate::WireApi::Responses,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             query_params: None,
             http_headers: None,
             env_http_headers: None,
@@ -3868,7 +4118,7 @@ model_verbosity = "high"
         };
         let o3_profile_config: Config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            o3_profile_overrides,
+            o3_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
         assert_eq!(
@@ -3881,6 +4131,7 @@ model_verbosity = "high"
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+                sandbox_deny_read: DenyReadPaths::from_config(None, &fixture.codex_home()),
                 enforce_residency: Constrained::allow_any(None),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 forced_auto_mode_downgraded_on_windows: false,
@@ -3892,15 +4143,18 @@ model_verbosity = "high"
                 mcp_servers: Constrained::allow_any(HashMap::new()),
                 mcp_oauth_credentials_store_mode: Default::default(),
                 mcp_oauth_callback_port: None,
+                lsp_servers: BTreeMap::new(),
                 model_providers: fixture.model_provider_map.clone(),
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
+                turn_limits: TurnLimits::default(),
                 agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
                 codex_home: fixture.codex_home(),
                 log_dir: fixture.codex_home().join("log"),
                 config_layer_stack: Default::default(),
                 startup_warnings: Vec::new(),
+                harness_overrides: o3_profile_overrides,
                 history: History::default(),
                 ephemeral: false,
                 file_opener: UriBasedFileOpener::VsCode,
@@ -3920,6 +4174,7 @@ model_verbosity = "high"
                 forced_login_method: None,
                 include_apply_patch_tool: false,
                 web_search_mode: None,
+                command_tools: BTreeMap::new(),
                 use_experimental_unified_exec_tool: !cfg!(windows),
                 ghost_snapshot: GhostSnapshotConfig::default(),
                 features: Features::with_defaults(),
@@ -3939,6 +4194,7 @@ model_verbosity = "high"
                 feedback_enabled: true,
                 tui_alternate_screen: AltScreenMode::Auto,
                 tui_status_line: None,
+                tui_status_line_segments: BTreeMap::new(),
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3957,7 +4213,7 @@ model_verbosity = "high"
         };
         let gpt3_profile_config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            gpt3_profile_overrides,
+            gpt3_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
         let expected_gpt3_profile_config = Config {
@@ -3969,6 +4225,7 @@ model_verbosity = "high"
             model_provider: fixture.openai_custom_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+            sandbox_deny_read: DenyReadPaths::from_config(None, &fixture.codex_home()),
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
@@ -3980,15 +4237,18 @@ model_verbosity = "high"
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
+            lsp_servers: BTreeMap::new(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            turn_limits: TurnLimits::default(),
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
+            harness_overrides: gpt3_profile_overrides,
             history: History::default(),
             ephemeral: false,
             file_opener: UriBasedFileOpener::VsCode,
@@ -4008,6 +4268,7 @@ model_verbosity = "high"
             forced_login_method: None,
             include_apply_patch_tool: false,
             web_search_mode: None,
+            command_tools: BTreeMap::new(),
             use_experimental_unified_exec_tool: !cfg!(windows),
             ghost_snapshot: GhostSnapshotConfig::default(),
             features: Features::with_defaults(),
@@ -4027,6 +4288,7 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_segments: BTreeMap::new(),
             otel: OtelConfig::default(),
         };
 
@@ -4041,11 +4303,17 @@ model_verbosity = "high"
 
         let default_profile_config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            default_profile_overrides,
+            default_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
 
-        assert_eq!(expected_gpt3_profile_config, default_profile_config);
+        assert_eq!(
+            Config {
+                harness_overrides: default_profile_overrides,
+                ..expected_gpt3_profile_config
+            },
+            default_profile_config
+        );
         Ok(())
     }
 
@@ -4060,7 +4328,7 @@ model_verbosity = "high"
         };
         let zdr_profile_config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            zdr_profile_overrides,
+            zdr_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
         let expected_zdr_profile_config = Config {
@@ -4072,6 +4340,7 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+            sandbox_deny_read: DenyReadPaths::from_config(None, &fixture.codex_home()),
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
@@ -4083,15 +4352,18 @@ model_verbosity = "high"
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
+            lsp_servers: BTreeMap::new(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            turn_limits: TurnLimits::default(),
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
+            harness_overrides: zdr_profile_overrides,
             history: History::default(),
             ephemeral: false,
             file_opener: UriBasedFileOpener::VsCode,
@@ -4111,6 +4383,7 @@ model_verbosity = "high"
             forced_login_method: None,
             include_apply_patch_tool: false,
             web_search_mode: None,
+            command_tools: BTreeMap::new(),
             use_experimental_unified_exec_tool: !cfg!(windows),
             ghost_snapshot: GhostSnapshotConfig::default(),
             features: Features::with_defaults(),
@@ -4130,6 +4403,7 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_segments: BTreeMap::new(),
             otel: OtelConfig::default(),
         };
 
@@ -4149,7 +4423,7 @@ model_verbosity = "high"
         };
         let gpt5_profile_config = Config::load_from_base_config_with_overrides(
             fixture.cfg.clone(),
-            gpt5_profile_overrides,
+            gpt5_profile_overrides.clone(),
             fixture.codex_home(),
         )?;
         let expected_gpt5_profile_config = Config {
@@ -4161,6 +4435,7 @@ model_verbosity = "high"
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
+            sandbox_deny_read: DenyReadPaths::from_config(None, &fixture.codex_home()),
             enforce_residency: Constrained::allow_any(None),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
@@ -4172,15 +4447,18 @@ model_verbosity = "high"
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
+            lsp_servers: BTreeMap::new(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            turn_limits: TurnLimits::default(),
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
+            harness_overrides: gpt5_profile_overrides,
             history: History::default(),
             ephemeral: false,
             file_opener: UriBasedFileOpener::VsCode,
@@ -4200,6 +4478,7 @@ model_verbosity = "high"
             forced_login_method: None,
             include_apply_patch_tool: false,
             web_search_mode: None,
+            command_tools: BTreeMap::new(),
             use_experimental_unified_exec_tool: !cfg!(windows),
             ghost_snapshot: GhostSnapshotConfig::default(),
             features: Features::with_defaults(),
@@ -4219,6 +4498,7 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_segments: BTreeMap::new(),
             otel: OtelConfig::default(),
         };
 
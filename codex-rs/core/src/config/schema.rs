@@ -8,6 +8,7 @@ use schemars::schema::ObjectValidation;
 use schemars::schema::RootSchema;
 use schemars::schema::Schema;
 use schemars::schema::SchemaObject;
+use schemars::schema::SingleOrVec;
 use serde_json::Map;
 use serde_json::Value;
 use std::path::Path;
@@ -62,6 +63,134 @@ pub fn config_schema() -> RootSchema {
         .into_root_schema_for::<ConfigToml>()
 }
 
+/// What the schema says about a single config key, for `codex config explain`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigKeySchema {
+    pub description: Option<String>,
+    /// JSON schema type names, e.g. `string` or `object`.
+    pub types: Vec<String>,
+    /// Accepted values when the key is an enum.
+    pub allowed_values: Vec<Value>,
+    pub default: Option<Value>,
+}
+
+/// Looks up the schema for a dotted key path such as `tools.web_search` or
+/// `profiles.work.model`. Map entries (profiles, providers, MCP servers)
+/// match any key. Returns `None` when the schema does not know the key.
+pub fn config_key_schema(segments: &[String]) -> Option<ConfigKeySchema> {
+    let root = config_schema();
+    let definitions = &root.definitions;
+    let mut current = Schema::Object(root.schema.clone());
+    for segment in segments {
+        let object = resolve_schema(&current, definitions)?;
+        let validation = object.object.as_deref()?;
+        current = match validation.properties.get(segment) {
+            Some(child) => child.clone(),
+            None => match validation.additional_properties.as_deref() {
+                Some(Schema::Bool(false)) | None => return None,
+                Some(Schema::Bool(true)) => Schema::Bool(true),
+                Some(child) => child.clone(),
+            },
+        };
+    }
+
+    let Schema::Object(outer) = &current else {
+        return Some(ConfigKeySchema::default());
+    };
+    let resolved = resolve_schema(&current, definitions).unwrap_or(outer);
+    let description = outer
+        .metadata
+        .as_deref()
+        .and_then(|metadata| metadata.description.clone())
+        .or_else(|| {
+            resolved
+                .metadata
+                .as_deref()
+                .and_then(|metadata| metadata.description.clone())
+        });
+    let default = outer
+        .metadata
+        .as_deref()
+        .and_then(|metadata| metadata.default.clone())
+        .or_else(|| {
+            resolved
+                .metadata
+                .as_deref()
+                .and_then(|metadata| metadata.default.clone())
+        })
+        .filter(|value| !value.is_null());
+    let mut types = Vec::new();
+    let mut allowed_values = Vec::new();
+    collect_types_and_values(resolved, definitions, &mut types, &mut allowed_values);
+
+    Some(ConfigKeySchema {
+        description,
+        types,
+        allowed_values,
+        default,
+    })
+}
+
+fn collect_types_and_values(
+    object: &SchemaObject,
+    definitions: &schemars::Map<String, Schema>,
+    types: &mut Vec<String>,
+    allowed_values: &mut Vec<Value>,
+) {
+    if let Some(instance_type) = &object.instance_type {
+        let instance_types = match instance_type {
+            SingleOrVec::Single(single) => vec![**single],
+            SingleOrVec::Vec(many) => many.clone(),
+        };
+        for instance_type in instance_types {
+            let name = match serde_json::to_value(instance_type) {
+                Ok(Value::String(name)) => name,
+                _ => continue,
+            };
+            if !types.contains(&name) {
+                types.push(name);
+            }
+        }
+    }
+    if let Some(values) = &object.enum_values {
+        allowed_values.extend(values.iter().cloned());
+    }
+    if let Some(subschemas) = object.subschemas.as_deref() {
+        let variants = subschemas
+            .one_of
+            .iter()
+            .chain(subschemas.any_of.iter())
+            .flatten();
+        for variant in variants {
+            if let Some(variant) = resolve_schema(variant, definitions) {
+                collect_types_and_values(variant, definitions, types, allowed_values);
+            }
+        }
+    }
+}
+
+/// Follows `$ref`s (and the single-entry `allOf` wrapper schemars emits for
+/// documented references) to the schema object that describes a value.
+pub(crate) fn resolve_schema<'a>(
+    schema: &'a Schema,
+    definitions: &'a schemars::Map<String, Schema>,
+) -> Option<&'a SchemaObject> {
+    let Schema::Object(object) = schema else {
+        return None;
+    };
+    if let Some(reference) = object.reference.as_deref() {
+        let name = reference.strip_prefix("#/definitions/")?;
+        return resolve_schema(definitions.get(name)?, definitions);
+    }
+    if object.object.is_none()
+        && let Some(subschemas) = object.subschemas.as_deref()
+        && let Some([single]) = subschemas.all_of.as_deref()
+    {
+        return resolve_schema(single, definitions);
+    }
+    Some(object)
+}
+
 /// Canonicalize a JSON value by sorting its keys.
 fn canonicalize(value: &Value) -> Value {
     match value {
@@ -97,8 +226,11 @@ pub fn write_config_schema(out_path: &Path) -> anyhow::Result<()> {
 
 #[cfg(test)]
 mod tests {
+    use super::ConfigKeySchema;
     use super::canonicalize;
+    use super::config_key_schema;
     use super::config_schema_json;
+    use pretty_assertions::assert_eq;
 
     use similar::TextDiff;
 
@@ -129,4 +261,31 @@ Run `just write-config-schema` to overwrite with your changes.\n\n{diff}"
             );
         }
     }
+
+    #[test]
+    fn config_key_schema_describes_nested_and_map_keys() {
+        let view_image = config_key_schema(&["tools".to_string(), "view_image".to_string()]);
+        assert_eq!(
+            view_image,
+            Some(ConfigKeySchema {
+                description: Some(
+                    "Enable the `view_image` tool that lets the agent attach local images."
+                        .to_string()
+                ),
+                types: vec!["boolean".to_string()],
+                allowed_values: Vec::new(),
+                default: None,
+            })
+        );
+
+        let profile_model = config_key_schema(&[
+            "profiles".to_string(),
+            "work".to_string(),
+            "model".to_string(),
+        ])
+        .expect("profile keys are known");
+        assert_eq!(profile_model.types, vec!["string".to_string()]);
+
+        assert_eq!(config_key_schema(&["modle".to_string()]), None);
+    }
 }
@@ -3,11 +3,15 @@ use super::ConfigToml;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
 use crate::config_loader::CloudRequirementsLoader;
+use crate::config_loader::ConfigDiagnostic;
+use crate::config_loader::ConfigDiagnosticSeverity;
 use crate::config_loader::ConfigLayerEntry;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigLayerStackOrdering;
+use crate::config_loader::ConfigLoadError;
 use crate::config_loader::ConfigRequirementsToml;
 use crate::config_loader::LoaderOverrides;
+use crate::config_loader::config_layer_diagnostics;
 use crate::config_loader::load_config_layers_state;
 use crate::config_loader::merge_toml_values;
 use crate::path_utils;
@@ -202,6 +206,79 @@ impl ConfigService {
         }
     }
 
+    /// Checks the config files visible from `cwd` (or only the user and
+    /// system layers when `cwd` is `None`) without loading a session.
+    ///
+    /// Each file is checked on its own first so findings point at a line and
+    /// column; if they are all clean, the merged config is built to catch
+    /// semantic problems such as a missing profile or a value that
+    /// requirements disallow.
+    pub async fn validate(
+        &self,
+        cwd: Option<PathBuf>,
+    ) -> Result<Vec<ConfigDiagnostic>, ConfigServiceError> {
+        let cwd = cwd
+            .map(AbsolutePathBuf::try_from)
+            .transpose()
+            .map_err(|err| {
+                ConfigServiceError::io("failed to resolve config cwd to an absolute path", err)
+            })?;
+        let layers = match load_config_layers_state(
+            &self.codex_home,
+            cwd.clone(),
+            &self.cli_overrides,
+            self.loader_overrides.clone(),
+            self.cloud_requirements.clone(),
+        )
+        .await
+        {
+            Ok(layers) => layers,
+            Err(err) => match config_load_error(&err) {
+                Some(load_err) => return Ok(vec![load_err.config_error().clone().into()]),
+                None => {
+                    return Err(ConfigServiceError::io(
+                        "failed to read configuration layers",
+                        err,
+                    ));
+                }
+            },
+        };
+
+        let mut diagnostics = config_layer_diagnostics(&layers).await;
+        if diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == ConfigDiagnosticSeverity::Error)
+        {
+            return Ok(diagnostics);
+        }
+
+        if let Err(err) = validate_config(&layers.effective_config()) {
+            diagnostics.push(ConfigDiagnostic::error(format!(
+                "merged configuration is invalid: {}",
+                err.message()
+            )));
+            return Ok(diagnostics);
+        }
+
+        let build_result = crate::config::ConfigBuilder::default()
+            .codex_home(self.codex_home.clone())
+            .cli_overrides(self.cli_overrides.clone())
+            .loader_overrides(self.loader_overrides.clone())
+            .fallback_cwd(cwd.map(|cwd| cwd.to_path_buf()))
+            .cloud_requirements(self.cloud_requirements.clone())
+            .build()
+            .await;
+        if let Err(err) = build_result {
+            let diagnostic = match config_load_error(&err) {
+                Some(load_err) => load_err.config_error().clone().into(),
+                None => ConfigDiagnostic::error(err.to_string()),
+            };
+            diagnostics.push(diagnostic);
+        }
+
+        Ok(diagnostics)
+    }
+
     pub async fn write_value(
         &self,
         params: ConfigValueWriteParams,
@@ -280,6 +357,10 @@ impl ConfigService {
             ));
         }
 
+        // Edits are made to the file as written, so `${VAR}` references and
+        // values from included files are never copied into it. The expanded
+        // config gets the same edits for validation.
+        let mut raw_user_config = user_layer.raw_config.clone();
         let mut user_config = user_layer.config.clone();
         let mut parsed_segments = Vec::new();
         let mut config_edits = Vec::new();
@@ -288,25 +369,31 @@ impl ConfigService {
             let segments = parse_key_path(&key_path).map_err(|message| {
                 ConfigServiceError::write(ConfigWriteErrorCode::ConfigValidationError, message)
             })?;
-            let original_value = value_at_path(&user_config, &segments).cloned();
+            let original_value = value_at_path(&raw_user_config, &segments).cloned();
             let parsed_value = parse_value(value).map_err(|message| {
                 ConfigServiceError::write(ConfigWriteErrorCode::ConfigValidationError, message)
             })?;
 
-            apply_merge(&mut user_config, &segments, parsed_value.as_ref(), strategy).map_err(
-                |err| match err {
-                    MergeError::PathNotFound => ConfigServiceError::write(
-                        ConfigWriteErrorCode::ConfigPathNotFound,
-                        "Path not found",
-                    ),
-                    MergeError::Validation(message) => ConfigServiceError::write(
-                        ConfigWriteErrorCode::ConfigValidationError,
-                        message,
-                    ),
-                },
-            )?;
-
-            let updated_value = value_at_path(&user_config, &segments).cloned();
+            apply_merge(
+                &mut raw_user_config,
+                &segments,
+                parsed_value.as_ref(),
+                strategy.clone(),
+            )
+            .map_err(|err| match err {
+                MergeError::PathNotFound => ConfigServiceError::write(
+                    ConfigWriteErrorCode::ConfigPathNotFound,
+                    "Path not found",
+                ),
+                MergeError::Validation(message) => {
+                    ConfigServiceError::write(ConfigWriteErrorCode::ConfigValidationError, message)
+                }
+            })?;
+            // The expanded copy has no `include` key, so clearing under it
+            // may find no path; the edit itself was already validated above.
+            let _ = apply_merge(&mut user_config, &segments, parsed_value.as_ref(), strategy);
+
+            let updated_value = value_at_path(&raw_user_config, &segments).cloned();
             if original_value != updated_value {
                 let edit = match updated_value {
                     Some(value) => ConfigEdit::SetPath {
@@ -332,7 +419,11 @@ impl ConfigService {
             )
         })?;
 
-        let updated_layers = layers.with_user_config(&provided_path, user_config.clone());
+        let updated_layers = layers.with_user_layer(
+            user_layer
+                .into_owned()
+                .with_edited_config(raw_user_config, user_config),
+        );
         let effective = updated_layers.effective_config();
         validate_config(&effective).map_err(|err| {
             ConfigServiceError::write(
@@ -577,6 +668,11 @@ fn toml_value_to_value(value: &TomlValue) -> anyhow::Result<toml_edit::Value> {
     }
 }
 
+fn config_load_error(err: &std::io::Error) -> Option<&ConfigLoadError> {
+    err.get_ref()
+        .and_then(|err| err.downcast_ref::<ConfigLoadError>())
+}
+
 fn validate_config(value: &TomlValue) -> Result<(), toml::de::Error> {
     let _: ConfigToml = value.clone().try_into()?;
     Ok(())
@@ -644,6 +740,8 @@ fn compute_override_metadata(
     effective: &TomlValue,
     segments: &[String],
 ) -> Option<OverriddenMetadata> {
+    // Compared with the effective value, so this reads the expanded config
+    // rather than the file as written.
     let user_value = match layers.get_user_layer() {
         Some(user_layer) => value_at_path(&user_layer.config, segments),
         None => return None,
@@ -800,6 +898,53 @@ remote_compaction = true
         Ok(())
     }
 
+    #[tokio::test]
+    async fn write_value_edits_the_file_as_written() -> Result<()> {
+        let tmp = tempdir().expect("tempdir");
+        std::fs::write(
+            tmp.path().join("extra.toml"),
+            "[features]\nunified_exec = true\n",
+        )?;
+        let original = r#"include = ["extra.toml"]
+model = "${CODEX_CONFIG_SERVICE_TEST_UNSET:-gpt-5}"
+"#;
+        std::fs::write(tmp.path().join(CONFIG_TOML_FILE), original)?;
+
+        let service = ConfigService::new_with_defaults(tmp.path().to_path_buf());
+        service
+            .write_value(ConfigValueWriteParams {
+                file_path: Some(tmp.path().join(CONFIG_TOML_FILE).display().to_string()),
+                key_path: "features.remote_compaction".to_string(),
+                value: serde_json::json!(true),
+                merge_strategy: MergeStrategy::Replace,
+                expected_version: None,
+            })
+            .await
+            .expect("write succeeds");
+        // The expanded value equals the new one, but the file still holds
+        // the reference, so the edit must be written.
+        service
+            .write_value(ConfigValueWriteParams {
+                file_path: Some(tmp.path().join(CONFIG_TOML_FILE).display().to_string()),
+                key_path: "model".to_string(),
+                value: serde_json::json!("gpt-5"),
+                merge_strategy: MergeStrategy::Replace,
+                expected_version: None,
+            })
+            .await
+            .expect("write succeeds");
+
+        let updated = std::fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE))?;
+        let expected = r#"include = ["extra.toml"]
+model = "gpt-5"
+
+[features]
+remote_compaction = true
+"#;
+        assert_eq!(updated, expected);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn read_includes_origins_and_layers() {
         let tmp = tempdir().expect("tempdir");
@@ -1214,4 +1359,65 @@ beta = "b"
 
         Ok(())
     }
+
+    fn service_without_managed_config(codex_home: &Path) -> ConfigService {
+        ConfigService::new(
+            codex_home.to_path_buf(),
+            vec![],
+            LoaderOverrides {
+                managed_config_path: Some(codex_home.join("managed_config.toml")),
+                #[cfg(target_os = "macos")]
+                managed_preferences_base64: None,
+                macos_managed_config_requirements_base64: None,
+            },
+            CloudRequirementsLoader::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn validate_warns_about_unknown_keys_with_location() {
+        let tmp = tempdir().expect("tempdir");
+        let user_path = tmp.path().join(CONFIG_TOML_FILE);
+        std::fs::write(
+            &user_path,
+            "model = \"gpt-5\"\n\n[tools]\nview_imag = true\n",
+        )
+        .unwrap();
+
+        let diagnostics = service_without_managed_config(tmp.path())
+            .validate(None)
+            .await
+            .expect("validate");
+
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.severity, ConfigDiagnosticSeverity::Warning);
+        assert_eq!(diagnostic.path.as_deref(), Some(user_path.as_path()));
+        let range = diagnostic.range.expect("range");
+        assert_eq!((range.start.line, range.start.column), (4, 1));
+        assert_eq!(
+            diagnostic.message,
+            "unknown configuration key `tools.view_imag`; it will be ignored"
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_reports_type_errors_as_errors() {
+        let tmp = tempdir().expect("tempdir");
+        std::fs::write(
+            tmp.path().join(CONFIG_TOML_FILE),
+            "model_context_window = \"large\"\n",
+        )
+        .unwrap();
+
+        let diagnostics = service_without_managed_config(tmp.path())
+            .validate(None)
+            .await
+            .expect("validate");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, ConfigDiagnosticSeverity::Error);
+        let range = diagnostics[0].range.expect("range");
+        assert_eq!((range.start.line, range.start.column), (1, 24));
+    }
 }
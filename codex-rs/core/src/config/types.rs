@@ -374,6 +374,8 @@ pub enum OtelExporterKind {
         #[serde(default)]
         tls: Option<OtelTlsConfig>,
     },
+    /// Write OTLP-JSON to rotating files under `$CODEX_HOME/otel/`.
+    File,
 }
 
 /// OTEL settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -391,6 +393,13 @@ pub struct OtelConfigToml {
 
     /// Optional trace exporter
     pub trace_exporter: Option<OtelExporterKind>,
+
+    /// Optional metrics exporter. Defaults to statsig.
+    pub metrics_exporter: Option<OtelExporterKind>,
+
+    /// Address (for example `127.0.0.1:9464`) on which `codex app-server`
+    /// serves a Prometheus `/metrics` endpoint.
+    pub prometheus_listen: Option<String>,
 }
 
 /// Effective OTEL settings after defaults are applied.
@@ -401,6 +410,7 @@ pub struct OtelConfig {
     pub exporter: OtelExporterKind,
     pub trace_exporter: OtelExporterKind,
     pub metrics_exporter: OtelExporterKind,
+    pub prometheus_listen: Option<String>,
 }
 
 impl Default for OtelConfig {
@@ -411,6 +421,7 @@ impl Default for OtelConfig {
             exporter: OtelExporterKind::None,
             trace_exporter: OtelExporterKind::None,
             metrics_exporter: OtelExporterKind::Statsig,
+            prometheus_listen: None,
         }
     }
 }
@@ -492,6 +503,59 @@ pub struct Tui {
     /// When set, the TUI renders the selected items as the status line.
     #[serde(default)]
     pub status_line: Option<Vec<String>>,
+
+    /// User-defined status line segments keyed by identifier.
+    ///
+    /// A segment is shown when its identifier appears in `status_line`.
+    #[serde(default)]
+    pub status_line_segments: BTreeMap<String, StatusLineSegment>,
+}
+
+/// A custom status line segment backed by a command or a template.
+///
+/// Exactly one of `command` or `template` should be set. Command segments
+/// render the first non-empty line of stdout; template segments substitute
+/// `{placeholder}` names with values from the current session.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct StatusLineSegment {
+    /// Command (program followed by arguments) whose output is rendered.
+    #[serde(default)]
+    pub command: Option<Vec<String>>,
+
+    /// Template rendered over session state, e.g. `"{thread_name} {total_tokens}"`.
+    #[serde(default)]
+    pub template: Option<String>,
+
+    /// Seconds between command refreshes. Defaults to 5; values below 1 are
+    /// raised to 1.
+    #[serde(default)]
+    pub refresh_interval_secs: Option<u64>,
+
+    /// Maximum time to wait for the command, in milliseconds. Defaults to 1000.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+impl StatusLineSegment {
+    pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1000);
+    /// Keeps `refresh_interval_secs = 0` from re-running the command in a
+    /// tight loop.
+    pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn refresh_interval(&self) -> Duration {
+        self.refresh_interval_secs
+            .map(Duration::from_secs)
+            .unwrap_or(Self::DEFAULT_REFRESH_INTERVAL)
+            .max(Self::MIN_REFRESH_INTERVAL)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(Self::DEFAULT_TIMEOUT)
+    }
 }
 
 const fn default_true() -> bool {
@@ -538,6 +602,77 @@ pub struct SkillsConfig {
     pub config: Vec<SkillConfig>,
 }
 
+/// A language server Codex launches for files with matching extensions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct LspServerConfig {
+    /// Executable that speaks LSP over stdio, e.g. `rust-analyzer`.
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// File extensions (without the leading dot) handled by this server.
+    pub file_extensions: Vec<String>,
+    /// Language identifier sent with opened documents. Defaults to the
+    /// server's name in `[lsp_servers]`.
+    #[serde(default)]
+    pub language_id: Option<String>,
+    /// Value sent as `initializationOptions` in the `initialize` request.
+    #[serde(default)]
+    pub initialization_options: Option<serde_json::Value>,
+    /// Let the server run code from the workspace. When false, build scripts,
+    /// proc-macros and check-on-save are turned off for `rust-analyzer`.
+    #[serde(default)]
+    pub run_workspace_code: bool,
+}
+
+/// A function tool backed by a shell command, declared as
+/// `[tools.commands.<name>]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CommandToolConfig {
+    /// Description of the tool shown to the model.
+    pub description: String,
+    /// Command run through the user's shell. The tool arguments are written to
+    /// its stdin as JSON and its stdout is returned to the model.
+    pub command: String,
+    /// JSON Schema for the tool arguments. Defaults to an object without
+    /// properties.
+    #[serde(default)]
+    pub input_schema: Option<serde_json::Value>,
+    /// Maximum time in milliseconds the command may run.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Limits that stop a turn before the model is done, so unattended runs
+/// cannot loop forever. Set under `[limits]` or `[profiles.<name>.limits]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TurnLimits {
+    /// Maximum number of model requests in a turn.
+    pub max_turns: Option<u32>,
+    /// Maximum number of tool calls in a turn.
+    pub max_tool_calls: Option<u32>,
+    /// Maximum duration of a turn, in seconds, including auto-compaction.
+    pub timeout_secs: Option<u64>,
+    /// Maximum number of failed tool calls in a row.
+    pub max_consecutive_failures: Option<u32>,
+}
+
+impl TurnLimits {
+    /// Takes each limit from `self`, falling back to `other` where unset.
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            max_turns: self.max_turns.or(other.max_turns),
+            max_tool_calls: self.max_tool_calls.or(other.max_tool_calls),
+            timeout_secs: self.timeout_secs.or(other.timeout_secs),
+            max_consecutive_failures: self
+                .max_consecutive_failures
+                .or(other.max_consecutive_failures),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct SandboxWorkspaceWrite {
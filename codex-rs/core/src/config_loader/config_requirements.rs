@@ -221,6 +221,9 @@ pub enum SandboxModeRequirement {
     #[serde(rename = "workspace-write")]
     WorkspaceWrite,
 
+    #[serde(rename = "workspace-overlay")]
+    WorkspaceOverlay,
+
     #[serde(rename = "danger-full-access")]
     DangerFullAccess,
 
@@ -233,6 +236,7 @@ impl From<SandboxMode> for SandboxModeRequirement {
         match mode {
             SandboxMode::ReadOnly => SandboxModeRequirement::ReadOnly,
             SandboxMode::WorkspaceWrite => SandboxModeRequirement::WorkspaceWrite,
+            SandboxMode::WorkspaceOverlay => SandboxModeRequirement::WorkspaceOverlay,
             SandboxMode::DangerFullAccess => SandboxModeRequirement::DangerFullAccess,
         }
     }
@@ -322,6 +326,9 @@ impl TryFrom<ConfigRequirementsWithSources> for ConfigRequirements {
                         SandboxPolicy::WorkspaceWrite { .. } => {
                             SandboxModeRequirement::WorkspaceWrite
                         }
+                        SandboxPolicy::WorkspaceOverlay { .. } => {
+                            SandboxModeRequirement::WorkspaceOverlay
+                        }
                         SandboxPolicy::DangerFullAccess => SandboxModeRequirement::DangerFullAccess,
                         SandboxPolicy::ExternalSandbox { .. } => {
                             SandboxModeRequirement::ExternalSandbox
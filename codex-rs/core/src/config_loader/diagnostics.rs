@@ -1,10 +1,12 @@
 //! Helpers for mapping config parse/validation failures to file locations and
 //! rendering them in a user-friendly way.
 
-use crate::config::CONFIG_TOML_FILE;
 use crate::config::ConfigToml;
-use codex_app_server_protocol::ConfigLayerSource;
+use crate::config::schema::config_schema;
+use crate::config::schema::resolve_schema;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
+use schemars::Map as SchemaMap;
+use schemars::schema::Schema;
 use serde_path_to_error::Path as SerdePath;
 use serde_path_to_error::Segment as SerdeSegment;
 use std::fmt;
@@ -15,11 +17,13 @@ use std::path::PathBuf;
 use toml_edit::Document;
 use toml_edit::Item;
 use toml_edit::Table;
+use toml_edit::TableLike;
 use toml_edit::Value;
 
 use super::ConfigLayerEntry;
 use super::ConfigLayerStack;
 use super::ConfigLayerStackOrdering;
+use super::includes::INCLUDE_KEY;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TextPosition {
@@ -88,6 +92,188 @@ impl std::error::Error for ConfigLoadError {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigDiagnosticSeverity {
+    /// The config cannot be loaded as written.
+    Error,
+    /// The config loads, but part of it is ignored.
+    Warning,
+}
+
+/// A finding from `config_layer_diagnostics`. Findings about a specific file
+/// carry its path and the range they refer to; checks on the merged config do
+/// not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    pub severity: ConfigDiagnosticSeverity,
+    pub path: Option<PathBuf>,
+    pub range: Option<TextRange>,
+    pub message: String,
+}
+
+impl ConfigDiagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: ConfigDiagnosticSeverity::Error,
+            path: None,
+            range: None,
+            message: message.into(),
+        }
+    }
+
+    fn from_config_error(severity: ConfigDiagnosticSeverity, error: ConfigError) -> Self {
+        Self {
+            severity,
+            path: Some(error.path),
+            range: Some(error.range),
+            message: error.message,
+        }
+    }
+
+    /// The located error, when this diagnostic points into a file.
+    pub fn config_error(&self) -> Option<ConfigError> {
+        Some(ConfigError::new(
+            self.path.clone()?,
+            self.range?,
+            self.message.clone(),
+        ))
+    }
+}
+
+impl From<ConfigError> for ConfigDiagnostic {
+    fn from(error: ConfigError) -> Self {
+        Self::from_config_error(ConfigDiagnosticSeverity::Error, error)
+    }
+}
+
+/// Keys accepted by serde aliases, kept for compatibility or handled by the
+/// loader itself that the generated schema does not list.
+const SCHEMA_UNLISTED_KEYS: &[&str] = &[
+    INCLUDE_KEY,
+    "web_search_request",
+    "ignore_untracked_files_over_bytes",
+    "large_untracked_dir_warning_threshold",
+];
+
+/// Checks every file-backed layer: TOML syntax and types against `ConfigToml`
+/// are errors, keys the schema does not know about are warnings because they
+/// are silently ignored at load time.
+pub(crate) async fn config_layer_diagnostics(layers: &ConfigLayerStack) -> Vec<ConfigDiagnostic> {
+    let mut diagnostics = Vec::new();
+    for layer in layers.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true) {
+        for path in layer.config_files() {
+            let contents = match tokio::fs::read_to_string(&path).await {
+                Ok(contents) => contents,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    diagnostics.push(ConfigDiagnostic {
+                        severity: ConfigDiagnosticSeverity::Error,
+                        path: Some(path.clone()),
+                        range: None,
+                        message: format!("failed to read config file: {err}"),
+                    });
+                    continue;
+                }
+            };
+            diagnostics.extend(config_file_diagnostics(&path, &contents));
+        }
+    }
+    diagnostics
+}
+
+pub(crate) fn config_file_diagnostics(path: &Path, contents: &str) -> Vec<ConfigDiagnostic> {
+    let _guard = path.parent().map(AbsolutePathBufGuard::new);
+    if let Some(error) = config_error_from_config_toml(path, contents) {
+        return vec![error.into()];
+    }
+    unknown_key_diagnostics(path, contents)
+}
+
+fn unknown_key_diagnostics(path: &Path, contents: &str) -> Vec<ConfigDiagnostic> {
+    let Ok(doc) = contents.parse::<Document<String>>() else {
+        return Vec::new();
+    };
+    let Some(root) = doc.as_item().as_table_like() else {
+        return Vec::new();
+    };
+    let schema = config_schema();
+    let mut diagnostics = Vec::new();
+    collect_unknown_keys(
+        root,
+        &Schema::Object(schema.schema),
+        &schema.definitions,
+        &mut Vec::new(),
+        &mut |key_path, span| {
+            let range = span
+                .map(|span| text_range_from_span(contents, span))
+                .unwrap_or_else(default_range);
+            diagnostics.push(ConfigDiagnostic::from_config_error(
+                ConfigDiagnosticSeverity::Warning,
+                ConfigError::new(
+                    path.to_path_buf(),
+                    range,
+                    format!("unknown configuration key `{key_path}`; it will be ignored"),
+                ),
+            ));
+        },
+    );
+    diagnostics
+}
+
+fn collect_unknown_keys(
+    table: &dyn TableLike,
+    schema: &Schema,
+    definitions: &SchemaMap<String, Schema>,
+    key_path: &mut Vec<String>,
+    report: &mut dyn FnMut(String, Option<std::ops::Range<usize>>),
+) {
+    let Some(object) = resolve_schema(schema, definitions) else {
+        return;
+    };
+    // Enums and other composite shapes would need full schema evaluation; only
+    // plain objects are checked.
+    if object.subschemas.is_some() {
+        return;
+    }
+    let Some(validation) = object.object.as_deref() else {
+        return;
+    };
+    let closed = !validation.properties.is_empty()
+        || matches!(
+            validation.additional_properties.as_deref(),
+            Some(Schema::Bool(false))
+        );
+
+    for (key, item) in table.iter() {
+        let child = match validation.properties.get(key) {
+            Some(child) => Some(child),
+            None => match validation.additional_properties.as_deref() {
+                Some(Schema::Bool(false)) | None => {
+                    if closed && !SCHEMA_UNLISTED_KEYS.contains(&key) {
+                        let span = table
+                            .get_key_value(key)
+                            .and_then(|(key, _)| key.span())
+                            .or_else(|| item.span());
+                        let mut full_path = key_path.clone();
+                        full_path.push(key.to_string());
+                        report(full_path.join("."), span);
+                    }
+                    continue;
+                }
+                Some(Schema::Bool(true)) => None,
+                Some(child) => Some(child),
+            },
+        };
+        if let Some(child) = child
+            && let Some(child_table) = item.as_table_like()
+        {
+            key_path.push(key.to_string());
+            collect_unknown_keys(child_table, child, definitions, key_path, report);
+            key_path.pop();
+        }
+    }
+}
+
 pub(crate) fn io_error_from_config_error(
     kind: io::ErrorKind,
     error: ConfigError,
@@ -157,45 +343,30 @@ where
     I: IntoIterator<Item = &'a ConfigLayerEntry>,
 {
     for layer in layers {
-        let Some(path) = config_path_for_layer(layer) else {
-            continue;
-        };
-        let contents = match tokio::fs::read_to_string(&path).await {
-            Ok(contents) => contents,
-            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
-            Err(err) => {
-                tracing::debug!("Failed to read config file {}: {err}", path.display());
+        for path in layer.config_files() {
+            let contents = match tokio::fs::read_to_string(&path).await {
+                Ok(contents) => contents,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    tracing::debug!("Failed to read config file {}: {err}", path.display());
+                    continue;
+                }
+            };
+
+            let Some(parent) = path.parent() else {
+                tracing::debug!("Config file {} has no parent directory", path.display());
                 continue;
+            };
+            let _guard = AbsolutePathBufGuard::new(parent);
+            if let Some(error) = config_error_from_config_toml(&path, &contents) {
+                return Some(error);
             }
-        };
-
-        let Some(parent) = path.parent() else {
-            tracing::debug!("Config file {} has no parent directory", path.display());
-            continue;
-        };
-        let _guard = AbsolutePathBufGuard::new(parent);
-        if let Some(error) = config_error_from_config_toml(&path, &contents) {
-            return Some(error);
         }
     }
 
     None
 }
 
-fn config_path_for_layer(layer: &ConfigLayerEntry) -> Option<PathBuf> {
-    match &layer.name {
-        ConfigLayerSource::System { file } => Some(file.to_path_buf()),
-        ConfigLayerSource::User { file } => Some(file.to_path_buf()),
-        ConfigLayerSource::Project { dot_codex_folder } => {
-            Some(dot_codex_folder.as_path().join(CONFIG_TOML_FILE))
-        }
-        ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => Some(file.to_path_buf()),
-        ConfigLayerSource::Mdm { .. }
-        | ConfigLayerSource::SessionFlags
-        | ConfigLayerSource::LegacyManagedConfigTomlFromMdm => None,
-    }
-}
-
 fn text_range_from_span(contents: &str, span: std::ops::Range<usize>) -> TextRange {
     let start = position_for_offset(contents, span.start);
     let end_index = if span.end > span.start {
@@ -1,3 +1,4 @@
+use super::state::ConfigLayerEntry;
 use codex_app_server_protocol::ConfigLayerMetadata;
 use serde_json::Value as JsonValue;
 use sha2::Digest;
@@ -7,7 +8,7 @@ use toml::Value as TomlValue;
 
 pub(super) fn record_origins(
     value: &TomlValue,
-    meta: &ConfigLayerMetadata,
+    layer: &ConfigLayerEntry,
     path: &mut Vec<String>,
     origins: &mut HashMap<String, ConfigLayerMetadata>,
 ) {
@@ -15,20 +16,22 @@ pub(super) fn record_origins(
         TomlValue::Table(table) => {
             for (key, val) in table {
                 path.push(key.clone());
-                record_origins(val, meta, path, origins);
+                record_origins(val, layer, path, origins);
                 path.pop();
             }
         }
         TomlValue::Array(items) => {
             for (idx, item) in (0_i32..).zip(items.iter()) {
                 path.push(idx.to_string());
-                record_origins(item, meta, path, origins);
+                record_origins(item, layer, path, origins);
                 path.pop();
             }
         }
         _ => {
             if !path.is_empty() {
-                origins.insert(path.join("."), meta.clone());
+                let key = path.join(".");
+                let meta = layer.metadata_for_key(&key);
+                origins.insert(key, meta);
             }
         }
     }
@@ -0,0 +1,478 @@
+//! Expansion of `${VAR}` references and `include` directives in config files.
+//!
+//! A config file may pull in other files with a top-level
+//! `include = ["~/.codex/team.toml"]`. Included files are merged underneath
+//! the including file, in order, so the including file always wins. Paths are
+//! resolved relative to the directory of the file that names them, and
+//! included files may include further files. Project config files may only
+//! include files inside their own `.codex/` folder.
+//!
+//! References that cannot be resolved (unset variables without a default,
+//! or shell forms such as `${1}`) are left as literal text and reported as
+//! warnings rather than failing the whole config.
+
+use super::diagnostics::config_error_from_toml;
+use super::diagnostics::io_error_from_config_error;
+use super::merge::merge_toml_values;
+use super::resolve_relative_paths_in_config_toml;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use dunce::canonicalize as normalize_path;
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use toml::Value as TomlValue;
+
+/// Top-level key listing the files a config file includes.
+pub(super) const INCLUDE_KEY: &str = "include";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct ExpandedConfigFile {
+    pub config: TomlValue,
+    /// The file as parsed, before anything was interpolated or merged.
+    pub raw: TomlValue,
+    /// Files pulled in through `include`, in the order they were merged.
+    pub includes: Vec<AbsolutePathBuf>,
+    /// Dotted keys whose value came from an included file.
+    pub included_key_files: HashMap<String, AbsolutePathBuf>,
+    /// `${VAR}` references that were left as literal text, one message each.
+    pub warnings: Vec<String>,
+}
+
+/// Expands a parsed config file: `${VAR}` references in string values are
+/// replaced, relative paths are resolved against the file's directory, and
+/// the files listed in `include` are loaded and merged underneath it. When
+/// `include_root` is set, every included file must live under that directory.
+pub(super) async fn expand_config_file(
+    file: &AbsolutePathBuf,
+    config: TomlValue,
+    include_root: Option<&Path>,
+) -> io::Result<ExpandedConfigFile> {
+    let mut chain = vec![canonical_path(file)];
+    let include_root =
+        include_root.map(|root| normalize_path(root).unwrap_or_else(|_| root.to_path_buf()));
+    expand_config_file_inner(file, config, include_root.as_deref(), &mut chain).await
+}
+
+fn expand_config_file_inner<'a>(
+    file: &'a AbsolutePathBuf,
+    mut config: TomlValue,
+    include_root: Option<&'a Path>,
+    chain: &'a mut Vec<PathBuf>,
+) -> BoxFuture<'a, io::Result<ExpandedConfigFile>> {
+    async move {
+        let raw = config.clone();
+        let mut warnings: Vec<String> =
+            interpolate_env_vars(&mut config, &|name| std::env::var(name).ok())
+                .into_iter()
+                .map(|warning| format!("Config file {}: {warning}", file.display()))
+                .collect();
+        let include_paths = take_include_paths(&mut config).map_err(|message| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid config file {}: {message}", file.display()),
+            )
+        })?;
+
+        let base_dir = file.parent().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Config file {} has no parent directory", file.display()),
+            )
+        })?;
+        let config = resolve_relative_paths_in_config_toml(config, base_dir.as_path())?;
+
+        let mut merged = TomlValue::Table(toml::map::Map::new());
+        let mut includes = Vec::new();
+        let mut included_key_files = HashMap::new();
+        for include_path in include_paths {
+            let included = AbsolutePathBuf::resolve_path_against_base(&include_path, &base_dir)?;
+            let canonical = canonical_path(&included);
+            if let Some(root) = include_root
+                && !canonical.starts_with(root)
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Config file {} may only include files inside {}, not {}",
+                        file.display(),
+                        root.display(),
+                        included.display()
+                    ),
+                ));
+            }
+            if chain.contains(&canonical) {
+                let cycle = chain
+                    .iter()
+                    .chain(std::iter::once(&canonical))
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Config include cycle detected: {cycle}"),
+                ));
+            }
+
+            let contents = tokio::fs::read_to_string(&included).await.map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!(
+                        "Failed to read config file {} included from {}: {err}",
+                        included.display(),
+                        file.display()
+                    ),
+                )
+            })?;
+            let included_config: TomlValue = toml::from_str(&contents).map_err(|err| {
+                let config_error =
+                    config_error_from_toml(included.as_path(), &contents, err.clone());
+                io_error_from_config_error(io::ErrorKind::InvalidData, config_error, Some(err))
+            })?;
+
+            chain.push(canonical);
+            let expanded =
+                expand_config_file_inner(&included, included_config, include_root, chain).await?;
+            warnings.extend(expanded.warnings);
+            chain.pop();
+
+            let mut keys = Vec::new();
+            collect_leaf_keys(&expanded.config, &mut Vec::new(), &mut keys);
+            for key in keys {
+                let source = expanded
+                    .included_key_files
+                    .get(&key)
+                    .cloned()
+                    .unwrap_or_else(|| included.clone());
+                included_key_files.insert(key, source);
+            }
+            merge_toml_values(&mut merged, &expanded.config);
+            includes.extend(expanded.includes);
+            includes.push(included);
+        }
+
+        // Values set by the file itself take precedence over its includes.
+        let mut own_keys = Vec::new();
+        collect_leaf_keys(&config, &mut Vec::new(), &mut own_keys);
+        for key in own_keys {
+            included_key_files.remove(&key);
+        }
+        merge_toml_values(&mut merged, &config);
+
+        Ok(ExpandedConfigFile {
+            config: merged,
+            raw,
+            includes,
+            included_key_files,
+            warnings,
+        })
+    }
+    .boxed()
+}
+
+fn canonical_path(path: &AbsolutePathBuf) -> PathBuf {
+    normalize_path(path.as_path()).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Removes the top-level `include` entry from `config`, returning the paths
+/// it lists. A single string is accepted as shorthand for a one-entry array.
+fn take_include_paths(config: &mut TomlValue) -> Result<Vec<String>, String> {
+    let Some(table) = config.as_table_mut() else {
+        return Ok(Vec::new());
+    };
+    match table.remove(INCLUDE_KEY) {
+        None => Ok(Vec::new()),
+        Some(TomlValue::String(path)) => Ok(vec![path]),
+        Some(TomlValue::Array(entries)) => entries
+            .into_iter()
+            .map(|entry| match entry {
+                TomlValue::String(path) => Ok(path),
+                _ => Err(format!("`{INCLUDE_KEY}` must be an array of file paths")),
+            })
+            .collect(),
+        Some(_) => Err(format!("`{INCLUDE_KEY}` must be an array of file paths")),
+    }
+}
+
+/// Replaces `${VAR}` and `${VAR:-default}` references in every string value
+/// of `value`. The default applies when `VAR` is unset or empty; `$${`
+/// produces a literal `${`. References that cannot be resolved are kept
+/// verbatim; the returned warnings name the key and the reference.
+pub(super) fn interpolate_env_vars(
+    value: &mut TomlValue,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    interpolate_value(value, lookup, &mut Vec::new(), &mut warnings);
+    warnings
+}
+
+fn interpolate_value(
+    value: &mut TomlValue,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    path: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) {
+    match value {
+        TomlValue::String(text) => {
+            if text.contains('$') {
+                let mut problems = Vec::new();
+                *text = interpolate_str(text, lookup, &mut problems);
+                warnings.extend(problems.into_iter().map(|problem| {
+                    format!("`{}`: {problem}; left as literal text", path.join("."))
+                }));
+            }
+        }
+        TomlValue::Table(table) => {
+            for (key, child) in table.iter_mut() {
+                path.push(key.clone());
+                interpolate_value(child, lookup, path, warnings);
+                path.pop();
+            }
+        }
+        TomlValue::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                path.push(index.to_string());
+                interpolate_value(item, lookup, path, warnings);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn interpolate_str(
+    input: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    problems: &mut Vec<String>,
+) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(index) = rest.find('$') {
+        output.push_str(&rest[..index]);
+        let tail = &rest[index..];
+        if let Some(after) = tail.strip_prefix("$${") {
+            output.push_str("${");
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("${") {
+            let Some(end) = after.find('}') else {
+                problems.push("unterminated `${` reference".to_string());
+                output.push_str(tail);
+                return output;
+            };
+            let expression = &after[..end];
+            let reference = &tail[..end + 3];
+            rest = &after[end + 1..];
+            let (name, default) = match expression.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (expression, None),
+            };
+            if !is_valid_env_var_name(name) {
+                problems.push(format!(
+                    "`{reference}` is not an environment variable reference"
+                ));
+                output.push_str(reference);
+                continue;
+            }
+            match (lookup(name), default) {
+                (Some(value), Some(default)) if value.is_empty() => output.push_str(default),
+                (Some(value), _) => output.push_str(&value),
+                (None, Some(default)) => output.push_str(default),
+                (None, None) => {
+                    problems.push(format!("environment variable `{name}` is not set"));
+                    output.push_str(reference);
+                }
+            }
+        } else {
+            output.push('$');
+            rest = &tail[1..];
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn is_valid_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first == '_' || first.is_ascii_alphabetic())
+        && chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
+}
+
+fn collect_leaf_keys(value: &TomlValue, path: &mut Vec<String>, out: &mut Vec<String>) {
+    match value {
+        TomlValue::Table(table) => {
+            for (key, child) in table {
+                path.push(key.clone());
+                collect_leaf_keys(child, path, out);
+                path.pop();
+            }
+        }
+        TomlValue::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                path.push(index.to_string());
+                collect_leaf_keys(item, path, out);
+                path.pop();
+            }
+        }
+        _ => {
+            if !path.is_empty() {
+                out.push(path.join("."));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "TEAM_TOKEN" => Some("secret".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn interpolates_env_vars_with_defaults_and_escapes() {
+        let mut value: TomlValue = toml::from_str(
+            r#"
+token = "Bearer ${TEAM_TOKEN}"
+region = "${REGION:-us-east-1}"
+empty = "${EMPTY:-fallback}"
+literal = "$${TEAM_TOKEN} costs $5"
+args = ["--token=${TEAM_TOKEN}"]
+"#,
+        )
+        .expect("parse");
+
+        let warnings = interpolate_env_vars(&mut value, &lookup);
+        assert_eq!(warnings, Vec::<String>::new());
+
+        let expected: TomlValue = toml::from_str(
+            r#"
+token = "Bearer secret"
+region = "us-east-1"
+empty = "fallback"
+literal = "${TEAM_TOKEN} costs $5"
+args = ["--token=secret"]
+"#,
+        )
+        .expect("parse");
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn unresolved_references_stay_literal_with_warnings() {
+        let mut value: TomlValue = toml::from_str(
+            r#"
+[mcp_servers.docs.env]
+API_KEY = "${DOCS_KEY}"
+
+[shell]
+script = "echo ${1} ${PWD##*/} ${TEAM_TOKEN}"
+"#,
+        )
+        .expect("parse");
+
+        let warnings = interpolate_env_vars(&mut value, &lookup);
+
+        let expected: TomlValue = toml::from_str(
+            r#"
+[mcp_servers.docs.env]
+API_KEY = "${DOCS_KEY}"
+
+[shell]
+script = "echo ${1} ${PWD##*/} secret"
+"#,
+        )
+        .expect("parse");
+        assert_eq!(value, expected);
+        assert_eq!(
+            warnings,
+            vec![
+                "`mcp_servers.docs.env.API_KEY`: environment variable `DOCS_KEY` is not set; left as literal text".to_string(),
+                "`shell.script`: `${1}` is not an environment variable reference; left as literal text".to_string(),
+                "`shell.script`: `${PWD##*/}` is not an environment variable reference; left as literal text".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn includes_merge_underneath_and_track_key_files() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let shared = tmp.path().join("shared");
+        std::fs::create_dir(&shared)?;
+        std::fs::write(
+            shared.join("team.toml"),
+            "model = \"team-model\"\nmodel_provider = \"team\"\n",
+        )?;
+        let config_path = AbsolutePathBuf::from_absolute_path(tmp.path().join("config.toml"))?;
+        let config: TomlValue =
+            toml::from_str("include = [\"shared/team.toml\"]\nmodel = \"mine\"\n")?;
+
+        let expanded = expand_config_file(&config_path, config, None).await?;
+
+        let team_file = AbsolutePathBuf::from_absolute_path(shared.join("team.toml"))?;
+        let expected_config: TomlValue =
+            toml::from_str("model = \"mine\"\nmodel_provider = \"team\"\n")?;
+        assert_eq!(expanded.config, expected_config);
+        assert_eq!(expanded.includes, vec![team_file.clone()]);
+        assert_eq!(
+            expanded.included_key_files,
+            HashMap::from([("model_provider".to_string(), team_file)])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn include_cycles_are_reported() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        std::fs::write(tmp.path().join("a.toml"), "include = [\"b.toml\"]\n")?;
+        std::fs::write(tmp.path().join("b.toml"), "include = [\"a.toml\"]\n")?;
+        let config_path = AbsolutePathBuf::from_absolute_path(tmp.path().join("a.toml"))?;
+        let config: TomlValue = toml::from_str("include = [\"b.toml\"]\n")?;
+
+        let err = expand_config_file(&config_path, config, None)
+            .await
+            .expect_err("cycle should fail");
+
+        let message = err.to_string();
+        assert!(
+            message.starts_with("Config include cycle detected:"),
+            "{message}"
+        );
+        assert!(message.ends_with("a.toml"), "{message}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn includes_outside_the_include_root_are_rejected() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let dot_codex = tmp.path().join(".codex");
+        std::fs::create_dir(&dot_codex)?;
+        std::fs::write(dot_codex.join("shared.toml"), "model = \"shared\"\n")?;
+        std::fs::write(tmp.path().join("outside.toml"), "model = \"outside\"\n")?;
+        let config_path = AbsolutePathBuf::from_absolute_path(dot_codex.join("config.toml"))?;
+
+        let inside: TomlValue = toml::from_str("include = [\"shared.toml\"]\n")?;
+        let expanded = expand_config_file(&config_path, inside, Some(&dot_codex)).await?;
+        assert_eq!(expanded.config, toml::from_str("model = \"shared\"\n")?);
+
+        let outside: TomlValue = toml::from_str("include = [\"../outside.toml\"]\n")?;
+        let err = expand_config_file(&config_path, outside, Some(&dot_codex))
+            .await
+            .expect_err("include outside .codex should fail");
+        assert!(
+            err.to_string().contains("may only include files inside"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ mod cloud_requirements;
 mod config_requirements;
 mod diagnostics;
 mod fingerprint;
+mod includes;
 mod layer_io;
 #[cfg(target_os = "macos")]
 mod macos;
@@ -17,6 +18,7 @@ use crate::config::CONFIG_TOML_FILE;
 use crate::config::ConfigToml;
 use crate::config::deserialize_config_toml_with_base;
 use crate::config_loader::config_requirements::ConfigRequirementsWithSources;
+use crate::config_loader::includes::expand_config_file;
 use crate::config_loader::layer_io::LoadedConfigLayers;
 use crate::git_info::resolve_root_git_project_for_trust;
 use codex_app_server_protocol::ConfigLayerSource;
@@ -41,11 +43,14 @@ pub use config_requirements::RequirementSource;
 pub use config_requirements::ResidencyRequirement;
 pub use config_requirements::SandboxModeRequirement;
 pub use config_requirements::Sourced;
+pub use diagnostics::ConfigDiagnostic;
+pub use diagnostics::ConfigDiagnosticSeverity;
 pub use diagnostics::ConfigError;
 pub use diagnostics::ConfigLoadError;
 pub use diagnostics::TextPosition;
 pub use diagnostics::TextRange;
 pub(crate) use diagnostics::config_error_from_toml;
+pub(crate) use diagnostics::config_layer_diagnostics;
 pub(crate) use diagnostics::first_layer_config_error;
 pub(crate) use diagnostics::first_layer_config_error_from_entries;
 pub use diagnostics::format_config_error;
@@ -139,8 +144,6 @@ pub async fn load_config_layers_state(
     )
     .await?;
 
-    let mut layers = Vec::<ConfigLayerEntry>::new();
-
     let cli_overrides_layer = if cli_overrides.is_empty() {
         None
     } else {
@@ -155,6 +158,67 @@ pub async fn load_config_layers_state(
         )?)
     };
 
+    let mut layers = load_file_backed_layers(codex_home, cwd, cli_overrides_layer.as_ref()).await?;
+
+    // Add a layer for runtime overrides from the CLI or UI, if any exist.
+    if let Some(cli_overrides_layer) = cli_overrides_layer {
+        layers.push(ConfigLayerEntry::new(
+            ConfigLayerSource::SessionFlags,
+            cli_overrides_layer,
+        ));
+    }
+
+    // Make a best-effort to support the legacy `managed_config.toml` as a
+    // config layer on top of everything else. For fields in
+    // `managed_config.toml` that do not have an equivalent in
+    // `ConfigRequirements`, note users can still override these values on a
+    // per-turn basis in the TUI and VS Code.
+    let LoadedConfigLayers {
+        managed_config,
+        managed_config_from_mdm,
+    } = loaded_config_layers;
+    if let Some(config) = managed_config {
+        let managed_parent = config.file.as_path().parent().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Managed config file {} has no parent directory",
+                    config.file.as_path().display()
+                ),
+            )
+        })?;
+        let managed_config =
+            resolve_relative_paths_in_config_toml(config.managed_config, managed_parent)?;
+        layers.push(ConfigLayerEntry::new(
+            ConfigLayerSource::LegacyManagedConfigTomlFromFile { file: config.file },
+            managed_config,
+        ));
+    }
+    if let Some(config) = managed_config_from_mdm {
+        layers.push(ConfigLayerEntry::new(
+            ConfigLayerSource::LegacyManagedConfigTomlFromMdm,
+            config,
+        ));
+    }
+
+    ConfigLayerStack::new(
+        layers,
+        config_requirements_toml.clone().try_into()?,
+        config_requirements_toml.into_toml(),
+    )
+}
+
+/// Loads the layers backed by `config.toml` files: system, user, and (when
+/// `cwd` is set) project layers, each with its includes expanded.
+/// `cli_overrides_layer` only takes part in locating the project root and
+/// deciding project trust; it is not added as a layer.
+async fn load_file_backed_layers(
+    codex_home: &Path,
+    cwd: Option<AbsolutePathBuf>,
+    cli_overrides_layer: Option<&TomlValue>,
+) -> io::Result<Vec<ConfigLayerEntry>> {
+    let mut layers = Vec::<ConfigLayerEntry>::new();
+
     // Include an entry for the "system" config folder, loading its config.toml,
     // if it exists.
     let system_config_toml_file = if cfg!(unix) {
@@ -199,7 +263,7 @@ pub async fn load_config_layers_state(
         for layer in &layers {
             merge_toml_values(&mut merged_so_far, &layer.config);
         }
-        if let Some(cli_overrides_layer) = cli_overrides_layer.as_ref() {
+        if let Some(cli_overrides_layer) = cli_overrides_layer {
             merge_toml_values(&mut merged_so_far, cli_overrides_layer);
         }
 
@@ -251,96 +315,107 @@ pub async fn load_config_layers_state(
         layers.extend(project_layers);
     }
 
-    // Add a layer for runtime overrides from the CLI or UI, if any exist.
-    if let Some(cli_overrides_layer) = cli_overrides_layer {
-        layers.push(ConfigLayerEntry::new(
-            ConfigLayerSource::SessionFlags,
-            cli_overrides_layer,
-        ));
-    }
+    Ok(layers)
+}
 
-    // Make a best-effort to support the legacy `managed_config.toml` as a
-    // config layer on top of everything else. For fields in
-    // `managed_config.toml` that do not have an equivalent in
-    // `ConfigRequirements`, note users can still override these values on a
-    // per-turn basis in the TUI and VS Code.
-    let LoadedConfigLayers {
-        managed_config,
-        managed_config_from_mdm,
-    } = loaded_config_layers;
-    if let Some(config) = managed_config {
-        let managed_parent = config.file.as_path().parent().ok_or_else(|| {
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!(
-                    "Managed config file {} has no parent directory",
-                    config.file.as_path().display()
-                ),
-            )
-        })?;
-        let managed_config =
-            resolve_relative_paths_in_config_toml(config.managed_config, managed_parent)?;
-        layers.push(ConfigLayerEntry::new(
-            ConfigLayerSource::LegacyManagedConfigTomlFromFile { file: config.file },
-            managed_config,
-        ));
-    }
-    if let Some(config) = managed_config_from_mdm {
-        layers.push(ConfigLayerEntry::new(
-            ConfigLayerSource::LegacyManagedConfigTomlFromMdm,
-            config,
-        ));
+/// Re-reads the file-backed layers of `stack` (system, user, and project
+/// `config.toml` files together with their includes) for a running session
+/// rooted at `cwd`. Managed layers, session flags, and requirements are carried
+/// over from `stack` unchanged, so the loader overrides and cloud requirements
+/// the session started with stay in effect; the session flags also still take
+/// part in locating the project root and deciding trust.
+///
+/// Returns `None` when every re-read layer still has the same version and
+/// expanded config, i.e. nothing that affects the effective config changed on
+/// disk.
+pub(crate) async fn reload_config_file_layers(
+    stack: &ConfigLayerStack,
+    codex_home: &Path,
+    cwd: AbsolutePathBuf,
+) -> io::Result<Option<ConfigLayerStack>> {
+    let is_file_layer = |layer: &&ConfigLayerEntry| {
+        matches!(
+            layer.name,
+            ConfigLayerSource::System { .. }
+                | ConfigLayerSource::User { .. }
+                | ConfigLayerSource::Project { .. }
+        )
+    };
+    let previous_layers = stack.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true);
+    let session_flags = previous_layers
+        .iter()
+        .find(|layer| layer.name == ConfigLayerSource::SessionFlags)
+        .map(|layer| &layer.config);
+    let reloaded_layers = load_file_backed_layers(codex_home, Some(cwd), session_flags).await?;
+
+    // `version` only covers each layer's own file, so the expanded config is
+    // compared too to pick up changes in included files.
+    let previous_fingerprints = previous_layers
+        .iter()
+        .filter(is_file_layer)
+        .map(|layer| (&layer.name, &layer.version, &layer.config))
+        .collect::<Vec<_>>();
+    let reloaded_fingerprints = reloaded_layers
+        .iter()
+        .map(|layer| (&layer.name, &layer.version, &layer.config))
+        .collect::<Vec<_>>();
+    if previous_fingerprints == reloaded_fingerprints {
+        return Ok(None);
     }
 
+    let mut layers: Vec<ConfigLayerEntry> = reloaded_layers
+        .into_iter()
+        .chain(
+            previous_layers
+                .into_iter()
+                .filter(|layer| !is_file_layer(layer))
+                .cloned(),
+        )
+        .collect();
+    // Stable, so project layers keep their root-to-cwd order.
+    layers.sort_by_key(|layer| layer.name.precedence());
     ConfigLayerStack::new(
         layers,
-        config_requirements_toml.clone().try_into()?,
-        config_requirements_toml.into_toml(),
+        stack.requirements().clone(),
+        stack.requirements_toml().clone(),
     )
+    .map(Some)
 }
 
-/// Attempts to load a config.toml file from `config_toml`.
-/// - If the file exists and is valid TOML, passes the parsed `toml::Value` to
+/// Attempts to load a config.toml file from `toml_file`.
+/// - If the file exists and is valid TOML, expands `${VAR}` references and
+///   `include` directives, passes the resulting `toml::Value` to
 ///   `create_entry` and returns the resulting layer entry.
 /// - If the file does not exist, uses an empty `Table` with `create_entry` and
 ///   returns the resulting layer entry.
 /// - If there is an error reading the file or parsing the TOML, returns an
 ///   error.
 async fn load_config_toml_for_required_layer(
-    config_toml: impl AsRef<Path>,
+    toml_file: &AbsolutePathBuf,
     create_entry: impl FnOnce(TomlValue) -> ConfigLayerEntry,
 ) -> io::Result<ConfigLayerEntry> {
-    let toml_file = config_toml.as_ref();
-    let toml_value = match tokio::fs::read_to_string(toml_file).await {
+    let expanded = match tokio::fs::read_to_string(toml_file).await {
         Ok(contents) => {
             let config: TomlValue = toml::from_str(&contents).map_err(|err| {
-                let config_error = config_error_from_toml(toml_file, &contents, err.clone());
+                let config_error =
+                    config_error_from_toml(toml_file.as_path(), &contents, err.clone());
                 io_error_from_config_error(io::ErrorKind::InvalidData, config_error, Some(err))
             })?;
-            let config_parent = toml_file.parent().ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    format!(
-                        "Config file {} has no parent directory",
-                        toml_file.display()
-                    ),
-                )
-            })?;
-            resolve_relative_paths_in_config_toml(config, config_parent)
+            expand_config_file(toml_file, config, None).await
         }
         Err(e) => {
             if e.kind() == io::ErrorKind::NotFound {
-                Ok(TomlValue::Table(toml::map::Map::new()))
-            } else {
-                Err(io::Error::new(
-                    e.kind(),
-                    format!("Failed to read config file {}: {e}", toml_file.display()),
-                ))
+                return Ok(create_entry(TomlValue::Table(toml::map::Map::new())));
             }
+            Err(io::Error::new(
+                e.kind(),
+                format!("Failed to read config file {}: {e}", toml_file.display()),
+            ))
         }
     }?;
 
-    Ok(create_entry(toml_value))
+    let config = expanded.config.clone();
+    Ok(create_entry(config).with_expansion(expanded))
 }
 
 /// If available, apply requirements from `/etc/codex/requirements.toml` to
@@ -748,10 +823,26 @@ async fn load_project_layers(
                         continue;
                     }
                 };
-                let config =
-                    resolve_relative_paths_in_config_toml(config, dot_codex_abs.as_path())?;
-                let entry =
-                    project_layer_entry(trust_context, &dot_codex_abs, &layer_dir, config, true);
+                // Includes and environment variables are only expanded for
+                // trusted projects; untrusted layers are kept verbatim. Project
+                // includes must stay inside the project's `.codex/` folder.
+                let entry = if decision.is_trusted() {
+                    let expanded =
+                        expand_config_file(&config_file, config, Some(dot_codex_abs.as_path()))
+                            .await?;
+                    project_layer_entry(
+                        trust_context,
+                        &dot_codex_abs,
+                        &layer_dir,
+                        expanded.config.clone(),
+                        true,
+                    )
+                    .with_expansion(expanded)
+                } else {
+                    let config =
+                        resolve_relative_paths_in_config_toml(config, dot_codex_abs.as_path())?;
+                    project_layer_entry(trust_context, &dot_codex_abs, &layer_dir, config, true)
+                };
                 layers.push(entry);
             }
             Err(err) => {
@@ -1,8 +1,10 @@
+use crate::config::CONFIG_TOML_FILE;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::ConfigRequirementsToml;
 
 use super::fingerprint::record_origins;
 use super::fingerprint::version_for_toml;
+use super::includes::ExpandedConfigFile;
 use super::merge::merge_toml_values;
 use codex_app_server_protocol::ConfigLayer;
 use codex_app_server_protocol::ConfigLayerMetadata;
@@ -27,8 +29,20 @@ pub struct LoaderOverrides {
 pub struct ConfigLayerEntry {
     pub name: ConfigLayerSource,
     pub config: TomlValue,
+    /// The layer's own file as written, before `${VAR}` references and
+    /// `include` are expanded. Edits and `version` are based on it so that
+    /// expanded values are never written back to the file.
+    pub raw_config: TomlValue,
     pub version: String,
     pub disabled_reason: Option<String>,
+    /// Files pulled in through `include`, in the order they were merged.
+    pub includes: Vec<AbsolutePathBuf>,
+    /// Dotted keys whose value came from an included file rather than from
+    /// the layer's own config file.
+    pub included_key_files: HashMap<String, AbsolutePathBuf>,
+    /// Problems found while expanding the layer's files that did not stop it
+    /// from loading, such as `${VAR}` references left as literal text.
+    pub warnings: Vec<String>,
 }
 
 impl ConfigLayerEntry {
@@ -36,9 +50,13 @@ impl ConfigLayerEntry {
         let version = version_for_toml(&config);
         Self {
             name,
+            raw_config: config.clone(),
             config,
             version,
             disabled_reason: None,
+            includes: Vec::new(),
+            included_key_files: HashMap::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -50,12 +68,35 @@ impl ConfigLayerEntry {
         let version = version_for_toml(&config);
         Self {
             name,
+            raw_config: config.clone(),
             config,
             version,
             disabled_reason: Some(disabled_reason.into()),
+            includes: Vec::new(),
+            included_key_files: HashMap::new(),
+            warnings: Vec::new(),
         }
     }
 
+    pub(super) fn with_expansion(mut self, expanded: ExpandedConfigFile) -> Self {
+        self.version = version_for_toml(&expanded.raw);
+        self.raw_config = expanded.raw;
+        self.includes = expanded.includes;
+        self.included_key_files = expanded.included_key_files;
+        self.warnings = expanded.warnings;
+        self
+    }
+
+    /// Replaces the layer's contents after an edit: `raw_config` is the
+    /// edited file and `config` the same edit applied to the expanded values.
+    /// The includes are kept.
+    pub fn with_edited_config(mut self, raw_config: TomlValue, config: TomlValue) -> Self {
+        self.version = version_for_toml(&raw_config);
+        self.raw_config = raw_config;
+        self.config = config;
+        self
+    }
+
     pub fn is_disabled(&self) -> bool {
         self.disabled_reason.is_some()
     }
@@ -64,6 +105,16 @@ impl ConfigLayerEntry {
         ConfigLayerMetadata {
             name: self.name.clone(),
             version: self.version.clone(),
+            file: None,
+        }
+    }
+
+    /// Like [Self::metadata], but also names the included file that supplied
+    /// `key`, if any.
+    pub fn metadata_for_key(&self, key: &str) -> ConfigLayerMetadata {
+        ConfigLayerMetadata {
+            file: self.included_key_files.get(key).cloned(),
+            ..self.metadata()
         }
     }
 
@@ -88,6 +139,25 @@ impl ConfigLayerEntry {
             ConfigLayerSource::LegacyManagedConfigTomlFromMdm => None,
         }
     }
+
+    /// The layer's own config file followed by the files it includes. Layers
+    /// that do not come from a file (MDM, session flags) have none.
+    pub fn config_files(&self) -> Vec<PathBuf> {
+        let path = match &self.name {
+            ConfigLayerSource::System { file }
+            | ConfigLayerSource::User { file }
+            | ConfigLayerSource::LegacyManagedConfigTomlFromFile { file } => file.to_path_buf(),
+            ConfigLayerSource::Project { dot_codex_folder } => {
+                dot_codex_folder.as_path().join(CONFIG_TOML_FILE)
+            }
+            ConfigLayerSource::Mdm { .. }
+            | ConfigLayerSource::SessionFlags
+            | ConfigLayerSource::LegacyManagedConfigTomlFromMdm => return Vec::new(),
+        };
+        std::iter::once(path)
+            .chain(self.includes.iter().map(AbsolutePathBuf::to_path_buf))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -149,13 +219,16 @@ impl ConfigLayerStack {
     /// replaced; otherwise, it is inserted into the stack at the appropriate
     /// position based on precedence rules.
     pub fn with_user_config(&self, config_toml: &AbsolutePathBuf, user_config: TomlValue) -> Self {
-        let user_layer = ConfigLayerEntry::new(
+        self.with_user_layer(ConfigLayerEntry::new(
             ConfigLayerSource::User {
                 file: config_toml.clone(),
             },
             user_config,
-        );
+        ))
+    }
 
+    /// Like [Self::with_user_config], but takes the whole layer entry.
+    pub fn with_user_layer(&self, user_layer: ConfigLayerEntry) -> Self {
         let mut layers = self.layers.clone();
         match self.user_layer_index {
             Some(index) => {
@@ -204,7 +277,7 @@ impl ConfigLayerStack {
         let mut path = Vec::new();
 
         for layer in self.get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false) {
-            record_origins(&layer.config, &layer.metadata(), &mut path, &mut origins);
+            record_origins(&layer.config, layer, &mut path, &mut origins);
         }
 
         origins
@@ -231,6 +231,101 @@ extra = true
     assert_eq!(nested.get("extra"), Some(&TomlValue::Boolean(true)));
 }
 
+#[tokio::test]
+async fn user_config_includes_are_merged_and_tracked_in_origins() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let team_file = tmp.path().join("team.toml");
+    std::fs::write(
+        &team_file,
+        r#"
+model = "team-model"
+model_provider = "${CODEX_TEST_UNSET_PROVIDER:-team-provider}"
+"#,
+    )?;
+    std::fs::write(
+        tmp.path().join(CONFIG_TOML_FILE),
+        r#"
+include = ["team.toml"]
+model = "my-model"
+"#,
+    )?;
+
+    let overrides = LoaderOverrides {
+        managed_config_path: Some(tmp.path().join("managed_config.toml")),
+        #[cfg(target_os = "macos")]
+        managed_preferences_base64: None,
+        macos_managed_config_requirements_base64: None,
+    };
+    let layers = load_config_layers_state(
+        tmp.path(),
+        None,
+        &[] as &[(String, TomlValue)],
+        overrides,
+        CloudRequirementsLoader::default(),
+    )
+    .await?;
+
+    let effective = layers.effective_config();
+    assert_eq!(
+        effective.get("model"),
+        Some(&TomlValue::String("my-model".to_string()))
+    );
+    assert_eq!(
+        effective.get("model_provider"),
+        Some(&TomlValue::String("team-provider".to_string()))
+    );
+    assert_eq!(effective.get("include"), None);
+
+    let team_file = AbsolutePathBuf::from_absolute_path(&team_file)?;
+    let user_layer = layers.get_user_layer().expect("user layer");
+    assert_eq!(user_layer.includes, vec![team_file.clone()]);
+
+    let origins = layers.origins();
+    assert_eq!(origins["model"].name, user_layer.name);
+    assert_eq!(origins["model"].file, None);
+    assert_eq!(origins["model_provider"].name, user_layer.name);
+    assert_eq!(origins["model_provider"].file, Some(team_file));
+    Ok(())
+}
+
+#[tokio::test]
+async fn missing_env_var_in_user_config_is_kept_with_a_warning() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    std::fs::write(
+        tmp.path().join(CONFIG_TOML_FILE),
+        "model = \"${CODEX_TEST_UNSET_MODEL}\"\n",
+    )?;
+
+    let layers = load_config_layers_state(
+        tmp.path(),
+        None,
+        &[] as &[(String, TomlValue)],
+        LoaderOverrides {
+            managed_config_path: Some(tmp.path().join("managed_config.toml")),
+            #[cfg(target_os = "macos")]
+            managed_preferences_base64: None,
+            macos_managed_config_requirements_base64: None,
+        },
+        CloudRequirementsLoader::default(),
+    )
+    .await?;
+
+    let user_layer = layers.get_user_layer().expect("user layer");
+    assert_eq!(
+        user_layer.config.get("model"),
+        Some(&TomlValue::String("${CODEX_TEST_UNSET_MODEL}".to_string()))
+    );
+    assert_eq!(user_layer.warnings.len(), 1);
+    assert!(
+        user_layer.warnings[0].contains(
+            "`model`: environment variable `CODEX_TEST_UNSET_MODEL` is not set; left as literal text"
+        ),
+        "{:?}",
+        user_layer.warnings
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn returns_empty_when_all_layers_missing() {
     let tmp = tempdir().expect("tempdir");
@@ -263,8 +358,12 @@ async fn returns_empty_when_all_layers_missing() {
                     .expect("resolve user config.toml path")
             },
             config: TomlValue::Table(toml::map::Map::new()),
+            raw_config: TomlValue::Table(toml::map::Map::new()),
             version: version_for_toml(&TomlValue::Table(toml::map::Map::new())),
             disabled_reason: None,
+            includes: Vec::new(),
+            included_key_files: HashMap::new(),
+            warnings: Vec::new(),
         },
         user_layer,
     );
@@ -829,8 +928,12 @@ async fn project_layer_is_added_when_dot_codex_exists_without_config_toml() -> s
                 dot_codex_folder: AbsolutePathBuf::from_absolute_path(project_root.join(".codex"))?,
             },
             config: TomlValue::Table(toml::map::Map::new()),
+            raw_config: TomlValue::Table(toml::map::Map::new()),
             version: version_for_toml(&TomlValue::Table(toml::map::Map::new())),
             disabled_reason: None,
+            includes: Vec::new(),
+            included_key_files: HashMap::new(),
+            warnings: Vec::new(),
         }],
         project_layers
     );
@@ -922,8 +1025,12 @@ async fn codex_home_within_project_tree_is_not_double_loaded() -> std::io::Resul
                 dot_codex_folder: AbsolutePathBuf::from_absolute_path(&nested_dot_codex)?,
             },
             config: child_config.clone(),
+            raw_config: child_config.clone(),
             version: version_for_toml(&child_config),
             disabled_reason: None,
+            includes: Vec::new(),
+            included_key_files: HashMap::new(),
+            warnings: Vec::new(),
         }],
         project_layers
     );
@@ -0,0 +1,423 @@
+//! Hot reload of config files while a session is running.
+//!
+//! When the [`FileWatcher`](crate::file_watcher::FileWatcher) reports that a
+//! config file changed, the session re-reads its file-backed config layers and
+//! rebuilds the [`Config`] with the harness overrides it was started with.
+//! Settings that are resolved per turn are taken from the rebuilt config when
+//! the next turn starts. Everything else is wired up once when the session is
+//! created, so those keys are only reported as requiring a restart.
+//!
+//! Approval and sandbox settings are only ever tightened by a reload. A file
+//! edit that would loosen them, directly or by switching `profile`, is
+//! reported as needing confirmation and left unapplied until the user picks
+//! the looser mode themselves.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use toml::Value as TomlValue;
+
+use crate::config::Config;
+use crate::config_loader::reload_config_file_layers;
+use crate::protocol::AskForApproval;
+use crate::protocol::SandboxPolicy;
+
+/// Top-level config keys that take effect on the next turn. The same keys
+/// nested under `profiles.<name>` are treated alike.
+const NEXT_TURN_KEYS: &[&str] = &[
+    "approval_policy",
+    "mcp_oauth_credentials_store",
+    "mcp_servers",
+    "model",
+    "model_auto_compact_token_limit",
+    "model_context_window",
+    "model_reasoning_effort",
+    "model_reasoning_summary",
+    "personality",
+    "sandbox_mode",
+    "sandbox_workspace_write",
+    "shell_environment_policy",
+    "tool_output_token_limit",
+    "web_search",
+];
+
+/// Table under `[tools]` that declares command tools, which are registered
+/// again on each turn. The other `tools` keys toggle built-in tools through
+/// features and need a restart.
+const COMMAND_TOOLS_KEY: &str = "commands";
+
+/// Acknowledgement flags Codex itself writes to `config.toml`. They have no
+/// effect on a running session, so changes to them are not reported.
+const BOOKKEEPING_KEYS: &[&str] = &["notice", "windows_wsl_setup_acknowledged"];
+
+/// Keys that resolve to the approval policy.
+const APPROVAL_KEYS: &[&str] = &["approval_policy"];
+
+/// Keys that resolve to the sandbox policy.
+const SANDBOX_KEYS: &[&str] = &["sandbox_mode", "sandbox_workspace_write"];
+
+/// Key that selects the active profile, which can change both policies.
+const PROFILE_KEY: &str = "profile";
+
+#[derive(Debug)]
+pub(crate) struct ConfigReload {
+    /// The previous config with every next-turn setting taken from disk.
+    pub(crate) config: Config,
+    /// Changed keys that take effect on the next turn.
+    pub(crate) applied: Vec<String>,
+    /// Changed keys that only take effect once the session is restarted.
+    pub(crate) requires_restart: Vec<String>,
+    /// Changed keys that would loosen approvals or the sandbox. They are not
+    /// applied; the user has to choose the looser mode explicitly.
+    pub(crate) needs_confirmation: Vec<String>,
+}
+
+/// Re-reads the config files behind `previous`. Returns `None` when nothing
+/// that affects the effective config changed.
+pub(crate) async fn reload_config(previous: &Config) -> io::Result<Option<ConfigReload>> {
+    let cwd = AbsolutePathBuf::from_absolute_path(&previous.cwd)?;
+    let Some(config_layer_stack) =
+        reload_config_file_layers(&previous.config_layer_stack, &previous.codex_home, cwd).await?
+    else {
+        return Ok(None);
+    };
+
+    let mut changed = changed_keys(
+        &previous.config_layer_stack.effective_config(),
+        &config_layer_stack.effective_config(),
+    );
+    changed.retain(|key| !BOOKKEEPING_KEYS.contains(&top_level_key(key)));
+    if changed.is_empty() {
+        return Ok(None);
+    }
+
+    let reloaded = Config::load_from_config_layer_stack(
+        config_layer_stack,
+        previous.harness_overrides.clone(),
+        previous.codex_home.clone(),
+    )
+    .await?;
+    let loosens_approval = approval_loosens(
+        previous.approval_policy.value(),
+        reloaded.approval_policy.value(),
+    );
+    let loosens_sandbox =
+        sandbox_loosens(previous.sandbox_policy.get(), reloaded.sandbox_policy.get());
+    let (needs_confirmation, changed): (Vec<String>, Vec<String>) = changed
+        .into_iter()
+        .partition(|key| loosens_policy(key, loosens_approval, loosens_sandbox));
+    let (applied, requires_restart): (Vec<String>, Vec<String>) = changed
+        .into_iter()
+        .partition(|key| applies_on_next_turn(key));
+
+    let mut config = with_next_turn_settings(previous, reloaded);
+    if loosens_approval {
+        config.approval_policy = previous.approval_policy.clone();
+    }
+    if loosens_sandbox {
+        config.sandbox_policy = previous.sandbox_policy.clone();
+    }
+    Ok(Some(ConfigReload {
+        config,
+        applied,
+        requires_restart,
+        needs_confirmation,
+    }))
+}
+
+/// Whether the change to `key` is one of those that loosened the approval or
+/// sandbox policy. Switching `profile` counts for both, since the new profile
+/// may set either.
+fn loosens_policy(key: &str, loosens_approval: bool, loosens_sandbox: bool) -> bool {
+    if key == PROFILE_KEY {
+        return loosens_approval || loosens_sandbox;
+    }
+    let setting = top_level_key(profile_setting(key));
+    (loosens_approval && APPROVAL_KEYS.contains(&setting))
+        || (loosens_sandbox && SANDBOX_KEYS.contains(&setting))
+}
+
+/// Whether switching from `previous` to `next` asks the user less often.
+/// Moving between `on-failure` and `on-request` counts as loosening because
+/// neither is strictly tighter than the other.
+fn approval_loosens(previous: AskForApproval, next: AskForApproval) -> bool {
+    let strictness = |policy: AskForApproval| match policy {
+        AskForApproval::Never => 0,
+        AskForApproval::OnFailure | AskForApproval::OnRequest => 1,
+        AskForApproval::UnlessTrusted => 2,
+    };
+    previous != next && strictness(next) <= strictness(previous)
+}
+
+/// Whether `next` permits anything that `previous` does not.
+fn sandbox_loosens(previous: &SandboxPolicy, next: &SandboxPolicy) -> bool {
+    let within = match (previous, next) {
+        (_, SandboxPolicy::ReadOnly) | (SandboxPolicy::DangerFullAccess, _) => true,
+        (
+            SandboxPolicy::ExternalSandbox {
+                network_access: previous,
+            },
+            SandboxPolicy::ExternalSandbox {
+                network_access: next,
+            },
+        ) => previous.is_enabled() || !next.is_enabled(),
+        (
+            SandboxPolicy::WorkspaceWrite {
+                writable_roots: previous_roots,
+                network_access: previous_network,
+                exclude_tmpdir_env_var: previous_exclude_tmpdir,
+                exclude_slash_tmp: previous_exclude_slash_tmp,
+            },
+            SandboxPolicy::WorkspaceWrite {
+                writable_roots: next_roots,
+                network_access: next_network,
+                exclude_tmpdir_env_var: next_exclude_tmpdir,
+                exclude_slash_tmp: next_exclude_slash_tmp,
+            },
+        ) => {
+            next_roots.iter().all(|root| previous_roots.contains(root))
+                && (*previous_network || !next_network)
+                && (*next_exclude_tmpdir || !previous_exclude_tmpdir)
+                && (*next_exclude_slash_tmp || !previous_exclude_slash_tmp)
+        }
+        (
+            SandboxPolicy::WorkspaceWrite {
+                network_access: previous,
+                ..
+            }
+            | SandboxPolicy::WorkspaceOverlay {
+                network_access: previous,
+            },
+            SandboxPolicy::WorkspaceOverlay {
+                network_access: next,
+            },
+        ) => *previous || !next,
+        _ => false,
+    };
+    !within
+}
+
+/// Copies the settings that can change mid-session from `reloaded` onto
+/// `previous`.
+fn with_next_turn_settings(previous: &Config, reloaded: Config) -> Config {
+    let mut config = previous.clone();
+    config.config_layer_stack = reloaded.config_layer_stack;
+    config.model = reloaded.model;
+    config.model_reasoning_effort = reloaded.model_reasoning_effort;
+    config.model_reasoning_summary = reloaded.model_reasoning_summary;
+    config.model_context_window = reloaded.model_context_window;
+    config.model_auto_compact_token_limit = reloaded.model_auto_compact_token_limit;
+    config.tool_output_token_limit = reloaded.tool_output_token_limit;
+    config.personality = reloaded.personality;
+    config.approval_policy = reloaded.approval_policy;
+    config.sandbox_policy = reloaded.sandbox_policy;
+    config.web_search_mode = reloaded.web_search_mode;
+    config.command_tools = reloaded.command_tools;
+    config.shell_environment_policy = reloaded.shell_environment_policy;
+    config.mcp_servers = reloaded.mcp_servers;
+    config.mcp_oauth_credentials_store_mode = reloaded.mcp_oauth_credentials_store_mode;
+    config
+}
+
+fn applies_on_next_turn(key: &str) -> bool {
+    let key = profile_setting(key);
+    if let Some(tool) = key.strip_prefix("tools.") {
+        return top_level_key(tool) == COMMAND_TOOLS_KEY;
+    }
+    NEXT_TURN_KEYS.contains(&top_level_key(key))
+}
+
+/// Strips a leading `profiles.<name>.` so profile settings are classified
+/// like their top-level counterparts.
+fn profile_setting(key: &str) -> &str {
+    match key.strip_prefix("profiles.") {
+        Some(rest) => rest.split_once('.').map_or(rest, |(_, setting)| setting),
+        None => key,
+    }
+}
+
+fn top_level_key(key: &str) -> &str {
+    key.split('.').next().unwrap_or(key)
+}
+
+/// Dotted paths of the leaf values that differ between `previous` and
+/// `reloaded`, sorted. Arrays are compared as a whole.
+fn changed_keys(previous: &TomlValue, reloaded: &TomlValue) -> Vec<String> {
+    let mut previous_leaves = BTreeMap::new();
+    let mut reloaded_leaves = BTreeMap::new();
+    flatten_leaves(previous, String::new(), &mut previous_leaves);
+    flatten_leaves(reloaded, String::new(), &mut reloaded_leaves);
+
+    let keys: BTreeSet<&String> = previous_leaves
+        .keys()
+        .chain(reloaded_leaves.keys())
+        .collect();
+    keys.into_iter()
+        .filter(|key| previous_leaves.get(*key) != reloaded_leaves.get(*key))
+        .cloned()
+        .collect()
+}
+
+fn flatten_leaves<'a>(
+    value: &'a TomlValue,
+    prefix: String,
+    leaves: &mut BTreeMap<String, &'a TomlValue>,
+) {
+    match value {
+        TomlValue::Table(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten_leaves(value, path, leaves);
+            }
+        }
+        _ => {
+            leaves.insert(prefix, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn changed_keys_reports_added_removed_and_modified_leaves() {
+        let previous: TomlValue = toml::from_str(
+            r#"
+model = "gpt-5"
+approval_policy = "on-request"
+
+[mcp_servers.docs]
+command = "docs-server"
+"#,
+        )
+        .expect("parse previous");
+        let reloaded: TomlValue = toml::from_str(
+            r#"
+model = "gpt-5.1"
+approval_policy = "on-request"
+
+[mcp_servers.docs]
+command = "docs-server"
+args = ["--verbose"]
+
+[model_providers.local]
+name = "Local"
+"#,
+        )
+        .expect("parse reloaded");
+
+        assert_eq!(
+            changed_keys(&previous, &reloaded),
+            vec![
+                "mcp_servers.docs.args".to_string(),
+                "model".to_string(),
+                "model_providers.local.name".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn next_turn_keys_include_profile_settings() {
+        assert!(applies_on_next_turn("model"));
+        assert!(applies_on_next_turn(
+            "sandbox_workspace_write.network_access"
+        ));
+        assert!(applies_on_next_turn("mcp_servers.docs.command"));
+        assert!(applies_on_next_turn("profiles.work.approval_policy"));
+        assert!(applies_on_next_turn(
+            "tools.commands.run_migrations.command"
+        ));
+        assert!(!applies_on_next_turn("tools.web_search"));
+        assert!(!applies_on_next_turn("model_provider"));
+        assert!(!applies_on_next_turn("model_providers.local.base_url"));
+        assert!(!applies_on_next_turn("features.unified_exec"));
+        assert!(!applies_on_next_turn("profiles.work.model_provider"));
+        assert!(!applies_on_next_turn("profile"));
+    }
+
+    #[test]
+    fn profile_switches_that_loosen_policies_need_confirmation() {
+        assert!(loosens_policy("profile", true, false));
+        assert!(loosens_policy("profile", false, true));
+        assert!(!loosens_policy("profile", false, false));
+        assert!(loosens_policy("profiles.work.approval_policy", true, false));
+        assert!(!loosens_policy(
+            "profiles.work.approval_policy",
+            false,
+            true
+        ));
+        assert!(loosens_policy(
+            "sandbox_workspace_write.network_access",
+            false,
+            true
+        ));
+        assert!(!loosens_policy("model", true, true));
+    }
+
+    #[test]
+    fn approval_changes_only_tighten_without_confirmation() {
+        assert!(!approval_loosens(
+            AskForApproval::OnRequest,
+            AskForApproval::UnlessTrusted
+        ));
+        assert!(!approval_loosens(
+            AskForApproval::Never,
+            AskForApproval::OnFailure
+        ));
+        assert!(approval_loosens(
+            AskForApproval::OnRequest,
+            AskForApproval::Never
+        ));
+        assert!(approval_loosens(
+            AskForApproval::OnFailure,
+            AskForApproval::OnRequest
+        ));
+        assert!(!approval_loosens(
+            AskForApproval::Never,
+            AskForApproval::Never
+        ));
+    }
+
+    #[test]
+    fn sandbox_changes_only_tighten_without_confirmation() {
+        let workspace_write = |network_access: bool| SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        };
+
+        assert!(!sandbox_loosens(
+            &SandboxPolicy::DangerFullAccess,
+            &workspace_write(true)
+        ));
+        assert!(!sandbox_loosens(
+            &workspace_write(true),
+            &workspace_write(false)
+        ));
+        assert!(!sandbox_loosens(
+            &workspace_write(false),
+            &SandboxPolicy::ReadOnly
+        ));
+        assert!(sandbox_loosens(
+            &workspace_write(false),
+            &workspace_write(true)
+        ));
+        assert!(sandbox_loosens(
+            &SandboxPolicy::ReadOnly,
+            &workspace_write(false)
+        ));
+        assert!(sandbox_loosens(
+            &workspace_write(true),
+            &SandboxPolicy::DangerFullAccess
+        ));
+    }
+}
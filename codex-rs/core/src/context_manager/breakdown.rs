@@ -0,0 +1,385 @@
+//! Accounting of what occupies the context window, grouped by category.
+//!
+//! Token counts reuse the same per-item estimate as
+//! [`ContextManager::estimate_token_count`](super::ContextManager), so the
+//! category totals add up to the estimated prompt size. Images are reported
+//! separately from the message or tool output that carries them.
+
+use std::collections::HashMap;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::function_call_output_content_items_to_text;
+use codex_protocol::protocol::ContextBreakdownEvent;
+use codex_protocol::protocol::ContextCategory;
+use codex_protocol::protocol::ContextCategoryUsage;
+use codex_protocol::protocol::ContextItemUsage;
+
+use crate::instructions::SkillInstructions;
+use crate::instructions::UserInstructions;
+use crate::session_prefix::is_session_prefix;
+use crate::truncate::TokenCounter;
+
+/// Number of items reported in [`ContextBreakdownEvent::largest_items`].
+const LARGEST_ITEMS_LIMIT: usize = 10;
+
+/// Text that replaces a tool output dropped via `Op::DropContextItems`.
+const DROPPED_TOOL_OUTPUT_PLACEHOLDER: &str = "[tool output removed from context by the user]";
+
+/// Text that replaces an image dropped via `Op::DropContextItems`.
+const DROPPED_IMAGE_PLACEHOLDER: &str = "[image removed from context by the user]";
+
+/// Images have no stable identifier, so they are addressed by the position of
+/// the history item that carries them (e.g. `image:12`).
+const IMAGE_ID_PREFIX: &str = "image:";
+
+const PREVIEW_MAX_CHARS: usize = 80;
+
+fn image_item_id(index: usize) -> String {
+    format!("{IMAGE_ID_PREFIX}{index}")
+}
+
+fn parse_image_item_id(id: &str) -> Option<usize> {
+    id.strip_prefix(IMAGE_ID_PREFIX)?.parse().ok()
+}
+
+/// Builds the breakdown for `items`. `item_tokens` must return the same
+/// estimate used for the overall prompt size.
+pub(super) fn build_breakdown(
+    items: &[ResponseItem],
+    base_instructions: &str,
+    counter: TokenCounter,
+    model_context_window: Option<i64>,
+    item_tokens: impl Fn(&ResponseItem) -> i64,
+) -> ContextBreakdownEvent {
+    let tool_names = tool_names_by_call_id(items);
+    let mut entries = Vec::new();
+
+    entries.push(ContextItemUsage {
+        id: None,
+        category: ContextCategory::BaseInstructions,
+        tool_name: None,
+        preview: preview(base_instructions),
+        tokens: to_i64(counter.count(base_instructions)),
+    });
+
+    for (index, item) in items.iter().enumerate() {
+        let tokens = item_tokens(item);
+        let image_tokens = image_urls(item)
+            .map(|url| to_i64(counter.count(url)))
+            .fold(0i64, i64::saturating_add);
+        if image_tokens > 0 {
+            entries.push(ContextItemUsage {
+                id: Some(image_item_id(index)),
+                category: ContextCategory::Images,
+                tool_name: None,
+                preview: String::new(),
+                tokens: image_tokens.min(tokens),
+            });
+        }
+        let tokens = tokens.saturating_sub(image_tokens).max(0);
+
+        let entry = match item {
+            ResponseItem::Message { role, content, .. } => ContextItemUsage {
+                id: None,
+                category: message_category(role, content),
+                tool_name: None,
+                preview: preview(&message_text(content)),
+                tokens,
+            },
+            ResponseItem::Reasoning { summary, .. } => ContextItemUsage {
+                id: None,
+                category: ContextCategory::Reasoning,
+                tool_name: None,
+                preview: preview(
+                    &summary
+                        .iter()
+                        .map(|ReasoningItemReasoningSummary::SummaryText { text }| text.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                tokens,
+            },
+            ResponseItem::Compaction { .. } => ContextItemUsage {
+                id: None,
+                category: ContextCategory::Compaction,
+                tool_name: None,
+                preview: String::new(),
+                tokens,
+            },
+            ResponseItem::FunctionCall {
+                name, arguments, ..
+            } => ContextItemUsage {
+                id: None,
+                category: ContextCategory::ToolCalls,
+                tool_name: Some(name.clone()),
+                preview: preview(arguments),
+                tokens,
+            },
+            ResponseItem::CustomToolCall { name, input, .. } => ContextItemUsage {
+                id: None,
+                category: ContextCategory::ToolCalls,
+                tool_name: Some(name.clone()),
+                preview: preview(input),
+                tokens,
+            },
+            ResponseItem::LocalShellCall { .. } => ContextItemUsage {
+                id: None,
+                category: ContextCategory::ToolCalls,
+                tool_name: Some("local_shell".to_string()),
+                preview: String::new(),
+                tokens,
+            },
+            ResponseItem::WebSearchCall { .. } => ContextItemUsage {
+                id: None,
+                category: ContextCategory::ToolCalls,
+                tool_name: Some("web_search".to_string()),
+                preview: String::new(),
+                tokens,
+            },
+            ResponseItem::FunctionCallOutput { call_id, output } => ContextItemUsage {
+                id: Some(call_id.clone()),
+                category: ContextCategory::ToolOutputs,
+                tool_name: tool_names.get(call_id.as_str()).cloned(),
+                preview: preview(&function_output_text(&output.body)),
+                tokens,
+            },
+            ResponseItem::CustomToolCallOutput { call_id, output } => ContextItemUsage {
+                id: Some(call_id.clone()),
+                category: ContextCategory::ToolOutputs,
+                tool_name: tool_names.get(call_id.as_str()).cloned(),
+                preview: preview(output),
+                tokens,
+            },
+            ResponseItem::GhostSnapshot { .. } | ResponseItem::Other => continue,
+        };
+        entries.push(entry);
+    }
+
+    let total_tokens = entries
+        .iter()
+        .fold(0i64, |acc, entry| acc.saturating_add(entry.tokens));
+
+    let mut categories: Vec<ContextCategoryUsage> = Vec::new();
+    for entry in &entries {
+        let tool_name = match entry.category {
+            ContextCategory::ToolOutputs => entry.tool_name.clone(),
+            _ => None,
+        };
+        match categories
+            .iter_mut()
+            .find(|usage| usage.category == entry.category && usage.tool_name == tool_name)
+        {
+            Some(usage) => {
+                usage.tokens = usage.tokens.saturating_add(entry.tokens);
+                usage.item_count = usage.item_count.saturating_add(1);
+            }
+            None => categories.push(ContextCategoryUsage {
+                category: entry.category,
+                tool_name,
+                tokens: entry.tokens,
+                item_count: 1,
+            }),
+        }
+    }
+    categories.sort_by(|a, b| b.tokens.cmp(&a.tokens));
+
+    // Base instructions are always present and cannot be dropped, so they
+    // would only crowd out actionable items.
+    let mut largest_items: Vec<ContextItemUsage> = entries
+        .into_iter()
+        .filter(|entry| entry.category != ContextCategory::BaseInstructions && entry.tokens > 0)
+        .collect();
+    largest_items.sort_by(|a, b| b.tokens.cmp(&a.tokens));
+    largest_items.truncate(LARGEST_ITEMS_LIMIT);
+
+    ContextBreakdownEvent {
+        total_tokens,
+        model_context_window,
+        categories,
+        largest_items,
+    }
+}
+
+/// Replaces tool outputs (addressed by call id) and images (addressed by
+/// [`image_item_id`]) with placeholders. Returns the ids that changed.
+pub(super) fn drop_items(items: &mut [ResponseItem], item_ids: &[String]) -> Vec<String> {
+    let mut dropped = Vec::new();
+    for id in item_ids {
+        let changed = match parse_image_item_id(id) {
+            Some(index) => items.get_mut(index).is_some_and(drop_images),
+            None => items
+                .iter_mut()
+                .find(|item| output_call_id(item) == Some(id.as_str()))
+                .is_some_and(drop_tool_output),
+        };
+        if changed && !dropped.contains(id) {
+            dropped.push(id.clone());
+        }
+    }
+    dropped
+}
+
+fn drop_images(item: &mut ResponseItem) -> bool {
+    let mut replaced = false;
+    match item {
+        ResponseItem::Message { content, .. } => {
+            for content_item in content.iter_mut() {
+                if matches!(content_item, ContentItem::InputImage { .. }) {
+                    *content_item = ContentItem::InputText {
+                        text: DROPPED_IMAGE_PLACEHOLDER.to_string(),
+                    };
+                    replaced = true;
+                }
+            }
+        }
+        ResponseItem::FunctionCallOutput { output, .. } => {
+            if let Some(content_items) = output.content_items_mut() {
+                for content_item in content_items.iter_mut() {
+                    if matches!(
+                        content_item,
+                        FunctionCallOutputContentItem::InputImage { .. }
+                    ) {
+                        *content_item = FunctionCallOutputContentItem::InputText {
+                            text: DROPPED_IMAGE_PLACEHOLDER.to_string(),
+                        };
+                        replaced = true;
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    replaced
+}
+
+fn drop_tool_output(item: &mut ResponseItem) -> bool {
+    match item {
+        ResponseItem::FunctionCallOutput { output, .. } => {
+            if matches!(&output.body, FunctionCallOutputBody::Text(text) if text == DROPPED_TOOL_OUTPUT_PLACEHOLDER)
+            {
+                return false;
+            }
+            output.body = FunctionCallOutputBody::Text(DROPPED_TOOL_OUTPUT_PLACEHOLDER.to_string());
+            true
+        }
+        ResponseItem::CustomToolCallOutput { output, .. } => {
+            if output == DROPPED_TOOL_OUTPUT_PLACEHOLDER {
+                return false;
+            }
+            *output = DROPPED_TOOL_OUTPUT_PLACEHOLDER.to_string();
+            true
+        }
+        _ => false,
+    }
+}
+
+pub(super) fn output_call_id(item: &ResponseItem) -> Option<&str> {
+    match item {
+        ResponseItem::FunctionCallOutput { call_id, .. }
+        | ResponseItem::CustomToolCallOutput { call_id, .. } => Some(call_id),
+        _ => None,
+    }
+}
+
+pub(super) fn tool_names_by_call_id(items: &[ResponseItem]) -> HashMap<&str, String> {
+    let mut names = HashMap::new();
+    for item in items {
+        match item {
+            ResponseItem::FunctionCall { name, call_id, .. }
+            | ResponseItem::CustomToolCall { name, call_id, .. } => {
+                names.insert(call_id.as_str(), name.clone());
+            }
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                ..
+            } => {
+                names.insert(call_id.as_str(), "local_shell".to_string());
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+fn message_category(role: &str, content: &[ContentItem]) -> ContextCategory {
+    match role {
+        "assistant" => ContextCategory::AssistantMessages,
+        "user" if UserInstructions::is_user_instructions(content) => ContextCategory::ProjectDocs,
+        "user" if SkillInstructions::is_skill_instructions(content) => ContextCategory::Skills,
+        "user"
+            if content.iter().any(
+                |item| matches!(item, ContentItem::InputText { text } if is_session_prefix(text)),
+            ) =>
+        {
+            ContextCategory::DeveloperContext
+        }
+        "user" => ContextCategory::UserMessages,
+        _ => ContextCategory::DeveloperContext,
+    }
+}
+
+fn image_urls(item: &ResponseItem) -> Box<dyn Iterator<Item = &str> + '_> {
+    match item {
+        ResponseItem::Message { content, .. } => Box::new(content.iter().filter_map(
+            |content_item| match content_item {
+                ContentItem::InputImage { image_url } => Some(image_url.as_str()),
+                _ => None,
+            },
+        )),
+        ResponseItem::FunctionCallOutput { output, .. } => match &output.body {
+            FunctionCallOutputBody::ContentItems(content_items) => Box::new(
+                content_items
+                    .iter()
+                    .filter_map(|content_item| match content_item {
+                        FunctionCallOutputContentItem::InputImage { image_url } => {
+                            Some(image_url.as_str())
+                        }
+                        _ => None,
+                    }),
+            ),
+            FunctionCallOutputBody::Text(_) => Box::new(std::iter::empty()),
+        },
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn message_text(content: &[ContentItem]) -> String {
+    content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                Some(text.as_str())
+            }
+            ContentItem::InputImage { .. } => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(super) fn function_output_text(body: &FunctionCallOutputBody) -> String {
+    match body {
+        FunctionCallOutputBody::Text(text) => text.clone(),
+        FunctionCallOutputBody::ContentItems(content_items) => {
+            function_call_output_content_items_to_text(content_items).unwrap_or_default()
+        }
+    }
+}
+
+/// Collapses whitespace and truncates to a short single line.
+fn preview(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= PREVIEW_MAX_CHARS {
+        return collapsed;
+    }
+    let mut truncated: String = collapsed.chars().take(PREVIEW_MAX_CHARS - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn to_i64(value: usize) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
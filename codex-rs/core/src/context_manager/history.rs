@@ -1,23 +1,34 @@
 use crate::codex::TurnContext;
+use crate::context_manager::breakdown;
 use crate::context_manager::normalize;
+use crate::context_manager::prune;
 use crate::instructions::SkillInstructions;
 use crate::instructions::UserInstructions;
 use crate::session_prefix::is_session_prefix;
+use crate::truncate::TokenCounter;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::approx_tokens_from_byte_count;
 use crate::truncate::truncate_function_output_items_with_policy;
-use crate::truncate::truncate_text;
+use crate::truncate::truncate_text_with_counter;
 use crate::user_shell_command::is_user_shell_command_text;
 use codex_protocol::models::BaseInstructions;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::ContextBreakdownEvent;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 /// Transcript of thread history
 #[derive(Debug, Clone, Default)]
@@ -25,6 +36,45 @@ pub(crate) struct ContextManager {
     /// The oldest items are at the beginning of the vector.
     items: Vec<ResponseItem>,
     token_info: Option<TokenUsageInfo>,
+    /// Counts tokens for local estimates and token-based truncation.
+    token_counter: TokenCounter,
+    item_tokens: ItemTokenCache,
+}
+
+/// Token estimates keyed by a cheap fingerprint of the item and the counter,
+/// so each item is BPE-encoded once even though estimates are recomputed
+/// after every turn. Shared by clones of the history.
+#[derive(Debug, Clone, Default)]
+struct ItemTokenCache(Arc<Mutex<HashMap<u64, i64>>>);
+
+impl ItemTokenCache {
+    fn estimate(&self, item: &ResponseItem, counter: TokenCounter) -> i64 {
+        let Some(key) = item_key(item, counter) else {
+            return estimate_item_token_count(item, counter);
+        };
+        if let Some(tokens) = self.lock().get(&key) {
+            return *tokens;
+        }
+
+        let tokens = estimate_item_token_count(item, counter);
+        self.lock().insert(key, tokens);
+        tokens
+    }
+
+    /// Drops every entry once the cache is much larger than the history, which
+    /// happens after items were rewritten or removed.
+    fn trim(&self, live_items: usize) {
+        let mut entries = self.lock();
+        if entries.len() > live_items.saturating_mul(2).saturating_add(64) {
+            entries.clear();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, i64>> {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
 }
 
 impl ContextManager {
@@ -32,9 +82,17 @@ impl ContextManager {
         Self {
             items: Vec::new(),
             token_info: TokenUsageInfo::new_or_append(&None, &None, None),
+            token_counter: TokenCounter::default(),
+            item_tokens: ItemTokenCache::default(),
         }
     }
 
+    /// Sets the counter used for subsequent estimates and truncation. Items
+    /// already recorded are not re-truncated.
+    pub(crate) fn set_token_counter(&mut self, token_counter: TokenCounter) {
+        self.token_counter = token_counter;
+    }
+
     pub(crate) fn token_info(&self) -> Option<TokenUsageInfo> {
         self.token_info.clone()
     }
@@ -84,31 +142,85 @@ impl ContextManager {
         &self.items
     }
 
-    // Estimate token usage using byte-based heuristics from the truncation helpers.
-    // This is a coarse lower bound, not a tokenizer-accurate count.
+    // Estimate token usage with the turn's token counter. Item overhead (JSON framing) and
+    // encrypted reasoning are still approximations, so this is an estimate even when a
+    // tokenizer is available.
     pub(crate) fn estimate_token_count(&self, turn_context: &TurnContext) -> Option<i64> {
         let model_info = &turn_context.model_info;
         let personality = turn_context.personality.or(turn_context.config.personality);
         let base_instructions = BaseInstructions {
             text: model_info.get_model_instructions(personality),
         };
-        self.estimate_token_count_with_base_instructions(&base_instructions)
+        self.estimate_token_count_with_counter(&base_instructions, turn_context.token_counter)
     }
 
     pub(crate) fn estimate_token_count_with_base_instructions(
         &self,
         base_instructions: &BaseInstructions,
     ) -> Option<i64> {
-        let base_tokens =
-            i64::try_from(approx_token_count(&base_instructions.text)).unwrap_or(i64::MAX);
+        self.estimate_token_count_with_counter(base_instructions, self.token_counter)
+    }
 
+    fn estimate_token_count_with_counter(
+        &self,
+        base_instructions: &BaseInstructions,
+        counter: TokenCounter,
+    ) -> Option<i64> {
+        let base_tokens = i64::try_from(counter.count(&base_instructions.text)).unwrap_or(i64::MAX);
+
+        self.item_tokens.trim(self.items.len());
         let items_tokens = self.items.iter().fold(0i64, |acc, item| {
-            acc.saturating_add(estimate_item_token_count(item))
+            acc.saturating_add(self.item_tokens.estimate(item, counter))
         });
 
         Some(base_tokens.saturating_add(items_tokens))
     }
 
+    /// Groups the estimated prompt size by category and lists the largest items.
+    pub(crate) fn breakdown(
+        &self,
+        base_instructions: &BaseInstructions,
+        model_context_window: Option<i64>,
+    ) -> ContextBreakdownEvent {
+        let counter = self.token_counter;
+        breakdown::build_breakdown(
+            &self.items,
+            &base_instructions.text,
+            counter,
+            model_context_window,
+            |item| self.item_tokens.estimate(item, counter),
+        )
+    }
+
+    /// Truncates, then elides, outputs of tool calls from before the most
+    /// recent turns until the estimated prompt size is at most
+    /// `target_tokens`. Returns the number of outputs that were rewritten.
+    pub(crate) fn prune_tool_outputs(
+        &mut self,
+        base_instructions: &BaseInstructions,
+        target_tokens: i64,
+    ) -> usize {
+        let Some(estimated_tokens) =
+            self.estimate_token_count_with_base_instructions(base_instructions)
+        else {
+            return 0;
+        };
+        let counter = self.token_counter;
+        prune::prune_tool_outputs(
+            &mut self.items,
+            estimated_tokens.saturating_sub(target_tokens),
+            counter,
+            |item| self.item_tokens.estimate(item, counter),
+        )
+    }
+
+    /// Replaces the given tool outputs (by call id) and images (by the ids
+    /// reported in [`Self::breakdown`]) with placeholders. Call/output pairs are
+    /// kept intact. Returns the ids that were replaced.
+    pub(crate) fn drop_items(&mut self, item_ids: &[String]) -> Vec<String> {
+        breakdown::drop_items(&mut self.items, item_ids)
+    }
+
     pub(crate) fn remove_first_item(&mut self) {
         if !self.items.is_empty() {
             // Remove the oldest item (front of the list). Items are ordered from
@@ -232,7 +344,7 @@ impl ContextManager {
                 )
             })
             .fold(0i64, |acc, item| {
-                acc.saturating_add(estimate_item_token_count(item))
+                acc.saturating_add(self.item_tokens.estimate(item, self.token_counter))
             })
     }
 
@@ -242,7 +354,7 @@ impl ContextManager {
             if !is_codex_generated_item(item) {
                 break;
             }
-            total = total.saturating_add(estimate_item_token_count(item));
+            total = total.saturating_add(self.item_tokens.estimate(item, self.token_counter));
         }
         total
     }
@@ -281,14 +393,19 @@ impl ContextManager {
         match item {
             ResponseItem::FunctionCallOutput { call_id, output } => {
                 let body = match &output.body {
-                    FunctionCallOutputBody::Text(content) => FunctionCallOutputBody::Text(
-                        truncate_text(content, policy_with_serialization_budget),
-                    ),
+                    FunctionCallOutputBody::Text(content) => {
+                        FunctionCallOutputBody::Text(truncate_text_with_counter(
+                            content,
+                            policy_with_serialization_budget,
+                            self.token_counter,
+                        ))
+                    }
                     FunctionCallOutputBody::ContentItems(items) => {
                         FunctionCallOutputBody::ContentItems(
                             truncate_function_output_items_with_policy(
                                 items,
                                 policy_with_serialization_budget,
+                                self.token_counter,
                             ),
                         )
                     }
@@ -302,7 +419,11 @@ impl ContextManager {
                 }
             }
             ResponseItem::CustomToolCallOutput { call_id, output } => {
-                let truncated = truncate_text(output, policy_with_serialization_budget);
+                let truncated = truncate_text_with_counter(
+                    output,
+                    policy_with_serialization_budget,
+                    self.token_counter,
+                );
                 ResponseItem::CustomToolCallOutput {
                     call_id: call_id.clone(),
                     output: truncated,
@@ -347,7 +468,7 @@ fn estimate_reasoning_length(encoded_len: usize) -> usize {
         .saturating_sub(650)
 }
 
-fn estimate_item_token_count(item: &ResponseItem) -> i64 {
+fn estimate_item_token_count(item: &ResponseItem, counter: TokenCounter) -> i64 {
     match item {
         ResponseItem::GhostSnapshot { .. } => 0,
         ResponseItem::Reasoning {
@@ -362,9 +483,105 @@ fn estimate_item_token_count(item: &ResponseItem) -> i64 {
         }
         item => {
             let serialized = serde_json::to_string(item).unwrap_or_default();
-            i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
+            i64::try_from(counter.count(&serialized)).unwrap_or(i64::MAX)
+        }
+    }
+}
+
+/// Fingerprint of `item` for [`ItemTokenCache`]: its kind, call id or role,
+/// and the length and first bytes of every text it carries, so a lookup does
+/// not serialize the item. `None` for items that are not worth caching
+/// because their estimate is already cheap or they are rare.
+fn item_key(item: &ResponseItem, counter: TokenCounter) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    counter.hash(&mut hasher);
+    std::mem::discriminant(item).hash(&mut hasher);
+    match item {
+        ResponseItem::Message { role, content, .. } => {
+            role.hash(&mut hasher);
+            for content in content {
+                match content {
+                    ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                        hash_text(&mut hasher, text);
+                    }
+                    ContentItem::InputImage { image_url } => hash_text(&mut hasher, image_url),
+                }
+            }
+        }
+        ResponseItem::Reasoning {
+            id,
+            summary,
+            content,
+            encrypted_content: None,
+        } => {
+            id.hash(&mut hasher);
+            for ReasoningItemReasoningSummary::SummaryText { text } in summary {
+                hash_text(&mut hasher, text);
+            }
+            for content in content.iter().flatten() {
+                match content {
+                    ReasoningItemContent::ReasoningText { text }
+                    | ReasoningItemContent::Text { text } => hash_text(&mut hasher, text),
+                }
+            }
+        }
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => {
+            call_id.hash(&mut hasher);
+            name.hash(&mut hasher);
+            hash_text(&mut hasher, arguments);
+        }
+        ResponseItem::FunctionCallOutput { call_id, output } => {
+            call_id.hash(&mut hasher);
+            output.success.hash(&mut hasher);
+            match &output.body {
+                FunctionCallOutputBody::Text(text) => hash_text(&mut hasher, text),
+                FunctionCallOutputBody::ContentItems(items) => {
+                    for item in items {
+                        match item {
+                            FunctionCallOutputContentItem::InputText { text } => {
+                                hash_text(&mut hasher, text);
+                            }
+                            FunctionCallOutputContentItem::InputImage { image_url } => {
+                                hash_text(&mut hasher, image_url);
+                            }
+                        }
+                    }
+                }
+            }
         }
+        ResponseItem::CustomToolCall {
+            call_id,
+            name,
+            input,
+            ..
+        } => {
+            call_id.hash(&mut hasher);
+            name.hash(&mut hasher);
+            hash_text(&mut hasher, input);
+        }
+        ResponseItem::CustomToolCallOutput { call_id, output } => {
+            call_id.hash(&mut hasher);
+            hash_text(&mut hasher, output);
+        }
+        ResponseItem::Reasoning { .. }
+        | ResponseItem::LocalShellCall { .. }
+        | ResponseItem::WebSearchCall { .. }
+        | ResponseItem::GhostSnapshot { .. }
+        | ResponseItem::Compaction { .. }
+        | ResponseItem::Other => return None,
     }
+    Some(hasher.finish())
+}
+
+fn hash_text(hasher: &mut DefaultHasher, text: &str) {
+    text.len().hash(hasher);
+    let bytes = text.as_bytes();
+    bytes.get(..64).unwrap_or(bytes).hash(hasher);
 }
 
 pub(crate) fn is_codex_generated_item(item: &ResponseItem) -> bool {
@@ -12,6 +12,7 @@ use codex_protocol::models::LocalShellExecAction;
 use codex_protocol::models::LocalShellStatus;
 use codex_protocol::models::ReasoningItemContent;
 use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::protocol::ContextCategory;
 use pretty_assertions::assert_eq;
 use regex_lite::Regex;
 
@@ -199,8 +200,12 @@ fn trailing_codex_generated_tokens_stop_at_first_non_generated_item() {
         trailing_function_output.clone(),
         trailing_custom_output.clone(),
     ]);
-    let expected_tokens = estimate_item_token_count(&trailing_function_output)
-        .saturating_add(estimate_item_token_count(&trailing_custom_output));
+    let expected_tokens =
+        estimate_item_token_count(&trailing_function_output, TokenCounter::default())
+            .saturating_add(estimate_item_token_count(
+                &trailing_custom_output,
+                TokenCounter::default(),
+            ));
 
     assert_eq!(
         history.get_trailing_codex_generated_items_tokens(),
@@ -241,10 +246,34 @@ fn total_token_usage_includes_only_trailing_codex_generated_items() {
 
     assert_eq!(
         history.get_total_token_usage(true),
-        100 + estimate_item_token_count(&trailing_output)
+        100 + estimate_item_token_count(&trailing_output, TokenCounter::default())
     );
 }
 
+#[test]
+fn cached_item_token_counts_match_uncached_estimates() {
+    let cache = ItemTokenCache::default();
+    let output = function_call_output("call-1", "some tool output");
+    let reasoning = reasoning_with_encrypted_content(400);
+    let expected = estimate_item_token_count(&output, TokenCounter::default());
+
+    assert_eq!(cache.estimate(&output, TokenCounter::default()), expected);
+    assert_eq!(cache.estimate(&output, TokenCounter::default()), expected);
+    assert_eq!(
+        cache.estimate(&reasoning, TokenCounter::default()),
+        estimate_item_token_count(&reasoning, TokenCounter::default())
+    );
+    assert_eq!(cache.lock().len(), 1);
+
+    // Rewriting an output under the same call id is estimated afresh.
+    let rewritten = function_call_output("call-1", "some tool output, now much longer");
+    assert_eq!(
+        cache.estimate(&rewritten, TokenCounter::default()),
+        estimate_item_token_count(&rewritten, TokenCounter::default())
+    );
+    assert_eq!(cache.lock().len(), 2);
+}
+
 #[test]
 fn get_history_for_prompt_drops_ghost_commits() {
     let items = vec![ResponseItem::GhostSnapshot {
@@ -392,6 +421,251 @@ fn replace_last_turn_images_does_not_touch_user_images() {
     assert_eq!(history.raw_items(), items);
 }
 
+#[test]
+fn breakdown_groups_tool_outputs_by_tool_and_matches_estimate() {
+    let items = vec![
+        user_input_text_msg("list the files"),
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{\"command\":[\"ls\"]}".to_string(),
+            call_id: "call-shell".to_string(),
+        },
+        function_call_output("call-shell", &"file.txt\n".repeat(200)),
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "read_file".to_string(),
+            arguments: "{}".to_string(),
+            call_id: "call-read".to_string(),
+        },
+        function_call_output("call-read", "short"),
+        assistant_msg("done"),
+    ];
+    let history = create_history_with_items(items);
+    let base_instructions = BaseInstructions {
+        text: "You are a coding agent.".to_string(),
+    };
+
+    let breakdown = history.breakdown(&base_instructions, Some(100_000));
+
+    assert_eq!(
+        Some(breakdown.total_tokens),
+        history.estimate_token_count_with_base_instructions(&base_instructions)
+    );
+    assert_eq!(breakdown.model_context_window, Some(100_000));
+    let category_total = breakdown
+        .categories
+        .iter()
+        .map(|usage| usage.tokens)
+        .sum::<i64>();
+    assert_eq!(category_total, breakdown.total_tokens);
+
+    let tool_output_rows = breakdown
+        .categories
+        .iter()
+        .filter(|usage| usage.category == ContextCategory::ToolOutputs)
+        .map(|usage| (usage.tool_name.as_deref(), usage.item_count))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tool_output_rows,
+        vec![(Some("shell"), 1), (Some("read_file"), 1)]
+    );
+
+    let largest = &breakdown.largest_items[0];
+    assert_eq!(largest.id.as_deref(), Some("call-shell"));
+    assert_eq!(largest.category, ContextCategory::ToolOutputs);
+    assert_eq!(largest.tool_name.as_deref(), Some("shell"));
+    assert!(
+        breakdown
+            .largest_items
+            .iter()
+            .all(|item| item.category != ContextCategory::BaseInstructions)
+    );
+}
+
+#[test]
+fn breakdown_reports_images_separately_from_their_message() {
+    let image_url = format!("data:image/png;base64,{}", "A".repeat(4_000));
+    let items = vec![ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![
+            ContentItem::InputText {
+                text: "what is this?".to_string(),
+            },
+            ContentItem::InputImage { image_url },
+        ],
+        end_turn: None,
+        phase: None,
+    }];
+    let history = create_history_with_items(items);
+
+    let breakdown = history.breakdown(&BaseInstructions::default(), None);
+
+    let image = breakdown
+        .largest_items
+        .iter()
+        .find(|item| item.category == ContextCategory::Images)
+        .expect("image entry");
+    assert_eq!(image.id.as_deref(), Some("image:0"));
+    let message = breakdown
+        .largest_items
+        .iter()
+        .find(|item| item.category == ContextCategory::UserMessages)
+        .expect("message entry");
+    assert_eq!(message.preview, "what is this?");
+    assert!(image.tokens > message.tokens);
+}
+
+#[test]
+fn drop_items_replaces_tool_outputs_and_images_with_placeholders() {
+    let items = vec![
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputImage {
+                image_url: "data:image/png;base64,AAA".to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        },
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{}".to_string(),
+            call_id: "call-1".to_string(),
+        },
+        function_call_output("call-1", "lots of output"),
+        custom_tool_call_output("call-2", "patch output"),
+    ];
+    let mut history = create_history_with_items(items);
+
+    let dropped = history.drop_items(&[
+        "call-1".to_string(),
+        "image:0".to_string(),
+        "call-2".to_string(),
+        "image:1".to_string(),
+        "missing".to_string(),
+    ]);
+
+    assert_eq!(dropped, vec!["call-1", "image:0", "call-2"]);
+    assert_eq!(
+        history.raw_items(),
+        vec![
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "[image removed from context by the user]".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{}".to_string(),
+                call_id: "call-1".to_string(),
+            },
+            function_call_output("call-1", "[tool output removed from context by the user]"),
+            custom_tool_call_output("call-2", "[tool output removed from context by the user]"),
+        ]
+    );
+
+    // Dropping again is a no-op.
+    assert_eq!(
+        history.drop_items(&["call-1".to_string()]),
+        Vec::<String>::new()
+    );
+}
+
+fn shell_turn(prompt: &str, call_id: &str, output: &str) -> Vec<ResponseItem> {
+    vec![
+        user_input_text_msg(prompt),
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{}".to_string(),
+            call_id: call_id.to_string(),
+        },
+        function_call_output(call_id, output),
+    ]
+}
+
+fn output_text_for(history: &ContextManager, call_id: &str) -> String {
+    history
+        .raw_items()
+        .iter()
+        .find_map(|item| match item {
+            ResponseItem::FunctionCallOutput {
+                call_id: id,
+                output,
+            } if id == call_id => match &output.body {
+                FunctionCallOutputBody::Text(text) => Some(text.clone()),
+                FunctionCallOutputBody::ContentItems(_) => None,
+            },
+            _ => None,
+        })
+        .expect("function call output")
+}
+
+fn large_output(label: &str) -> String {
+    (0..1_000)
+        .map(|line| format!("{label} line {line}\n"))
+        .collect()
+}
+
+#[test]
+fn prune_tool_outputs_truncates_old_outputs_and_keeps_recent_turns() {
+    let items = [
+        shell_turn("first", "call-1", &large_output("first")),
+        shell_turn("second", "call-2", &large_output("second")),
+        shell_turn("third", "call-3", &large_output("third")),
+    ]
+    .concat();
+    let mut history = create_history_with_items(items);
+    let base_instructions = BaseInstructions {
+        text: String::new(),
+    };
+    let before = history
+        .estimate_token_count_with_base_instructions(&base_instructions)
+        .expect("estimate");
+
+    let pruned = history.prune_tool_outputs(&base_instructions, before - 100);
+
+    assert_eq!(pruned, 1);
+    let first = output_text_for(&history, "call-1");
+    assert!(first.starts_with("[Older tool output truncated to save context."));
+    assert!(first.contains("first line 0"));
+    assert!(first.contains("first line 999"));
+    assert_eq!(output_text_for(&history, "call-2"), large_output("second"));
+    assert_eq!(output_text_for(&history, "call-3"), large_output("third"));
+}
+
+#[test]
+fn prune_tool_outputs_elides_when_truncation_is_not_enough() {
+    let items = [
+        shell_turn("first", "call-1", &large_output("first")),
+        shell_turn("second", "call-2", &large_output("second")),
+        shell_turn("third", "call-3", &large_output("third")),
+    ]
+    .concat();
+    let mut history = create_history_with_items(items);
+    let base_instructions = BaseInstructions {
+        text: String::new(),
+    };
+
+    assert_eq!(history.prune_tool_outputs(&base_instructions, 0), 1);
+    assert_eq!(
+        output_text_for(&history, "call-1"),
+        "[Output of the `shell` call was elided to save context. Call the tool again if you need it.]"
+    );
+    assert_eq!(output_text_for(&history, "call-2"), large_output("second"));
+
+    // Already elided outputs are left alone.
+    assert_eq!(history.prune_tool_outputs(&base_instructions, 0), 0);
+}
+
 #[test]
 fn remove_first_item_handles_local_shell_pair() {
     let items = vec![
@@ -1,5 +1,7 @@
+mod breakdown;
 mod history;
 mod normalize;
+mod prune;
 
 pub(crate) use history::ContextManager;
 pub(crate) use history::is_codex_generated_item;
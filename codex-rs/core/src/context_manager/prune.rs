@@ -0,0 +1,156 @@
+//! Incremental pruning of old tool outputs.
+//!
+//! When the context window fills up, auto-compaction first tries to make room
+//! by shrinking the outputs of tool calls from earlier turns, oldest first:
+//! large outputs are truncated to their head and tail, and if that is not
+//! enough they are replaced by a short placeholder. The calls themselves stay
+//! in the history, so the model can re-run one to get its output back. The
+//! most recent turns are never touched.
+
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::ResponseItem;
+
+use crate::context_manager::breakdown::function_output_text;
+use crate::context_manager::breakdown::output_call_id;
+use crate::context_manager::breakdown::tool_names_by_call_id;
+use crate::context_manager::history::is_user_turn_boundary;
+use crate::truncate::TokenCounter;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text_with_counter;
+
+/// Tool outputs from this many of the most recent user turns are left intact.
+const KEEP_RECENT_TURNS: usize = 2;
+
+/// Token budget for an output shrunk during the truncation pass.
+const TRUNCATED_OUTPUT_TOKENS: usize = 256;
+
+const TRUNCATED_OUTPUT_NOTE: &str = "[Older tool output truncated to save context. Call the tool again if you need the full output.]";
+
+const ELIDED_OUTPUT_PREFIX: &str = "[Output of the `";
+
+/// Rewrites outputs of tool calls made before the last [`KEEP_RECENT_TURNS`]
+/// user turns until roughly `tokens_to_free` tokens have been released.
+/// Returns the number of outputs that were rewritten.
+pub(super) fn prune_tool_outputs(
+    items: &mut [ResponseItem],
+    tokens_to_free: i64,
+    counter: TokenCounter,
+    item_tokens: impl Fn(&ResponseItem) -> i64,
+) -> usize {
+    if tokens_to_free <= 0 {
+        return 0;
+    }
+
+    let candidates = prunable_outputs(items);
+    let mut freed = 0i64;
+    let mut rewritten = vec![false; candidates.len()];
+
+    // First pass: keep the head and tail of large outputs.
+    for (candidate, (index, _)) in candidates.iter().enumerate() {
+        if freed >= tokens_to_free {
+            break;
+        }
+        let item = &mut items[*index];
+        let Some(text) = output_text(item).filter(|text| !is_pruned(text)) else {
+            continue;
+        };
+        let truncated = truncate_text_with_counter(
+            &text,
+            TruncationPolicy::Tokens(TRUNCATED_OUTPUT_TOKENS),
+            counter,
+        );
+        if truncated == text {
+            continue;
+        }
+        let replacement = format!("{TRUNCATED_OUTPUT_NOTE}\n{truncated}");
+        if let Some(saved) = replace_if_smaller(item, replacement, &item_tokens) {
+            freed = freed.saturating_add(saved);
+            rewritten[candidate] = true;
+        }
+    }
+
+    // Second pass: drop outputs entirely, leaving a note naming the tool.
+    for (candidate, (index, tool_name)) in candidates.iter().enumerate() {
+        if freed >= tokens_to_free {
+            break;
+        }
+        let item = &mut items[*index];
+        if output_text(item).is_some_and(|text| text.starts_with(ELIDED_OUTPUT_PREFIX)) {
+            continue;
+        }
+        let replacement = format!(
+            "{ELIDED_OUTPUT_PREFIX}{tool_name}` call was elided to save context. Call the tool again if you need it.]"
+        );
+        if let Some(saved) = replace_if_smaller(item, replacement, &item_tokens) {
+            freed = freed.saturating_add(saved);
+            rewritten[candidate] = true;
+        }
+    }
+
+    rewritten.into_iter().filter(|rewritten| *rewritten).count()
+}
+
+/// Indexes of tool outputs that precede the protected recent turns, oldest
+/// first, paired with the name of the tool that produced them.
+fn prunable_outputs(items: &[ResponseItem]) -> Vec<(usize, String)> {
+    let Some(protected_from) = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| is_user_turn_boundary(item))
+        .map(|(index, _)| index)
+        .rev()
+        .nth(KEEP_RECENT_TURNS - 1)
+    else {
+        return Vec::new();
+    };
+
+    let tool_names = tool_names_by_call_id(items);
+    items[..protected_from]
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            let call_id = output_call_id(item)?;
+            let tool_name = tool_names
+                .get(call_id)
+                .cloned()
+                .unwrap_or_else(|| "tool".to_string());
+            Some((index, tool_name))
+        })
+        .collect()
+}
+
+fn is_pruned(text: &str) -> bool {
+    text.starts_with(TRUNCATED_OUTPUT_NOTE) || text.starts_with(ELIDED_OUTPUT_PREFIX)
+}
+
+fn output_text(item: &ResponseItem) -> Option<String> {
+    match item {
+        ResponseItem::FunctionCallOutput { output, .. } => Some(function_output_text(&output.body)),
+        ResponseItem::CustomToolCallOutput { output, .. } => Some(output.clone()),
+        _ => None,
+    }
+}
+
+/// Replaces the output text of `item` when that makes it smaller and returns
+/// the number of tokens saved.
+fn replace_if_smaller(
+    item: &mut ResponseItem,
+    text: String,
+    item_tokens: impl Fn(&ResponseItem) -> i64,
+) -> Option<i64> {
+    let before = item_tokens(item);
+    let mut replaced = item.clone();
+    match &mut replaced {
+        ResponseItem::FunctionCallOutput { output, .. } => {
+            output.body = FunctionCallOutputBody::Text(text);
+        }
+        ResponseItem::CustomToolCallOutput { output, .. } => *output = text,
+        _ => return None,
+    }
+    let saved = before.saturating_sub(item_tokens(&replaced));
+    if saved <= 0 {
+        return None;
+    }
+    *item = replaced;
+    Some(saved)
+}
@@ -12,6 +12,7 @@ use std::time::Instant;
 use async_channel::Sender;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
 use tokio_util::sync::CancellationToken;
@@ -31,8 +32,8 @@ use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
-use crate::text_encoding::bytes_to_string_smart;
 use codex_utils_pty::process_group::kill_child_process_group;
+use codex_utils_text_encoding::bytes_to_string_smart;
 
 pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
 
@@ -67,6 +68,9 @@ pub struct ExecParams {
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    /// Data written to the command's stdin, which is then closed. When `None`
+    /// stdin is not connected at all.
+    pub stdin: Option<String>,
 }
 
 /// Mechanism to terminate an exec invocation before it finishes naturally.
@@ -175,6 +179,7 @@ pub async fn process_exec_tool_call(
         windows_sandbox_level,
         justification,
         arg0: _,
+        stdin,
     } = params;
 
     let (program, args) = command.split_first().ok_or_else(|| {
@@ -192,6 +197,7 @@ pub async fn process_exec_tool_call(
         expiration,
         sandbox_permissions,
         justification,
+        stdin,
     };
 
     let manager = SandboxManager::new();
@@ -204,6 +210,8 @@ pub async fn process_exec_tool_call(
             codex_linux_sandbox_exe: codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
             windows_sandbox_level,
+            overlay_dir: None,
+            deny_read: &[],
         })
         .map_err(CodexErr::from)?;
 
@@ -226,6 +234,7 @@ pub(crate) async fn execute_exec_env(
         sandbox_permissions,
         justification,
         arg0,
+        stdin,
     } = env;
 
     let params = ExecParams {
@@ -237,6 +246,7 @@ pub(crate) async fn execute_exec_env(
         windows_sandbox_level,
         justification,
         arg0,
+        stdin,
     };
 
     let start = Instant::now();
@@ -327,8 +337,15 @@ async fn exec_windows_sandbox(
         env,
         expiration,
         windows_sandbox_level,
+        stdin,
         ..
     } = params;
+    if stdin.is_some() {
+        return Err(CodexErr::Io(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "writing to stdin is not supported under the Windows sandbox",
+        )));
+    }
     // TODO(iceweasel-oai): run_windows_sandbox_capture should support all
     // variants of ExecExpiration, not just timeout.
     let timeout_ms = expiration.timeout_ms();
@@ -681,6 +698,7 @@ async fn exec(
         arg0,
         expiration,
         windows_sandbox_level: _,
+        stdin,
         ..
     } = params;
 
@@ -691,16 +709,31 @@ async fn exec(
         ))
     })?;
     let arg0_ref = arg0.as_deref();
-    let child = spawn_child_async(
+    let stdio_policy = if stdin.is_some() {
+        StdioPolicy::RedirectForShellToolWithStdin
+    } else {
+        StdioPolicy::RedirectForShellTool
+    };
+    let mut child = spawn_child_async(
         PathBuf::from(program),
         args.into(),
         arg0_ref,
         cwd,
         sandbox_policy,
-        StdioPolicy::RedirectForShellTool,
+        stdio_policy,
         env,
     )
     .await?;
+    if let Some(input) = stdin
+        && let Some(mut child_stdin) = child.stdin.take()
+    {
+        // Written from a separate task so a command that produces output
+        // before draining its input cannot deadlock against us. A command that
+        // exits without reading everything closes the pipe, which is fine.
+        tokio::spawn(async move {
+            let _ = child_stdin.write_all(input.as_bytes()).await;
+        });
+    }
     consume_truncated_output(child, expiration, stdout_stream).await
 }
 
@@ -1065,6 +1098,7 @@ mod tests {
             windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
             justification: None,
             arg0: None,
+            stdin: None,
         };
 
         let output = exec(params, SandboxType::None, &SandboxPolicy::ReadOnly, None).await?;
@@ -1095,6 +1129,32 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn exec_writes_stdin_larger_than_the_env_limit() -> Result<()> {
+        let input = "x".repeat(512 * 1024);
+        let params = ExecParams {
+            command: vec!["/bin/sh".to_string(), "-c".to_string(), "wc -c".to_string()],
+            cwd: std::env::current_dir()?,
+            expiration: 10_000.into(),
+            env: std::env::vars().collect(),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
+            justification: None,
+            arg0: None,
+            stdin: Some(input.clone()),
+        };
+
+        let output = exec(params, SandboxType::None, &SandboxPolicy::ReadOnly, None).await?;
+
+        assert_eq!(output.exit_status.code(), Some(0));
+        assert_eq!(
+            output.stdout.from_utf8_lossy().text.trim(),
+            input.len().to_string()
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn process_exec_tool_call_respects_cancellation_token() -> Result<()> {
         let command = long_running_command();
@@ -1111,6 +1171,7 @@ mod tests {
             windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel::Disabled,
             justification: None,
             arg0: None,
+            stdin: None,
         };
         tokio::spawn(async move {
             tokio::time::sleep(Duration::from_millis(1_000)).await;
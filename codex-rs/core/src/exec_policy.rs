@@ -340,10 +340,12 @@ pub fn render_decision_for_unmatched_command(
                     // command has not been flagged as dangerous.
                     Decision::Allow
                 }
-                SandboxPolicy::ReadOnly | SandboxPolicy::WorkspaceWrite { .. } => {
-                    // In restricted sandboxes (ReadOnly/WorkspaceWrite), do not prompt for
-                    // non‑escalated, non‑dangerous commands — let the sandbox enforce
-                    // restrictions (e.g., block network/write) without a user prompt.
+                SandboxPolicy::ReadOnly
+                | SandboxPolicy::WorkspaceWrite { .. }
+                | SandboxPolicy::WorkspaceOverlay { .. } => {
+                    // In restricted sandboxes (ReadOnly/WorkspaceWrite/WorkspaceOverlay), do
+                    // not prompt for non‑escalated, non‑dangerous commands — let the sandbox
+                    // enforce restrictions (e.g., block network/write) without a user prompt.
                     if sandbox_permissions.requires_escalated_permissions() {
                         Decision::Prompt
                     } else {
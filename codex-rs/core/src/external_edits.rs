@@ -0,0 +1,282 @@
+//! Detection of files the user edits while the agent is working.
+//!
+//! The session snapshots every file the agent reads or patches and asks the
+//! [`FileWatcher`](crate::file_watcher::FileWatcher) to report changes to it.
+//! Before the next model request, each reported file is compared with its
+//! snapshot and real differences are surfaced to the model as a short diff, so
+//! it re-reads the file instead of patching stale content. The agent's own
+//! edits refresh the snapshot first and are therefore never reported: patches
+//! record the files they touch, and every command the agent runs is bracketed
+//! by a comparison of all tracked files before it starts (so earlier user
+//! edits are kept) and a fresh snapshot after it ends (so whatever the command
+//! changed, e.g. `sed -i` or `cargo fmt`, is attributed to the agent). Edits
+//! the user makes while a command is running are attributed to the agent too.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ignore::gitignore::GitignoreBuilder;
+
+/// Files larger than this are not tracked.
+const MAX_TRACKED_FILE_BYTES: u64 = 512 * 1024;
+
+/// Diff lines shown per file before the rest is elided.
+const MAX_DIFF_LINES: usize = 40;
+
+const EXTERNAL_EDITS_OPEN_TAG: &str = "<external_edits>";
+const EXTERNAL_EDITS_CLOSE_TAG: &str = "</external_edits>";
+
+#[derive(Debug, Default)]
+pub(crate) struct ExternalEditTracker {
+    /// Last content the agent saw for each tracked file; `None` when the file
+    /// did not exist.
+    snapshots: HashMap<PathBuf, Option<String>>,
+    changed: HashSet<PathBuf>,
+    /// Edits found before an agent command ran, held until the next model
+    /// request.
+    pending_descriptions: Vec<String>,
+}
+
+impl ExternalEditTracker {
+    pub(crate) fn is_tracked(&self, path: &Path) -> bool {
+        self.snapshots.contains_key(path)
+    }
+
+    /// Records what the agent now knows `path` to contain.
+    pub(crate) fn record(&mut self, path: PathBuf, content: Option<String>) {
+        self.changed.remove(&path);
+        self.snapshots.insert(path, content);
+    }
+
+    /// Marks tracked paths as possibly modified; untracked paths are ignored.
+    pub(crate) fn mark_changed(&mut self, paths: impl IntoIterator<Item = PathBuf>) {
+        for path in paths {
+            if self.is_tracked(&path) {
+                self.changed.insert(path);
+            }
+        }
+    }
+
+    /// Every tracked path, in path order.
+    pub(crate) fn tracked_paths(&self) -> Vec<PathBuf> {
+        let mut paths = self.snapshots.keys().cloned().collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+
+    /// Last content recorded for `path`.
+    pub(crate) fn snapshot(&self, path: &Path) -> Option<&str> {
+        self.snapshots.get(path).and_then(Option::as_deref)
+    }
+
+    /// Stops tracking `path`, returning whether it was tracked.
+    pub(crate) fn forget(&mut self, path: &Path) -> bool {
+        self.changed.remove(path);
+        self.snapshots.remove(path).is_some()
+    }
+
+    /// Holds a description until [`Self::take_pending_descriptions`].
+    pub(crate) fn push_pending_description(&mut self, description: String) {
+        self.pending_descriptions.push(description);
+    }
+
+    pub(crate) fn take_pending_descriptions(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending_descriptions)
+    }
+
+    /// Drains the paths reported since the last call, paired with their
+    /// snapshots, in path order.
+    pub(crate) fn take_changed(&mut self) -> Vec<(PathBuf, Option<String>)> {
+        let mut changed = self
+            .changed
+            .drain()
+            .map(|path| {
+                let snapshot = self.snapshots.get(&path).cloned().flatten();
+                (path, snapshot)
+            })
+            .collect::<Vec<_>>();
+        changed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        changed
+    }
+}
+
+/// Normalizes `path` the way the file watcher reports it. Only the parent is
+/// canonicalized so deleted files keep a stable key.
+pub(crate) fn tracked_path(path: &Path) -> Option<PathBuf> {
+    let parent = std::fs::canonicalize(path.parent()?).ok()?;
+    Some(parent.join(path.file_name()?))
+}
+
+/// Reads a tracked file. Missing, oversized and non-UTF-8 files all read as
+/// `None`.
+pub(crate) async fn read_tracked_file(path: &Path) -> Option<String> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_TRACKED_FILE_BYTES {
+        return None;
+    }
+    tokio::fs::read_to_string(path).await.ok()
+}
+
+/// Describes how `path` changed from `before` to `after`, or `None` when the
+/// content is unchanged.
+pub(crate) fn describe_external_edit(
+    path: &Path,
+    before: Option<&str>,
+    after: Option<&str>,
+) -> Option<String> {
+    if before == after {
+        return None;
+    }
+    let display = path.display().to_string();
+    let (before, after) = match (before, after) {
+        (Some(_), None) => return Some(format!("{display} was deleted.")),
+        (None, Some("")) => {
+            return Some(format!("{display} was created (empty)."));
+        }
+        (before, after) => (before.unwrap_or_default(), after.unwrap_or_default()),
+    };
+
+    let diff = similar::TextDiff::from_lines(before, after)
+        .unified_diff()
+        .context_radius(2)
+        .header(&display, &display)
+        .to_string();
+    let lines = diff.lines().collect::<Vec<_>>();
+    let mut shown = lines
+        .iter()
+        .take(MAX_DIFF_LINES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.len() > MAX_DIFF_LINES {
+        let remaining = lines.len() - MAX_DIFF_LINES;
+        shown.push_str(&format!("\n... ({remaining} more diff lines)"));
+    }
+    Some(format!("{display} was modified:\n```diff\n{shown}\n```"))
+}
+
+/// Builds the developer message that tells the model about external edits.
+pub(crate) fn external_edits_message(descriptions: &[String]) -> String {
+    format!(
+        "{EXTERNAL_EDITS_OPEN_TAG}\nThe user changed these files outside of this conversation since you last read or edited them. Re-read a file before editing it again and do not undo the user's changes unless asked.\n\n{}\n{EXTERNAL_EDITS_CLOSE_TAG}",
+        descriptions.join("\n\n")
+    )
+}
+
+/// Whether `path` is excluded by a `.gitignore` between it and the root of
+/// its git repository. Paths outside a repository are never ignored.
+pub(crate) fn is_gitignored(path: &Path) -> bool {
+    let Some(repo_root) = path
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join(".git").exists())
+    else {
+        return false;
+    };
+
+    let mut builder = GitignoreBuilder::new(repo_root);
+    for dir in path.ancestors().skip(1) {
+        let gitignore = dir.join(".gitignore");
+        if gitignore.is_file() {
+            // Errors in individual patterns leave the rest of the file usable.
+            let _ = builder.add(gitignore);
+        }
+        if dir == repo_root {
+            break;
+        }
+    }
+    match builder.build() {
+        Ok(gitignore) => gitignore
+            .matched_path_or_any_parents(path, false)
+            .is_ignore(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn only_tracked_paths_are_reported_and_reads_clear_them() {
+        let mut tracker = ExternalEditTracker::default();
+        tracker.record(PathBuf::from("/repo/a.rs"), Some("a".to_string()));
+        tracker.record(PathBuf::from("/repo/b.rs"), Some("b".to_string()));
+
+        tracker.mark_changed([
+            PathBuf::from("/repo/b.rs"),
+            PathBuf::from("/repo/a.rs"),
+            PathBuf::from("/repo/untracked.rs"),
+        ]);
+        // Reading the file again means the agent already saw the new content.
+        tracker.record(PathBuf::from("/repo/b.rs"), Some("b2".to_string()));
+
+        assert_eq!(
+            tracker.take_changed(),
+            vec![(PathBuf::from("/repo/a.rs"), Some("a".to_string()))]
+        );
+        assert_eq!(tracker.take_changed(), Vec::new());
+    }
+
+    #[test]
+    fn forget_drops_snapshot_and_pending_change() {
+        let mut tracker = ExternalEditTracker::default();
+        tracker.record(PathBuf::from("/repo/a.rs"), Some("a".to_string()));
+        tracker.mark_changed([PathBuf::from("/repo/a.rs")]);
+
+        assert!(tracker.forget(Path::new("/repo/a.rs")));
+        assert!(!tracker.forget(Path::new("/repo/a.rs")));
+        assert_eq!(tracker.tracked_paths(), Vec::<PathBuf>::new());
+        assert_eq!(tracker.take_changed(), Vec::new());
+    }
+
+    #[test]
+    fn describe_external_edit_renders_a_short_diff() {
+        let path = Path::new("/repo/src/lib.rs");
+        assert_eq!(
+            describe_external_edit(path, Some("same\n"), Some("same\n")),
+            None
+        );
+
+        let description =
+            describe_external_edit(path, Some("one\ntwo\n"), Some("one\nzwei\n")).expect("diff");
+        assert_eq!(
+            description,
+            "/repo/src/lib.rs was modified:\n```diff\n--- /repo/src/lib.rs\n+++ /repo/src/lib.rs\n@@ -1,2 +1,2 @@\n one\n-two\n+zwei\n```"
+        );
+
+        assert_eq!(
+            describe_external_edit(path, Some("one\n"), None),
+            Some("/repo/src/lib.rs was deleted.".to_string())
+        );
+    }
+
+    #[test]
+    fn describe_external_edit_truncates_long_diffs() {
+        let after = (0..100).map(|i| format!("line {i}\n")).collect::<String>();
+        let description =
+            describe_external_edit(Path::new("/repo/big.txt"), Some(""), Some(&after))
+                .expect("diff");
+        assert!(description.contains("line 0"));
+        assert!(!description.contains("line 99"));
+        assert!(description.contains("more diff lines)"));
+    }
+
+    #[test]
+    fn is_gitignored_respects_nested_gitignore_files() {
+        let repo = TempDir::new().expect("tempdir");
+        std::fs::create_dir(repo.path().join(".git")).expect("git dir");
+        std::fs::create_dir_all(repo.path().join("crate/src")).expect("src dir");
+        std::fs::write(repo.path().join(".gitignore"), "target/\n").expect("root gitignore");
+        std::fs::write(repo.path().join("crate/.gitignore"), "*.log\n").expect("gitignore");
+
+        assert!(is_gitignored(&repo.path().join("target/debug/out.rs")));
+        assert!(is_gitignored(&repo.path().join("crate/build.log")));
+        assert!(!is_gitignored(&repo.path().join("crate/src/lib.rs")));
+        assert!(!is_gitignored(Path::new("/definitely/not/in/a/repo.rs")));
+    }
+}
@@ -131,6 +131,15 @@ pub enum Feature {
     Personality,
     /// Use the Responses API WebSocket transport for OpenAI by default.
     ResponsesWebsockets,
+    /// Count context tokens with the model's BPE tokenizer instead of byte heuristics.
+    TokenizerContextAccounting,
+    /// Truncate and elide old tool outputs before summarizing the conversation.
+    ToolOutputPruning,
+    /// Tell the model about files the user edited while the agent was working.
+    ExternalEditDetection,
+    /// Index workspace symbols with tree-sitter for `find_symbol`, `outline_file`
+    /// and `@` symbol mentions.
+    SymbolIndex,
 }
 
 impl Feature {
@@ -585,6 +594,30 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::TokenizerContextAccounting,
+        key: "tokenizer_context_accounting",
+        stage: Stage::Stable,
+        default_enabled: true,
+    },
+    FeatureSpec {
+        id: Feature::ToolOutputPruning,
+        key: "tool_output_pruning",
+        stage: Stage::Stable,
+        default_enabled: true,
+    },
+    FeatureSpec {
+        id: Feature::ExternalEditDetection,
+        key: "external_edit_detection",
+        stage: Stage::Stable,
+        default_enabled: true,
+    },
+    FeatureSpec {
+        id: Feature::SymbolIndex,
+        key: "symbol_index",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
 ];
 
 /// Push a warning event if any under-development features are enabled.
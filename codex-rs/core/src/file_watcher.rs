@@ -1,5 +1,6 @@
-//! Watches skill roots for changes and broadcasts coarse-grained
-//! `FileWatcherEvent`s that higher-level components react to on the next turn.
+//! Watches skill roots, config files, and files the agent has touched, and
+//! broadcasts coarse-grained `FileWatcherEvent`s that higher-level components
+//! react to on the next turn.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -22,15 +23,31 @@ use tokio::time::sleep_until;
 use tracing::warn;
 
 use crate::config::Config;
+use crate::config_loader::ConfigLayerStackOrdering;
 use crate::skills::loader::skill_roots_from_layer_stack_with_agents;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileWatcherEvent {
-    SkillsChanged { paths: Vec<PathBuf> },
+    SkillsChanged {
+        paths: Vec<PathBuf>,
+    },
+    /// Files registered via [`FileWatcher::watch_file`] changed on disk. The
+    /// change may come from any thread, including the agent itself.
+    TrackedFilesChanged {
+        paths: Vec<PathBuf>,
+    },
+    /// A config file backing one of the registered configs (including files
+    /// pulled in through `include`) changed on disk.
+    ConfigChanged {
+        paths: Vec<PathBuf>,
+    },
 }
 
 struct WatchState {
     skills_roots: HashSet<PathBuf>,
+    /// Tracked files with the number of sessions tracking each of them.
+    tracked_files: HashMap<PathBuf, usize>,
+    config_files: HashSet<PathBuf>,
 }
 
 struct FileWatcherInner {
@@ -104,6 +121,8 @@ impl FileWatcher {
         let (tx, _) = broadcast::channel(128);
         let state = Arc::new(RwLock::new(WatchState {
             skills_roots: HashSet::new(),
+            tracked_files: HashMap::new(),
+            config_files: HashSet::new(),
         }));
         let file_watcher = Self {
             inner: Some(Mutex::new(inner)),
@@ -120,6 +139,8 @@ impl FileWatcher {
             inner: None,
             state: Arc::new(RwLock::new(WatchState {
                 skills_roots: HashSet::new(),
+                tracked_files: HashMap::new(),
+                config_files: HashSet::new(),
             })),
             tx,
         }
@@ -135,6 +156,67 @@ impl FileWatcher {
         for root in roots {
             self.register_skills_root(root.path);
         }
+        for layer in config
+            .config_layer_stack
+            .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, true)
+        {
+            for path in layer.config_files() {
+                self.register_config_file(path);
+            }
+        }
+    }
+
+    /// Starts reporting changes to `path`. The parent directory is watched
+    /// rather than the file itself so editors that save by renaming a temp
+    /// file over the original are still observed.
+    pub(crate) fn watch_file(&self, path: PathBuf) {
+        let Some(parent) = path.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        {
+            let mut state = match self.state.write() {
+                Ok(state) => state,
+                Err(err) => err.into_inner(),
+            };
+            let sessions = state.tracked_files.entry(path).or_default();
+            *sessions += 1;
+            if *sessions > 1 {
+                return;
+            }
+        }
+        self.watch_path(parent, RecursiveMode::NonRecursive);
+    }
+
+    /// Stops reporting changes to `path` for one caller of
+    /// [`FileWatcher::watch_file`]. The parent directory is unwatched once no
+    /// tracked or config file needs it anymore.
+    pub(crate) fn unwatch_file(&self, path: &Path) {
+        let Some(parent) = path.parent() else {
+            return;
+        };
+        {
+            let mut state = match self.state.write() {
+                Ok(state) => state,
+                Err(err) => err.into_inner(),
+            };
+            let Some(sessions) = state.tracked_files.get_mut(path) else {
+                return;
+            };
+            *sessions -= 1;
+            if *sessions > 0 {
+                return;
+            }
+            state.tracked_files.remove(path);
+            let parent_in_use = state
+                .tracked_files
+                .keys()
+                .chain(state.config_files.iter())
+                .any(|other| other.parent() == Some(parent));
+            if parent_in_use {
+                return;
+            }
+        }
+        self.unwatch_path(parent);
     }
 
     // Bridge `notify`'s callback-based events into the Tokio runtime and
@@ -149,10 +231,19 @@ impl FileWatcher {
             handle.spawn(async move {
                 let now = Instant::now();
                 let mut skills = ThrottledPaths::new(now);
+                let mut tracked = ThrottledPaths::new(now);
+                let mut config = ThrottledPaths::new(now);
 
                 loop {
                     let now = Instant::now();
-                    let next_deadline = skills.next_deadline(now);
+                    let next_deadline = [
+                        skills.next_deadline(now),
+                        tracked.next_deadline(now),
+                        config.next_deadline(now),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .min();
                     let timer_deadline = next_deadline
                         .unwrap_or_else(|| now + Duration::from_secs(60 * 60 * 24 * 365));
                     let timer = sleep_until(timer_deadline);
@@ -163,12 +254,22 @@ impl FileWatcher {
                             match res {
                                 Some(Ok(event)) => {
                                     let skills_paths = classify_event(&event, &state);
+                                    let tracked_paths = classify_tracked_paths(&event, &state);
+                                    let config_paths = classify_config_paths(&event, &state);
                                     let now = Instant::now();
                                     skills.add(skills_paths);
+                                    tracked.add(tracked_paths);
+                                    config.add(config_paths);
 
                                     if let Some(paths) = skills.take_ready(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = tracked.take_ready(now) {
+                                        let _ = tx.send(FileWatcherEvent::TrackedFilesChanged { paths });
+                                    }
+                                    if let Some(paths) = config.take_ready(now) {
+                                        let _ = tx.send(FileWatcherEvent::ConfigChanged { paths });
+                                    }
                                 }
                                 Some(Err(err)) => {
                                     warn!("file watcher error: {err}");
@@ -180,6 +281,12 @@ impl FileWatcher {
                                     if let Some(paths) = skills.take_pending(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = tracked.take_pending(now) {
+                                        let _ = tx.send(FileWatcherEvent::TrackedFilesChanged { paths });
+                                    }
+                                    if let Some(paths) = config.take_pending(now) {
+                                        let _ = tx.send(FileWatcherEvent::ConfigChanged { paths });
+                                    }
                                     break;
                                 }
                             }
@@ -189,6 +296,12 @@ impl FileWatcher {
                             if let Some(paths) = skills.take_ready(now) {
                                 let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                             }
+                            if let Some(paths) = tracked.take_ready(now) {
+                                let _ = tx.send(FileWatcherEvent::TrackedFilesChanged { paths });
+                            }
+                            if let Some(paths) = config.take_ready(now) {
+                                let _ = tx.send(FileWatcherEvent::ConfigChanged { paths });
+                            }
                         }
                     }
                 }
@@ -209,6 +322,24 @@ impl FileWatcher {
         self.watch_path(root, RecursiveMode::Recursive);
     }
 
+    /// Config files may not exist yet (e.g. a fresh `$CODEX_HOME`), so the
+    /// parent directory is watched and creating the file is reported too.
+    fn register_config_file(&self, path: PathBuf) {
+        let Some(parent) = path.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        {
+            let mut state = match self.state.write() {
+                Ok(state) => state,
+                Err(err) => err.into_inner(),
+            };
+            if !state.config_files.insert(path) {
+                return;
+            }
+        }
+        self.watch_path(parent, RecursiveMode::NonRecursive);
+    }
+
     fn watch_path(&self, path: PathBuf, mode: RecursiveMode) {
         let Some(inner) = &self.inner else {
             return;
@@ -235,6 +366,25 @@ impl FileWatcher {
         }
         guard.watched_paths.insert(watch_path, mode);
     }
+
+    /// Removes a non-recursive watch. Recursive watches belong to skills
+    /// roots and are kept.
+    fn unwatch_path(&self, path: &Path) {
+        let Some(inner) = &self.inner else {
+            return;
+        };
+        let mut guard = match inner.lock() {
+            Ok(guard) => guard,
+            Err(err) => err.into_inner(),
+        };
+        if guard.watched_paths.get(path) != Some(&RecursiveMode::NonRecursive) {
+            return;
+        }
+        guard.watched_paths.remove(path);
+        if let Err(err) = guard.watcher.unwatch(path) {
+            warn!("failed to unwatch {}: {err}", path.display());
+        }
+    }
 }
 
 fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
@@ -256,6 +406,32 @@ fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
     skills_paths
 }
 
+fn classify_tracked_paths(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
+    let state = match state.read() {
+        Ok(state) => state,
+        Err(err) => err.into_inner(),
+    };
+    event
+        .paths
+        .iter()
+        .filter(|path| state.tracked_files.contains_key(*path))
+        .cloned()
+        .collect()
+}
+
+fn classify_config_paths(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
+    let state = match state.read() {
+        Ok(state) => state,
+        Err(err) => err.into_inner(),
+    };
+    event
+        .paths
+        .iter()
+        .filter(|path| state.config_files.contains(*path))
+        .cloned()
+        .collect()
+}
+
 fn is_skills_path(path: &Path, roots: &HashSet<PathBuf>) -> bool {
     roots.iter().any(|root| path.starts_with(root))
 }
@@ -319,6 +495,8 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root.clone()]),
+            tracked_files: HashMap::new(),
+            config_files: HashSet::new(),
         });
         let event = notify_event(vec![
             root.join("demo/SKILL.md"),
@@ -335,6 +513,8 @@ mod tests {
         let root_b = path("/tmp/workspace/.codex/skills");
         let state = RwLock::new(WatchState {
             skills_roots: HashSet::from([root_a.clone(), root_b.clone()]),
+            tracked_files: HashMap::new(),
+            config_files: HashSet::new(),
         });
         let event = notify_event(vec![
             root_a.join("alpha/SKILL.md"),
@@ -349,6 +529,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn classify_tracked_paths_only_reports_registered_files() {
+        let watcher = FileWatcher::noop();
+        watcher.watch_file(path("/tmp/workspace/src/lib.rs"));
+        let event = notify_event(vec![
+            path("/tmp/workspace/src/lib.rs"),
+            path("/tmp/workspace/src/main.rs"),
+        ]);
+
+        let classified = classify_tracked_paths(&event, &watcher.state);
+        assert_eq!(classified, vec![path("/tmp/workspace/src/lib.rs")]);
+    }
+
+    #[test]
+    fn unwatch_file_keeps_files_other_sessions_still_track() {
+        let watcher = FileWatcher::noop();
+        let file = path("/tmp/workspace/src/lib.rs");
+        watcher.watch_file(file.clone());
+        watcher.watch_file(file.clone());
+        let event = notify_event(vec![file.clone()]);
+
+        watcher.unwatch_file(&file);
+        assert_eq!(
+            classify_tracked_paths(&event, &watcher.state),
+            vec![file.clone()]
+        );
+
+        watcher.unwatch_file(&file);
+        assert_eq!(
+            classify_tracked_paths(&event, &watcher.state),
+            Vec::<PathBuf>::new()
+        );
+    }
+
+    #[test]
+    fn classify_config_paths_only_reports_registered_config_files() {
+        let watcher = FileWatcher::noop();
+        watcher.register_config_file(path("/tmp/codex-home/config.toml"));
+        watcher.register_config_file(path("/tmp/codex-home/shared.toml"));
+        let event = notify_event(vec![
+            path("/tmp/codex-home/config.toml"),
+            path("/tmp/codex-home/history.jsonl"),
+        ]);
+
+        let classified = classify_config_paths(&event, &watcher.state);
+        assert_eq!(classified, vec![path("/tmp/codex-home/config.toml")]);
+    }
+
     #[test]
     fn register_skills_root_dedupes_state_entries() {
         let watcher = FileWatcher::noop();
@@ -150,6 +150,24 @@ pub async fn get_head_commit_hash(cwd: &Path) -> Option<String> {
     }
 }
 
+/// Resolve `rev` (a branch, tag or abbreviated sha) to a full commit hash.
+pub async fn resolve_commit_hash(cwd: &Path, rev: &str) -> Option<String> {
+    let spec = format!("{rev}^{{commit}}");
+    let output =
+        run_git_command_with_timeout(&["rev-parse", "--verify", "--quiet", &spec], cwd).await?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let hash = stdout.trim();
+    if hash.is_empty() {
+        None
+    } else {
+        Some(hash.to_string())
+    }
+}
+
 fn parse_git_remote_urls(stdout: &str) -> Option<BTreeMap<String, String>> {
     let mut remotes = BTreeMap::new();
     for line in stdout.lines() {
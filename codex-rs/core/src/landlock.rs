@@ -33,6 +33,8 @@ where
         sandbox_policy,
         sandbox_policy_cwd,
         use_bwrap_sandbox,
+        None,
+        &[],
     );
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(
@@ -51,11 +53,16 @@ where
 ///
 /// The helper performs the actual sandboxing (bubblewrap + seccomp) after
 /// parsing these arguments. See `docs/linux_sandbox.md` for the Linux semantics.
+/// `overlay_dir` is the scratch directory that receives the command's writes
+/// under `SandboxPolicy::WorkspaceOverlay`, and every `deny_read` path is
+/// masked so the command cannot read it.
 pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
     use_bwrap_sandbox: bool,
+    overlay_dir: Option<&Path>,
+    deny_read: &[PathBuf],
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -76,6 +83,14 @@ pub(crate) fn create_linux_sandbox_command_args(
     if use_bwrap_sandbox {
         linux_cmd.push("--use-bwrap-sandbox".to_string());
     }
+    if let Some(overlay_dir) = overlay_dir {
+        linux_cmd.push("--overlay-dir".to_string());
+        linux_cmd.push(overlay_dir.to_string_lossy().to_string());
+    }
+    for path in deny_read {
+        linux_cmd.push("--deny-read".to_string());
+        linux_cmd.push(path.to_string_lossy().to_string());
+    }
 
     // Separator so that command arguments starting with `-` are not parsed as
     // options of the helper itself.
@@ -98,13 +113,15 @@ mod tests {
         let cwd = Path::new("/tmp");
         let policy = SandboxPolicy::ReadOnly;
 
-        let with_bwrap = create_linux_sandbox_command_args(command.clone(), &policy, cwd, true);
+        let with_bwrap =
+            create_linux_sandbox_command_args(command.clone(), &policy, cwd, true, None, &[]);
         assert_eq!(
             with_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             true
         );
 
-        let without_bwrap = create_linux_sandbox_command_args(command, &policy, cwd, false);
+        let without_bwrap =
+            create_linux_sandbox_command_args(command, &policy, cwd, false, None, &[]);
         assert_eq!(
             without_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             false
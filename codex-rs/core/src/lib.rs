@@ -23,6 +23,7 @@ mod codex_delegate;
 mod command_safety;
 pub mod config;
 pub mod config_loader;
+mod config_reload;
 pub mod connectors;
 mod context_manager;
 pub mod custom_prompts;
@@ -32,6 +33,7 @@ pub mod error;
 pub mod exec;
 pub mod exec_env;
 mod exec_policy;
+mod external_edits;
 pub mod features;
 mod file_watcher;
 mod flags;
@@ -39,6 +41,7 @@ pub mod git_info;
 pub mod hooks;
 pub mod instructions;
 pub mod landlock;
+mod lsp;
 pub mod mcp;
 mod mcp_connection_manager;
 pub mod models_manager;
@@ -58,7 +61,6 @@ pub mod sandboxing;
 mod session_prefix;
 mod stream_events_utils;
 mod tagged_block_parser;
-mod text_encoding;
 pub mod token_data;
 mod truncate;
 mod unified_exec;
@@ -73,6 +75,7 @@ pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
 mod event_mapping;
+pub mod review_dismissals;
 pub mod review_format;
 pub mod review_prompts;
 mod thread_manager;
@@ -99,9 +102,11 @@ pub mod shell_snapshot;
 pub mod skills;
 pub mod spawn;
 pub mod state_db;
+pub mod symbol_index;
 pub mod terminal;
 mod tools;
 pub mod turn_diff_tracker;
+mod turn_limits;
 mod turn_metadata;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
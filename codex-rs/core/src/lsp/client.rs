@@ -0,0 +1,579 @@
+//! Minimal JSON-RPC client for a language server spoken to over stdio.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::ChildStderr;
+use tokio::process::ChildStdin;
+use tokio::process::ChildStdout;
+use tokio::sync::Mutex;
+use tokio::sync::Notify;
+use tokio::sync::oneshot;
+use tracing::debug;
+use tracing::warn;
+use url::Url;
+
+use crate::config::types::LspServerConfig;
+use crate::exec::ExecExpiration;
+use crate::exec::SandboxType;
+use crate::sandboxing::CommandSpec;
+use crate::sandboxing::SandboxPermissions;
+use crate::spawn::StdioPolicy;
+use crate::spawn::spawn_child_async;
+use crate::tools::sandboxing::SandboxAttempt;
+
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(30);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+type PendingRequests = Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value>>>>>;
+
+/// Diagnostics last published by the server for one document.
+#[derive(Debug, Clone, Default)]
+struct PublishedDiagnostics {
+    /// Incremented on every `textDocument/publishDiagnostics` for the document.
+    generation: u64,
+    diagnostics: Vec<Value>,
+}
+
+type DiagnosticsStore = Arc<Mutex<HashMap<Url, PublishedDiagnostics>>>;
+
+pub(crate) struct LspClient {
+    language_id: String,
+    stdin: Mutex<ChildStdin>,
+    next_id: AtomicI64,
+    pending: PendingRequests,
+    diagnostics: DiagnosticsStore,
+    diagnostics_updated: Arc<Notify>,
+    /// Version of every document opened with the server.
+    open_documents: Mutex<HashMap<Url, i32>>,
+    /// Answer to `workspace/configuration` requests, when Codex overrides
+    /// the server's own settings.
+    workspace_settings: Option<Value>,
+    // Held so the server is killed when the client is dropped.
+    _child: Child,
+}
+
+impl LspClient {
+    /// Launches the server under `attempt`'s sandbox, in its working
+    /// directory, and performs the `initialize` handshake.
+    pub(crate) async fn start(
+        name: &str,
+        config: &LspServerConfig,
+        attempt: &SandboxAttempt<'_>,
+        env: HashMap<String, String>,
+    ) -> Result<Arc<Self>> {
+        if attempt.sandbox == SandboxType::WindowsRestrictedToken {
+            bail!("language server `{name}` cannot run under the Windows sandbox");
+        }
+        let root = attempt.sandbox_cwd;
+        let exec_env = attempt.env_for(CommandSpec {
+            program: config.command.clone(),
+            args: config.args.clone(),
+            cwd: root.to_path_buf(),
+            env,
+            expiration: ExecExpiration::DefaultTimeout,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+            stdin: None,
+        })?;
+        let (program, args) = exec_env
+            .command
+            .split_first()
+            .ok_or_else(|| anyhow!("language server `{name}` has an empty command"))?;
+        let mut child = spawn_child_async(
+            PathBuf::from(program),
+            args.to_vec(),
+            exec_env.arg0.as_deref(),
+            exec_env.cwd,
+            attempt.policy,
+            StdioPolicy::RedirectForShellToolWithStdin,
+            exec_env.env,
+        )
+        .await
+        .with_context(|| format!("failed to launch language server `{}`", config.command))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("language server stdin was not captured"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("language server stdout was not captured"))?;
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(drain_stderr(name.to_string(), stderr));
+        }
+        let initialization_options = initialization_options(config);
+        let workspace_settings = restricts_workspace_code(config)
+            .then(|| initialization_options.clone())
+            .flatten();
+
+        let client = Arc::new(Self {
+            language_id: config
+                .language_id
+                .clone()
+                .unwrap_or_else(|| name.to_string()),
+            stdin: Mutex::new(stdin),
+            next_id: AtomicI64::new(1),
+            pending: PendingRequests::default(),
+            diagnostics: DiagnosticsStore::default(),
+            diagnostics_updated: Arc::new(Notify::new()),
+            open_documents: Mutex::new(HashMap::new()),
+            workspace_settings,
+            _child: child,
+        });
+        tokio::spawn(read_messages(
+            stdout,
+            Arc::downgrade(&client),
+            Arc::clone(&client.pending),
+            Arc::clone(&client.diagnostics),
+            Arc::clone(&client.diagnostics_updated),
+        ));
+
+        let root_uri = file_url(root)?;
+        let params = json!({
+            "processId": std::process::id(),
+            "rootUri": root_uri.as_str(),
+            "workspaceFolders": [{ "uri": root_uri.as_str(), "name": name }],
+            "initializationOptions": initialization_options,
+            "capabilities": {
+                "textDocument": {
+                    "synchronization": { "didSave": true },
+                    "publishDiagnostics": {},
+                    "hover": { "contentFormat": ["plaintext", "markdown"] },
+                    "definition": { "linkSupport": true },
+                    "references": {},
+                },
+                "workspace": { "workspaceFolders": true, "configuration": true },
+            },
+        });
+        tokio::time::timeout(INITIALIZE_TIMEOUT, client.request("initialize", params))
+            .await
+            .map_err(|_| anyhow!("language server `{name}` did not finish initializing"))??;
+        client.notify("initialized", json!({})).await?;
+        Ok(client)
+    }
+
+    /// Sends `path`'s current contents to the server, opening the document on
+    /// first use. Returns the document's URI and the text that was sent.
+    pub(crate) async fn sync_document(&self, path: &Path) -> Result<(Url, String)> {
+        let uri = file_url(path)?;
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let mut open_documents = self.open_documents.lock().await;
+        match open_documents.get_mut(&uri) {
+            Some(version) => {
+                *version += 1;
+                let version = *version;
+                drop(open_documents);
+                self.notify(
+                    "textDocument/didChange",
+                    json!({
+                        "textDocument": { "uri": uri.as_str(), "version": version },
+                        "contentChanges": [{ "text": &text }],
+                    }),
+                )
+                .await?;
+            }
+            None => {
+                open_documents.insert(uri.clone(), 1);
+                drop(open_documents);
+                self.notify(
+                    "textDocument/didOpen",
+                    json!({
+                        "textDocument": {
+                            "uri": uri.as_str(),
+                            "languageId": self.language_id,
+                            "version": 1,
+                            "text": &text,
+                        },
+                    }),
+                )
+                .await?;
+            }
+        }
+        // The file is already on disk; servers that only check saved files
+        // (rust-analyzer's `cargo check`) rely on this.
+        self.notify(
+            "textDocument/didSave",
+            json!({ "textDocument": { "uri": uri.as_str() } }),
+        )
+        .await?;
+        Ok((uri, text))
+    }
+
+    /// Syncs `path` and waits until `deadline` for the server to publish fresh
+    /// diagnostics for it. Falls back to the last published diagnostics.
+    /// Returns the synced text alongside, since diagnostic columns count
+    /// UTF-16 code units of it.
+    pub(crate) async fn diagnostics(
+        &self,
+        path: &Path,
+        deadline: tokio::time::Instant,
+    ) -> Result<(String, Vec<Value>)> {
+        let uri = file_url(path)?;
+        let before = self.diagnostics_generation(&uri).await;
+        let (_, text) = self.sync_document(path).await?;
+
+        loop {
+            let updated = self.diagnostics_updated.notified();
+            if self.diagnostics_generation(&uri).await > before {
+                break;
+            }
+            if tokio::time::timeout_at(deadline, updated).await.is_err() {
+                break;
+            }
+        }
+        let diagnostics = self
+            .diagnostics
+            .lock()
+            .await
+            .get(&uri)
+            .map(|published| published.diagnostics.clone())
+            .unwrap_or_default();
+        Ok((text, diagnostics))
+    }
+
+    /// Sends a `textDocument/*` request positioned at `line`/`character`
+    /// (zero-based, `character` counted in characters) in `path`.
+    pub(crate) async fn position_request(
+        &self,
+        method: &str,
+        path: &Path,
+        line: u32,
+        character: u32,
+        extra: Option<(&str, Value)>,
+    ) -> Result<Value> {
+        let (uri, text) = self.sync_document(path).await?;
+        let character = utf16_column(line_text(&text, line), character);
+        let mut params = json!({
+            "textDocument": { "uri": uri.as_str() },
+            "position": { "line": line, "character": character },
+        });
+        if let (Some((key, value)), Some(object)) = (extra, params.as_object_mut()) {
+            object.insert(key.to_string(), value);
+        }
+        tokio::time::timeout(REQUEST_TIMEOUT, self.request(method, params))
+            .await
+            .map_err(|_| anyhow!("language server did not answer `{method}` in time"))?
+    }
+
+    async fn diagnostics_generation(&self, uri: &Url) -> u64 {
+        self.diagnostics
+            .lock()
+            .await
+            .get(uri)
+            .map_or(0, |published| published.generation)
+    }
+
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id, tx);
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(err) = self.send(&message).await {
+            self.pending.lock().await.remove(&id);
+            return Err(err);
+        }
+        rx.await
+            .map_err(|_| anyhow!("language server exited before answering `{method}`"))?
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> Result<()> {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await
+    }
+
+    async fn respond(&self, id: Value, result: Value) -> Result<()> {
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+            .await
+    }
+
+    async fn send(&self, message: &Value) -> Result<()> {
+        let body = serde_json::to_vec(message)?;
+        let mut stdin = self.stdin.lock().await;
+        stdin
+            .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+            .await?;
+        stdin.write_all(&body).await?;
+        stdin.flush().await?;
+        Ok(())
+    }
+}
+
+/// Reads framed messages from the server until it closes stdout, routing
+/// responses to their pending requests and keeping published diagnostics.
+async fn read_messages(
+    stdout: ChildStdout,
+    client: std::sync::Weak<LspClient>,
+    pending: PendingRequests,
+    diagnostics: DiagnosticsStore,
+    diagnostics_updated: Arc<Notify>,
+) {
+    let mut reader = BufReader::new(stdout);
+    loop {
+        let message = match read_message(&mut reader).await {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(err) => {
+                warn!("failed to read from language server: {err:#}");
+                break;
+            }
+        };
+
+        let method = message.get("method").and_then(Value::as_str);
+        let id = message.get("id").cloned();
+        match (method, id) {
+            (None, Some(id)) => {
+                let Some(id) = id.as_i64() else {
+                    continue;
+                };
+                let Some(tx) = pending.lock().await.remove(&id) else {
+                    continue;
+                };
+                let result = match message.get("error") {
+                    Some(error) => Err(anyhow!(
+                        "{}",
+                        error
+                            .get("message")
+                            .and_then(Value::as_str)
+                            .unwrap_or("language server returned an error")
+                    )),
+                    None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                };
+                let _ = tx.send(result);
+            }
+            (Some("textDocument/publishDiagnostics"), None) => {
+                let params = message.get("params").cloned().unwrap_or(Value::Null);
+                let Some(uri) = params
+                    .get("uri")
+                    .and_then(Value::as_str)
+                    .and_then(|uri| Url::parse(uri).ok())
+                else {
+                    continue;
+                };
+                let published = params
+                    .get("diagnostics")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                let mut store = diagnostics.lock().await;
+                let entry = store.entry(uri).or_default();
+                entry.generation += 1;
+                entry.diagnostics = published;
+                drop(store);
+                diagnostics_updated.notify_waiters();
+            }
+            (Some(method), Some(id)) => {
+                let Some(client) = client.upgrade() else {
+                    break;
+                };
+                // Server-to-client requests (configuration, capability
+                // registration, progress) get an empty answer so the server
+                // does not stall waiting for one.
+                let result = match method {
+                    "workspace/configuration" => {
+                        let items = message
+                            .pointer("/params/items")
+                            .and_then(Value::as_array)
+                            .map_or(0, Vec::len);
+                        let settings = client.workspace_settings.clone().unwrap_or(Value::Null);
+                        Value::Array(vec![settings; items])
+                    }
+                    _ => Value::Null,
+                };
+                if let Err(err) = client.respond(id, result).await {
+                    debug!("failed to answer language server request {method}: {err:#}");
+                }
+            }
+            (Some(_), None) => {}
+        }
+    }
+
+    // Fail every request still waiting on the server.
+    pending.lock().await.clear();
+}
+
+/// Logs the server's stderr so a chatty server cannot fill the pipe and block.
+async fn drain_stderr(name: String, stderr: ChildStderr) {
+    let mut lines = BufReader::new(stderr).lines();
+    loop {
+        match lines.next_line().await {
+            Ok(Some(line)) => debug!("language server `{name}`: {line}"),
+            Ok(None) | Err(_) => break,
+        }
+    }
+}
+
+async fn read_message(reader: &mut BufReader<ChildStdout>) -> Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            content_length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+    let content_length =
+        content_length.ok_or_else(|| anyhow!("message without a Content-Length header"))?;
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+/// Whether Codex must keep `config`'s server from running workspace code.
+fn restricts_workspace_code(config: &LspServerConfig) -> bool {
+    !config.run_workspace_code
+        && Path::new(&config.command)
+            .file_stem()
+            .and_then(OsStr::to_str)
+            == Some("rust-analyzer")
+}
+
+/// `initializationOptions` for `config`. Unless the server may run workspace
+/// code, rust-analyzer is told not to run build scripts, proc-macros or
+/// `cargo check`, whatever the configured options say.
+fn initialization_options(config: &LspServerConfig) -> Option<Value> {
+    if !restricts_workspace_code(config) {
+        return config.initialization_options.clone();
+    }
+    let mut options = match &config.initialization_options {
+        Some(Value::Object(options)) => options.clone(),
+        _ => Map::new(),
+    };
+    set_option(&mut options, &["cargo", "buildScripts", "enable"], false);
+    set_option(&mut options, &["procMacro", "enable"], false);
+    set_option(&mut options, &["checkOnSave"], false);
+    Some(Value::Object(options))
+}
+
+fn set_option(options: &mut Map<String, Value>, path: &[&str], value: bool) {
+    match path {
+        [] => {}
+        [key] => {
+            options.insert((*key).to_string(), Value::Bool(value));
+        }
+        [key, rest @ ..] => {
+            let entry = options.entry(*key).or_insert_with(|| json!({}));
+            if !entry.is_object() {
+                *entry = json!({});
+            }
+            if let Some(object) = entry.as_object_mut() {
+                set_option(object, rest, value);
+            }
+        }
+    }
+}
+
+/// Line `line` (zero-based) of `text`, or an empty string past the end.
+pub(crate) fn line_text(text: &str, line: u32) -> &str {
+    text.lines().nth(line as usize).unwrap_or_default()
+}
+
+/// Converts a column counted in characters to UTF-16 code units, the unit
+/// LSP positions use.
+pub(crate) fn utf16_column(line: &str, character: u32) -> u32 {
+    let units: usize = line
+        .chars()
+        .take(character as usize)
+        .map(char::len_utf16)
+        .sum();
+    // Columns past the end of the line keep their distance from it.
+    let past_end = (character as usize).saturating_sub(line.chars().count());
+    u32::try_from(units + past_end).unwrap_or(u32::MAX)
+}
+
+/// Converts a column counted in UTF-16 code units back to characters.
+pub(crate) fn char_column(line: &str, utf16: u32) -> u32 {
+    let mut units = 0;
+    let mut characters = 0;
+    for ch in line.chars() {
+        if units >= utf16 as usize {
+            break;
+        }
+        units += ch.len_utf16();
+        characters += 1;
+    }
+    let past_end = (utf16 as usize).saturating_sub(units);
+    u32::try_from(characters + past_end).unwrap_or(u32::MAX)
+}
+
+pub(crate) fn file_url(path: &Path) -> Result<Url> {
+    Url::from_file_path(path).map_err(|()| anyhow!("`{}` is not an absolute path", path.display()))
+}
+
+pub(crate) fn url_to_path(uri: &str) -> Option<PathBuf> {
+    Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn server(command: &str, run_workspace_code: bool) -> LspServerConfig {
+        LspServerConfig {
+            command: command.to_string(),
+            args: Vec::new(),
+            file_extensions: vec!["rs".to_string()],
+            language_id: None,
+            initialization_options: Some(json!({
+                "cargo": { "features": "all", "buildScripts": { "enable": true } },
+            })),
+            run_workspace_code,
+        }
+    }
+
+    #[test]
+    fn rust_analyzer_does_not_run_workspace_code_by_default() {
+        assert_eq!(
+            initialization_options(&server("/usr/bin/rust-analyzer", false)),
+            Some(json!({
+                "cargo": { "features": "all", "buildScripts": { "enable": false } },
+                "procMacro": { "enable": false },
+                "checkOnSave": false,
+            }))
+        );
+    }
+
+    #[test]
+    fn configured_options_pass_through_when_workspace_code_may_run() {
+        let expected = server("rust-analyzer", true).initialization_options;
+
+        assert_eq!(
+            initialization_options(&server("rust-analyzer", true)),
+            expected
+        );
+        assert_eq!(
+            initialization_options(&server("gopls", false)),
+            server("gopls", false).initialization_options
+        );
+    }
+}
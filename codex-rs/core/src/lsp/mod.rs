@@ -0,0 +1,504 @@
+//! Language servers configured under `[lsp_servers]`.
+//!
+//! Servers are launched lazily, one per configured server and workspace root,
+//! the first time a file with a matching extension needs diagnostics or
+//! navigation. They run under the turn's sandbox policy with the shell tool's
+//! environment, and are restarted when a later turn uses a different policy.
+//! Documents are re-sent from disk before every request, so the server always
+//! sees the latest edits made by the agent or the user.
+
+mod client;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_protocol::protocol::SandboxPolicy;
+use futures::future::join_all;
+use serde_json::Value;
+use serde_json::json;
+use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::codex::TurnContext;
+use crate::config::types::LspServerConfig;
+use crate::exec_env::create_env;
+use crate::features::Feature;
+use crate::sandboxing::SandboxManager;
+use crate::tools::sandboxing::SandboxAttempt;
+use crate::tools::sandboxing::SandboxablePreference;
+use client::LspClient;
+use client::char_column;
+use client::line_text;
+use client::url_to_path;
+
+/// How long to wait for servers to publish diagnostics after files changed.
+const DIAGNOSTICS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum number of diagnostics reported per file.
+const MAX_DIAGNOSTICS_PER_FILE: usize = 50;
+
+/// A zero-based position in a document. Unlike on the wire, `character`
+/// counts characters rather than UTF-16 code units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LspPosition {
+    pub(crate) line: u32,
+    pub(crate) character: u32,
+}
+
+/// A location returned by go-to-definition or find-references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LspLocation {
+    pub(crate) path: PathBuf,
+    pub(crate) position: LspPosition,
+}
+
+pub(crate) struct LspManager {
+    servers: BTreeMap<String, LspServerConfig>,
+    clients: Mutex<HashMap<(String, PathBuf), CachedClient>>,
+}
+
+/// A server started for one workspace root. The cell is filled once the
+/// server has initialized, which happens outside the `clients` lock so other
+/// servers stay usable meanwhile.
+struct CachedClient {
+    policy: SandboxPolicy,
+    client: Arc<OnceCell<Arc<LspClient>>>,
+}
+
+impl LspManager {
+    pub(crate) fn new(servers: BTreeMap<String, LspServerConfig>) -> Self {
+        Self {
+            servers,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.servers.is_empty()
+    }
+
+    /// Whether a configured server handles `path`.
+    pub(crate) fn handles(&self, path: &Path) -> bool {
+        self.server_for(path).is_some()
+    }
+
+    /// Diagnostics for `path`, formatted one per line relative to the turn's
+    /// working directory.
+    pub(crate) async fn diagnostics(&self, turn: &TurnContext, path: &Path) -> Result<Vec<String>> {
+        let client = self.client_for(turn, path).await?;
+        collect_diagnostics(&client, turn, path, Instant::now() + DIAGNOSTICS_TIMEOUT).await
+    }
+
+    /// Diagnostics for the files an edit touched, or `None` when no configured
+    /// server handles them or none of them has diagnostics. Server failures are
+    /// logged rather than surfaced, since the edit itself succeeded.
+    pub(crate) async fn diagnostics_for_edit(
+        &self,
+        turn: &TurnContext,
+        paths: &[PathBuf],
+    ) -> Option<String> {
+        let paths: Vec<&PathBuf> = paths.iter().filter(|path| self.handles(path)).collect();
+        // Servers start together, then every file shares one deadline for
+        // fresh diagnostics.
+        let clients = join_all(paths.iter().map(|path| self.client_for(turn, path))).await;
+        let deadline = Instant::now() + DIAGNOSTICS_TIMEOUT;
+        let results = join_all(paths.iter().zip(clients).map(|(path, client)| async move {
+            match client {
+                Ok(client) => collect_diagnostics(&client, turn, path, deadline).await,
+                Err(err) => Err(err),
+            }
+        }))
+        .await;
+
+        let mut lines = Vec::new();
+        for (path, result) in paths.iter().zip(results) {
+            match result {
+                Ok(diagnostics) => lines.extend(diagnostics),
+                Err(err) => warn!(
+                    "failed to collect diagnostics for {}: {err:#}",
+                    path.display()
+                ),
+            }
+        }
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    pub(crate) async fn definition(
+        &self,
+        turn: &TurnContext,
+        path: &Path,
+        position: LspPosition,
+    ) -> Result<Vec<LspLocation>> {
+        let client = self.client_for(turn, path).await?;
+        let response = client
+            .position_request(
+                "textDocument/definition",
+                path,
+                position.line,
+                position.character,
+                None,
+            )
+            .await?;
+        Ok(with_char_columns(parse_locations(&response)).await)
+    }
+
+    pub(crate) async fn references(
+        &self,
+        turn: &TurnContext,
+        path: &Path,
+        position: LspPosition,
+    ) -> Result<Vec<LspLocation>> {
+        let client = self.client_for(turn, path).await?;
+        let response = client
+            .position_request(
+                "textDocument/references",
+                path,
+                position.line,
+                position.character,
+                Some(("context", json!({ "includeDeclaration": true }))),
+            )
+            .await?;
+        Ok(with_char_columns(parse_locations(&response)).await)
+    }
+
+    /// Hover text at `position`, or `None` when the server has nothing to show.
+    pub(crate) async fn hover(
+        &self,
+        turn: &TurnContext,
+        path: &Path,
+        position: LspPosition,
+    ) -> Result<Option<String>> {
+        let client = self.client_for(turn, path).await?;
+        let response = client
+            .position_request(
+                "textDocument/hover",
+                path,
+                position.line,
+                position.character,
+                None,
+            )
+            .await?;
+        let text = response.get("contents").map(hover_text).unwrap_or_default();
+        Ok((!text.trim().is_empty()).then(|| text.trim().to_string()))
+    }
+
+    fn server_for(&self, path: &Path) -> Option<(&String, &LspServerConfig)> {
+        let extension = path.extension()?.to_str()?;
+        self.servers.iter().find(|(_, server)| {
+            server
+                .file_extensions
+                .iter()
+                .any(|candidate| candidate.trim_start_matches('.') == extension)
+        })
+    }
+
+    async fn client_for(&self, turn: &TurnContext, path: &Path) -> Result<Arc<LspClient>> {
+        let (name, server) = self
+            .server_for(path)
+            .ok_or_else(|| anyhow!("no language server is configured for {}", path.display()))?;
+        let key = (name.clone(), turn.cwd.clone());
+        let cell = {
+            let mut clients = self.clients.lock().await;
+            match clients.get(&key) {
+                Some(cached) if cached.policy == turn.sandbox_policy => Arc::clone(&cached.client),
+                // A server started under another sandbox policy is replaced;
+                // it exits once the requests still using it finish.
+                _ => {
+                    let client = Arc::new(OnceCell::new());
+                    clients.insert(
+                        key,
+                        CachedClient {
+                            policy: turn.sandbox_policy.clone(),
+                            client: Arc::clone(&client),
+                        },
+                    );
+                    client
+                }
+            }
+        };
+        let client = cell
+            .get_or_try_init(|| start_client(turn, name, server))
+            .await?;
+        Ok(Arc::clone(client))
+    }
+}
+
+async fn start_client(
+    turn: &TurnContext,
+    name: &str,
+    server: &LspServerConfig,
+) -> Result<Arc<LspClient>> {
+    // Servers load workspace code (build scripts, plugins), so they get
+    // the same sandbox as the shell tool's first attempt.
+    let manager = SandboxManager::new();
+    let use_linux_sandbox_bwrap = turn.features.enabled(Feature::UseLinuxSandboxBwrap);
+    let attempt = SandboxAttempt {
+        sandbox: manager.select_initial(
+            &turn.sandbox_policy,
+            SandboxablePreference::Auto,
+            turn.windows_sandbox_level,
+        ),
+        policy: &turn.sandbox_policy,
+        manager: &manager,
+        sandbox_cwd: &turn.cwd,
+        codex_linux_sandbox_exe: turn.codex_linux_sandbox_exe.as_ref(),
+        use_linux_sandbox_bwrap,
+        windows_sandbox_level: turn.windows_sandbox_level,
+        deny_read: turn.sandbox_deny_read(),
+    };
+    let env = create_env(&turn.shell_environment_policy, None);
+    LspClient::start(name, server, &attempt, env).await
+}
+
+/// Syncs `path` with `client` and formats the diagnostics published by
+/// `deadline`, one per line relative to the turn's working directory.
+async fn collect_diagnostics(
+    client: &LspClient,
+    turn: &TurnContext,
+    path: &Path,
+    deadline: Instant,
+) -> Result<Vec<String>> {
+    let (text, diagnostics) = client.diagnostics(path, deadline).await?;
+    let display_path = display_path(&turn.cwd, path);
+    let mut lines: Vec<String> = diagnostics
+        .iter()
+        .take(MAX_DIAGNOSTICS_PER_FILE)
+        .map(|diagnostic| format_diagnostic(&display_path, &text, diagnostic))
+        .collect();
+    if diagnostics.len() > MAX_DIAGNOSTICS_PER_FILE {
+        lines.push(format!(
+            "{display_path}: {} more diagnostics omitted",
+            diagnostics.len() - MAX_DIAGNOSTICS_PER_FILE
+        ));
+    }
+    Ok(lines)
+}
+
+/// Formats `path` relative to `root` when it lies inside it.
+pub(crate) fn display_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Formats an LSP diagnostic on `text` as
+/// `path:line:col: severity: message (source)`, with one-based line and
+/// character column numbers.
+fn format_diagnostic(display_path: &str, text: &str, diagnostic: &Value) -> String {
+    let mut position = position_at(diagnostic, "/range/start").unwrap_or(LspPosition {
+        line: 0,
+        character: 0,
+    });
+    position.character = char_column(line_text(text, position.line), position.character);
+    let severity = match diagnostic.get("severity").and_then(Value::as_u64) {
+        Some(1) => "error",
+        Some(2) => "warning",
+        Some(3) => "info",
+        Some(4) => "hint",
+        _ => "diagnostic",
+    };
+    let message = diagnostic
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .lines()
+        .next()
+        .unwrap_or_default();
+    let mut line = format!(
+        "{display_path}:{}:{}: {severity}: {message}",
+        position.line + 1,
+        position.character + 1
+    );
+    if let Some(source) = diagnostic.get("source").and_then(Value::as_str) {
+        line.push_str(&format!(" ({source})"));
+    }
+    line
+}
+
+/// Parses a definition or references response: `Location`, `Location[]`,
+/// `LocationLink[]` or `null`.
+fn parse_locations(response: &Value) -> Vec<LspLocation> {
+    let items = match response {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(_) => vec![response],
+        _ => Vec::new(),
+    };
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let (uri, position) = match item.get("targetUri") {
+                Some(uri) => (uri, position_at(item, "/targetSelectionRange/start")?),
+                None => (item.get("uri")?, position_at(item, "/range/start")?),
+            };
+            Some(LspLocation {
+                path: url_to_path(uri.as_str()?)?,
+                position,
+            })
+        })
+        .collect()
+}
+
+/// Rewrites the UTF-16 columns of `locations` as character columns, reading
+/// each target file once.
+async fn with_char_columns(mut locations: Vec<LspLocation>) -> Vec<LspLocation> {
+    let mut texts: HashMap<PathBuf, String> = HashMap::new();
+    for location in &mut locations {
+        if !texts.contains_key(&location.path) {
+            let text = tokio::fs::read_to_string(&location.path)
+                .await
+                .unwrap_or_default();
+            texts.insert(location.path.clone(), text);
+        }
+        if let Some(text) = texts.get(&location.path) {
+            location.position.character = char_column(
+                line_text(text, location.position.line),
+                location.position.character,
+            );
+        }
+    }
+    locations
+}
+
+fn position_at(value: &Value, pointer: &str) -> Option<LspPosition> {
+    let position = value.pointer(pointer)?;
+    Some(LspPosition {
+        line: u32::try_from(position.get("line")?.as_u64()?).ok()?,
+        character: u32::try_from(position.get("character")?.as_u64()?).ok()?,
+    })
+}
+
+/// Flattens hover contents (`MarkupContent`, `MarkedString` or an array of
+/// `MarkedString`) into plain text.
+fn hover_text(contents: &Value) -> String {
+    match contents {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(hover_text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        Value::Object(object) => object
+            .get("value")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn format_diagnostic_uses_one_based_positions() {
+        let diagnostic = json!({
+            "range": {
+                "start": { "line": 4, "character": 8 },
+                "end": { "line": 4, "character": 12 },
+            },
+            "severity": 1,
+            "source": "rustc",
+            "message": "cannot find value `x` in this scope\nhelp: ...",
+        });
+
+        assert_eq!(
+            format_diagnostic("src/lib.rs", "", &diagnostic),
+            "src/lib.rs:5:9: error: cannot find value `x` in this scope (rustc)"
+        );
+    }
+
+    #[test]
+    fn format_diagnostic_counts_characters_not_utf16_units() {
+        let diagnostic = json!({
+            "range": {
+                "start": { "line": 1, "character": 10 },
+                "end": { "line": 1, "character": 11 },
+            },
+            "severity": 2,
+            "message": "unused variable",
+        });
+
+        assert_eq!(
+            format_diagnostic("src/lib.rs", "fn main() {\n    \"😀\"; x\n}\n", &diagnostic),
+            "src/lib.rs:2:10: warning: unused variable"
+        );
+    }
+
+    #[test]
+    fn columns_round_trip_between_characters_and_utf16() {
+        let line = "let é = \"😀\"; x";
+
+        assert_eq!(client::utf16_column(line, 4), 4);
+        assert_eq!(client::utf16_column(line, 14), 15);
+        assert_eq!(client::char_column(line, 15), 14);
+        assert_eq!(client::char_column(line, 4), 4);
+        // Columns past the end of the line keep their distance from it.
+        assert_eq!(client::utf16_column(line, 17), 18);
+        assert_eq!(client::char_column(line, 18), 17);
+    }
+
+    #[test]
+    fn parse_locations_accepts_locations_and_links() {
+        let root = std::env::temp_dir();
+        let uri = client::file_url(&root.join("lib.rs")).expect("file url");
+        let location = json!({
+            "uri": uri.as_str(),
+            "range": {
+                "start": { "line": 1, "character": 2 },
+                "end": { "line": 1, "character": 5 },
+            },
+        });
+        let link = json!([{
+            "targetUri": uri.as_str(),
+            "targetRange": location["range"].clone(),
+            "targetSelectionRange": {
+                "start": { "line": 3, "character": 4 },
+                "end": { "line": 3, "character": 7 },
+            },
+        }]);
+
+        assert_eq!(
+            parse_locations(&location),
+            vec![LspLocation {
+                path: root.join("lib.rs"),
+                position: LspPosition {
+                    line: 1,
+                    character: 2,
+                },
+            }]
+        );
+        assert_eq!(
+            parse_locations(&link),
+            vec![LspLocation {
+                path: root.join("lib.rs"),
+                position: LspPosition {
+                    line: 3,
+                    character: 4,
+                },
+            }]
+        );
+        assert_eq!(parse_locations(&Value::Null), Vec::new());
+    }
+
+    #[test]
+    fn hover_text_flattens_marked_strings() {
+        let contents = json!([
+            { "language": "rust", "value": "fn main()" },
+            "Program entry point.",
+        ]);
+
+        assert_eq!(hover_text(&contents), "fn main()\n\nProgram entry point.");
+    }
+}
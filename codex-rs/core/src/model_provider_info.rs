@@ -75,6 +75,13 @@ pub struct ModelProviderInfo {
     /// this may be necessary when using this programmatically.
     pub experimental_bearer_token: Option<String>,
 
+    /// Command (program followed by its arguments) that prints a bearer token
+    /// for this provider, either as plain text or as JSON with `token` and an
+    /// optional `expires_at` (RFC 3339) or `expires_in` (seconds). The token
+    /// is cached in memory, refreshed shortly before it expires or after a
+    /// `401 Unauthorized`, and never persisted.
+    pub auth_command: Option<Vec<String>>,
+
     /// Which wire protocol this provider expects.
     #[serde(default)]
     pub wire_api: WireApi,
@@ -161,6 +168,7 @@ impl ModelProviderInfo {
             retry_429: false,
             retry_5xx: true,
             retry_transport: true,
+            retry_401: self.auth_command.is_some(),
         };
 
         Ok(ApiProvider {
@@ -233,6 +241,7 @@ impl ModelProviderInfo {
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: Some(
@@ -322,6 +331,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_command: None,
         wire_api,
         query_params: None,
         http_headers: None,
@@ -351,6 +361,7 @@ base_url = "http://localhost:11434/v1"
             env_key: None,
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: None,
@@ -380,6 +391,7 @@ query_params = { api-version = "2025-04-01-preview" }
             env_key: Some("AZURE_OPENAI_API_KEY".into()),
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Responses,
             query_params: Some(maplit::hashmap! {
                 "api-version".to_string() => "2025-04-01-preview".to_string(),
@@ -412,6 +424,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             env_key: Some("API_KEY".into()),
             env_key_instructions: None,
             experimental_bearer_token: None,
+            auth_command: None,
             wire_api: WireApi::Responses,
             query_params: None,
             http_headers: Some(maplit::hashmap! {
@@ -431,6 +444,32 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn test_deserialize_auth_command_model_provider_toml() {
+        let provider_toml = r#"
+name = "Gateway"
+base_url = "https://gateway.example.com/v1"
+auth_command = ["my-token-helper", "--audience", "codex"]
+        "#;
+
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+        assert_eq!(
+            provider.auth_command,
+            Some(vec![
+                "my-token-helper".to_string(),
+                "--audience".to_string(),
+                "codex".to_string(),
+            ])
+        );
+        assert!(
+            provider
+                .to_api_provider(None)
+                .expect("api provider")
+                .retry
+                .retry_401
+        );
+    }
+
     #[test]
     fn test_deserialize_chat_wire_api_shows_helpful_error() {
         let provider_toml = r#"
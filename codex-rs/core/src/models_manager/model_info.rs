@@ -13,7 +13,10 @@ use codex_protocol::openai_models::default_input_modalities;
 
 use crate::config::Config;
 use crate::features::Feature;
+use crate::features::Features;
+use crate::truncate::TokenCounter;
 use crate::truncate::approx_bytes_for_tokens;
+use codex_utils_tokenizer::EncodingKind;
 use tracing::warn;
 
 pub const BASE_INSTRUCTIONS: &str = include_str!("../../prompt.md");
@@ -323,6 +326,44 @@ pub(crate) fn find_model_info_for_slug(slug: &str) -> ModelInfo {
     }
 }
 
+/// Selects the BPE vocabulary used to count tokens for `slug`.
+///
+/// Returns `None` for models whose tokenizer is unknown (for example local OSS
+/// providers); callers then fall back to the byte-based heuristic.
+pub(crate) fn tokenizer_encoding_for_slug(slug: &str) -> Option<EncodingKind> {
+    let slug = slug.strip_prefix("openai/").unwrap_or(slug);
+    const O200K_PREFIXES: &[&str] = &[
+        "gpt-5",
+        "gpt-4o",
+        "gpt-4.1",
+        "gpt-oss",
+        "o1",
+        "o3",
+        "o4",
+        "codex-",
+        "test-gpt-5",
+    ];
+    if O200K_PREFIXES.iter().any(|prefix| slug.starts_with(prefix)) {
+        Some(EncodingKind::O200kBase)
+    } else if slug.starts_with("gpt-4") || slug.starts_with("gpt-3.5") {
+        Some(EncodingKind::Cl100kBase)
+    } else {
+        None
+    }
+}
+
+/// Builds the token counter for a turn running `model`.
+///
+/// Tokenizer accounting is on by default; disabling
+/// `Feature::TokenizerContextAccounting` makes every model use the byte-based
+/// heuristic.
+pub(crate) fn token_counter_for_model(model: &ModelInfo, features: &Features) -> TokenCounter {
+    if !features.enabled(Feature::TokenizerContextAccounting) {
+        return TokenCounter::default();
+    }
+    TokenCounter::new(tokenizer_encoding_for_slug(&model.slug))
+}
+
 fn supported_reasoning_level_low_medium_high() -> Vec<ReasoningEffortPreset> {
     vec![
         ReasoningEffortPreset {
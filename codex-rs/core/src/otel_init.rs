@@ -9,6 +9,7 @@ use codex_otel::config::OtelSettings;
 use codex_otel::config::OtelTlsConfig as OtelTlsSettings;
 use codex_otel::otel_provider::OtelProvider;
 use std::error::Error;
+use std::net::SocketAddr;
 
 /// Build an OpenTelemetry provider from the app Config.
 ///
@@ -18,6 +19,49 @@ pub fn build_provider(
     service_version: &str,
     service_name_override: Option<&str>,
     default_analytics_enabled: bool,
+) -> Result<Option<OtelProvider>, Box<dyn Error>> {
+    build_provider_impl(
+        config,
+        service_version,
+        service_name_override,
+        default_analytics_enabled,
+        None,
+    )
+}
+
+/// Like [`build_provider`], but also serves the Prometheus `/metrics`
+/// endpoint configured by `otel.prometheus_listen`. Only long-running
+/// servers should call this.
+pub fn build_provider_with_prometheus(
+    config: &Config,
+    service_version: &str,
+    service_name_override: Option<&str>,
+    default_analytics_enabled: bool,
+) -> Result<Option<OtelProvider>, Box<dyn Error>> {
+    let prometheus_listen = config
+        .otel
+        .prometheus_listen
+        .as_deref()
+        .map(|addr| {
+            addr.parse::<SocketAddr>()
+                .map_err(|err| format!("invalid otel.prometheus_listen `{addr}`: {err}"))
+        })
+        .transpose()?;
+    build_provider_impl(
+        config,
+        service_version,
+        service_name_override,
+        default_analytics_enabled,
+        prometheus_listen,
+    )
+}
+
+fn build_provider_impl(
+    config: &Config,
+    service_version: &str,
+    service_name_override: Option<&str>,
+    default_analytics_enabled: bool,
+    prometheus_listen: Option<SocketAddr>,
 ) -> Result<Option<OtelProvider>, Box<dyn Error>> {
     let to_otel_exporter = |kind: &Kind| match kind {
         Kind::None => OtelExporter::None,
@@ -63,6 +107,9 @@ pub fn build_provider(
                 client_private_key: config.client_private_key.clone(),
             }),
         },
+        Kind::File => OtelExporter::File {
+            directory: config.codex_home.join("otel"),
+        },
     };
 
     let exporter = to_otel_exporter(&config.otel.exporter);
@@ -89,6 +136,7 @@ pub fn build_provider(
         trace_exporter,
         metrics_exporter,
         runtime_metrics,
+        prometheus_listen,
     })
 }
 
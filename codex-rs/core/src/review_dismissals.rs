@@ -0,0 +1,241 @@
+//! Review findings the user dismissed, remembered per reviewed change.
+//!
+//! Stored at `~/.codex/review_dismissals.json`. Each entry pairs a change key
+//! (see [`review_change_key`]) with the fingerprints of the findings dismissed
+//! for it, so re-running a review on the same change hides them again. Only
+//! the most recently touched entries are kept.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_git::diff_working_tree;
+use codex_git::merge_base_with_head;
+use codex_protocol::protocol::ReviewTarget;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::git_info::get_head_commit_hash;
+use crate::git_info::resolve_commit_hash;
+
+/// Filename that stores dismissed review findings inside `~/.codex`.
+const REVIEW_DISMISSALS_FILENAME: &str = "review_dismissals.json";
+
+/// Oldest entries beyond this many changes are dropped on write.
+const MAX_REVIEW_DISMISSAL_CHANGES: usize = 200;
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct ReviewDismissals {
+    #[serde(default)]
+    changes: Vec<DismissedChange>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct DismissedChange {
+    change: String,
+    fingerprints: Vec<String>,
+}
+
+fn dismissals_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(REVIEW_DISMISSALS_FILENAME)
+}
+
+/// Identifies the change a review of `target` looks at, so dismissals carry
+/// over between runs on the same code. Working-tree reviews are keyed by the
+/// `HEAD` commit they sit on and a hash of their diff, so editing the code
+/// under review starts over.
+///
+/// Returns `None` when the change cannot be pinned down (for example outside
+/// a git repository); dismissals then only last for the session.
+pub async fn review_change_key(target: &ReviewTarget, cwd: &Path) -> Option<String> {
+    match target {
+        ReviewTarget::Commit { sha, .. } => resolve_commit_hash(cwd, sha).await,
+        ReviewTarget::CommitRange { base, head } => {
+            let base = resolve_commit_hash(cwd, base).await?;
+            let head = resolve_commit_hash(cwd, head).await?;
+            Some(format!("{base}..{head}"))
+        }
+        ReviewTarget::PatchFile { path } => {
+            let patch = tokio::fs::read(cwd.join(path)).await.ok()?;
+            Some(format!("patch:{}", sha256_hex(&patch)))
+        }
+        ReviewTarget::UncommittedChanges
+        | ReviewTarget::BaseBranch { .. }
+        | ReviewTarget::Paths { .. }
+        | ReviewTarget::Custom { .. } => {
+            let head = get_head_commit_hash(cwd).await?;
+            let diff = working_tree_diff(target.clone(), cwd.to_path_buf()).await?;
+            Some(format!("{head}:{}", sha256_hex(diff.as_bytes())))
+        }
+    }
+}
+
+/// The diff a working-tree review of `target` looks at. Custom reviews have
+/// no scope of their own and use the uncommitted changes.
+async fn working_tree_diff(target: ReviewTarget, cwd: PathBuf) -> Option<String> {
+    tokio::task::spawn_blocking(move || {
+        let (base, paths) = match target {
+            ReviewTarget::BaseBranch { branch } => (Some(branch), Vec::new()),
+            ReviewTarget::Paths { paths, base } => (base, paths),
+            ReviewTarget::UncommittedChanges
+            | ReviewTarget::Custom { .. }
+            | ReviewTarget::Commit { .. }
+            | ReviewTarget::CommitRange { .. }
+            | ReviewTarget::PatchFile { .. } => (None, Vec::new()),
+        };
+        let base = base.map(|base| {
+            merge_base_with_head(&cwd, &base)
+                .ok()
+                .flatten()
+                .unwrap_or(base)
+        });
+        diff_working_tree(&cwd, base.as_deref(), &paths).ok()
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Fingerprints of the findings dismissed for `change_key`.
+pub fn load_dismissed_findings(codex_home: &Path, change_key: &str) -> HashSet<String> {
+    read_dismissals(codex_home)
+        .changes
+        .into_iter()
+        .find(|entry| entry.change == change_key)
+        .map(|entry| entry.fingerprints.into_iter().collect())
+        .unwrap_or_default()
+}
+
+/// Records `fingerprint` as dismissed for `change_key`.
+pub fn dismiss_review_finding(
+    codex_home: &Path,
+    change_key: &str,
+    fingerprint: &str,
+) -> io::Result<()> {
+    let mut dismissals = read_dismissals(codex_home);
+    let mut entry = match dismissals
+        .changes
+        .iter()
+        .position(|entry| entry.change == change_key)
+    {
+        Some(idx) => dismissals.changes.remove(idx),
+        None => DismissedChange {
+            change: change_key.to_string(),
+            fingerprints: Vec::new(),
+        },
+    };
+    if !entry.fingerprints.iter().any(|fp| fp == fingerprint) {
+        entry.fingerprints.push(fingerprint.to_string());
+    }
+    // Most recently touched entries live at the end.
+    dismissals.changes.push(entry);
+    let overflow = dismissals
+        .changes
+        .len()
+        .saturating_sub(MAX_REVIEW_DISMISSAL_CHANGES);
+    dismissals.changes.drain(..overflow);
+
+    let json = serde_json::to_string_pretty(&dismissals).map_err(io::Error::other)?;
+    std::fs::create_dir_all(codex_home)?;
+    std::fs::write(dismissals_path(codex_home), json)
+}
+
+/// A missing or unreadable file is treated as empty; dismissals are a
+/// convenience and must never block a review.
+fn read_dismissals(codex_home: &Path) -> ReviewDismissals {
+    let Ok(contents) = std::fs::read_to_string(dismissals_path(codex_home)) else {
+        return ReviewDismissals::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|err| {
+        tracing::warn!("ignoring malformed {REVIEW_DISMISSALS_FILENAME}: {err}");
+        ReviewDismissals::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn dismissals_are_scoped_to_their_change() {
+        let home = tempfile::tempdir().expect("tempdir");
+
+        dismiss_review_finding(home.path(), "abc", "fp1").expect("dismiss");
+        dismiss_review_finding(home.path(), "abc", "fp1").expect("dismiss again");
+        dismiss_review_finding(home.path(), "def", "fp2").expect("dismiss");
+
+        assert_eq!(
+            load_dismissed_findings(home.path(), "abc"),
+            HashSet::from(["fp1".to_string()])
+        );
+        assert_eq!(
+            load_dismissed_findings(home.path(), "def"),
+            HashSet::from(["fp2".to_string()])
+        );
+        assert!(load_dismissed_findings(home.path(), "other").is_empty());
+    }
+
+    #[tokio::test]
+    async fn working_tree_keys_change_with_the_diff() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .current_dir(repo.path())
+                .args(args)
+                .status()
+                .expect("git");
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        std::fs::write(repo.path().join("lib.rs"), "fn a() {}\n").expect("write");
+        git(&["add", "-A"]);
+        git(&[
+            "-c",
+            "user.name=Tester",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-q",
+            "-m",
+            "init",
+        ]);
+
+        let target = ReviewTarget::UncommittedChanges;
+        std::fs::write(repo.path().join("lib.rs"), "fn b() {}\n").expect("write");
+        let first = review_change_key(&target, repo.path()).await.expect("key");
+        assert_eq!(
+            review_change_key(&target, repo.path()).await,
+            Some(first.clone())
+        );
+
+        std::fs::write(repo.path().join("lib.rs"), "fn c() {}\n").expect("write");
+        let second = review_change_key(&target, repo.path()).await.expect("key");
+        assert_ne!(first, second);
+        assert_eq!(first.split(':').next(), second.split(':').next());
+    }
+
+    #[test]
+    fn keeps_only_recent_changes() {
+        let home = tempfile::tempdir().expect("tempdir");
+        for idx in 0..=MAX_REVIEW_DISMISSAL_CHANGES {
+            dismiss_review_finding(home.path(), &format!("change-{idx}"), "fp").expect("dismiss");
+        }
+
+        assert!(load_dismissed_findings(home.path(), "change-0").is_empty());
+        assert_eq!(
+            read_dismissals(home.path()).changes.len(),
+            MAX_REVIEW_DISMISSAL_CHANGES
+        );
+    }
+}
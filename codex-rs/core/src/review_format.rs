@@ -1,3 +1,8 @@
+use std::path::Path;
+
+use sha2::Digest;
+use sha2::Sha256;
+
 use crate::protocol::ReviewFinding;
 use crate::protocol::ReviewOutputEvent;
 
@@ -57,6 +62,50 @@ pub fn format_review_findings_block(
     lines.join("\n")
 }
 
+/// Returns `path` relative to `root` with `/` separators, or `path` itself
+/// when it lies outside `root`.
+pub fn relative_finding_path(path: &Path, root: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Stable identifier for a finding, derived from its repository-relative
+/// path and its title (without the `[P1]` tag), so the same finding reported
+/// on a later run matches even if its lines moved.
+pub fn review_finding_fingerprint(relative_path: &str, title: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(relative_path.as_bytes());
+    hasher.update([0]);
+    hasher.update(normalize_finding_title(title).as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Drops the leading `[P0]`..`[P3]` tag and normalizes case and whitespace,
+/// since the model may re-rank or re-word a finding slightly between runs.
+fn normalize_finding_title(title: &str) -> String {
+    let title = title.trim();
+    let title = match title
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+    {
+        Some((tag, rest)) if tag.starts_with('P') => rest,
+        _ => title,
+    };
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 /// Render a human-readable review summary suitable for a user-facing message.
 ///
 /// Returns either the explanation, the formatted findings block, or both
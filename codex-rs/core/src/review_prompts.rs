@@ -1,3 +1,6 @@
+use codex_git::diff_commit_range;
+use codex_git::diff_working_tree;
+use codex_git::extract_paths_from_patch;
 use codex_git::merge_base_with_head;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
@@ -19,6 +22,17 @@ const COMMIT_PROMPT_WITH_TITLE: &str = "Review the code changes introduced by co
 const COMMIT_PROMPT: &str =
     "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings.";
 
+const COMMIT_RANGE_PROMPT: &str = "Review the combined code changes in the commit range {base}..{head}. Run `git log {range}` to see the individual commits and `git diff {range}` to inspect the changes. Provide prioritized, actionable findings.";
+
+const PATHS_PROMPT: &str = "Review the current code changes (staged and unstaged) to {paths}. Run `git diff HEAD -- {paths}` to inspect them and do not report issues outside these paths. Provide prioritized, actionable findings.";
+const PATHS_PROMPT_WITH_BASE: &str = "Review the code changes to {paths} against '{base}'. Run `git diff {baseSha} -- {paths}` to inspect them and do not report issues outside these paths. Provide prioritized, actionable findings.";
+
+const PATCH_FILE_PROMPT: &str = "Review the changes in the patch file {path}. The patch is not applied to this checkout; read the current sources where you need more context. It touches: {files}. Provide prioritized, actionable findings.";
+
+/// Diffs larger than this are not inlined into the prompt; the reviewer is
+/// left to produce them with the commands in the prompt instead.
+const MAX_INLINE_DIFF_BYTES: usize = 100_000;
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &Path,
@@ -57,6 +71,59 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
                 Ok(COMMIT_PROMPT.replace("{sha}", sha))
             }
         }
+        ReviewTarget::CommitRange { base, head } => {
+            let range = shell_quoted_rev(&format!("{base}..{head}"))?;
+            let prompt = COMMIT_RANGE_PROMPT
+                .replace("{range}", &range)
+                .replace("{base}", base)
+                .replace("{head}", head);
+            let diff = diff_commit_range(cwd, base, head, &[])?;
+            Ok(with_inline_diff(prompt, &diff))
+        }
+        ReviewTarget::Paths { paths, base } => {
+            if paths.is_empty() {
+                anyhow::bail!("审查路径不能为空");
+            }
+            let joined = shell_quoted_paths(paths)?;
+            match base {
+                Some(base) => {
+                    let base_sha = merge_base_with_head(cwd, base)?.unwrap_or_else(|| base.clone());
+                    let prompt = PATHS_PROMPT_WITH_BASE
+                        .replace("{paths}", &joined)
+                        .replace("{baseSha}", &shell_quoted_rev(&base_sha)?)
+                        .replace("{base}", base);
+                    let diff = diff_working_tree(cwd, Some(&base_sha), paths)?;
+                    Ok(with_inline_diff(prompt, &diff))
+                }
+                None => {
+                    let prompt = PATHS_PROMPT.replace("{paths}", &joined);
+                    let diff = diff_working_tree(cwd, None, paths)?;
+                    Ok(with_inline_diff(prompt, &diff))
+                }
+            }
+        }
+        ReviewTarget::PatchFile { path } => {
+            let patch = std::fs::read_to_string(cwd.join(path))
+                .map_err(|err| anyhow::anyhow!("读取补丁文件 {} 失败：{err}", path.display()))?;
+            let mut files = extract_paths_from_patch(&patch);
+            if files.is_empty() {
+                // Plain unified diffs have no `diff --git` headers.
+                files = patch
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("+++ "))
+                    .map(|file| file.split('\t').next().unwrap_or(file))
+                    .filter(|file| *file != "/dev/null")
+                    .map(|file| file.strip_prefix("b/").unwrap_or(file).to_string())
+                    .collect();
+            }
+            if files.is_empty() {
+                anyhow::bail!("补丁文件 {} 中没有找到任何改动", path.display());
+            }
+            let prompt = PATCH_FILE_PROMPT
+                .replace("{path}", &path.display().to_string())
+                .replace("{files}", &files.join(", "));
+            Ok(with_inline_diff(prompt, &patch))
+        }
         ReviewTarget::Custom { instructions } => {
             let prompt = instructions.trim();
             if prompt.is_empty() {
@@ -67,6 +134,29 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
     }
 }
 
+/// Joins `paths` for the `git diff` command in the prompt, quoting any that
+/// the shell would otherwise split or expand.
+fn shell_quoted_paths(paths: &[String]) -> anyhow::Result<String> {
+    shlex::try_join(paths.iter().map(String::as_str))
+        .map_err(|err| anyhow::anyhow!("审查路径无效：{err}"))
+}
+
+/// Quotes a revision or range for the `git` commands in the prompt, since it
+/// comes from the user as typed.
+fn shell_quoted_rev(rev: &str) -> anyhow::Result<String> {
+    shlex::try_join([rev]).map_err(|err| anyhow::anyhow!("无效的提交引用：{err}"))
+}
+
+/// Appends `diff` to `prompt` in a fenced block when it is small enough to
+/// save the reviewer a round of tool calls.
+fn with_inline_diff(prompt: String, diff: &str) -> String {
+    let diff = diff.trim_end();
+    if diff.is_empty() || diff.len() > MAX_INLINE_DIFF_BYTES {
+        return prompt;
+    }
+    format!("{prompt}\n\nThe changes to review:\n\n```diff\n{diff}\n```")
+}
+
 pub fn user_facing_hint(target: &ReviewTarget) -> String {
     match target {
         ReviewTarget::UncommittedChanges => "当前改动".to_string(),
@@ -79,6 +169,15 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
                 format!("提交 {short_sha}")
             }
         }
+        ReviewTarget::CommitRange { base, head } => format!("提交范围 {base}..{head}"),
+        ReviewTarget::Paths { paths, base } => {
+            let paths = paths.join("、");
+            match base {
+                Some(base) => format!("{paths} 针对 '{base}' 的改动"),
+                None => format!("{paths} 的当前改动"),
+            }
+        }
+        ReviewTarget::PatchFile { path } => format!("补丁 {}", path.display()),
         ReviewTarget::Custom { instructions } => instructions.trim().to_string(),
     }
 }
@@ -91,3 +190,69 @@ impl From<ResolvedReviewRequest> for ReviewRequest {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn patch_file_prompt_lists_touched_files_and_inlines_patch() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let patch = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-old\n+new\n";
+        std::fs::write(temp.path().join("fix.patch"), patch).expect("write patch");
+        let target = ReviewTarget::PatchFile {
+            path: PathBuf::from("fix.patch"),
+        };
+
+        let prompt = review_prompt(&target, temp.path()).expect("prompt");
+        assert!(prompt.starts_with(
+            "Review the changes in the patch file fix.patch. The patch is not applied"
+        ));
+        assert!(prompt.contains("It touches: src/lib.rs."));
+        assert!(prompt.ends_with(&format!("```diff\n{}\n```", patch.trim_end())));
+        assert_eq!(user_facing_hint(&target), "补丁 fix.patch");
+    }
+
+    #[test]
+    fn paths_are_quoted_for_the_shell() {
+        assert_eq!(
+            shell_quoted_paths(&[
+                "src/lib.rs".to_string(),
+                "docs/my notes.md".to_string(),
+                "$(touch pwned)".to_string(),
+            ])
+            .expect("quoted"),
+            "src/lib.rs 'docs/my notes.md' '$(touch pwned)'"
+        );
+    }
+
+    #[test]
+    fn revisions_are_quoted_for_the_shell() {
+        assert_eq!(
+            shell_quoted_rev("main..feature/x").expect("quoted"),
+            "main..feature/x"
+        );
+        assert_eq!(
+            shell_quoted_rev("$(touch pwned)..HEAD").expect("quoted"),
+            "'$(touch pwned)..HEAD'"
+        );
+    }
+
+    #[test]
+    fn paths_target_requires_paths() {
+        let target = ReviewTarget::Paths {
+            paths: Vec::new(),
+            base: None,
+        };
+        assert!(review_prompt(&target, Path::new(".")).is_err());
+        assert_eq!(
+            user_facing_hint(&ReviewTarget::Paths {
+                paths: vec!["crates/foo".to_string(), "docs".to_string()],
+                base: Some("main".to_string()),
+            }),
+            "crates/foo、docs 针对 'main' 的改动"
+        );
+    }
+}
@@ -22,6 +22,7 @@ use codex_state::DB_METRIC_BACKFILL_DURATION_MS;
 use codex_state::ExtractionOutcome;
 use codex_state::ThreadMetadataBuilder;
 use codex_state::apply_rollout_item;
+use codex_state::search_documents;
 use std::path::Path;
 use std::path::PathBuf;
 use tracing::info;
@@ -109,6 +110,7 @@ pub(crate) async fn extract_metadata_from_rollout(
     for item in &items {
         apply_rollout_item(&mut metadata, item, default_provider);
     }
+    let search_documents = items.iter().flat_map(search_documents).collect();
     if let Some(updated_at) = file_modified_time_utc(rollout_path).await {
         metadata.updated_at = updated_at;
     }
@@ -124,6 +126,7 @@ pub(crate) async fn extract_metadata_from_rollout(
     Ok(ExtractionOutcome {
         metadata,
         parse_errors,
+        search_documents,
     })
 }
 
@@ -231,6 +234,22 @@ pub(crate) async fn backfill_sessions(
                         warn!("failed to upsert rollout {}: {err}", rollout.path.display());
                     } else {
                         stats.upserted = stats.upserted.saturating_add(1);
+                        if let Err(err) = runtime
+                            .replace_search_documents(metadata.id, &outcome.search_documents)
+                            .await
+                        {
+                            if let Some(otel) = otel {
+                                otel.counter(
+                                    DB_ERROR_METRIC,
+                                    1,
+                                    &[("stage", "backfill_search_documents")],
+                                );
+                            }
+                            warn!(
+                                "failed to index rollout {} for search: {err}",
+                                rollout.path.display()
+                            );
+                        }
                         if let Ok(meta_line) =
                             rollout::list::read_session_meta_line(&rollout.path).await
                         {
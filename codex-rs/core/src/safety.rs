@@ -48,6 +48,20 @@ pub fn assess_patch_safety(
         }
     }
 
+    // Under the overlay, every change to the workspace is shown to the user
+    // before it lands. A patch already is such a change, so it is reviewed up
+    // front and then written to the workspace directly.
+    if matches!(sandbox_policy, SandboxPolicy::WorkspaceOverlay { .. }) {
+        return if policy == AskForApproval::Never {
+            SafetyCheck::Reject {
+                reason: "workspace changes require review; rejected by user approval settings"
+                    .to_string(),
+            }
+        } else {
+            SafetyCheck::AskUser
+        };
+    }
+
     // Even though the patch appears to be constrained to writable paths, it is
     // possible that paths in the patch are hard links to files outside the
     // writable roots, so we should still run `apply_patch` in a sandbox in that case.
@@ -108,7 +122,7 @@ fn is_write_patch_constrained_to_writable_paths(
 ) -> bool {
     // Early‑exit if there are no declared writable roots.
     let writable_roots = match sandbox_policy {
-        SandboxPolicy::ReadOnly => {
+        SandboxPolicy::ReadOnly | SandboxPolicy::WorkspaceOverlay { .. } => {
             return false;
         }
         SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
@@ -251,4 +265,37 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn workspace_overlay_always_reviews_patches() {
+        let tmp = TempDir::new().unwrap();
+        let cwd = tmp.path().to_path_buf();
+        let add_inside = ApplyPatchAction::new_add_for_test(&cwd.join("inner.txt"), "".to_string());
+        let policy = SandboxPolicy::WorkspaceOverlay {
+            network_access: false,
+        };
+
+        for approval_policy in [AskForApproval::OnRequest, AskForApproval::OnFailure] {
+            assert_eq!(
+                assess_patch_safety(
+                    &add_inside,
+                    approval_policy,
+                    &policy,
+                    &cwd,
+                    WindowsSandboxLevel::Disabled
+                ),
+                SafetyCheck::AskUser
+            );
+        }
+        assert!(matches!(
+            assess_patch_safety(
+                &add_inside,
+                AskForApproval::Never,
+                &policy,
+                &cwd,
+                WindowsSandboxLevel::Disabled
+            ),
+            SafetyCheck::Reject { .. }
+        ));
+    }
 }
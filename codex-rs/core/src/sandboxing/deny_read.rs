@@ -0,0 +1,287 @@
+//! Paths that sandboxed commands and the agent's file tools may not read.
+//!
+//! The Linux sandbox masks these paths with empty mounts (or Landlock read
+//! rules on the legacy pipeline, for explicitly configured lists only), and
+//! `read_file`, `list_dir` and
+//! `grep_files` refuse to return their contents so secrets such as SSH keys
+//! or `auth.json` cannot be exfiltrated through the model.
+
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ignore::WalkBuilder;
+
+/// Entries used when `sandbox_deny_read` is not set in `config.toml`.
+pub const DEFAULT_SANDBOX_DENY_READ: &[&str] = &[
+    "~/.ssh",
+    "~/.aws",
+    "~/.config/gh",
+    "~/.gnupg",
+    "~/.netrc",
+    ".env",
+];
+
+/// Files under `CODEX_HOME` that are denied regardless of configuration.
+const CODEX_HOME_CREDENTIAL_FILES: &[&str] = &["auth.json", ".credentials.json"];
+
+/// Upper bound on the entries visited under the cwd when looking for files
+/// that match a name entry such as `.env`.
+const MAX_NAME_SCAN_ENTRIES: usize = 20_000;
+
+/// The deny-read list from `config.toml`, with `~` already expanded.
+///
+/// Entries without a `/` (such as `.env`) are names that match a file or
+/// directory at any depth, like `.gitignore` patterns. Other relative entries
+/// stay relative so they follow the session cwd.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DenyReadPaths {
+    paths: Vec<PathBuf>,
+    names: Vec<OsString>,
+    /// Whether the list comes from `sandbox_deny_read` rather than the
+    /// defaults.
+    configured: bool,
+}
+
+impl DenyReadPaths {
+    /// Builds the list from the configured entries, falling back to
+    /// [`DEFAULT_SANDBOX_DENY_READ`] when none are configured. The credential
+    /// files in `codex_home` are always included.
+    pub fn from_config(entries: Option<&[String]>, codex_home: &Path) -> Self {
+        Self::from_config_with_home(entries, codex_home, dirs::home_dir().as_deref())
+    }
+
+    fn from_config_with_home(
+        entries: Option<&[String]>,
+        codex_home: &Path,
+        home: Option<&Path>,
+    ) -> Self {
+        let configured: Vec<&str> = match entries {
+            Some(entries) => entries.iter().map(String::as_str).collect(),
+            None => DEFAULT_SANDBOX_DENY_READ.to_vec(),
+        };
+        let mut paths: Vec<PathBuf> = Vec::new();
+        let mut names: Vec<OsString> = Vec::new();
+        for entry in configured
+            .into_iter()
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            if is_name_entry(entry) {
+                if !names.iter().any(|name| name == entry) {
+                    names.push(OsString::from(entry));
+                }
+            } else if let Some(path) = expand_home(entry, home) {
+                paths.push(path);
+            }
+        }
+        paths.extend(
+            CODEX_HOME_CREDENTIAL_FILES
+                .iter()
+                .map(|name| codex_home.join(name)),
+        );
+        let mut unique = Vec::with_capacity(paths.len());
+        for path in paths {
+            if !unique.contains(&path) {
+                unique.push(path);
+            }
+        }
+        Self {
+            paths: unique,
+            names,
+            configured: entries.is_some(),
+        }
+    }
+
+    /// Whether `sandbox_deny_read` was set explicitly. The legacy Landlock
+    /// pipeline only enforces explicit lists because it has to turn every
+    /// ancestor of a denied path into a list-only directory.
+    pub fn is_configured(&self) -> bool {
+        self.configured
+    }
+
+    /// Returns the denied paths with relative entries resolved against `cwd`.
+    /// Name entries are not included; see [`Self::existing`].
+    pub fn resolve(&self, cwd: &Path) -> Vec<PathBuf> {
+        self.paths.iter().map(|path| cwd.join(path)).collect()
+    }
+
+    /// Returns the denied paths that exist, resolved against `cwd` and with
+    /// symlinks followed, which is what the sandbox has to mask. Files and
+    /// directories under `cwd` that match a name entry are included too.
+    pub fn existing(&self, cwd: &Path) -> Vec<PathBuf> {
+        let mut existing = Vec::new();
+        for path in self.resolve(cwd).into_iter().chain(self.find_named(cwd)) {
+            if let Ok(path) = path.canonicalize()
+                && !existing.contains(&path)
+            {
+                existing.push(path);
+            }
+        }
+        existing
+    }
+
+    /// Walks `cwd` (without following symlinks or entering `.git`) for
+    /// entries matching a name entry. Matching directories are not entered.
+    fn find_named(&self, cwd: &Path) -> Vec<PathBuf> {
+        if self.names.is_empty() {
+            return Vec::new();
+        }
+        let mut found = Vec::new();
+        let walker = WalkBuilder::new(cwd)
+            .standard_filters(false)
+            .follow_links(false)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .build();
+        for entry in walker.take(MAX_NAME_SCAN_ENTRIES).flatten() {
+            if entry.depth() > 0 && self.names.iter().any(|name| name == entry.file_name()) {
+                found.push(entry.into_path());
+            }
+        }
+        // Sorting puts every directory before its contents, so nested matches
+        // are dropped in favor of the outermost one.
+        found.sort();
+        let mut outermost: Vec<PathBuf> = Vec::new();
+        for path in found {
+            if !outermost.iter().any(|outer| path.starts_with(outer)) {
+                outermost.push(path);
+            }
+        }
+        outermost
+    }
+
+    fn matches_name(&self, path: &Path) -> bool {
+        path.components()
+            .any(|component| self.names.iter().any(|name| name == component.as_os_str()))
+    }
+
+    /// Whether `path` is, or is inside, a denied path. Symlinks are followed
+    /// on both sides so a link into a denied directory is denied too.
+    pub fn is_denied(&self, path: &Path, cwd: &Path) -> bool {
+        let path = cwd.join(path);
+        let canonical = path.canonicalize().ok();
+        if self.matches_name(&path)
+            || canonical
+                .as_deref()
+                .is_some_and(|canonical| self.matches_name(canonical))
+        {
+            return true;
+        }
+        self.resolve(cwd).iter().any(|denied| {
+            path.starts_with(denied)
+                || canonical.as_ref().is_some_and(|canonical| {
+                    canonical.starts_with(denied)
+                        || denied
+                            .canonicalize()
+                            .is_ok_and(|denied| canonical.starts_with(denied))
+                })
+        })
+    }
+}
+
+/// Whether `entry` names a file or directory at any depth rather than a path.
+fn is_name_entry(entry: &str) -> bool {
+    !entry.contains('/') && entry != "~" && entry != "." && entry != ".."
+}
+
+fn expand_home(entry: &str, home: Option<&Path>) -> Option<PathBuf> {
+    if entry == "~" {
+        return home.map(Path::to_path_buf);
+    }
+    match entry.strip_prefix("~/") {
+        Some(rest) => home.map(|home| home.join(rest)),
+        None => Some(PathBuf::from(entry)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn defaults_expand_home_and_always_include_codex_credentials() {
+        let deny_read = DenyReadPaths::from_config_with_home(
+            None,
+            Path::new("/home/user/.codex"),
+            Some(Path::new("/home/user")),
+        );
+        assert_eq!(
+            deny_read.resolve(Path::new("/repo")),
+            vec![
+                PathBuf::from("/home/user/.ssh"),
+                PathBuf::from("/home/user/.aws"),
+                PathBuf::from("/home/user/.config/gh"),
+                PathBuf::from("/home/user/.gnupg"),
+                PathBuf::from("/home/user/.netrc"),
+                PathBuf::from("/home/user/.codex/auth.json"),
+                PathBuf::from("/home/user/.codex/.credentials.json"),
+            ]
+        );
+        assert!(!deny_read.is_configured());
+        assert!(deny_read.is_denied(Path::new("app/config/.env"), Path::new("/repo")));
+
+        let configured = vec!["~/secrets".to_string(), " ".to_string()];
+        let deny_read = DenyReadPaths::from_config_with_home(
+            Some(&configured),
+            Path::new("/home/user/.codex"),
+            Some(Path::new("/home/user")),
+        );
+        assert_eq!(
+            deny_read.resolve(Path::new("/repo")),
+            vec![
+                PathBuf::from("/home/user/secrets"),
+                PathBuf::from("/home/user/.codex/auth.json"),
+                PathBuf::from("/home/user/.codex/.credentials.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn name_entries_match_at_any_depth_under_cwd() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(tmp.path().join("app/nested")).expect("mkdir");
+        std::fs::write(tmp.path().join(".env"), "A=1").expect("write");
+        std::fs::write(tmp.path().join("app/nested/.env"), "B=2").expect("write");
+        std::fs::write(tmp.path().join("app/.env.example"), "B=").expect("write");
+        let configured = vec![".env".to_string()];
+        let deny_read = DenyReadPaths::from_config_with_home(
+            Some(&configured),
+            Path::new("/nonexistent/.codex"),
+            None,
+        );
+
+        assert!(deny_read.is_configured());
+        assert!(deny_read.is_denied(Path::new("app/nested/.env"), tmp.path()));
+        assert!(!deny_read.is_denied(Path::new("app/.env.example"), tmp.path()));
+        let root = tmp.path().canonicalize().expect("canonicalize");
+        assert_eq!(
+            deny_read.existing(tmp.path()),
+            vec![root.join(".env"), root.join("app/nested/.env")]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn denies_paths_inside_and_symlinked_into_denied_directories() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let secrets = tmp.path().join("secrets");
+        std::fs::create_dir(&secrets).expect("mkdir");
+        std::fs::write(secrets.join("key"), "secret").expect("write");
+        std::os::unix::fs::symlink(&secrets, tmp.path().join("link")).expect("symlink");
+        let configured = vec!["secrets".to_string()];
+        let deny_read = DenyReadPaths::from_config_with_home(
+            Some(&configured),
+            Path::new("/nonexistent/.codex"),
+            None,
+        );
+
+        assert!(deny_read.is_denied(&secrets.join("key"), tmp.path()));
+        assert!(deny_read.is_denied(Path::new("link/key"), tmp.path()));
+        assert!(!deny_read.is_denied(Path::new("secrets-not"), tmp.path()));
+        assert_eq!(
+            deny_read.existing(tmp.path()),
+            vec![secrets.canonicalize().expect("canonicalize")]
+        );
+    }
+}
@@ -27,6 +27,9 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
+pub mod deny_read;
+pub mod overlay;
+
 #[derive(Debug)]
 pub struct CommandSpec {
     pub program: String,
@@ -36,6 +39,8 @@ pub struct CommandSpec {
     pub expiration: ExecExpiration,
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
+    /// Data written to the command's stdin; see [`crate::exec::ExecParams`].
+    pub stdin: Option<String>,
 }
 
 #[derive(Debug)]
@@ -49,6 +54,7 @@ pub struct ExecEnv {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    pub stdin: Option<String>,
 }
 
 /// Bundled arguments for sandbox transformation.
@@ -62,6 +68,11 @@ pub(crate) struct SandboxTransformRequest<'a> {
     pub codex_linux_sandbox_exe: Option<&'a PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
     pub windows_sandbox_level: WindowsSandboxLevel,
+    /// Scratch directory of the [`overlay::WorkspaceOverlay`] the command
+    /// writes to under `SandboxPolicy::WorkspaceOverlay`.
+    pub overlay_dir: Option<&'a Path>,
+    /// Existing paths the Linux sandbox masks so the command cannot read them.
+    pub deny_read: &'a [PathBuf],
 }
 
 pub enum SandboxPreference {
@@ -127,6 +138,8 @@ impl SandboxManager {
             codex_linux_sandbox_exe,
             use_linux_sandbox_bwrap,
             windows_sandbox_level,
+            overlay_dir,
+            deny_read,
         } = request;
         let mut env = spec.env;
         if !policy.has_full_network_access() {
@@ -163,6 +176,8 @@ impl SandboxManager {
                     policy,
                     sandbox_policy_cwd,
                     use_linux_sandbox_bwrap,
+                    overlay_dir,
+                    deny_read,
                 );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
@@ -195,6 +210,7 @@ impl SandboxManager {
             sandbox_permissions: spec.sandbox_permissions,
             justification: spec.justification,
             arg0: arg0_override,
+            stdin: spec.stdin,
         })
     }
 
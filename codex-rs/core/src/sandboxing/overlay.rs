@@ -0,0 +1,571 @@
+//! Copy-on-write workspace overlay used by [`SandboxPolicy::WorkspaceOverlay`].
+//!
+//! Each command gets a scratch directory whose `upper` layer receives every
+//! write the command makes to the workspace (`codex-linux-sandbox` mounts it
+//! with overlayfs). Once the command exits, the upper layer is compared with
+//! the workspace to find the files that were added, modified or deleted. The
+//! user reviews those changes and only the accepted ones are copied into the
+//! workspace; the rest are dropped together with the scratch directory.
+//!
+//! [`SandboxPolicy::WorkspaceOverlay`]: crate::protocol::SandboxPolicy::WorkspaceOverlay
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::ReviewDecision;
+use tempfile::TempDir;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+
+/// Files larger than this are summarized instead of being shown in the review.
+const MAX_REVIEW_BYTES: u64 = 256 * 1024;
+
+#[derive(Debug)]
+pub struct WorkspaceOverlay {
+    scratch: TempDir,
+    workspace: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlayChange {
+    /// Absolute path of the changed file in the workspace.
+    pub path: PathBuf,
+    pub kind: OverlayChangeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+impl WorkspaceOverlay {
+    /// Creates the scratch directory for one command. It lives in the system
+    /// temp dir because overlayfs refuses upper dirs inside the lower one.
+    pub fn new(workspace: &Path) -> io::Result<Self> {
+        let scratch = tempfile::Builder::new()
+            .prefix("codex-overlay-")
+            .tempdir()?;
+        Ok(Self {
+            scratch,
+            workspace: workspace.to_path_buf(),
+        })
+    }
+
+    /// Directory passed to `codex-linux-sandbox` as `--overlay-dir`.
+    pub fn dir(&self) -> &Path {
+        self.scratch.path()
+    }
+
+    pub fn workspace(&self) -> &Path {
+        &self.workspace
+    }
+
+    fn upper_path(&self, path: &Path) -> Option<PathBuf> {
+        let relative = path.strip_prefix(&self.workspace).ok()?;
+        Some(self.dir().join("upper").join(relative))
+    }
+
+    /// Lists the files the command changed, sorted by path.
+    pub fn changes(&self) -> io::Result<Vec<OverlayChange>> {
+        let mut changes = Vec::new();
+        let upper = self.dir().join("upper");
+        if upper.is_dir() {
+            self.collect_changes(&upper, Path::new(""), false, &mut changes)?;
+        }
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        changes.dedup();
+        Ok(changes)
+    }
+
+    fn collect_changes(
+        &self,
+        upper_dir: &Path,
+        relative: &Path,
+        parent_opaque: bool,
+        changes: &mut Vec<OverlayChange>,
+    ) -> io::Result<()> {
+        let lower_dir = self.workspace.join(relative);
+        // An opaque directory was removed and recreated by the command, so
+        // nothing below it in the workspace survives unless the upper layer
+        // has it again.
+        let opaque = parent_opaque || (!relative.as_os_str().is_empty() && is_opaque(upper_dir));
+        if opaque && lower_dir.is_dir() {
+            for entry in fs::read_dir(&lower_dir)? {
+                let entry = entry?;
+                if fs::symlink_metadata(upper_dir.join(entry.file_name())).is_err() {
+                    push_deleted(&entry.path(), changes)?;
+                }
+            }
+        }
+
+        for entry in fs::read_dir(upper_dir)? {
+            let entry = entry?;
+            let upper_path = entry.path();
+            let relative = relative.join(entry.file_name());
+            let lower_path = self.workspace.join(&relative);
+            let upper_meta = fs::symlink_metadata(&upper_path)?;
+            let lower_meta = fs::symlink_metadata(&lower_path).ok();
+
+            if is_whiteout(&upper_path, &upper_meta) {
+                if lower_meta.is_some() {
+                    push_deleted(&lower_path, changes)?;
+                }
+                continue;
+            }
+
+            match lower_meta {
+                Some(lower_meta) if lower_meta.is_dir() && !upper_meta.is_dir() => {
+                    push_deleted(&lower_path, changes)?;
+                }
+                Some(lower_meta) if !lower_meta.is_dir() && upper_meta.is_dir() => {
+                    push_deleted(&lower_path, changes)?;
+                }
+                _ => {}
+            }
+
+            if upper_meta.is_dir() {
+                self.collect_changes(&upper_path, &relative, opaque, changes)?;
+                continue;
+            }
+
+            let kind = match fs::symlink_metadata(&lower_path) {
+                Ok(lower_meta) if !lower_meta.is_dir() => {
+                    if same_file_contents(&upper_path, &lower_path)? {
+                        continue;
+                    }
+                    OverlayChangeKind::Modified
+                }
+                _ => OverlayChangeKind::Added,
+            };
+            changes.push(OverlayChange {
+                path: lower_path,
+                kind,
+            });
+        }
+        Ok(())
+    }
+
+    /// Describes `change` the same way patch approvals do so clients can
+    /// render it with their existing diff views.
+    pub fn file_change(&self, change: &OverlayChange) -> FileChange {
+        let upper = self.upper_path(&change.path);
+        match change.kind {
+            OverlayChangeKind::Added => FileChange::Add {
+                content: upper.map(|path| review_text(&path)).unwrap_or_default(),
+            },
+            OverlayChangeKind::Deleted => FileChange::Delete {
+                content: review_text(&change.path),
+            },
+            OverlayChangeKind::Modified => {
+                let old = review_text(&change.path);
+                let new = upper.map(|path| review_text(&path)).unwrap_or_default();
+                let unified_diff = similar::TextDiff::from_lines(&old, &new)
+                    .unified_diff()
+                    .context_radius(1)
+                    .to_string();
+                FileChange::Update {
+                    unified_diff,
+                    move_path: None,
+                }
+            }
+        }
+    }
+
+    /// Copies `change` from the upper layer into the workspace.
+    pub fn apply(&self, change: &OverlayChange) -> io::Result<()> {
+        match change.kind {
+            OverlayChangeKind::Deleted => match fs::symlink_metadata(&change.path) {
+                Ok(_) => fs::remove_file(&change.path),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+                Err(err) => Err(err),
+            },
+            OverlayChangeKind::Added | OverlayChangeKind::Modified => {
+                let upper = self.upper_path(&change.path).ok_or_else(|| {
+                    io::Error::other(format!(
+                        "{} is outside of the workspace",
+                        change.path.display()
+                    ))
+                })?;
+                if let Some(parent) = change.path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let upper_meta = fs::symlink_metadata(&upper)?;
+                if let Ok(lower_meta) = fs::symlink_metadata(&change.path) {
+                    if lower_meta.is_dir() {
+                        return Err(io::Error::other(format!(
+                            "{} is a directory in the workspace",
+                            change.path.display()
+                        )));
+                    }
+                    if upper_meta.file_type().is_symlink() || lower_meta.file_type().is_symlink() {
+                        fs::remove_file(&change.path)?;
+                    }
+                }
+                if upper_meta.file_type().is_symlink() {
+                    copy_symlink(&upper, &change.path)
+                } else {
+                    fs::copy(&upper, &change.path).map(|_| ())
+                }
+            }
+        }
+    }
+}
+
+/// Shows the user the changes a command made in `overlay`, copies the
+/// accepted ones into the workspace and returns a note for the model that
+/// lists what happened to each file. Returns `None` when nothing changed.
+pub(crate) async fn review_overlay_changes(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    overlay: &WorkspaceOverlay,
+) -> Option<String> {
+    let changes = match overlay.changes() {
+        Ok(changes) => changes,
+        Err(err) => {
+            return Some(format!(
+                "Could not read the changes made in the workspace overlay; they were discarded: {err}"
+            ));
+        }
+    };
+    if changes.is_empty() {
+        return None;
+    }
+
+    if turn.approval_policy == AskForApproval::Never {
+        let discarded = changes
+            .iter()
+            .map(|change| change.path.as_path())
+            .collect::<Vec<_>>();
+        let message = format!(
+            "The workspace overlay needs approval to apply changes, but the approval policy is `never`; discarded {}: {}",
+            plural_files(changes.len()),
+            join_paths(overlay, &discarded)
+        );
+        session
+            .send_event(
+                turn,
+                EventMsg::Error(ErrorEvent {
+                    message: message.clone(),
+                    codex_error_info: Some(CodexErrorInfo::Other),
+                }),
+            )
+            .await;
+        return Some(message);
+    }
+
+    let accepted = {
+        let reason = format!(
+            "The command changed {} in the workspace overlay.",
+            plural_files(changes.len())
+        );
+        match request_review(session, turn, call_id, overlay, &changes, reason).await {
+            ReviewDecision::Approved | ReviewDecision::ApprovedExecpolicyAmendment { .. } => {
+                changes.iter().collect()
+            }
+            ReviewDecision::ReviewEachFile if changes.len() > 1 => {
+                let mut accepted = Vec::new();
+                for (index, change) in changes.iter().enumerate() {
+                    let reason = format!(
+                        "Change {} of {} made in the workspace overlay.",
+                        index + 1,
+                        changes.len()
+                    );
+                    match request_review(
+                        session,
+                        turn,
+                        call_id,
+                        overlay,
+                        std::slice::from_ref(change),
+                        reason,
+                    )
+                    .await
+                    {
+                        ReviewDecision::Approved
+                        | ReviewDecision::ApprovedForSession
+                        | ReviewDecision::ApprovedExecpolicyAmendment { .. } => {
+                            accepted.push(change);
+                        }
+                        ReviewDecision::Denied | ReviewDecision::ReviewEachFile => {}
+                        ReviewDecision::Abort => break,
+                    }
+                }
+                accepted
+            }
+            ReviewDecision::ApprovedForSession => changes.iter().collect(),
+            ReviewDecision::ReviewEachFile | ReviewDecision::Denied | ReviewDecision::Abort => {
+                Vec::new()
+            }
+        }
+    };
+
+    let mut applied = Vec::new();
+    let mut failed = Vec::new();
+    for change in &accepted {
+        match overlay.apply(change) {
+            Ok(()) => applied.push(change.path.as_path()),
+            Err(err) => failed.push(format!("{}: {err}", display_path(overlay, &change.path))),
+        }
+    }
+    let discarded: Vec<&Path> = changes
+        .iter()
+        .filter(|change| !accepted.iter().any(|accepted| accepted.path == change.path))
+        .map(|change| change.path.as_path())
+        .collect();
+
+    let mut summary = String::from("Changes made in the workspace overlay:");
+    if !applied.is_empty() {
+        summary.push_str("\napplied: ");
+        summary.push_str(&join_paths(overlay, &applied));
+    }
+    if !discarded.is_empty() {
+        summary.push_str("\ndiscarded: ");
+        summary.push_str(&join_paths(overlay, &discarded));
+    }
+    for failure in failed {
+        summary.push_str("\nfailed to apply ");
+        summary.push_str(&failure);
+    }
+    Some(summary)
+}
+
+async fn request_review(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    overlay: &WorkspaceOverlay,
+    changes: &[OverlayChange],
+    reason: String,
+) -> ReviewDecision {
+    let file_changes: HashMap<PathBuf, FileChange> = changes
+        .iter()
+        .map(|change| (change.path.clone(), overlay.file_change(change)))
+        .collect();
+    let rx = session
+        .request_overlay_review(turn, call_id.to_string(), file_changes, Some(reason))
+        .await;
+    rx.await.unwrap_or_default()
+}
+
+fn plural_files(count: usize) -> String {
+    if count == 1 {
+        "1 file".to_string()
+    } else {
+        format!("{count} files")
+    }
+}
+
+fn display_path(overlay: &WorkspaceOverlay, path: &Path) -> String {
+    path.strip_prefix(overlay.workspace())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn join_paths(overlay: &WorkspaceOverlay, paths: &[&Path]) -> String {
+    paths
+        .iter()
+        .map(|path| display_path(overlay, path))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Records every file at or below `path` in the workspace as deleted.
+fn push_deleted(path: &Path, changes: &mut Vec<OverlayChange>) -> io::Result<()> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.is_dir() {
+        for entry in fs::read_dir(path)? {
+            push_deleted(&entry?.path(), changes)?;
+        }
+    } else {
+        changes.push(OverlayChange {
+            path: path.to_path_buf(),
+            kind: OverlayChangeKind::Deleted,
+        });
+    }
+    Ok(())
+}
+
+fn same_file_contents(upper: &Path, lower: &Path) -> io::Result<bool> {
+    let upper_meta = fs::symlink_metadata(upper)?;
+    let lower_meta = fs::symlink_metadata(lower)?;
+    if upper_meta.file_type().is_symlink() || lower_meta.file_type().is_symlink() {
+        return Ok(upper_meta.file_type().is_symlink()
+            && lower_meta.file_type().is_symlink()
+            && fs::read_link(upper)? == fs::read_link(lower)?);
+    }
+    // Overlayfs copies a file up as soon as it is opened for writing or its
+    // metadata changes, so identical bytes and permissions are not a change.
+    if upper_meta.len() != lower_meta.len() || upper_meta.permissions() != lower_meta.permissions()
+    {
+        return Ok(false);
+    }
+    Ok(fs::read(upper)? == fs::read(lower)?)
+}
+
+fn review_text(path: &Path) -> String {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return String::new();
+    };
+    if meta.file_type().is_symlink() {
+        return match fs::read_link(path) {
+            Ok(target) => format!("symlink to {}\n", target.display()),
+            Err(_) => String::new(),
+        };
+    }
+    if meta.len() > MAX_REVIEW_BYTES {
+        return format!("({} bytes, too large to show)\n", meta.len());
+    }
+    match fs::read(path) {
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(err) => format!("(binary file, {} bytes)\n", err.as_bytes().len()),
+        },
+        Err(_) => String::new(),
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(from)?, to)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    fs::copy(from, to).map(|_| ())
+}
+
+/// Overlayfs records deletions as 0/0 character devices, or as files carrying
+/// the `user.overlay.whiteout` xattr on newer kernels.
+#[cfg(target_os = "linux")]
+fn is_whiteout(path: &Path, meta: &fs::Metadata) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::MetadataExt;
+
+    (meta.file_type().is_char_device() && meta.rdev() == 0)
+        || (meta.is_file() && xattr(path, "user.overlay.whiteout").is_some())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_whiteout(_path: &Path, _meta: &fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn is_opaque(path: &Path) -> bool {
+    xattr(path, "user.overlay.opaque").is_some_and(|value| value == b"y")
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_opaque(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn xattr(path: &Path, name: &str) -> Option<Vec<u8>> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let name = CString::new(name).ok()?;
+    let mut value = [0u8; 16];
+    // SAFETY: both strings are NUL-terminated and `value` outlives the call.
+    let len = unsafe {
+        libc::lgetxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_mut_ptr().cast(),
+            value.len(),
+        )
+    };
+    usize::try_from(len).ok().map(|len| value[..len].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn overlay_with_upper(workspace: &Path) -> (WorkspaceOverlay, PathBuf) {
+        let overlay = WorkspaceOverlay::new(workspace).expect("create overlay");
+        let upper = overlay.dir().join("upper");
+        fs::create_dir_all(&upper).expect("create upper");
+        (overlay, upper)
+    }
+
+    #[test]
+    fn lists_added_and_modified_files_but_not_untouched_copies() {
+        let workspace = tempfile::tempdir().expect("workspace");
+        fs::write(workspace.path().join("same.txt"), "same\n").expect("write");
+        fs::write(workspace.path().join("edited.txt"), "old\n").expect("write");
+        let (overlay, upper) = overlay_with_upper(workspace.path());
+        fs::write(upper.join("same.txt"), "same\n").expect("write");
+        fs::write(upper.join("edited.txt"), "new\n").expect("write");
+        fs::create_dir_all(upper.join("src")).expect("mkdir");
+        fs::write(upper.join("src/new.txt"), "hello\n").expect("write");
+
+        assert_eq!(
+            overlay.changes().expect("changes"),
+            vec![
+                OverlayChange {
+                    path: workspace.path().join("edited.txt"),
+                    kind: OverlayChangeKind::Modified,
+                },
+                OverlayChange {
+                    path: workspace.path().join("src/new.txt"),
+                    kind: OverlayChangeKind::Added,
+                },
+            ]
+        );
+        match overlay.file_change(&OverlayChange {
+            path: workspace.path().join("edited.txt"),
+            kind: OverlayChangeKind::Modified,
+        }) {
+            FileChange::Update { unified_diff, .. } => {
+                assert_eq!(unified_diff, "@@ -1 +1 @@\n-old\n+new\n");
+            }
+            other => panic!("expected update, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn applies_only_the_changes_it_is_given() {
+        let workspace = tempfile::tempdir().expect("workspace");
+        fs::write(workspace.path().join("gone.txt"), "bye\n").expect("write");
+        let (overlay, upper) = overlay_with_upper(workspace.path());
+        fs::create_dir_all(upper.join("src")).expect("mkdir");
+        fs::write(upper.join("src/kept.txt"), "kept\n").expect("write");
+        fs::write(upper.join("dropped.txt"), "dropped\n").expect("write");
+
+        overlay
+            .apply(&OverlayChange {
+                path: workspace.path().join("src/kept.txt"),
+                kind: OverlayChangeKind::Added,
+            })
+            .expect("apply add");
+        overlay
+            .apply(&OverlayChange {
+                path: workspace.path().join("gone.txt"),
+                kind: OverlayChangeKind::Deleted,
+            })
+            .expect("apply delete");
+
+        assert_eq!(
+            fs::read_to_string(workspace.path().join("src/kept.txt")).expect("read"),
+            "kept\n"
+        );
+        assert!(!workspace.path().join("gone.txt").exists());
+        assert!(!workspace.path().join("dropped.txt").exists());
+    }
+}
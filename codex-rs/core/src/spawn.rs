@@ -25,6 +25,9 @@ pub const CODEX_SANDBOX_ENV_VAR: &str = "CODEX_SANDBOX";
 #[derive(Debug, Clone, Copy)]
 pub enum StdioPolicy {
     RedirectForShellTool,
+    /// Like [`StdioPolicy::RedirectForShellTool`], but stdin is a pipe the
+    /// caller writes the command's input to.
+    RedirectForShellToolWithStdin,
     Inherit,
 }
 
@@ -66,7 +69,10 @@ pub(crate) async fn spawn_child_async(
 
     #[cfg(unix)]
     unsafe {
-        let detach_from_tty = matches!(stdio_policy, StdioPolicy::RedirectForShellTool);
+        let detach_from_tty = matches!(
+            stdio_policy,
+            StdioPolicy::RedirectForShellTool | StdioPolicy::RedirectForShellToolWithStdin
+        );
         #[cfg(target_os = "linux")]
         let parent_pid = libc::getpid();
         cmd.pre_exec(move || {
@@ -95,6 +101,11 @@ pub(crate) async fn spawn_child_async(
 
             cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
         }
+        StdioPolicy::RedirectForShellToolWithStdin => {
+            cmd.stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+        }
         StdioPolicy::Inherit => {
             // Inherit stdin, stdout, and stderr from the parent process.
             cmd.stdin(Stdio::inherit())
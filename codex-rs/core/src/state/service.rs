@@ -8,10 +8,12 @@ use crate::client::ModelClient;
 use crate::exec_policy::ExecPolicyManager;
 use crate::file_watcher::FileWatcher;
 use crate::hooks::Hooks;
+use crate::lsp::LspManager;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
 use crate::skills::SkillsManager;
 use crate::state_db::StateDbHandle;
+use crate::symbol_index::SymbolIndex;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecProcessManager;
 use codex_otel::OtelManager;
@@ -35,6 +37,10 @@ pub(crate) struct SessionServices {
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
     pub(crate) skills_manager: Arc<SkillsManager>,
     pub(crate) file_watcher: Arc<FileWatcher>,
+    /// Language servers from `[lsp_servers]`, launched on first use.
+    pub(crate) lsp_manager: LspManager,
+    /// Tree-sitter symbol index backing `find_symbol`, built on first use.
+    pub(crate) symbol_index: Arc<SymbolIndex>,
     pub(crate) agent_control: AgentControl,
     pub(crate) state_db: Option<StateDbHandle>,
     /// Session-scoped model client shared across turns.
@@ -3,9 +3,12 @@
 use codex_protocol::models::ResponseItem;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use crate::codex::SessionConfiguration;
+use crate::config::Config;
 use crate::context_manager::ContextManager;
+use crate::external_edits::ExternalEditTracker;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
@@ -26,6 +29,10 @@ pub(crate) struct SessionState {
     pub(crate) initial_context_seeded: bool,
     /// Previous rollout model for one-shot model-switch handling on first turn after resume.
     pub(crate) pending_resume_previous_model: Option<String>,
+    /// Files the agent has read or edited, for reporting concurrent user edits.
+    pub(crate) external_edits: ExternalEditTracker,
+    /// Config reloaded from disk, applied when the next turn starts.
+    pub(crate) pending_config_reload: Option<Arc<Config>>,
 }
 
 impl SessionState {
@@ -41,6 +48,8 @@ impl SessionState {
             mcp_dependency_prompted: HashSet::new(),
             initial_context_seeded: false,
             pending_resume_previous_model: None,
+            external_edits: ExternalEditTracker::default(),
+            pending_config_reload: None,
         }
     }
 
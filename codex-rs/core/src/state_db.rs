@@ -15,6 +15,7 @@ use codex_protocol::protocol::SessionSource;
 use codex_state::DB_METRIC_COMPARE_ERROR;
 pub use codex_state::LogEntry;
 use codex_state::STATE_DB_VERSION;
+pub use codex_state::SearchDocumentKind;
 use codex_state::ThreadMetadataBuilder;
 use serde_json::Value;
 use std::path::Path;
@@ -173,14 +174,7 @@ pub async fn list_thread_ids_db(
     }
 
     let anchor = cursor_to_anchor(cursor);
-    let allowed_sources: Vec<String> = allowed_sources
-        .iter()
-        .map(|value| match serde_json::to_value(value) {
-            Ok(Value::String(s)) => s,
-            Ok(other) => other.to_string(),
-            Err(_) => String::new(),
-        })
-        .collect();
+    let allowed_sources = session_sources_to_strings(allowed_sources);
     let model_providers = model_providers.map(<[String]>::to_vec);
     match ctx
         .list_thread_ids(
@@ -226,14 +220,7 @@ pub async fn list_threads_db(
     }
 
     let anchor = cursor_to_anchor(cursor);
-    let allowed_sources: Vec<String> = allowed_sources
-        .iter()
-        .map(|value| match serde_json::to_value(value) {
-            Ok(Value::String(s)) => s,
-            Ok(other) => other.to_string(),
-            Err(_) => String::new(),
-        })
-        .collect();
+    let allowed_sources = session_sources_to_strings(allowed_sources);
     let model_providers = model_providers.map(<[String]>::to_vec);
     match ctx
         .list_threads(
@@ -257,6 +244,46 @@ pub async fn list_threads_db(
     }
 }
 
+/// Full-text search over indexed session content using SQLite.
+pub async fn search_threads_db(
+    context: Option<&codex_state::StateRuntime>,
+    query: &str,
+    limit: usize,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+    archived: bool,
+) -> Option<Vec<codex_state::ThreadSearchHit>> {
+    let ctx = context?;
+    let allowed_sources = session_sources_to_strings(allowed_sources);
+    match ctx
+        .search_threads(
+            query,
+            limit,
+            allowed_sources.as_slice(),
+            model_providers,
+            archived,
+        )
+        .await
+    {
+        Ok(hits) => Some(hits),
+        Err(err) => {
+            warn!("state db search_threads failed: {err}");
+            None
+        }
+    }
+}
+
+fn session_sources_to_strings(sources: &[SessionSource]) -> Vec<String> {
+    sources
+        .iter()
+        .map(|value| match serde_json::to_value(value) {
+            Ok(Value::String(s)) => s,
+            Ok(other) => other.to_string(),
+            Err(_) => String::new(),
+        })
+        .collect()
+}
+
 /// Look up the rollout path for a thread id using SQLite.
 pub async fn find_rollout_path_by_id(
     context: Option<&codex_state::StateRuntime>,
@@ -410,6 +437,15 @@ pub async fn reconcile_rollout(
         );
         return;
     }
+    if let Err(err) = ctx
+        .replace_search_documents(metadata.id, &outcome.search_documents)
+        .await
+    {
+        warn!(
+            "state db reconcile_rollout search indexing failed {}: {err}",
+            rollout_path.display()
+        );
+    }
     if let Ok(meta_line) = crate::rollout::list::read_session_meta_line(rollout_path).await {
         persist_dynamic_tools(
             Some(ctx),
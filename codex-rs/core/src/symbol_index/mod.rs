@@ -0,0 +1,570 @@
+//! Workspace symbol index backed by tree-sitter.
+//!
+//! Definitions (functions, methods, types, modules, ...) are extracted from
+//! Rust, Python, JavaScript/TypeScript, Go and Java sources. A workspace is
+//! walked on its first lookup, and every directory the walk visits is
+//! watched. Later lookups only re-index the paths the watcher reported since,
+//! so they cost no I/O while nothing changes; the workspace is walked again
+//! only when the watcher dropped events. All lookups are blocking and should
+//! run on a blocking thread.
+
+mod parse;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::SystemTime;
+
+use ignore::Walk;
+use ignore::WalkBuilder;
+use notify::Event;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use parse::SourceLanguage;
+use parse::parse_symbols;
+
+/// Workspaces with more source files than this are only partially indexed.
+const MAX_INDEXED_FILES: usize = 20_000;
+
+/// Files larger than this are skipped; they are usually generated.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Class,
+    Struct,
+    Enum,
+    Interface,
+    Trait,
+    Impl,
+    Module,
+    Type,
+    Constant,
+    Macro,
+}
+
+impl SymbolKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Method => "method",
+            Self::Class => "class",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Interface => "interface",
+            Self::Trait => "trait",
+            Self::Impl => "impl",
+            Self::Module => "module",
+            Self::Type => "type",
+            Self::Constant => "constant",
+            Self::Macro => "macro",
+        }
+    }
+}
+
+impl fmt::Display for SymbolKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A definition found in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Name of the enclosing type, impl block or module, if any.
+    pub container: Option<String>,
+    /// Path relative to the workspace root for index lookups, or the path
+    /// passed to [`outline_file`].
+    pub path: PathBuf,
+    /// One-based, inclusive line range of the whole definition.
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Nesting depth within the file; top-level definitions are 0.
+    pub depth: usize,
+}
+
+#[derive(Default)]
+pub struct SymbolIndex {
+    workspaces: Mutex<HashMap<PathBuf, Arc<Workspace>>>,
+}
+
+struct IndexedFile {
+    len: u64,
+    modified: Option<SystemTime>,
+    symbols: Vec<Symbol>,
+}
+
+/// Index of one workspace root, locked separately so lookups in other
+/// workspaces never wait for its walk.
+struct Workspace {
+    /// Canonical root, so paths reported by the watcher match indexed ones.
+    root: PathBuf,
+    state: Mutex<WorkspaceState>,
+    changes: Arc<Mutex<PendingChanges>>,
+}
+
+struct WorkspaceState {
+    files: HashMap<PathBuf, IndexedFile>,
+    /// Directories the walk descended into. Each one is watched, and changes
+    /// outside of them (ignored directories) are not indexed.
+    dirs: HashSet<PathBuf>,
+    /// `None` when watching failed; every lookup then walks the workspace.
+    watcher: Option<RecommendedWatcher>,
+}
+
+/// Paths the watcher reported since the last lookup.
+struct PendingChanges {
+    paths: HashSet<PathBuf>,
+    /// The workspace has to be walked in full: before the first lookup and
+    /// whenever the watcher dropped events.
+    rescan: bool,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Definitions under `root` whose name matches `query`, best matches
+    /// first: exact, then case-insensitive, then prefix, then substring
+    /// matches. `Container::name` and `Container.name` queries also match the
+    /// enclosing definition.
+    pub fn find(
+        &self,
+        root: &Path,
+        query: &str,
+        kind: Option<SymbolKind>,
+        limit: usize,
+    ) -> Vec<Symbol> {
+        let query = SymbolQuery::new(query);
+        if query.name.is_empty() {
+            return Vec::new();
+        }
+
+        let workspace = {
+            let mut workspaces = self
+                .workspaces
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            Arc::clone(
+                workspaces
+                    .entry(root.to_path_buf())
+                    .or_insert_with(|| Arc::new(Workspace::new(root))),
+            )
+        };
+        let mut state = workspace
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        workspace.update(&mut state);
+
+        let mut matches: Vec<(u8, &Symbol)> = state
+            .files
+            .values()
+            .flat_map(|file| file.symbols.iter())
+            .filter(|symbol| kind.is_none_or(|kind| symbol.kind == kind))
+            .filter_map(|symbol| query.rank(symbol).map(|rank| (rank, symbol)))
+            .collect();
+        matches.sort_by(|(rank_a, a), (rank_b, b)| {
+            rank_a
+                .cmp(rank_b)
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.start_line.cmp(&b.start_line))
+        });
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, symbol)| symbol.clone())
+            .collect()
+    }
+}
+
+impl Workspace {
+    fn new(root: &Path) -> Self {
+        let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let changes = Arc::new(Mutex::new(PendingChanges {
+            paths: HashSet::new(),
+            rescan: true,
+        }));
+        let watcher_changes = Arc::clone(&changes);
+        let watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let mut changes = watcher_changes
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            match event {
+                Ok(event) if event.need_rescan() => changes.rescan = true,
+                Ok(event) if !event.kind.is_access() => changes.paths.extend(event.paths),
+                Ok(_) => {}
+                Err(_) => changes.rescan = true,
+            }
+        });
+        let watcher = match watcher {
+            Ok(watcher) => Some(watcher),
+            Err(err) => {
+                warn!(
+                    "symbol index falls back to walking {}: {err}",
+                    root.display()
+                );
+                None
+            }
+        };
+        Self {
+            root,
+            state: Mutex::new(WorkspaceState {
+                files: HashMap::new(),
+                dirs: HashSet::new(),
+                watcher,
+            }),
+            changes,
+        }
+    }
+
+    /// Re-indexes what changed since the previous lookup.
+    fn update(&self, state: &mut WorkspaceState) {
+        let (paths, rescan) = {
+            let mut changes = self.changes.lock().unwrap_or_else(PoisonError::into_inner);
+            (
+                std::mem::take(&mut changes.paths),
+                std::mem::take(&mut changes.rescan),
+            )
+        };
+        if rescan || state.watcher.is_none() {
+            self.rescan(state);
+            return;
+        }
+        for path in paths {
+            self.apply_change(state, &path);
+        }
+    }
+
+    /// Brings the index in line with the source files currently under the
+    /// root.
+    fn rescan(&self, state: &mut WorkspaceState) {
+        let (dirs, seen) = index_walk(
+            &self.root,
+            WalkBuilder::new(&self.root).build(),
+            &mut state.files,
+        );
+        state.files.retain(|path, _| seen.contains(path));
+        let stale: Vec<PathBuf> = state.dirs.difference(&dirs).cloned().collect();
+        for dir in stale {
+            state.unwatch(&dir);
+        }
+        for dir in &dirs {
+            if !state.dirs.contains(dir) {
+                state.watch(dir);
+            }
+        }
+        state.dirs = dirs;
+    }
+
+    /// Re-indexes `path`, a file or directory the watcher reported.
+    fn apply_change(&self, state: &mut WorkspaceState, path: &Path) {
+        let Some(parent) = path.parent() else {
+            return;
+        };
+        if !state.dirs.contains(parent) {
+            // Inside an ignored directory, or one that is indexed through its
+            // own event.
+            return;
+        }
+        // Listing the parent applies the same ignore rules as the full walk.
+        let listed = path.exists()
+            && WalkBuilder::new(parent)
+                .max_depth(Some(1))
+                .build()
+                .filter_map(Result::ok)
+                .any(|entry| entry.path() == path);
+        if !listed {
+            self.forget(state, path);
+            return;
+        }
+        let (dirs, _) = index_walk(&self.root, WalkBuilder::new(path).build(), &mut state.files);
+        for dir in dirs {
+            if !state.dirs.contains(&dir) {
+                state.watch(&dir);
+                state.dirs.insert(dir);
+            }
+        }
+    }
+
+    /// Drops `path` and everything below it from the index.
+    fn forget(&self, state: &mut WorkspaceState, path: &Path) {
+        state
+            .files
+            .retain(|relative, _| !self.root.join(relative).starts_with(path));
+        let removed: Vec<PathBuf> = state
+            .dirs
+            .iter()
+            .filter(|dir| dir.starts_with(path))
+            .cloned()
+            .collect();
+        for dir in removed {
+            state.unwatch(&dir);
+            state.dirs.remove(&dir);
+        }
+    }
+}
+
+impl WorkspaceState {
+    fn watch(&mut self, dir: &Path) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        if let Err(err) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+            // A directory without a watch would go stale, so walk on every
+            // lookup instead.
+            warn!("symbol index stops watching: {err}");
+            self.watcher = None;
+        }
+    }
+
+    fn unwatch(&mut self, dir: &Path) {
+        if let Some(watcher) = self.watcher.as_mut() {
+            // The watch is already gone when the directory was removed.
+            let _ = watcher.unwatch(dir);
+        }
+    }
+}
+
+/// Definitions in `path` in document order, or `None` when the file's
+/// language is not supported.
+pub fn outline_file(path: &Path) -> std::io::Result<Option<Vec<Symbol>>> {
+    let Some(language) = SourceLanguage::for_path(path) else {
+        return Ok(None);
+    };
+    let source = std::fs::read_to_string(path)?;
+    Ok(Some(parse_symbols(language, path, &source)))
+}
+
+/// Indexes the source files `walk` yields, re-parsing those whose size or
+/// modification time changed. Returns the directories it visited and the
+/// files it saw, relative to `root`.
+fn index_walk(
+    root: &Path,
+    walk: Walk,
+    files: &mut HashMap<PathBuf, IndexedFile>,
+) -> (HashSet<PathBuf>, HashSet<PathBuf>) {
+    let mut dirs = HashSet::new();
+    let mut seen = HashSet::new();
+    for entry in walk.filter_map(Result::ok) {
+        let path = entry.path();
+        let Some(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            dirs.insert(path.to_path_buf());
+            continue;
+        }
+        let Some(language) = SourceLanguage::for_path(path) else {
+            continue;
+        };
+        if !file_type.is_file() {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.len() > MAX_FILE_BYTES {
+            continue;
+        }
+
+        let relative = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+        let modified = metadata.modified().ok();
+        let indexed = files.get(&relative);
+        if indexed.is_none() && files.len() >= MAX_INDEXED_FILES {
+            continue;
+        }
+        seen.insert(relative.clone());
+        if indexed.is_some_and(|file| file.len == metadata.len() && file.modified == modified) {
+            continue;
+        }
+        let symbols = std::fs::read_to_string(path)
+            .map(|source| parse_symbols(language, &relative, &source))
+            .unwrap_or_default();
+        files.insert(
+            relative,
+            IndexedFile {
+                len: metadata.len(),
+                modified,
+                symbols,
+            },
+        );
+    }
+    (dirs, seen)
+}
+
+struct SymbolQuery {
+    container: Option<String>,
+    name: String,
+    name_lower: String,
+}
+
+impl SymbolQuery {
+    fn new(query: &str) -> Self {
+        let query = query.trim();
+        let (container, name) = match query.rsplit_once("::").or_else(|| query.rsplit_once('.')) {
+            Some((container, name)) => (Some(container.to_lowercase()), name),
+            None => (None, query),
+        };
+        Self {
+            container,
+            name: name.to_string(),
+            name_lower: name.to_lowercase(),
+        }
+    }
+
+    /// Lower is better; `None` when `symbol` does not match.
+    fn rank(&self, symbol: &Symbol) -> Option<u8> {
+        if let Some(container) = &self.container
+            && symbol
+                .container
+                .as_ref()
+                .is_none_or(|candidate| candidate.to_lowercase() != *container)
+        {
+            return None;
+        }
+        if symbol.name == self.name {
+            return Some(0);
+        }
+        let name_lower = symbol.name.to_lowercase();
+        if name_lower == self.name_lower {
+            Some(1)
+        } else if name_lower.starts_with(&self.name_lower) {
+            Some(2)
+        } else if name_lower.contains(&self.name_lower) {
+            Some(3)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn names(symbols: &[Symbol]) -> Vec<String> {
+        symbols
+            .iter()
+            .map(|symbol| {
+                format!(
+                    "{}:{} {}",
+                    symbol.path.display(),
+                    symbol.start_line,
+                    symbol.name
+                )
+            })
+            .collect()
+    }
+
+    /// Looks `query` up until the result matches `expected`, giving the
+    /// watcher time to report earlier writes.
+    fn find_eventually(
+        index: &SymbolIndex,
+        root: &Path,
+        query: &str,
+        expected: &[&str],
+    ) -> Vec<String> {
+        let mut found = Vec::new();
+        for _ in 0..100 {
+            found = names(&index.find(root, query, None, 10));
+            if found == expected {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        found
+    }
+
+    #[test]
+    fn find_ranks_matches_and_follows_file_changes() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        std::fs::write(
+            root.path().join("lib.rs"),
+            "fn parse_config() {}\n\nfn parse() {}\n",
+        )?;
+        std::fs::write(root.path().join("app.py"), "def reparse():\n    pass\n")?;
+        std::fs::write(root.path().join("notes.txt"), "fn parse() {}\n")?;
+
+        let index = SymbolIndex::new();
+        assert_eq!(
+            names(&index.find(root.path(), "parse", None, 10)),
+            vec![
+                "lib.rs:3 parse",
+                "lib.rs:1 parse_config",
+                "app.py:1 reparse"
+            ]
+        );
+
+        std::fs::write(root.path().join("lib.rs"), "fn parse_args() {}\n")?;
+        std::fs::remove_file(root.path().join("app.py"))?;
+        assert_eq!(
+            find_eventually(&index, root.path(), "parse", &["lib.rs:1 parse_args"]),
+            vec!["lib.rs:1 parse_args"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_indexes_new_directories_but_not_ignored_ones() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        std::fs::write(root.path().join(".ignore"), "generated/\n")?;
+        std::fs::create_dir(root.path().join("generated"))?;
+        std::fs::write(root.path().join("lib.rs"), "fn first() {}\n")?;
+
+        let index = SymbolIndex::new();
+        assert_eq!(
+            names(&index.find(root.path(), "first", None, 10)),
+            vec!["lib.rs:1 first"]
+        );
+
+        std::fs::write(
+            root.path().join("generated/out.rs"),
+            "fn added_generated() {}\n",
+        )?;
+        std::fs::create_dir(root.path().join("src"))?;
+        std::fs::write(root.path().join("src/mod.rs"), "fn added() {}\n")?;
+        assert_eq!(
+            find_eventually(&index, root.path(), "added", &["src/mod.rs:1 added"]),
+            vec!["src/mod.rs:1 added"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_filters_by_kind_and_container() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        std::fs::write(
+            root.path().join("lib.rs"),
+            "struct Config;\n\nimpl Config {\n    fn new() -> Self { Config }\n}\n\nfn new() {}\n",
+        )?;
+
+        let index = SymbolIndex::new();
+        assert_eq!(
+            names(&index.find(root.path(), "Config::new", None, 10)),
+            vec!["lib.rs:4 new"]
+        );
+        assert_eq!(
+            names(&index.find(root.path(), "Config", Some(SymbolKind::Struct), 10)),
+            vec!["lib.rs:1 Config"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,308 @@
+//! Extracts symbol definitions from source files with tree-sitter grammars.
+
+use std::path::Path;
+
+use tree_sitter::Node;
+use tree_sitter::Parser;
+
+use super::Symbol;
+use super::SymbolKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SourceLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Go,
+    Java,
+}
+
+impl SourceLanguage {
+    pub(super) fn for_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        let language = match extension {
+            "rs" => Self::Rust,
+            "py" | "pyi" => Self::Python,
+            "js" | "jsx" | "mjs" | "cjs" => Self::JavaScript,
+            "ts" | "mts" | "cts" => Self::TypeScript,
+            "tsx" => Self::Tsx,
+            "go" => Self::Go,
+            "java" => Self::Java,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    fn grammar(self) -> tree_sitter::Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::Java => tree_sitter_java::LANGUAGE.into(),
+        }
+    }
+
+    /// Maps a node kind to the symbol it defines and the field holding its
+    /// name. `parent` is the kind of the enclosing symbol, if any.
+    fn definition(
+        self,
+        node: Node<'_>,
+        parent: Option<SymbolKind>,
+    ) -> Option<(SymbolKind, &'static str)> {
+        let in_type = matches!(
+            parent,
+            Some(
+                SymbolKind::Class
+                    | SymbolKind::Impl
+                    | SymbolKind::Trait
+                    | SymbolKind::Interface
+                    | SymbolKind::Enum
+            )
+        );
+        let function = if in_type {
+            SymbolKind::Method
+        } else {
+            SymbolKind::Function
+        };
+        let definition = match (self, node.kind()) {
+            (Self::Rust, "function_item" | "function_signature_item") => (function, "name"),
+            (Self::Rust, "struct_item" | "union_item") => (SymbolKind::Struct, "name"),
+            (Self::Rust, "enum_item") => (SymbolKind::Enum, "name"),
+            (Self::Rust, "trait_item") => (SymbolKind::Trait, "name"),
+            (Self::Rust, "impl_item") => (SymbolKind::Impl, "type"),
+            (Self::Rust, "mod_item") => (SymbolKind::Module, "name"),
+            (Self::Rust, "const_item" | "static_item") => (SymbolKind::Constant, "name"),
+            (Self::Rust, "type_item") => (SymbolKind::Type, "name"),
+            (Self::Rust, "macro_definition") => (SymbolKind::Macro, "name"),
+
+            (Self::Python, "function_definition") => (function, "name"),
+            (Self::Python, "class_definition") => (SymbolKind::Class, "name"),
+
+            (
+                Self::JavaScript | Self::TypeScript | Self::Tsx,
+                "function_declaration" | "generator_function_declaration",
+            ) => (SymbolKind::Function, "name"),
+            (
+                Self::JavaScript | Self::TypeScript | Self::Tsx,
+                "class_declaration" | "abstract_class_declaration",
+            ) => (SymbolKind::Class, "name"),
+            (
+                Self::JavaScript | Self::TypeScript | Self::Tsx,
+                "method_definition" | "method_signature" | "abstract_method_signature",
+            ) => (SymbolKind::Method, "name"),
+            (Self::JavaScript | Self::TypeScript | Self::Tsx, "variable_declarator")
+                if parent.is_none()
+                    && node.child_by_field_name("value").is_some_and(|value| {
+                        matches!(value.kind(), "arrow_function" | "function_expression")
+                    }) =>
+            {
+                (SymbolKind::Function, "name")
+            }
+            (Self::TypeScript | Self::Tsx, "interface_declaration") => {
+                (SymbolKind::Interface, "name")
+            }
+            (Self::TypeScript | Self::Tsx, "type_alias_declaration") => (SymbolKind::Type, "name"),
+            (Self::TypeScript | Self::Tsx, "enum_declaration") => (SymbolKind::Enum, "name"),
+
+            (Self::Go, "function_declaration") => (SymbolKind::Function, "name"),
+            (Self::Go, "method_declaration") => (SymbolKind::Method, "name"),
+            (Self::Go, "type_spec") => {
+                let kind = match node.child_by_field_name("type").map(|ty| ty.kind()) {
+                    Some("struct_type") => SymbolKind::Struct,
+                    Some("interface_type") => SymbolKind::Interface,
+                    _ => SymbolKind::Type,
+                };
+                (kind, "name")
+            }
+
+            (Self::Java, "class_declaration" | "record_declaration") => (SymbolKind::Class, "name"),
+            (Self::Java, "interface_declaration" | "annotation_type_declaration") => {
+                (SymbolKind::Interface, "name")
+            }
+            (Self::Java, "enum_declaration") => (SymbolKind::Enum, "name"),
+            (Self::Java, "method_declaration" | "constructor_declaration") => {
+                (SymbolKind::Method, "name")
+            }
+
+            _ => return None,
+        };
+        Some(definition)
+    }
+}
+
+/// Parses `source` and returns its definitions in document order.
+pub(super) fn parse_symbols(language: SourceLanguage, path: &Path, source: &str) -> Vec<Symbol> {
+    let mut parser = Parser::new();
+    if parser.set_language(&language.grammar()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+    let mut symbols = Vec::new();
+    collect(language, tree.root_node(), source, path, None, &mut symbols);
+    symbols
+}
+
+/// Walks the named children of `node`, appending definitions to `symbols`.
+/// `enclosing` is the index in `symbols` of the innermost enclosing
+/// definition.
+fn collect(
+    language: SourceLanguage,
+    node: Node<'_>,
+    source: &str,
+    path: &Path,
+    enclosing: Option<usize>,
+    symbols: &mut Vec<Symbol>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let parent = enclosing.map(|index| symbols[index].kind);
+        let definition = language
+            .definition(child, parent)
+            .and_then(|(kind, field)| {
+                let name = child
+                    .child_by_field_name(field)?
+                    .utf8_text(source.as_bytes())
+                    .ok()?;
+                Some((kind, name.to_string()))
+            });
+        let Some((kind, name)) = definition else {
+            collect(language, child, source, path, enclosing, symbols);
+            continue;
+        };
+
+        let container = if language == SourceLanguage::Go && kind == SymbolKind::Method {
+            go_receiver_type(child, source)
+        } else {
+            enclosing.map(|index| symbols[index].name.clone())
+        };
+        symbols.push(Symbol {
+            name,
+            kind,
+            container,
+            path: path.to_path_buf(),
+            start_line: child.start_position().row + 1,
+            end_line: child.end_position().row + 1,
+            depth: enclosing.map_or(0, |index| symbols[index].depth + 1),
+        });
+        let index = symbols.len() - 1;
+        collect(language, child, source, path, Some(index), symbols);
+    }
+}
+
+/// The receiver type of a Go method, e.g. `Server` for `func (s *Server) Run()`.
+fn go_receiver_type(method: Node<'_>, source: &str) -> Option<String> {
+    let receiver = method.child_by_field_name("receiver")?;
+    first_type_identifier(receiver)?
+        .utf8_text(source.as_bytes())
+        .ok()
+        .map(str::to_string)
+}
+
+fn first_type_identifier(node: Node<'_>) -> Option<Node<'_>> {
+    if node.kind() == "type_identifier" {
+        return Some(node);
+    }
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .find_map(first_type_identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn outline(
+        language: SourceLanguage,
+        source: &str,
+    ) -> Vec<(SymbolKind, String, Option<String>, usize, usize)> {
+        parse_symbols(language, Path::new("file"), source)
+            .into_iter()
+            .map(|symbol| {
+                (
+                    symbol.kind,
+                    symbol.name,
+                    symbol.container,
+                    symbol.start_line,
+                    symbol.end_line,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rust_impl_methods_are_nested() {
+        let source = "struct Config {\n    path: String,\n}\n\nimpl Config {\n    fn load() -> Self {\n        todo!()\n    }\n}\n\nfn main() {}\n";
+
+        assert_eq!(
+            outline(SourceLanguage::Rust, source),
+            vec![
+                (SymbolKind::Struct, "Config".to_string(), None, 1, 3),
+                (SymbolKind::Impl, "Config".to_string(), None, 5, 9),
+                (
+                    SymbolKind::Method,
+                    "load".to_string(),
+                    Some("Config".to_string()),
+                    6,
+                    8
+                ),
+                (SymbolKind::Function, "main".to_string(), None, 11, 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_and_typescript_definitions() {
+        let python =
+            "class Greeter:\n    def greet(self):\n        pass\n\ndef main():\n    pass\n";
+        assert_eq!(
+            outline(SourceLanguage::Python, python),
+            vec![
+                (SymbolKind::Class, "Greeter".to_string(), None, 1, 3),
+                (
+                    SymbolKind::Method,
+                    "greet".to_string(),
+                    Some("Greeter".to_string()),
+                    2,
+                    3
+                ),
+                (SymbolKind::Function, "main".to_string(), None, 5, 6),
+            ]
+        );
+
+        let typescript = "interface Props {\n  name: string;\n}\n\nexport const render = (props: Props) => props.name;\n";
+        assert_eq!(
+            outline(SourceLanguage::TypeScript, typescript),
+            vec![
+                (SymbolKind::Interface, "Props".to_string(), None, 1, 3),
+                (SymbolKind::Function, "render".to_string(), None, 5, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn go_methods_use_receiver_as_container() {
+        let source = "package main\n\ntype Server struct{}\n\nfunc (s *Server) Run() {}\n";
+
+        assert_eq!(
+            outline(SourceLanguage::Go, source),
+            vec![
+                (SymbolKind::Struct, "Server".to_string(), None, 3, 3),
+                (
+                    SymbolKind::Method,
+                    "Run".to_string(),
+                    Some("Server".to_string()),
+                    5,
+                    5
+                ),
+            ]
+        );
+    }
+}
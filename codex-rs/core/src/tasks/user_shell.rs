@@ -151,6 +151,7 @@ pub(crate) async fn execute_user_shell_command(
         sandbox_permissions: SandboxPermissions::UseDefault,
         justification: None,
         arg0: None,
+        stdin: None,
     };
 
     let stdout_stream = Some(StdoutStream {
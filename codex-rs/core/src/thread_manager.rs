@@ -71,6 +71,8 @@ fn build_file_watcher(codex_home: PathBuf, skills_manager: Arc<SkillsManager>) -
                     Ok(FileWatcherEvent::SkillsChanged { .. }) => {
                         skills_manager.clear_cache();
                     }
+                    Ok(FileWatcherEvent::TrackedFilesChanged { .. })
+                    | Ok(FileWatcherEvent::ConfigChanged { .. }) => {}
                     Err(broadcast::error::RecvError::Closed) => break,
                     Err(broadcast::error::RecvError::Lagged(_)) => continue,
                 }
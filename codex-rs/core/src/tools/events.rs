@@ -366,6 +366,9 @@ async fn emit_exec_stage(
 ) {
     match stage {
         ToolEventStage::Begin => {
+            ctx.session
+                .collect_external_edits_before_agent_command()
+                .await;
             emit_exec_command_begin(
                 ctx,
                 exec_input.command,
@@ -379,6 +382,12 @@ async fn emit_exec_stage(
         }
         ToolEventStage::Success(output)
         | ToolEventStage::Failure(ToolEventFailure::Output(output)) => {
+            ctx.session.resnapshot_tracked_files().await;
+            if output.exit_code == 0 {
+                ctx.session
+                    .track_agent_files(read_paths(exec_input.cwd, exec_input.parsed_cmd))
+                    .await;
+            }
             let exec_result = ExecCommandResult {
                 stdout: output.stdout.text.clone(),
                 stderr: output.stderr.text.clone(),
@@ -390,6 +399,7 @@ async fn emit_exec_stage(
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
         ToolEventStage::Failure(ToolEventFailure::Message(message)) => {
+            ctx.session.resnapshot_tracked_files().await;
             let text = message.to_string();
             let exec_result = ExecCommandResult {
                 stdout: String::new(),
@@ -432,6 +442,34 @@ async fn emit_exec_end(
         .await;
 }
 
+/// Files a successful command read, as far as the parsed command tells.
+fn read_paths(cwd: &Path, parsed_cmd: &[ParsedCommand]) -> Vec<PathBuf> {
+    parsed_cmd
+        .iter()
+        .filter_map(|parsed| match parsed {
+            ParsedCommand::Read { path, .. } => Some(cwd.join(path)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Every path an `apply_patch` call wrote to, including move destinations.
+fn patched_paths(changes: &HashMap<PathBuf, FileChange>) -> Vec<PathBuf> {
+    changes
+        .iter()
+        .flat_map(|(path, change)| {
+            let move_path = match change {
+                FileChange::Update {
+                    move_path: Some(move_path),
+                    ..
+                } => Some(move_path.clone()),
+                _ => None,
+            };
+            std::iter::once(path.clone()).chain(move_path)
+        })
+        .collect()
+}
+
 async fn emit_patch_end(
     ctx: ToolEventCtx<'_>,
     changes: HashMap<PathBuf, FileChange>,
@@ -439,6 +477,7 @@ async fn emit_patch_end(
     stderr: String,
     success: bool,
 ) {
+    let patched_paths = patched_paths(&changes);
     ctx.session
         .send_event(
             ctx.turn,
@@ -452,6 +491,7 @@ async fn emit_patch_end(
             }),
         )
         .await;
+    ctx.session.track_agent_files(patched_paths).await;
 
     if let Some(tracker) = ctx.turn_diff_tracker {
         let unified_diff = {
@@ -127,6 +127,7 @@ impl ToolHandler for ApplyPatchHandler {
                         );
                         emitter.begin(event_ctx).await;
 
+                        let touched_paths = file_paths.clone();
                         let req = ApplyPatchRequest {
                             action: apply.action,
                             file_paths,
@@ -154,6 +155,13 @@ impl ToolHandler for ApplyPatchHandler {
                             Some(&tracker),
                         );
                         let content = emitter.finish(event_ctx, out).await?;
+                        let content = with_lsp_diagnostics(
+                            session.as_ref(),
+                            turn.as_ref(),
+                            &touched_paths,
+                            content,
+                        )
+                        .await;
                         Ok(ToolOutput::Function {
                             body: FunctionCallOutputBody::Text(content),
                             success: Some(true),
@@ -181,6 +189,29 @@ impl ToolHandler for ApplyPatchHandler {
     }
 }
 
+/// Appends language server diagnostics for the files a successful patch
+/// left on disk, so the model sees new errors without running a build.
+async fn with_lsp_diagnostics(
+    session: &Session,
+    turn: &TurnContext,
+    paths: &[AbsolutePathBuf],
+    content: String,
+) -> String {
+    let lsp = &session.services.lsp_manager;
+    if !lsp.is_enabled() {
+        return content;
+    }
+    let paths: Vec<_> = paths
+        .iter()
+        .map(|path| path.to_path_buf())
+        .filter(|path| path.is_file())
+        .collect();
+    match lsp.diagnostics_for_edit(turn, &paths).await {
+        Some(diagnostics) => format!("{content}\n\nDiagnostics after this patch:\n{diagnostics}"),
+        None => content,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn intercept_apply_patch(
     command: &[String],
@@ -352,6 +383,7 @@ It is important to remember:
 - You must include a header with your intended action (Add/Delete/Update)
 - You must prefix new lines with `+` even when creating a new file
 - File references can only be relative, NEVER ABSOLUTE.
+- A standard unified diff (for example `git diff` output) is also accepted in place of the envelope.
 "#
             .to_string(),
         strict: false,
@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde_json::Value;
+
+use crate::config::types::CommandToolConfig;
+use crate::exec_env::create_env;
+use crate::exec_policy::ExecApprovalRequest;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::ExecCommandSource;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::runtimes::shell::ShellRequest;
+use crate::tools::runtimes::shell::ShellRuntime;
+use crate::tools::sandboxing::ToolCtx;
+
+/// Runs a `[tools.commands.<name>]` command from config through the shell
+/// runtime, so it gets the same sandboxing and approvals as the `shell` tool.
+/// The JSON arguments are written to the command's stdin.
+pub struct CommandToolHandler {
+    tool: CommandToolConfig,
+}
+
+impl CommandToolHandler {
+    pub fn new(tool: CommandToolConfig) -> Self {
+        Self { tool }
+    }
+}
+
+#[async_trait]
+impl ToolHandler for CommandToolHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let ToolPayload::Function { arguments } = payload else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "unsupported payload for command tool: {tool_name}"
+            )));
+        };
+        let args: Value = parse_arguments(&arguments)?;
+
+        let shell = session.user_shell();
+        let command = shell.derive_exec_args(&self.tool.command, true);
+        let cwd = turn.cwd.clone();
+        let mut env = create_env(
+            &turn.shell_environment_policy,
+            Some(session.conversation_id),
+        );
+        env.extend(session.dependency_env().await);
+
+        let emitter = ToolEmitter::shell(
+            command.clone(),
+            cwd.clone(),
+            ExecCommandSource::Agent,
+            false,
+        );
+        let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
+        emitter.begin(event_ctx).await;
+
+        let features = session.features();
+        let exec_approval_requirement = session
+            .services
+            .exec_policy
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                features: &features,
+                command: &command,
+                approval_policy: turn.approval_policy,
+                sandbox_policy: &turn.sandbox_policy,
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+            })
+            .await;
+
+        let req = ShellRequest {
+            command,
+            cwd,
+            timeout_ms: self.tool.timeout_ms,
+            env,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+            exec_approval_requirement,
+            stdin: Some(args.to_string()),
+        };
+        let mut orchestrator = ToolOrchestrator::new();
+        let mut runtime = ShellRuntime::new();
+        let tool_ctx = ToolCtx {
+            session: session.as_ref(),
+            turn: turn.as_ref(),
+            call_id: call_id.clone(),
+            tool_name,
+        };
+        let out = orchestrator
+            .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
+            .await;
+
+        // A successful run hands its stdout to the model as is; failures get the
+        // same formatted output as the shell tool so the model sees stderr too.
+        let stdout = out
+            .as_ref()
+            .ok()
+            .filter(|output| output.exit_code == 0)
+            .map(|output| output.stdout.text.clone());
+        let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
+        let content = emitter.finish(event_ctx, out).await?;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(stdout.unwrap_or(content)),
+            success: Some(true),
+        })
+    }
+}
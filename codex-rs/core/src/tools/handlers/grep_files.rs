@@ -8,9 +8,11 @@ use tokio::process::Command;
 use tokio::time::timeout;
 
 use crate::function_tool::FunctionCallError;
+use crate::sandboxing::deny_read::DenyReadPaths;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_not_denied;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -73,6 +75,7 @@ impl ToolHandler for GrepFilesHandler {
         let search_path = turn.resolve_path(args.path.clone());
 
         verify_path_exists(&search_path).await?;
+        ensure_not_denied(&turn, &search_path)?;
 
         let include = args.include.as_deref().map(str::trim).and_then(|val| {
             if val.is_empty() {
@@ -82,8 +85,15 @@ impl ToolHandler for GrepFilesHandler {
             }
         });
 
-        let search_results =
-            run_rg_search(pattern, include.as_deref(), &search_path, limit, &turn.cwd).await?;
+        let search_results = run_rg_search(
+            pattern,
+            include.as_deref(),
+            &search_path,
+            limit,
+            &turn.cwd,
+            &turn.config.sandbox_deny_read,
+        )
+        .await?;
 
         if search_results.is_empty() {
             Ok(ToolOutput::Function {
@@ -112,6 +122,7 @@ async fn run_rg_search(
     search_path: &Path,
     limit: usize,
     cwd: &Path,
+    deny_read: &DenyReadPaths,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut command = Command::new("rg");
     command
@@ -140,7 +151,7 @@ async fn run_rg_search(
         })?;
 
     match output.status.code() {
-        Some(0) => Ok(parse_results(&output.stdout, limit)),
+        Some(0) => Ok(parse_results(&output.stdout, limit, deny_read, cwd)),
         Some(1) => Ok(Vec::new()),
         _ => {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -151,14 +162,22 @@ async fn run_rg_search(
     }
 }
 
-fn parse_results(stdout: &[u8], limit: usize) -> Vec<String> {
+/// Collects up to `limit` matching paths, leaving out denied files so a
+/// pattern cannot be used to probe their contents. Every path is checked
+/// against the deny list itself, so matches outside `cwd` are covered too.
+fn parse_results(
+    stdout: &[u8],
+    limit: usize,
+    deny_read: &DenyReadPaths,
+    cwd: &Path,
+) -> Vec<String> {
     let mut results = Vec::new();
     for line in stdout.split(|byte| *byte == b'\n') {
         if line.is_empty() {
             continue;
         }
         if let Ok(text) = std::str::from_utf8(line) {
-            if text.is_empty() {
+            if text.is_empty() || deny_read.is_denied(Path::new(text), cwd) {
                 continue;
             }
             results.push(text.to_string());
@@ -179,7 +198,7 @@ mod tests {
     #[test]
     fn parses_basic_results() {
         let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n";
-        let parsed = parse_results(stdout, 10);
+        let parsed = parse_results(stdout, 10, &DenyReadPaths::default(), Path::new("/tmp"));
         assert_eq!(
             parsed,
             vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
@@ -189,13 +208,22 @@ mod tests {
     #[test]
     fn parse_truncates_after_limit() {
         let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n/tmp/file_c.rs\n";
-        let parsed = parse_results(stdout, 2);
+        let parsed = parse_results(stdout, 2, &DenyReadPaths::default(), Path::new("/tmp"));
         assert_eq!(
             parsed,
             vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
         );
     }
 
+    #[test]
+    fn parse_skips_denied_paths() {
+        let stdout = b"/tmp/secrets/key.pem\n/srv/app/.env\n/tmp/file_a.rs\n/tmp/file_b.rs\n";
+        let configured = vec!["/tmp/secrets".to_string(), ".env".to_string()];
+        let deny_read = DenyReadPaths::from_config(Some(&configured), Path::new("/nonexistent"));
+        let parsed = parse_results(stdout, 1, &deny_read, Path::new("/tmp"));
+        assert_eq!(parsed, vec!["/tmp/file_a.rs".to_string()]);
+    }
+
     #[tokio::test]
     async fn run_search_returns_results() -> anyhow::Result<()> {
         if !rg_available() {
@@ -207,7 +235,7 @@ mod tests {
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
         std::fs::write(dir.join("other.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 10, dir).await?;
+        let results = run_rg_search("alpha", None, dir, 10, dir, &DenyReadPaths::default()).await?;
         assert_eq!(results.len(), 2);
         assert!(results.iter().any(|path| path.ends_with("match_one.txt")));
         assert!(results.iter().any(|path| path.ends_with("match_two.txt")));
@@ -224,7 +252,15 @@ mod tests {
         std::fs::write(dir.join("match_one.rs"), "alpha beta gamma").unwrap();
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
 
-        let results = run_rg_search("alpha", Some("*.rs"), dir, 10, dir).await?;
+        let results = run_rg_search(
+            "alpha",
+            Some("*.rs"),
+            dir,
+            10,
+            dir,
+            &DenyReadPaths::default(),
+        )
+        .await?;
         assert_eq!(results.len(), 1);
         assert!(results.iter().all(|path| path.ends_with("match_one.rs")));
         Ok(())
@@ -241,7 +277,7 @@ mod tests {
         std::fs::write(dir.join("two.txt"), "alpha two").unwrap();
         std::fs::write(dir.join("three.txt"), "alpha three").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 2, dir).await?;
+        let results = run_rg_search("alpha", None, dir, 2, dir, &DenyReadPaths::default()).await?;
         assert_eq!(results.len(), 2);
         Ok(())
     }
@@ -255,7 +291,7 @@ mod tests {
         let dir = temp.path();
         std::fs::write(dir.join("one.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 5, dir).await?;
+        let results = run_rg_search("alpha", None, dir, 5, dir, &DenyReadPaths::default()).await?;
         assert!(results.is_empty());
         Ok(())
     }
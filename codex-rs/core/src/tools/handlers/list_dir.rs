@@ -11,9 +11,11 @@ use serde::Deserialize;
 use tokio::fs;
 
 use crate::function_tool::FunctionCallError;
+use crate::sandboxing::deny_read::DenyReadPaths;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_not_denied;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -53,7 +55,7 @@ impl ToolHandler for ListDirHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation { payload, turn, .. } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -97,8 +99,10 @@ impl ToolHandler for ListDirHandler {
                 "dir_path must be an absolute path".to_string(),
             ));
         }
+        ensure_not_denied(&turn, &path)?;
 
-        let entries = list_dir_slice(&path, offset, limit, depth).await?;
+        let deny_read = &turn.config.sandbox_deny_read;
+        let entries = list_dir_slice(&path, offset, limit, depth, deny_read, &turn.cwd).await?;
         let mut output = Vec::with_capacity(entries.len() + 1);
         output.push(format!("Absolute path: {}", path.display()));
         output.extend(entries);
@@ -114,9 +118,11 @@ async fn list_dir_slice(
     offset: usize,
     limit: usize,
     depth: usize,
+    deny_read: &DenyReadPaths,
+    cwd: &Path,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut entries = Vec::new();
-    collect_entries(path, Path::new(""), depth, &mut entries).await?;
+    collect_entries(path, Path::new(""), depth, deny_read, cwd, &mut entries).await?;
 
     if entries.is_empty() {
         return Ok(Vec::new());
@@ -152,6 +158,8 @@ async fn collect_entries(
     dir_path: &Path,
     relative_prefix: &Path,
     depth: usize,
+    deny_read: &DenyReadPaths,
+    cwd: &Path,
     entries: &mut Vec<DirEntry>,
 ) -> Result<(), FunctionCallError> {
     let mut queue = VecDeque::new();
@@ -198,7 +206,11 @@ async fn collect_entries(
         dir_entries.sort_unstable_by(|a, b| a.3.name.cmp(&b.3.name));
 
         for (entry_path, relative_path, kind, dir_entry) in dir_entries {
-            if kind == DirEntryKind::Directory && remaining_depth > 1 {
+            // Denied directories are listed but never expanded.
+            if kind == DirEntryKind::Directory
+                && remaining_depth > 1
+                && !deny_read.is_denied(&entry_path, cwd)
+            {
                 queue.push_back((entry_path, relative_path, remaining_depth - 1));
             }
             entries.push(dir_entry);
@@ -306,7 +318,7 @@ mod tests {
             symlink(dir_path.join("entry.txt"), &link_path).expect("create symlink");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 20, 3)
+        let entries = list_dir_slice(dir_path, 1, 20, 3, &DenyReadPaths::default(), dir_path)
             .await
             .expect("list directory");
 
@@ -340,7 +352,7 @@ mod tests {
             .await
             .expect("create sub dir");
 
-        let err = list_dir_slice(dir_path, 10, 1, 2)
+        let err = list_dir_slice(dir_path, 10, 1, 2, &DenyReadPaths::default(), dir_path)
             .await
             .expect_err("offset exceeds entries");
         assert_eq!(
@@ -367,17 +379,19 @@ mod tests {
             .await
             .expect("write deeper");
 
-        let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1)
-            .await
-            .expect("list depth 1");
+        let entries_depth_one =
+            list_dir_slice(dir_path, 1, 10, 1, &DenyReadPaths::default(), dir_path)
+                .await
+                .expect("list depth 1");
         assert_eq!(
             entries_depth_one,
             vec!["nested/".to_string(), "root.txt".to_string(),]
         );
 
-        let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2)
-            .await
-            .expect("list depth 2");
+        let entries_depth_two =
+            list_dir_slice(dir_path, 1, 20, 2, &DenyReadPaths::default(), dir_path)
+                .await
+                .expect("list depth 2");
         assert_eq!(
             entries_depth_two,
             vec![
@@ -388,9 +402,10 @@ mod tests {
             ]
         );
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3)
-            .await
-            .expect("list depth 3");
+        let entries_depth_three =
+            list_dir_slice(dir_path, 1, 30, 3, &DenyReadPaths::default(), dir_path)
+                .await
+                .expect("list depth 3");
         assert_eq!(
             entries_depth_three,
             vec![
@@ -420,7 +435,7 @@ mod tests {
             .await
             .expect("write b child");
 
-        let first_page = list_dir_slice(dir_path, 1, 2, 2)
+        let first_page = list_dir_slice(dir_path, 1, 2, 2, &DenyReadPaths::default(), dir_path)
             .await
             .expect("list page one");
         assert_eq!(
@@ -432,7 +447,7 @@ mod tests {
             ]
         );
 
-        let second_page = list_dir_slice(dir_path, 3, 2, 2)
+        let second_page = list_dir_slice(dir_path, 3, 2, 2, &DenyReadPaths::default(), dir_path)
             .await
             .expect("list page two");
         assert_eq!(
@@ -455,9 +470,16 @@ mod tests {
             .await
             .expect("write gamma");
 
-        let entries = list_dir_slice(dir_path, 2, usize::MAX, 1)
-            .await
-            .expect("list without overflow");
+        let entries = list_dir_slice(
+            dir_path,
+            2,
+            usize::MAX,
+            1,
+            &DenyReadPaths::default(),
+            dir_path,
+        )
+        .await
+        .expect("list without overflow");
         assert_eq!(
             entries,
             vec!["beta.txt".to_string(), "gamma.txt".to_string(),]
@@ -476,7 +498,7 @@ mod tests {
                 .expect("write file");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 25, 1)
+        let entries = list_dir_slice(dir_path, 1, 25, 1, &DenyReadPaths::default(), dir_path)
             .await
             .expect("list directory");
         assert_eq!(entries.len(), 26);
@@ -498,7 +520,8 @@ mod tests {
         tokio::fs::write(nested.join("child.txt"), b"child").await?;
         tokio::fs::write(deeper.join("grandchild.txt"), b"deep").await?;
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 3, 3).await?;
+        let entries_depth_three =
+            list_dir_slice(dir_path, 1, 3, 3, &DenyReadPaths::default(), dir_path).await?;
         assert_eq!(
             entries_depth_three,
             vec![
@@ -511,4 +534,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn does_not_expand_denied_directories() -> anyhow::Result<()> {
+        let temp = tempdir()?;
+        let dir_path = temp.path();
+        let secrets = dir_path.join("secrets");
+        tokio::fs::create_dir(&secrets).await?;
+        tokio::fs::write(secrets.join("key.pem"), b"secret").await?;
+        tokio::fs::write(dir_path.join("readme.md"), b"hi").await?;
+
+        let configured = vec![secrets.to_string_lossy().into_owned()];
+        let deny_read = DenyReadPaths::from_config(Some(&configured), dir_path);
+        let entries = list_dir_slice(dir_path, 1, 10, 2, &deny_read, dir_path).await?;
+        assert_eq!(
+            entries,
+            vec!["readme.md".to_string(), "secrets/".to_string()]
+        );
+
+        Ok(())
+    }
 }
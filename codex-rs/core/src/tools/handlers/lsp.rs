@@ -0,0 +1,200 @@
+use std::path::Path;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::lsp::LspLocation;
+use crate::lsp::LspPosition;
+use crate::lsp::display_path;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_not_denied;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub(crate) const LSP_DIAGNOSTICS_TOOL_NAME: &str = "lsp_diagnostics";
+pub(crate) const LSP_DEFINITION_TOOL_NAME: &str = "lsp_definition";
+pub(crate) const LSP_REFERENCES_TOOL_NAME: &str = "lsp_references";
+pub(crate) const LSP_HOVER_TOOL_NAME: &str = "lsp_hover";
+
+/// Maximum number of locations returned by definition and references lookups.
+const MAX_LOCATIONS: usize = 100;
+
+pub struct LspHandler;
+
+#[derive(Deserialize)]
+struct DiagnosticsArgs {
+    paths: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct PositionArgs {
+    path: String,
+    line: u32,
+    column: u32,
+}
+
+#[async_trait]
+impl ToolHandler for LspHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "lsp handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let lsp = &session.services.lsp_manager;
+        let root = turn.cwd.as_path();
+        let content = if tool_name == LSP_DIAGNOSTICS_TOOL_NAME {
+            let args: DiagnosticsArgs = parse_arguments(&arguments)?;
+            if args.paths.is_empty() {
+                return Err(FunctionCallError::RespondToModel(
+                    "paths must list at least one file".to_string(),
+                ));
+            }
+            let mut lines = Vec::new();
+            for path in args.paths {
+                let path = turn.resolve_path(Some(path));
+                ensure_not_denied(turn.as_ref(), &path)?;
+                let diagnostics = lsp
+                    .diagnostics(turn.as_ref(), &path)
+                    .await
+                    .map_err(|err| FunctionCallError::RespondToModel(format!("{err:#}")))?;
+                if diagnostics.is_empty() {
+                    lines.push(format!("{}: no diagnostics", display_path(root, &path)));
+                } else {
+                    lines.extend(diagnostics);
+                }
+            }
+            lines.join("\n")
+        } else {
+            let args: PositionArgs = parse_arguments(&arguments)?;
+            if args.line == 0 || args.column == 0 {
+                return Err(FunctionCallError::RespondToModel(
+                    "line and column must be 1-indexed".to_string(),
+                ));
+            }
+            let path = turn.resolve_path(Some(args.path));
+            ensure_not_denied(turn.as_ref(), &path)?;
+            let position = LspPosition {
+                line: args.line - 1,
+                character: args.column - 1,
+            };
+            let result = match tool_name.as_str() {
+                LSP_DEFINITION_TOOL_NAME => lsp
+                    .definition(turn.as_ref(), &path, position)
+                    .await
+                    .map(|locations| format_locations(root, &locations, "definition")),
+                LSP_REFERENCES_TOOL_NAME => lsp
+                    .references(turn.as_ref(), &path, position)
+                    .await
+                    .map(|locations| format_locations(root, &locations, "reference")),
+                LSP_HOVER_TOOL_NAME => {
+                    lsp.hover(turn.as_ref(), &path, position)
+                        .await
+                        .map(|hover| {
+                            hover.unwrap_or_else(|| {
+                                "No hover information at this position.".to_string()
+                            })
+                        })
+                }
+                _ => {
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "unsupported lsp tool: {tool_name}"
+                    )));
+                }
+            };
+            result.map_err(|err| FunctionCallError::RespondToModel(format!("{err:#}")))?
+        };
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(content),
+            success: Some(true),
+        })
+    }
+}
+
+/// Formats locations as `path:line:col`, one per line, with one-based
+/// positions.
+fn format_locations(root: &Path, locations: &[LspLocation], kind: &str) -> String {
+    if locations.is_empty() {
+        return format!("No {kind} found.");
+    }
+    let mut lines: Vec<String> = locations
+        .iter()
+        .take(MAX_LOCATIONS)
+        .map(|location| {
+            format!(
+                "{}:{}:{}",
+                display_path(root, &location.path),
+                location.position.line + 1,
+                location.position.character + 1
+            )
+        })
+        .collect();
+    if locations.len() > MAX_LOCATIONS {
+        lines.push(format!(
+            "... {} more locations omitted",
+            locations.len() - MAX_LOCATIONS
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn format_locations_is_relative_and_one_based() {
+        let root = PathBuf::from("/workspace");
+        let locations = vec![
+            LspLocation {
+                path: root.join("src/lib.rs"),
+                position: LspPosition {
+                    line: 9,
+                    character: 4,
+                },
+            },
+            LspLocation {
+                path: PathBuf::from("/rustlib/core/src/option.rs"),
+                position: LspPosition {
+                    line: 0,
+                    character: 0,
+                },
+            },
+        ];
+
+        assert_eq!(
+            format_locations(&root, &locations, "definition"),
+            "src/lib.rs:10:5\n/rustlib/core/src/option.rs:1:1"
+        );
+        assert_eq!(
+            format_locations(&root, &[], "reference"),
+            "No reference found."
+        );
+    }
+}
@@ -1,29 +1,36 @@
 pub mod apply_patch;
 pub(crate) mod collab;
+mod command_tool;
 mod dynamic;
 mod get_memory;
 mod grep_files;
 mod list_dir;
+pub(crate) mod lsp;
 mod mcp;
 mod mcp_resource;
 mod plan;
 mod read_file;
 mod request_user_input;
 mod shell;
+pub(crate) mod symbols;
 mod test_sync;
 mod unified_exec;
 mod view_image;
 
 pub use plan::PLAN_TOOL;
 use serde::Deserialize;
+use std::path::Path;
 
+use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 pub use apply_patch::ApplyPatchHandler;
 pub use collab::CollabHandler;
+pub use command_tool::CommandToolHandler;
 pub use dynamic::DynamicToolHandler;
 pub use get_memory::GetMemoryHandler;
 pub use grep_files::GrepFilesHandler;
 pub use list_dir::ListDirHandler;
+pub use lsp::LspHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use plan::PlanHandler;
@@ -32,6 +39,7 @@ pub use request_user_input::RequestUserInputHandler;
 pub(crate) use request_user_input::request_user_input_tool_description;
 pub use shell::ShellCommandHandler;
 pub use shell::ShellHandler;
+pub use symbols::SymbolsHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
@@ -44,3 +52,15 @@ where
         FunctionCallError::RespondToModel(format!("failed to parse function arguments: {err}"))
     })
 }
+
+/// Rejects paths covered by `sandbox_deny_read` so the file tools cannot hand
+/// their contents to the model.
+fn ensure_not_denied(turn: &TurnContext, path: &Path) -> Result<(), FunctionCallError> {
+    if turn.config.sandbox_deny_read.is_denied(path, &turn.cwd) {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "access to `{}` is denied by `sandbox_deny_read`",
+            path.display()
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::symbol_index::Symbol;
+use crate::symbol_index::SymbolKind;
+use crate::symbol_index::outline_file;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_not_denied;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub(crate) const FIND_SYMBOL_TOOL_NAME: &str = "find_symbol";
+pub(crate) const OUTLINE_FILE_TOOL_NAME: &str = "outline_file";
+
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 200;
+
+pub struct SymbolsHandler;
+
+#[derive(Deserialize)]
+struct FindSymbolArgs {
+    query: String,
+    #[serde(default)]
+    kind: Option<SymbolKind>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Deserialize)]
+struct OutlineFileArgs {
+    path: String,
+}
+
+#[async_trait]
+impl ToolHandler for SymbolsHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "symbols handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let content = match tool_name.as_str() {
+            FIND_SYMBOL_TOOL_NAME => {
+                let args: FindSymbolArgs = parse_arguments(&arguments)?;
+                if args.query.trim().is_empty() {
+                    return Err(FunctionCallError::RespondToModel(
+                        "query must not be empty".to_string(),
+                    ));
+                }
+                let index = Arc::clone(&session.services.symbol_index);
+                let root = turn.cwd.clone();
+                let limit = args.limit.clamp(1, MAX_LIMIT);
+                let symbols = tokio::task::spawn_blocking(move || {
+                    index.find(&root, &args.query, args.kind, limit)
+                })
+                .await
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("symbol search failed: {err}"))
+                })?;
+                let symbols: Vec<Symbol> = symbols
+                    .into_iter()
+                    .filter(|symbol| {
+                        !turn
+                            .config
+                            .sandbox_deny_read
+                            .is_denied(&turn.cwd.join(&symbol.path), &turn.cwd)
+                    })
+                    .collect();
+                format_matches(&symbols)
+            }
+            OUTLINE_FILE_TOOL_NAME => {
+                let args: OutlineFileArgs = parse_arguments(&arguments)?;
+                let path = turn.resolve_path(Some(args.path));
+                ensure_not_denied(turn.as_ref(), &path)?;
+                let outline_path = path.clone();
+                let outline = tokio::task::spawn_blocking(move || outline_file(&outline_path))
+                    .await
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!("outline failed: {err}"))
+                    })?
+                    .map_err(|err| {
+                        FunctionCallError::RespondToModel(format!(
+                            "failed to read {}: {err}",
+                            path.display()
+                        ))
+                    })?;
+                match outline {
+                    Some(symbols) => format_outline(&path, &symbols),
+                    None => {
+                        return Err(FunctionCallError::RespondToModel(format!(
+                            "outline_file does not support {}; supported languages are Rust, Python, JavaScript, TypeScript, Go and Java",
+                            path.display()
+                        )));
+                    }
+                }
+            }
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported symbols tool: {tool_name}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(content),
+            success: Some(true),
+        })
+    }
+}
+
+/// Formats search results as `path:start-end kind name`, one per line.
+fn format_matches(symbols: &[Symbol]) -> String {
+    if symbols.is_empty() {
+        return "No matching symbols found.".to_string();
+    }
+    symbols
+        .iter()
+        .map(|symbol| {
+            let mut line = format!(
+                "{}:{}-{} {} {}",
+                symbol.path.display(),
+                symbol.start_line,
+                symbol.end_line,
+                symbol.kind,
+                symbol.name
+            );
+            if let Some(container) = &symbol.container {
+                line.push_str(&format!(" (in {container})"));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats a file outline as `start-end kind name`, indented by nesting depth.
+fn format_outline(path: &Path, symbols: &[Symbol]) -> String {
+    if symbols.is_empty() {
+        return format!("No definitions found in {}.", path.display());
+    }
+    symbols
+        .iter()
+        .map(|symbol| {
+            format!(
+                "{}{}-{} {} {}",
+                "  ".repeat(symbol.depth),
+                symbol.start_line,
+                symbol.end_line,
+                symbol.kind,
+                symbol.name
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn symbol(
+        name: &str,
+        kind: SymbolKind,
+        container: Option<&str>,
+        lines: (usize, usize),
+        depth: usize,
+    ) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            kind,
+            container: container.map(str::to_string),
+            path: PathBuf::from("src/config.rs"),
+            start_line: lines.0,
+            end_line: lines.1,
+            depth,
+        }
+    }
+
+    #[test]
+    fn formats_matches_and_outlines() {
+        let symbols = vec![
+            symbol("Config", SymbolKind::Impl, None, (10, 30), 0),
+            symbol("load", SymbolKind::Method, Some("Config"), (12, 20), 1),
+        ];
+
+        assert_eq!(
+            format_matches(&symbols),
+            "src/config.rs:10-30 impl Config\nsrc/config.rs:12-20 method load (in Config)"
+        );
+        assert_eq!(
+            format_outline(Path::new("src/config.rs"), &symbols),
+            "10-30 impl Config\n  12-20 method load"
+        );
+        assert_eq!(format_matches(&[]), "No matching symbols found.");
+    }
+}
@@ -17,6 +17,7 @@ use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolPayload;
 use crate::tools::router::ToolCall;
 use crate::tools::router::ToolRouter;
+use crate::turn_limits::ToolCallBudget;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
@@ -27,6 +28,7 @@ pub(crate) struct ToolCallRuntime {
     session: Arc<Session>,
     turn_context: Arc<TurnContext>,
     tracker: SharedTurnDiffTracker,
+    tool_call_budget: ToolCallBudget,
     parallel_execution: Arc<RwLock<()>>,
 }
 
@@ -36,12 +38,14 @@ impl ToolCallRuntime {
         session: Arc<Session>,
         turn_context: Arc<TurnContext>,
         tracker: SharedTurnDiffTracker,
+        tool_call_budget: ToolCallBudget,
     ) -> Self {
         Self {
             router,
             session,
             turn_context,
             tracker,
+            tool_call_budget,
             parallel_execution: Arc::new(RwLock::new(())),
         }
     }
@@ -52,6 +56,15 @@ impl ToolCallRuntime {
         call: ToolCall,
         cancellation_token: CancellationToken,
     ) -> impl std::future::Future<Output = Result<ResponseInputItem, CodexErr>> {
+        // Calls past `max_tool_calls` are answered without running, so
+        // parallel calls cannot overshoot the limit.
+        if !self.tool_call_budget.try_take() {
+            let response = Self::response(
+                &call,
+                "Not run: this turn reached its tool call limit (max_tool_calls).".to_string(),
+            );
+            return Either::Left(std::future::ready(Ok(response)));
+        }
         let supports_parallel = self.router.tool_supports_parallel(&call.tool_name);
 
         let router = Arc::clone(&self.router);
@@ -92,35 +105,42 @@ impl ToolCallRuntime {
                 }
             }));
 
-        async move {
-            match handle.await {
-                Ok(Ok(response)) => Ok(response),
-                Ok(Err(FunctionCallError::Fatal(message))) => Err(CodexErr::Fatal(message)),
-                Ok(Err(other)) => Err(CodexErr::Fatal(other.to_string())),
-                Err(err) => Err(CodexErr::Fatal(format!(
-                    "tool task failed to receive: {err:?}"
-                ))),
+        Either::Right(
+            async move {
+                match handle.await {
+                    Ok(Ok(response)) => Ok(response),
+                    Ok(Err(FunctionCallError::Fatal(message))) => Err(CodexErr::Fatal(message)),
+                    Ok(Err(other)) => Err(CodexErr::Fatal(other.to_string())),
+                    Err(err) => Err(CodexErr::Fatal(format!(
+                        "tool task failed to receive: {err:?}"
+                    ))),
+                }
             }
-        }
-        .in_current_span()
+            .in_current_span(),
+        )
     }
 }
 
 impl ToolCallRuntime {
     fn aborted_response(call: &ToolCall, secs: f32) -> ResponseInputItem {
+        Self::response(call, Self::abort_message(call, secs))
+    }
+
+    /// Answers `call` with `message` in the output shape its tool expects.
+    fn response(call: &ToolCall, message: String) -> ResponseInputItem {
         match &call.payload {
             ToolPayload::Custom { .. } => ResponseInputItem::CustomToolCallOutput {
                 call_id: call.call_id.clone(),
-                output: Self::abort_message(call, secs),
+                output: message,
             },
             ToolPayload::Mcp { .. } => ResponseInputItem::McpToolCallOutput {
                 call_id: call.call_id.clone(),
-                result: Err(Self::abort_message(call, secs)),
+                result: Err(message),
             },
             _ => ResponseInputItem::FunctionCallOutput {
                 call_id: call.call_id.clone(),
                 output: FunctionCallOutputPayload {
-                    body: FunctionCallOutputBody::Text(Self::abort_message(call, secs)),
+                    body: FunctionCallOutputBody::Text(message),
                     ..Default::default()
                 },
             },
@@ -60,6 +60,10 @@ impl ToolRegistry {
         self.handlers.get(name).map(Arc::clone)
     }
 
+    pub fn has_handler(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
     // TODO(jif) for dynamic tools.
     // pub fn register(&mut self, name: impl Into<String>, handler: Arc<dyn ToolHandler>) {
     //     let name = name.into();
@@ -273,6 +277,7 @@ fn sandbox_policy_tag(policy: &SandboxPolicy) -> &'static str {
     match policy {
         SandboxPolicy::ReadOnly => "read-only",
         SandboxPolicy::WorkspaceWrite { .. } => "workspace-write",
+        SandboxPolicy::WorkspaceOverlay { .. } => "workspace-overlay",
         SandboxPolicy::DangerFullAccess => "danger-full-access",
         SandboxPolicy::ExternalSandbox { .. } => "external-sandbox",
     }
@@ -6,6 +6,7 @@
 //! `SandboxAttempt` with a minimal environment.
 use crate::CODEX_APPLY_PATCH_ARG1;
 use crate::exec::ExecToolCallOutput;
+use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::execute_env;
@@ -64,6 +65,7 @@ impl ApplyPatchRuntime {
             env: HashMap::new(),
             sandbox_permissions: SandboxPermissions::UseDefault,
             justification: None,
+            stdin: None,
         })
     }
 
@@ -150,6 +152,24 @@ impl ToolRuntime<ApplyPatchRequest, ExecToolCallOutput> for ApplyPatchRuntime {
         ctx: &ToolCtx<'_>,
     ) -> Result<ExecToolCallOutput, ToolError> {
         let spec = Self::build_command_spec(req)?;
+        // Under the overlay the patch was already reviewed as a whole, so it is
+        // written straight to the workspace rather than to a scratch layer.
+        let workspace_write;
+        let attempt = match attempt.policy {
+            SandboxPolicy::WorkspaceOverlay { network_access } => {
+                workspace_write = SandboxPolicy::WorkspaceWrite {
+                    writable_roots: Vec::new(),
+                    network_access: *network_access,
+                    exclude_tmpdir_env_var: true,
+                    exclude_slash_tmp: true,
+                };
+                &SandboxAttempt {
+                    policy: &workspace_write,
+                    ..*attempt
+                }
+            }
+            _ => attempt,
+        };
         let env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
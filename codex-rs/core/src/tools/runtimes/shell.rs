@@ -9,6 +9,7 @@ use crate::features::Feature;
 use crate::powershell::prefix_powershell_script_with_utf8;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::execute_env;
+use crate::sandboxing::overlay::review_overlay_changes;
 use crate::shell::ShellType;
 use crate::tools::runtimes::build_command_spec;
 use crate::tools::runtimes::maybe_wrap_shell_lc_with_snapshot;
@@ -36,6 +37,8 @@ pub struct ShellRequest {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub exec_approval_requirement: ExecApprovalRequirement,
+    /// Input written to the command's stdin.
+    pub stdin: Option<String>,
 }
 
 #[derive(Default)]
@@ -155,7 +158,7 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
             command
         };
 
-        let spec = build_command_spec(
+        let mut spec = build_command_spec(
             &command,
             &req.cwd,
             &req.env,
@@ -163,12 +166,27 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
             req.sandbox_permissions,
             req.justification.clone(),
         )?;
+        spec.stdin = req.stdin.clone();
+        let overlay = attempt
+            .workspace_overlay()
+            .map_err(|err| ToolError::Codex(err.into()))?;
         let env = attempt
-            .env_for(spec)
+            .env_with_overlay(spec, overlay.as_ref())
             .map_err(|err| ToolError::Codex(err.into()))?;
-        let out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
+        let mut out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
             .await
             .map_err(ToolError::Codex)?;
+        if let Some(overlay) = &overlay
+            && let Some(summary) =
+                review_overlay_changes(ctx.session, ctx.turn, &ctx.call_id, overlay).await
+        {
+            for output in [&mut out.stdout, &mut out.aggregated_output] {
+                if !output.text.is_empty() && !output.text.ends_with('\n') {
+                    output.text.push('\n');
+                }
+                output.text.push_str(&summary);
+            }
+        }
         Ok(out)
     }
 }
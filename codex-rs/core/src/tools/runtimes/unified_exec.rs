@@ -190,10 +190,14 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
             req.justification.clone(),
         )
         .map_err(|_| ToolError::Rejected("missing command line for PTY".to_string()))?;
+        let overlay = attempt
+            .workspace_overlay()
+            .map_err(|err| ToolError::Codex(err.into()))?;
         let exec_env = attempt
-            .env_for(spec)
+            .env_with_overlay(spec, overlay.as_ref())
             .map_err(|err| ToolError::Codex(err.into()))?;
-        self.manager
+        let mut process = self
+            .manager
             .open_session_with_exec_env(&exec_env, req.tty)
             .await
             .map_err(|err| match err {
@@ -203,6 +207,10 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
                     }))
                 }
                 other => ToolError::Rejected(other.to_string()),
-            })
+            })?;
+        if let Some(overlay) = overlay {
+            process.set_workspace_overlay(overlay);
+        }
+        Ok(process)
     }
 }
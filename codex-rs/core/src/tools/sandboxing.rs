@@ -11,6 +11,7 @@ use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxTransformError;
+use crate::sandboxing::overlay::WorkspaceOverlay;
 use crate::state::SessionServices;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::protocol::AskForApproval;
@@ -276,12 +277,33 @@ pub(crate) struct SandboxAttempt<'a> {
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
     pub use_linux_sandbox_bwrap: bool,
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
+    pub(crate) deny_read: &'a [std::path::PathBuf],
 }
 
 impl<'a> SandboxAttempt<'a> {
     pub fn env_for(
         &self,
         spec: CommandSpec,
+    ) -> Result<crate::sandboxing::ExecEnv, SandboxTransformError> {
+        self.env_with_overlay(spec, None)
+    }
+
+    /// Creates the scratch overlay a command needs when the workspace is
+    /// mounted copy-on-write, which only the Linux sandbox supports.
+    pub(crate) fn workspace_overlay(&self) -> std::io::Result<Option<WorkspaceOverlay>> {
+        if matches!(self.policy, SandboxPolicy::WorkspaceOverlay { .. })
+            && self.sandbox == crate::exec::SandboxType::LinuxSeccomp
+        {
+            WorkspaceOverlay::new(self.sandbox_cwd).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn env_with_overlay(
+        &self,
+        spec: CommandSpec,
+        overlay: Option<&WorkspaceOverlay>,
     ) -> Result<crate::sandboxing::ExecEnv, SandboxTransformError> {
         self.manager
             .transform(crate::sandboxing::SandboxTransformRequest {
@@ -292,6 +314,8 @@ impl<'a> SandboxAttempt<'a> {
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe,
                 use_linux_sandbox_bwrap: self.use_linux_sandbox_bwrap,
                 windows_sandbox_level: self.windows_sandbox_level,
+                overlay_dir: overlay.map(WorkspaceOverlay::dir),
+                deny_read: self.deny_read,
             })
     }
 }
@@ -1,6 +1,7 @@
 use crate::agent::AgentRole;
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
+use crate::config::types::CommandToolConfig;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::tools::handlers::PLAN_TOOL;
@@ -9,7 +10,13 @@ use crate::tools::handlers::apply_patch::create_apply_patch_json_tool;
 use crate::tools::handlers::collab::DEFAULT_WAIT_TIMEOUT_MS;
 use crate::tools::handlers::collab::MAX_WAIT_TIMEOUT_MS;
 use crate::tools::handlers::collab::MIN_WAIT_TIMEOUT_MS;
+use crate::tools::handlers::lsp::LSP_DEFINITION_TOOL_NAME;
+use crate::tools::handlers::lsp::LSP_DIAGNOSTICS_TOOL_NAME;
+use crate::tools::handlers::lsp::LSP_HOVER_TOOL_NAME;
+use crate::tools::handlers::lsp::LSP_REFERENCES_TOOL_NAME;
 use crate::tools::handlers::request_user_input_tool_description;
+use crate::tools::handlers::symbols::FIND_SYMBOL_TOOL_NAME;
+use crate::tools::handlers::symbols::OUTLINE_FILE_TOOL_NAME;
 use crate::tools::registry::ToolRegistryBuilder;
 use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::dynamic_tools::DynamicToolSpec;
@@ -34,6 +41,9 @@ pub(crate) struct ToolsConfig {
     pub memory_tools: bool,
     pub request_rule_enabled: bool,
     pub experimental_supported_tools: Vec<String>,
+    pub command_tools: BTreeMap<String, CommandToolConfig>,
+    pub lsp_tools: bool,
+    pub symbol_tools: bool,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -54,6 +64,7 @@ impl ToolsConfig {
         let include_collaboration_modes_tools = features.enabled(Feature::CollaborationModes);
         let include_memory_tools = features.enabled(Feature::MemoryTool);
         let request_rule_enabled = features.enabled(Feature::RequestRule);
+        let include_symbol_tools = features.enabled(Feature::SymbolIndex);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
@@ -89,8 +100,26 @@ impl ToolsConfig {
             memory_tools: include_memory_tools,
             request_rule_enabled,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
+            command_tools: BTreeMap::new(),
+            lsp_tools: false,
+            symbol_tools: include_symbol_tools,
         }
     }
+
+    /// Registers the command-backed tools declared in config.
+    pub fn with_command_tools(
+        mut self,
+        command_tools: BTreeMap<String, CommandToolConfig>,
+    ) -> Self {
+        self.command_tools = command_tools;
+        self
+    }
+
+    /// Exposes the language server tools; set when `[lsp_servers]` is configured.
+    pub fn with_lsp_tools(mut self, lsp_tools: bool) -> Self {
+        self.lsp_tools = lsp_tools;
+        self
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -948,6 +977,135 @@ fn create_list_dir_tool() -> ToolSpec {
     })
 }
 
+fn create_lsp_diagnostics_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "paths".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String { description: None }),
+            description: Some(
+                "Files to check, absolute or relative to the working directory.".to_string(),
+            ),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: LSP_DIAGNOSTICS_TOOL_NAME.to_string(),
+        description: "Returns the language server's errors and warnings for the given files. Use it to check edits without running a full build."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["paths".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_lsp_position_tool(name: &str, description: &str) -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "File containing the symbol, absolute or relative to the working directory."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "line".to_string(),
+            JsonSchema::Number {
+                description: Some("1-indexed line of the symbol.".to_string()),
+            },
+        ),
+        (
+            "column".to_string(),
+            JsonSchema::Number {
+                description: Some("1-indexed column of the symbol.".to_string()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: name.to_string(),
+        description: description.to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec![
+                "path".to_string(),
+                "line".to_string(),
+                "column".to_string(),
+            ]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_find_symbol_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Symbol name or fragment. Use `Type::name` or `Type.name` to search inside a type."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "kind".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional kind filter: function, method, class, struct, enum, interface, trait, impl, module, type, constant or macro."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some("Maximum number of results to return (default 20).".to_string()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: FIND_SYMBOL_TOOL_NAME.to_string(),
+        description: "Finds definitions of functions, types and other symbols in the workspace by name and returns their files and line ranges. Prefer it over grep when looking for where something is defined."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_outline_file_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "File to outline, absolute or relative to the working directory.".to_string(),
+            ),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: OUTLINE_FILE_TOOL_NAME.to_string(),
+        description: "Lists the definitions in a source file with their line ranges, nested by containing type. Use it to find the part of a large file worth reading."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_list_mcp_resources_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -1113,6 +1271,27 @@ pub(crate) fn mcp_tool_to_openai_tool(
     })
 }
 
+fn command_tool_to_openai_tool(
+    name: &str,
+    tool: &CommandToolConfig,
+) -> Result<ResponsesApiTool, serde_json::Error> {
+    let input_schema = match &tool.input_schema {
+        Some(input_schema) => parse_tool_input_schema(input_schema)?,
+        None => JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    };
+
+    Ok(ResponsesApiTool {
+        name: name.to_string(),
+        description: tool.description.clone(),
+        strict: false,
+        parameters: input_schema,
+    })
+}
+
 fn dynamic_tool_to_openai_tool(
     tool: &DynamicToolSpec,
 ) -> Result<ResponsesApiTool, serde_json::Error> {
@@ -1252,10 +1431,12 @@ pub(crate) fn build_specs(
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
     use crate::tools::handlers::CollabHandler;
+    use crate::tools::handlers::CommandToolHandler;
     use crate::tools::handlers::DynamicToolHandler;
     use crate::tools::handlers::GetMemoryHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::ListDirHandler;
+    use crate::tools::handlers::LspHandler;
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
@@ -1263,6 +1444,7 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::RequestUserInputHandler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
+    use crate::tools::handlers::SymbolsHandler;
     use crate::tools::handlers::TestSyncHandler;
     use crate::tools::handlers::UnifiedExecHandler;
     use crate::tools::handlers::ViewImageHandler;
@@ -1418,6 +1600,63 @@ pub(crate) fn build_specs(
         builder.register_handler("close_agent", collab_handler);
     }
 
+    if config.lsp_tools {
+        let lsp_handler = Arc::new(LspHandler);
+        builder.push_spec_with_parallel_support(create_lsp_diagnostics_tool(), true);
+        builder.push_spec_with_parallel_support(
+            create_lsp_position_tool(
+                LSP_DEFINITION_TOOL_NAME,
+                "Finds where the symbol at the given position is defined, using the language server.",
+            ),
+            true,
+        );
+        builder.push_spec_with_parallel_support(
+            create_lsp_position_tool(
+                LSP_REFERENCES_TOOL_NAME,
+                "Lists every reference to the symbol at the given position, using the language server.",
+            ),
+            true,
+        );
+        builder.push_spec_with_parallel_support(
+            create_lsp_position_tool(
+                LSP_HOVER_TOOL_NAME,
+                "Shows the type signature and documentation of the symbol at the given position, using the language server.",
+            ),
+            true,
+        );
+        builder.register_handler(LSP_DIAGNOSTICS_TOOL_NAME, lsp_handler.clone());
+        builder.register_handler(LSP_DEFINITION_TOOL_NAME, lsp_handler.clone());
+        builder.register_handler(LSP_REFERENCES_TOOL_NAME, lsp_handler.clone());
+        builder.register_handler(LSP_HOVER_TOOL_NAME, lsp_handler);
+    }
+
+    if config.symbol_tools {
+        let symbols_handler = Arc::new(SymbolsHandler);
+        builder.push_spec_with_parallel_support(create_find_symbol_tool(), true);
+        builder.push_spec_with_parallel_support(create_outline_file_tool(), true);
+        builder.register_handler(FIND_SYMBOL_TOOL_NAME, symbols_handler.clone());
+        builder.register_handler(OUTLINE_FILE_TOOL_NAME, symbols_handler);
+    }
+
+    for (name, tool) in &config.command_tools {
+        if builder.has_handler(name) {
+            tracing::warn!("Skipping command tool {name:?}: a built-in tool has the same name");
+            continue;
+        }
+        match command_tool_to_openai_tool(name, tool) {
+            Ok(converted_tool) => {
+                builder.push_spec(ToolSpec::Function(converted_tool));
+                builder.register_handler(
+                    name.clone(),
+                    Arc::new(CommandToolHandler::new(tool.clone())),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to convert command tool {name:?} to OpenAI tool: {e:?}");
+            }
+        }
+    }
+
     if let Some(mcp_tools) = mcp_tools {
         let mut entries: Vec<(String, rmcp::model::Tool)> = mcp_tools.into_iter().collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -1674,6 +1913,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_specs_command_tools() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let command_tool = |description: &str| CommandToolConfig {
+            description: description.to_string(),
+            command: "./scripts/tool.sh".to_string(),
+            input_schema: None,
+            timeout_ms: None,
+        };
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        })
+        .with_command_tools(BTreeMap::from([
+            (
+                "run_migrations".to_string(),
+                command_tool("Apply pending migrations."),
+            ),
+            (
+                "view_image".to_string(),
+                command_tool("Shadows a built-in."),
+            ),
+        ]));
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+
+        assert_contains_tool_names(&tools, &["run_migrations", "view_image"]);
+        assert_eq!(
+            find_tool(&tools, "run_migrations").spec,
+            ToolSpec::Function(ResponsesApiTool {
+                name: "run_migrations".to_string(),
+                description: "Apply pending migrations.".to_string(),
+                strict: false,
+                parameters: JsonSchema::Object {
+                    properties: BTreeMap::new(),
+                    required: None,
+                    additional_properties: Some(false.into()),
+                },
+            })
+        );
+        let ToolSpec::Function(view_image) = &find_tool(&tools, "view_image").spec else {
+            panic!("expected function tool");
+        };
+        assert_ne!(view_image.description, "Shadows a built-in.");
+    }
+
     #[test]
     fn request_user_input_requires_collaboration_modes_feature() {
         let config = test_config();
@@ -1728,6 +2015,34 @@ mod tests {
         assert_contains_tool_names(&tools, &["get_memory"]);
     }
 
+    #[test]
+    fn symbol_tools_require_symbol_index_feature() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert!(
+            !tools.iter().any(|t| t.spec.name() == FIND_SYMBOL_TOOL_NAME
+                || t.spec.name() == OUTLINE_FILE_TOOL_NAME),
+            "symbol tools should be disabled when symbol_index feature is off"
+        );
+
+        features.enable(Feature::SymbolIndex);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, &[]).build();
+        assert_contains_tool_names(&tools, &[FIND_SYMBOL_TOOL_NAME, OUTLINE_FILE_TOOL_NAME]);
+        assert!(find_tool(&tools, FIND_SYMBOL_TOOL_NAME).supports_parallel_tool_calls);
+    }
+
     fn assert_model_tools(
         model_slug: &str,
         features: &Features,
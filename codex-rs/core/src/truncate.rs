@@ -6,6 +6,8 @@ use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::openai_models::TruncationMode;
 use codex_protocol::openai_models::TruncationPolicyConfig;
 use codex_protocol::protocol::TruncationPolicy as ProtocolTruncationPolicy;
+use codex_utils_tokenizer::EncodingKind;
+use codex_utils_tokenizer::Tokenizer;
 
 const APPROX_BYTES_PER_TOKEN: usize = 4;
 
@@ -76,6 +78,33 @@ impl std::ops::Mul<f64> for TruncationPolicy {
     }
 }
 
+/// Counts tokens for the active model.
+///
+/// Uses the model's BPE tokenizer when one is selected and falls back to the
+/// bytes-per-token heuristic otherwise (unknown models, or when tokenizer
+/// accounting is disabled).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct TokenCounter {
+    encoding: Option<EncodingKind>,
+}
+
+impl TokenCounter {
+    pub(crate) fn new(encoding: Option<EncodingKind>) -> Self {
+        Self { encoding }
+    }
+
+    fn tokenizer(self) -> Option<Tokenizer> {
+        self.encoding.map(Tokenizer::get)
+    }
+
+    pub(crate) fn count(self, text: &str) -> usize {
+        match self.tokenizer() {
+            Some(tokenizer) => tokenizer.count(text),
+            None => approx_token_count(text),
+        }
+    }
+}
+
 pub(crate) fn formatted_truncate_text(content: &str, policy: TruncationPolicy) -> String {
     if content.len() <= policy.byte_budget() {
         return content.to_string();
@@ -94,12 +123,33 @@ pub(crate) fn truncate_text(content: &str, policy: TruncationPolicy) -> String {
         }
     }
 }
+
+/// Like [`truncate_text`], but measures token budgets with `counter`.
+///
+/// Byte policies are unaffected; token policies use exact token boundaries
+/// when `counter` has a tokenizer.
+pub(crate) fn truncate_text_with_counter(
+    content: &str,
+    policy: TruncationPolicy,
+    counter: TokenCounter,
+) -> String {
+    match (policy, counter.tokenizer()) {
+        (TruncationPolicy::Tokens(max_tokens), Some(tokenizer)) => {
+            truncate_with_tokenizer(content, max_tokens, tokenizer)
+        }
+        _ => truncate_text(content, policy),
+    }
+}
+
 /// Globally truncate function output items to fit within the given
 /// truncation policy's budget, preserving as many text/image items as
 /// possible and appending a summary for any omitted text items.
+///
+/// Token budgets are measured with `counter`.
 pub(crate) fn truncate_function_output_items_with_policy(
     items: &[FunctionCallOutputContentItem],
     policy: TruncationPolicy,
+    counter: TokenCounter,
 ) -> Vec<FunctionCallOutputContentItem> {
     let mut out: Vec<FunctionCallOutputContentItem> = Vec::with_capacity(items.len());
     let mut remaining_budget = match policy {
@@ -118,7 +168,7 @@ pub(crate) fn truncate_function_output_items_with_policy(
 
                 let cost = match policy {
                     TruncationPolicy::Bytes(_) => text.len(),
-                    TruncationPolicy::Tokens(_) => approx_token_count(text),
+                    TruncationPolicy::Tokens(_) => counter.count(text),
                 };
 
                 if cost <= remaining_budget {
@@ -129,7 +179,7 @@ pub(crate) fn truncate_function_output_items_with_policy(
                         TruncationPolicy::Bytes(_) => TruncationPolicy::Bytes(remaining_budget),
                         TruncationPolicy::Tokens(_) => TruncationPolicy::Tokens(remaining_budget),
                     };
-                    let snippet = truncate_text(text, snippet_policy);
+                    let snippet = truncate_text_with_counter(text, snippet_policy, counter);
                     if snippet.is_empty() {
                         omitted_text_items += 1;
                     } else {
@@ -180,6 +230,20 @@ fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Opt
     }
 }
 
+/// Truncate the middle of a string to at most `max_tokens` tokens as measured by
+/// `tokenizer`, keeping equal token budgets for the beginning and the end.
+fn truncate_with_tokenizer(s: &str, max_tokens: usize, tokenizer: Tokenizer) -> String {
+    let Some(split) = tokenizer.split_to_budget(s, max_tokens) else {
+        return s.to_string();
+    };
+    let removed = split.total_tokens.saturating_sub(max_tokens);
+    let marker = format_truncation_marker(
+        TruncationPolicy::Tokens(max_tokens),
+        u64::try_from(removed).unwrap_or(u64::MAX),
+    );
+    assemble_truncated_output(&s[..split.head_end], &s[split.tail_start..], &marker)
+}
+
 /// Truncate a string using a byte budget derived from the token budget, without
 /// performing any real tokenization. This keeps the logic purely byte-based and
 /// uses a bytes placeholder in the truncated output.
@@ -307,14 +371,18 @@ pub(crate) fn approx_tokens_from_byte_count(bytes: usize) -> u64 {
 #[cfg(test)]
 mod tests {
 
+    use super::TokenCounter;
     use super::TruncationPolicy;
     use super::approx_token_count;
     use super::formatted_truncate_text;
     use super::split_string;
     use super::truncate_function_output_items_with_policy;
     use super::truncate_text;
+    use super::truncate_text_with_counter;
     use super::truncate_with_token_budget;
     use codex_protocol::models::FunctionCallOutputContentItem;
+    use codex_utils_tokenizer::EncodingKind;
+    use codex_utils_tokenizer::Tokenizer;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -323,6 +391,44 @@ mod tests {
         assert_eq!(split_string("abc", 0, 0), (3, "", ""));
     }
 
+    #[test]
+    fn token_counter_uses_tokenizer_when_available() {
+        let text = "上下文窗口剩余容量";
+        assert_eq!(
+            TokenCounter::default().count(text),
+            approx_token_count(text)
+        );
+        assert_eq!(
+            TokenCounter::new(Some(EncodingKind::O200kBase)).count(text),
+            Tokenizer::get(EncodingKind::O200kBase).count(text)
+        );
+    }
+
+    #[test]
+    fn truncate_text_with_counter_respects_exact_token_budget() {
+        let counter = TokenCounter::new(Some(EncodingKind::O200kBase));
+        let content = "日本語のテキスト ".repeat(200);
+        let total = counter.count(&content);
+
+        let truncated = truncate_text_with_counter(&content, TruncationPolicy::Tokens(40), counter);
+
+        let marker = format!("…{} tokens truncated…", total - 40);
+        assert!(truncated.contains(&marker), "missing marker: {truncated}");
+        let kept = truncated.replace(&marker, "");
+        assert!(counter.count(&kept) <= 44, "kept too much: {kept}");
+        assert!(content.starts_with(truncated.split('…').next().unwrap_or_default()));
+    }
+
+    #[test]
+    fn truncate_text_with_counter_keeps_byte_policies_unchanged() {
+        let counter = TokenCounter::new(Some(EncodingKind::O200kBase));
+        let content = "x".repeat(100);
+        assert_eq!(
+            truncate_text_with_counter(&content, TruncationPolicy::Bytes(10), counter),
+            truncate_text(&content, TruncationPolicy::Bytes(10))
+        );
+    }
+
     #[test]
     fn split_string_handles_empty_string() {
         assert_eq!(split_string("", 4, 4), (0, "", ""));
@@ -489,8 +595,11 @@ mod tests {
             FunctionCallOutputContentItem::InputText { text: t5 },
         ];
 
-        let output =
-            truncate_function_output_items_with_policy(&items, TruncationPolicy::Tokens(limit));
+        let output = truncate_function_output_items_with_policy(
+            &items,
+            TruncationPolicy::Tokens(limit),
+            TokenCounter::default(),
+        );
 
         // Expect: t1 (full), t2 (full), image, t3 (truncated), summary mentioning 2 omitted.
         assert_eq!(output.len(), 5);
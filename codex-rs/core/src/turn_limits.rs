@@ -0,0 +1,272 @@
+//! Enforces the turn limits configured under `[limits]`.
+//!
+//! A turn keeps sampling the model until it stops asking for tool calls. For
+//! unattended runs that loop can go on for a long time, so each turn counts
+//! its model requests and tool calls and is cut short once one of the
+//! configured limits is reached. Tool calls are checked as they are
+//! dispatched, so a response asking for several at once cannot overshoot
+//! `max_tool_calls`.
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use codex_protocol::models::ResponseInputItem;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::TurnLimit;
+use tokio::time::Instant;
+
+use crate::config::types::TurnLimits;
+
+pub(crate) struct TurnLimitTracker {
+    limits: TurnLimits,
+    deadline: Option<Instant>,
+    model_requests: u32,
+    tool_calls: u32,
+    consecutive_failures: u32,
+}
+
+impl TurnLimitTracker {
+    pub(crate) fn new(limits: TurnLimits) -> Self {
+        Self {
+            limits,
+            deadline: limits
+                .timeout_secs
+                .map(|secs| Instant::now() + Duration::from_secs(secs)),
+            model_requests: 0,
+            tool_calls: 0,
+            consecutive_failures: 0,
+        }
+    }
+
+    /// Runs `future` until the turn's deadline, if it has one. Returns `None`
+    /// when the turn timed out first. Everything the turn waits on, model
+    /// requests and auto-compaction alike, goes through here so the timeout
+    /// covers the whole turn.
+    pub(crate) async fn within_deadline<F: Future>(&self, future: F) -> Option<F::Output> {
+        match self.deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, future).await.ok(),
+            None => Some(future.await),
+        }
+    }
+
+    /// The tool calls the next model request may run before `max_tool_calls`
+    /// is reached.
+    pub(crate) fn tool_call_budget(&self) -> ToolCallBudget {
+        ToolCallBudget(
+            self.remaining_tool_calls()
+                .map(|remaining| Arc::new(AtomicU32::new(remaining))),
+        )
+    }
+
+    /// Records a finished model request together with the outputs of the tool
+    /// calls it made, in call order. Calls past the request's
+    /// [`ToolCallBudget`] were answered without running and are not counted.
+    /// Returns the limit that forbids another model request, if any.
+    pub(crate) fn record_model_request(
+        &mut self,
+        tool_outputs: &[ResponseInputItem],
+    ) -> Option<TurnLimit> {
+        self.model_requests += 1;
+        let ran = self
+            .remaining_tool_calls()
+            .map_or(tool_outputs.len(), |remaining| {
+                tool_outputs.len().min(remaining as usize)
+            });
+        for output in &tool_outputs[..ran] {
+            self.tool_calls += 1;
+            if tool_call_failed(output) {
+                self.consecutive_failures += 1;
+            } else {
+                self.consecutive_failures = 0;
+            }
+        }
+
+        let reached = |limit: Option<u32>, count: u32| limit.is_some_and(|limit| count >= limit);
+        if reached(
+            self.limits.max_consecutive_failures,
+            self.consecutive_failures,
+        ) {
+            Some(TurnLimit::MaxConsecutiveFailures)
+        } else if reached(self.limits.max_tool_calls, self.tool_calls) {
+            Some(TurnLimit::MaxToolCalls)
+        } else if reached(self.limits.max_turns, self.model_requests) {
+            Some(TurnLimit::MaxTurns)
+        } else {
+            None
+        }
+    }
+
+    fn remaining_tool_calls(&self) -> Option<u32> {
+        self.limits
+            .max_tool_calls
+            .map(|limit| limit.saturating_sub(self.tool_calls))
+    }
+
+    /// The error reported when `limit` stops the turn.
+    pub(crate) fn error_event(&self, limit: TurnLimit) -> ErrorEvent {
+        let message = match limit {
+            TurnLimit::MaxTurns => format!(
+                "Stopped after {} model requests (max_turns).",
+                self.model_requests
+            ),
+            TurnLimit::MaxToolCalls => {
+                format!(
+                    "Stopped after {} tool calls (max_tool_calls).",
+                    self.tool_calls
+                )
+            }
+            TurnLimit::Timeout => format!(
+                "Stopped after {} seconds (timeout).",
+                self.limits.timeout_secs.unwrap_or_default()
+            ),
+            TurnLimit::MaxConsecutiveFailures => format!(
+                "Stopped after {} failed tool calls in a row (max_consecutive_failures).",
+                self.consecutive_failures
+            ),
+        };
+        ErrorEvent {
+            message,
+            codex_error_info: Some(CodexErrorInfo::TurnLimitReached { limit }),
+        }
+    }
+}
+
+/// Tool calls one model request may still run, shared by the calls it
+/// dispatches in parallel. Unlimited when `max_tool_calls` is not set.
+#[derive(Clone, Default)]
+pub(crate) struct ToolCallBudget(Option<Arc<AtomicU32>>);
+
+impl ToolCallBudget {
+    /// Takes one call from the budget. Returns `false` once it is used up.
+    pub(crate) fn try_take(&self) -> bool {
+        self.0.as_ref().is_none_or(|remaining| {
+            remaining
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |left| {
+                    left.checked_sub(1)
+                })
+                .is_ok()
+        })
+    }
+}
+
+/// Freeform tool outputs carry no status, so they count as successes.
+fn tool_call_failed(output: &ResponseInputItem) -> bool {
+    match output {
+        ResponseInputItem::FunctionCallOutput { output, .. } => output.success == Some(false),
+        ResponseInputItem::McpToolCallOutput { result, .. } => match result {
+            Ok(result) => result.is_error == Some(true),
+            Err(_) => true,
+        },
+        ResponseInputItem::CustomToolCallOutput { .. } | ResponseInputItem::Message { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputBody;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn output(success: bool) -> ResponseInputItem {
+        ResponseInputItem::FunctionCallOutput {
+            call_id: "call".to_string(),
+            output: FunctionCallOutputPayload {
+                body: FunctionCallOutputBody::Text(String::new()),
+                success: Some(success),
+            },
+        }
+    }
+
+    #[test]
+    fn limits_trip_once_their_count_is_reached() {
+        let mut tracker = TurnLimitTracker::new(TurnLimits {
+            max_turns: Some(3),
+            max_tool_calls: Some(4),
+            ..Default::default()
+        });
+        assert_eq!(tracker.record_model_request(&[output(true)]), None);
+        assert_eq!(
+            tracker.record_model_request(&[output(true), output(true)]),
+            None
+        );
+        assert_eq!(tracker.record_model_request(&[]), Some(TurnLimit::MaxTurns));
+
+        let mut tracker = TurnLimitTracker::new(TurnLimits {
+            max_tool_calls: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(
+            tracker.record_model_request(&[output(true), output(false)]),
+            Some(TurnLimit::MaxToolCalls)
+        );
+        assert_eq!(
+            tracker.error_event(TurnLimit::MaxToolCalls).message,
+            "Stopped after 2 tool calls (max_tool_calls)."
+        );
+    }
+
+    #[test]
+    fn calls_past_the_budget_are_not_run_or_counted() {
+        let mut tracker = TurnLimitTracker::new(TurnLimits {
+            max_tool_calls: Some(3),
+            ..Default::default()
+        });
+        assert_eq!(tracker.record_model_request(&[output(true)]), None);
+
+        let budget = tracker.tool_call_budget();
+        assert_eq!(
+            [budget.try_take(), budget.try_take(), budget.try_take()],
+            [true, true, false]
+        );
+        assert_eq!(
+            tracker.record_model_request(&[output(true), output(true), output(true)]),
+            Some(TurnLimit::MaxToolCalls)
+        );
+        assert_eq!(
+            tracker.error_event(TurnLimit::MaxToolCalls).message,
+            "Stopped after 3 tool calls (max_tool_calls)."
+        );
+
+        assert!(
+            TurnLimitTracker::new(TurnLimits::default())
+                .tool_call_budget()
+                .try_take()
+        );
+    }
+
+    #[test]
+    fn successful_tool_call_resets_consecutive_failures() {
+        let mut tracker = TurnLimitTracker::new(TurnLimits {
+            max_consecutive_failures: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(
+            tracker.record_model_request(&[output(false), output(true)]),
+            None
+        );
+        assert_eq!(tracker.record_model_request(&[output(false)]), None);
+        assert_eq!(
+            tracker.record_model_request(&[output(false)]),
+            Some(TurnLimit::MaxConsecutiveFailures)
+        );
+    }
+
+    #[tokio::test]
+    async fn within_deadline_stops_waiting_once_the_turn_times_out() {
+        let tracker = TurnLimitTracker::new(TurnLimits {
+            timeout_secs: Some(0),
+            ..Default::default()
+        });
+        assert_eq!(
+            tracker.within_deadline(std::future::pending::<()>()).await,
+            None
+        );
+
+        let tracker = TurnLimitTracker::new(TurnLimits::default());
+        assert_eq!(tracker.within_deadline(async { 1 }).await, Some(1));
+    }
+}
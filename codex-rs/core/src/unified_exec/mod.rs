@@ -25,6 +25,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Weak;
 use std::time::Duration;
 
 use rand::Rng;
@@ -139,6 +140,8 @@ struct ProcessEntry {
     command: Vec<String>,
     tty: bool,
     last_used: tokio::time::Instant,
+    session: Weak<Session>,
+    turn: Arc<TurnContext>,
 }
 
 pub(crate) fn clamp_yield_time(yield_time_ms: u64) -> u64 {
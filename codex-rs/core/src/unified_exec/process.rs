@@ -13,6 +13,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StreamOutput;
 use crate::exec::is_likely_sandbox_denied;
+use crate::sandboxing::overlay::WorkspaceOverlay;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use codex_utils_pty::ExecCommandSession;
@@ -38,6 +39,7 @@ pub(crate) struct UnifiedExecProcess {
     output_drained: Arc<Notify>,
     output_task: JoinHandle<()>,
     sandbox_type: SandboxType,
+    workspace_overlay: std::sync::Mutex<Option<WorkspaceOverlay>>,
 }
 
 impl UnifiedExecProcess {
@@ -76,9 +78,26 @@ impl UnifiedExecProcess {
             output_drained,
             output_task,
             sandbox_type,
+            workspace_overlay: std::sync::Mutex::new(None),
         }
     }
 
+    /// Attaches the overlay that holds this process's writes until its exit
+    /// is observed and the user reviews them.
+    pub(crate) fn set_workspace_overlay(&mut self, overlay: WorkspaceOverlay) {
+        *self
+            .workspace_overlay
+            .get_mut()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(overlay);
+    }
+
+    pub(super) fn take_workspace_overlay(&self) -> Option<WorkspaceOverlay> {
+        self.workspace_overlay
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take()
+    }
+
     pub(super) fn writer_sender(&self) -> mpsc::Sender<Vec<u8>> {
         self.process_handle.writer_sender()
     }
@@ -14,6 +14,7 @@ use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::protocol::ExecCommandSource;
 use crate::sandboxing::ExecEnv;
+use crate::sandboxing::overlay::review_overlay_changes;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::events::ToolEventStage;
@@ -67,6 +68,13 @@ fn apply_unified_exec_env(mut env: HashMap<String, String>) -> HashMap<String, S
     env
 }
 
+fn append_overlay_summary(output: &mut String, summary: &str) {
+    if !output.is_empty() && !output.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str(summary);
+}
+
 struct PreparedProcessHandles {
     writer_tx: mpsc::Sender<Vec<u8>>,
     output_buffer: OutputBuffer,
@@ -174,9 +182,21 @@ impl UnifiedExecProcessManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
+        let mut output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
         let exit_code = process.exit_code();
         let has_exited = process.has_exited() || exit_code.is_some();
+        if has_exited
+            && let Some(overlay) = process.take_workspace_overlay()
+            && let Some(summary) = review_overlay_changes(
+                context.session.as_ref(),
+                context.turn.as_ref(),
+                &context.call_id,
+                &overlay,
+            )
+            .await
+        {
+            append_overlay_summary(&mut output, &summary);
+        }
         let chunk_id = generate_chunk_id();
         let process_id = request.process_id.clone();
         if has_exited {
@@ -286,7 +306,7 @@ impl UnifiedExecProcessManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
+        let mut output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
         let original_token_count = approx_token_count(&text);
         let chunk_id = generate_chunk_id();
 
@@ -303,6 +323,9 @@ impl UnifiedExecProcessManager {
             } => (Some(process_id), exit_code, call_id),
             ProcessStatus::Exited { exit_code, entry } => {
                 let call_id = entry.call_id.clone();
+                if let Some(summary) = Self::review_exited_overlay(&entry).await {
+                    append_overlay_summary(&mut output, &summary);
+                }
                 (None, exit_code, call_id)
             }
             ProcessStatus::Unknown => {
@@ -327,6 +350,19 @@ impl UnifiedExecProcessManager {
         Ok(response)
     }
 
+    /// Lets the user review the workspace overlay of a process whose exit was
+    /// observed by `write_stdin`.
+    async fn review_exited_overlay(entry: &ProcessEntry) -> Option<String> {
+        let overlay = entry.process.take_workspace_overlay()?;
+        let Some(session) = entry.session.upgrade() else {
+            return Some(
+                "Changes made in the workspace overlay were discarded because the session ended."
+                    .to_string(),
+            );
+        };
+        review_overlay_changes(&session, &entry.turn, &entry.call_id, &overlay).await
+    }
+
     async fn refresh_process_state(&self, process_id: &str) -> ProcessStatus {
         let mut store = self.process_store.lock().await;
         let Some(entry) = store.processes.get(process_id) else {
@@ -412,6 +448,8 @@ impl UnifiedExecProcessManager {
             command: command.to_vec(),
             tty,
             last_used: started_at,
+            session: Arc::downgrade(&context.session),
+            turn: Arc::clone(&context.turn),
         };
         let number_processes = {
             let mut store = self.process_store.lock().await;
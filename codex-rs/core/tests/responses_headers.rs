@@ -48,6 +48,7 @@ async fn responses_stream_includes_subagent_header_on_review() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_command: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -149,6 +150,7 @@ async fn responses_stream_includes_subagent_header_on_other() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_command: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -246,6 +248,7 @@ async fn responses_respects_model_info_overrides_from_config() {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_command: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
@@ -415,6 +415,7 @@ fn websocket_provider(server: &WebSocketTestServer) -> ModelProviderInfo {
         env_key: None,
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_command: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
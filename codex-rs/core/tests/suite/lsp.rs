@@ -0,0 +1,107 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used)]
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use anyhow::Result;
+use codex_core::config::types::LspServerConfig;
+use core_test_support::responses::ev_apply_patch_function_call;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::ApplyPatchModelOutput;
+use core_test_support::test_codex::TestCodexHarness;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+async fn lsp_harness() -> Result<TestCodexHarness> {
+    let server = codex_utils_cargo_bin::cargo_bin("test_lsp_server")?;
+    let builder = test_codex().with_config(move |config| {
+        config.include_apply_patch_tool = true;
+        config.lsp_servers = BTreeMap::from([(
+            "test".to_string(),
+            LspServerConfig {
+                command: server.to_string_lossy().to_string(),
+                args: Vec::new(),
+                file_extensions: vec!["txt".to_string()],
+                language_id: None,
+                initialization_options: None,
+                run_workspace_code: false,
+            },
+        )]);
+    });
+    TestCodexHarness::with_builder(builder).await
+}
+
+async fn mount_tool_call(harness: &TestCodexHarness, tool_call: serde_json::Value) {
+    mount_sse_sequence(
+        harness.server(),
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                tool_call,
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn apply_patch_reports_diagnostics_for_edited_files() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = lsp_harness().await?;
+    let call_id = "apply-with-diagnostics";
+    let patch = "*** Begin Patch\n*** Add File: notes.txt\n+fine\n+ERROR here\n*** End Patch";
+    mount_tool_call(&harness, ev_apply_patch_function_call(call_id, patch)).await;
+
+    harness.submit("add a file with an error").await?;
+
+    assert_eq!(
+        fs::read_to_string(harness.path("notes.txt"))?,
+        "fine\nERROR here\n"
+    );
+    let output = harness
+        .apply_patch_output(call_id, ApplyPatchModelOutput::Function)
+        .await;
+    assert!(
+        output.contains(
+            "Diagnostics after this patch:\nnotes.txt:2:1: error: found ERROR marker (test-lsp)"
+        ),
+        "unexpected output: {output}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lsp_definition_resolves_symbol_location() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = lsp_harness().await?;
+    fs::write(harness.path("code.txt"), "fn helper\n\ncall helper\n")?;
+    let call_id = "lsp-definition";
+    let args = json!({ "path": "code.txt", "line": 3, "column": 7 });
+    mount_tool_call(
+        &harness,
+        ev_function_call(call_id, "lsp_definition", &serde_json::to_string(&args)?),
+    )
+    .await;
+
+    harness.submit("where is helper defined?").await?;
+
+    assert_eq!(harness.function_call_stdout(call_id).await, "code.txt:1:4");
+
+    Ok(())
+}
@@ -82,6 +82,7 @@ mod list_dir;
 mod list_models;
 mod live_cli;
 mod live_reload;
+mod lsp;
 mod memory_tool;
 mod model_info_overrides;
 mod model_overrides;
@@ -118,6 +119,7 @@ mod tool_harness;
 mod tool_parallelism;
 mod tools;
 mod truncation;
+mod turn_limits;
 mod turn_state;
 mod undo;
 mod unified_exec;
@@ -69,6 +69,7 @@ async fn continue_after_stream_error() {
         env_key: Some("PATH".into()),
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_command: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
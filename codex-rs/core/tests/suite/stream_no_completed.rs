@@ -76,6 +76,7 @@ async fn retries_on_early_close() {
         env_key: Some("PATH".into()),
         env_key_instructions: None,
         experimental_bearer_token: None,
+        auth_command: None,
         wire_api: WireApi::Responses,
         query_params: None,
         http_headers: None,
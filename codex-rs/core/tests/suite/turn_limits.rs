@@ -0,0 +1,88 @@
+use anyhow::Result;
+use codex_core::config::types::TurnLimits;
+use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::TurnLimit;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn max_turns_stops_turn_with_partial_message() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "Looking around first."),
+                ev_function_call("call-1", "unknown_tool", "{}"),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| {
+            config.turn_limits = TurnLimits {
+                max_turns: Some(1),
+                ..Default::default()
+            };
+        })
+        .build(&server)
+        .await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "go".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let mut limit_errors = Vec::new();
+    let last_agent_message = loop {
+        match wait_for_event(&test.codex, |_| true).await {
+            EventMsg::Error(err) => limit_errors.push((err.message, err.codex_error_info)),
+            EventMsg::TurnComplete(ev) => break ev.last_agent_message,
+            _ => {}
+        }
+    };
+
+    assert_eq!(
+        limit_errors,
+        vec![(
+            "Stopped after 1 model requests (max_turns).".to_string(),
+            Some(CodexErrorInfo::TurnLimitReached {
+                limit: TurnLimit::MaxTurns,
+            }),
+        )]
+    );
+    assert_eq!(
+        last_agent_message,
+        Some("Looking around first.".to_string())
+    );
+    assert_eq!(responses.requests().len(), 1);
+
+    Ok(())
+}
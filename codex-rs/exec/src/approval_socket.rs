@@ -0,0 +1,631 @@
+//! `--approval-socket`: lets another process answer approval requests.
+//!
+//! Exec listens on a Unix socket and serves one client at a time. Every
+//! command or patch approval request is written to the client as a JSON line
+//! carrying an `id`, and the client answers with
+//! `{"id": "...", "decision": "approved"}` lines, using the same decisions as
+//! [`ReviewDecision`]. Requests stay pending until they are answered or their
+//! turn ends, so a client that connects late, or reconnects, first receives
+//! every request that is still waiting. A client that stops reading is
+//! dropped, so it cannot hold up the run.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::os::unix::fs::DirBuilderExt;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::bail;
+use codex_core::CodexThread;
+use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ExecApprovalRequestEvent;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use codex_protocol::ThreadId;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::io::Lines;
+use tokio::net::UnixListener;
+use tokio::net::unix::OwnedReadHalf;
+use tokio::net::unix::OwnedWriteHalf;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// How long a write to the client may take before the client is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) struct ApprovalSocket {
+    path: PathBuf,
+    messages_tx: mpsc::UnboundedSender<SocketMessage>,
+    /// Threads that sent requests, so answers can be submitted to them.
+    threads: Arc<Mutex<HashMap<ThreadId, Arc<CodexThread>>>>,
+}
+
+enum SocketMessage {
+    Request(PendingApproval),
+    /// The turn with event id `event_id` completed or was aborted; its
+    /// requests can no longer be answered.
+    TurnEnded {
+        thread_id: ThreadId,
+        event_id: String,
+    },
+}
+
+struct PendingApproval {
+    thread_id: ThreadId,
+    /// Id of the event that carried the request; core expects it back.
+    event_id: String,
+    request: ApprovalRequest,
+}
+
+enum ApprovalRequest {
+    Exec(ExecApprovalRequestEvent),
+    Patch(ApplyPatchApprovalRequestEvent),
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OutgoingLine<'a> {
+    ExecApprovalRequest {
+        id: &'a str,
+        thread_id: String,
+        #[serde(flatten)]
+        request: &'a ExecApprovalRequestEvent,
+    },
+    PatchApprovalRequest {
+        id: &'a str,
+        thread_id: String,
+        #[serde(flatten)]
+        request: &'a ApplyPatchApprovalRequestEvent,
+    },
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<&'a str>,
+        message: String,
+    },
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct ApprovalAnswer {
+    id: String,
+    decision: ReviewDecision,
+}
+
+struct Client {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+}
+
+impl ApprovalSocket {
+    /// Creates the socket at `path`, replacing a stale socket left behind by
+    /// an earlier run, and starts serving it. A socket that another process
+    /// is still listening on is left alone.
+    pub(crate) fn bind(path: PathBuf) -> anyhow::Result<Self> {
+        match std::fs::symlink_metadata(&path) {
+            Ok(metadata) if metadata.file_type().is_socket() => {
+                match std::os::unix::net::UnixStream::connect(&path) {
+                    Ok(_) => bail!("{} 上已有其他进程在监听", path.display()),
+                    Err(err) if err.kind() == std::io::ErrorKind::ConnectionRefused => {
+                        std::fs::remove_file(&path)
+                            .with_context(|| format!("无法删除旧的套接字 {}", path.display()))?;
+                    }
+                    Err(err) => {
+                        return Err(err)
+                            .with_context(|| format!("无法检查已有的套接字 {}", path.display()));
+                    }
+                }
+            }
+            Ok(_) => bail!("{} 已存在且不是套接字", path.display()),
+            Err(_) => {}
+        }
+        let listener = bind_private(&path)
+            .with_context(|| format!("无法在 {} 创建审批套接字", path.display()))?;
+
+        let (messages_tx, messages_rx) = mpsc::unbounded_channel();
+        let (answers_tx, answers_rx) = mpsc::unbounded_channel();
+        let threads = Arc::default();
+        tokio::spawn(serve(listener, messages_rx, answers_tx));
+        tokio::spawn(submit_answers(Arc::clone(&threads), answers_rx));
+        Ok(Self {
+            path,
+            messages_tx,
+            threads,
+        })
+    }
+
+    /// Sends `event` to the client if it is an approval request, and drops
+    /// the requests of a turn that ended.
+    pub(crate) fn forward(&self, thread_id: ThreadId, thread: &Arc<CodexThread>, event: &Event) {
+        let request = match &event.msg {
+            EventMsg::ExecApprovalRequest(ev) => ApprovalRequest::Exec(ev.clone()),
+            EventMsg::ApplyPatchApprovalRequest(ev) => ApprovalRequest::Patch(ev.clone()),
+            EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_) => {
+                self.send(SocketMessage::TurnEnded {
+                    thread_id,
+                    event_id: event.id.clone(),
+                });
+                return;
+            }
+            _ => return,
+        };
+        self.threads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(thread_id)
+            .or_insert_with(|| Arc::clone(thread));
+        self.send(SocketMessage::Request(PendingApproval {
+            thread_id,
+            event_id: event.id.clone(),
+            request,
+        }));
+    }
+
+    fn send(&self, message: SocketMessage) {
+        if self.messages_tx.send(message).is_err() {
+            warn!("approval socket task exited; request left unanswered");
+        }
+    }
+}
+
+/// Binds a listener at `path` that only the current user can connect to.
+/// Anyone who can connect can approve commands, so the socket is created in a
+/// fresh 0700 directory, restricted to 0600 there, and only then moved to
+/// `path`; it is never reachable with looser permissions.
+fn bind_private(path: &Path) -> anyhow::Result<UnixListener> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let staging = parent.join(format!(".codex-approval-{}", std::process::id()));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging)
+        .with_context(|| format!("无法创建临时目录 {}", staging.display()))?;
+    let staged = staging.join("socket");
+    let bound = UnixListener::bind(&staged)
+        .map_err(anyhow::Error::from)
+        .and_then(|listener| {
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+            std::fs::rename(&staged, path)?;
+            Ok(listener)
+        });
+    let _ = std::fs::remove_file(&staged);
+    let _ = std::fs::remove_dir(&staging);
+    bound
+}
+
+impl Drop for ApprovalSocket {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Submits the approvals decoded by [`serve`] to their threads.
+async fn submit_answers(
+    threads: Arc<Mutex<HashMap<ThreadId, Arc<CodexThread>>>>,
+    mut answers_rx: mpsc::UnboundedReceiver<(ThreadId, Op)>,
+) {
+    while let Some((thread_id, op)) = answers_rx.recv().await {
+        let thread = threads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&thread_id)
+            .cloned();
+        let Some(thread) = thread else {
+            warn!("approval answered for unknown thread {thread_id}");
+            continue;
+        };
+        if let Err(err) = thread.submit(op).await {
+            warn!("failed to submit approval for thread {thread_id}: {err}");
+        }
+    }
+}
+
+/// Serves approval requests from `messages_rx` to the connected client and
+/// sends the answers, as the op to submit to their thread, to `answers_tx`.
+async fn serve(
+    listener: UnixListener,
+    mut messages_rx: mpsc::UnboundedReceiver<SocketMessage>,
+    answers_tx: mpsc::UnboundedSender<(ThreadId, Op)>,
+) {
+    let mut next_id: u64 = 1;
+    let mut pending: BTreeMap<u64, PendingApproval> = BTreeMap::new();
+    let mut client: Option<Client> = None;
+    loop {
+        tokio::select! {
+            message = messages_rx.recv() => {
+                let request = match message {
+                    Some(SocketMessage::Request(request)) => request,
+                    Some(SocketMessage::TurnEnded { thread_id, event_id }) => {
+                        // Answers for a finished turn would be ignored, so stop
+                        // offering its requests to clients that connect later.
+                        pending.retain(|_, request| {
+                            request.thread_id != thread_id || request.event_id != event_id
+                        });
+                        continue;
+                    }
+                    None => break,
+                };
+                let id = next_id;
+                next_id += 1;
+                if let Some(connected) = client.as_mut()
+                    && write_line(&mut connected.writer, &request_line(id, &request))
+                        .await
+                        .is_err()
+                {
+                    client = None;
+                }
+                pending.insert(id, request);
+            }
+            accepted = listener.accept(), if client.is_none() => {
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(err) => {
+                        warn!("failed to accept approval socket client: {err}");
+                        continue;
+                    }
+                };
+                let (reader, writer) = stream.into_split();
+                let mut connected = Client {
+                    lines: BufReader::new(reader).lines(),
+                    writer,
+                };
+                let mut ok = true;
+                for (id, request) in &pending {
+                    if write_line(&mut connected.writer, &request_line(*id, request))
+                        .await
+                        .is_err()
+                    {
+                        ok = false;
+                        break;
+                    }
+                }
+                if ok {
+                    client = Some(connected);
+                }
+            }
+            line = next_line(&mut client) => {
+                let Ok(Some(line)) = line else {
+                    client = None;
+                    continue;
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let reply = match resolve(&line, &mut pending) {
+                    Ok(answer) => {
+                        let _ = answers_tx.send(answer);
+                        continue;
+                    }
+                    Err(reply) => reply,
+                };
+                if let Some(connected) = client.as_mut()
+                    && write_line(&mut connected.writer, &reply).await.is_err()
+                {
+                    client = None;
+                }
+            }
+        }
+    }
+}
+
+/// Turns one answer line into the op to submit to the request's thread,
+/// returning the error line to send back if it cannot be applied.
+fn resolve(
+    line: &str,
+    pending: &mut BTreeMap<u64, PendingApproval>,
+) -> Result<(ThreadId, Op), String> {
+    let answer = match parse_answer(line) {
+        Ok(answer) => answer,
+        Err(err) => return Err(error_line(None, format!("无法解析回复：{err}"))),
+    };
+    let Some(request) = answer
+        .id
+        .parse::<u64>()
+        .ok()
+        .and_then(|id| pending.remove(&id))
+    else {
+        return Err(error_line(
+            Some(&answer.id),
+            format!("没有编号为 {} 的待审批请求", answer.id),
+        ));
+    };
+    let op = match request.request {
+        ApprovalRequest::Exec(_) => Op::ExecApproval {
+            id: request.event_id,
+            decision: answer.decision,
+        },
+        ApprovalRequest::Patch(_) => Op::PatchApproval {
+            id: request.event_id,
+            decision: answer.decision,
+        },
+    };
+    Ok((request.thread_id, op))
+}
+
+fn parse_answer(line: &str) -> serde_json::Result<ApprovalAnswer> {
+    serde_json::from_str(line)
+}
+
+fn request_line(id: u64, pending: &PendingApproval) -> String {
+    let id = id.to_string();
+    let thread_id = pending.thread_id.to_string();
+    let line = match &pending.request {
+        ApprovalRequest::Exec(request) => OutgoingLine::ExecApprovalRequest {
+            id: &id,
+            thread_id,
+            request,
+        },
+        ApprovalRequest::Patch(request) => OutgoingLine::PatchApprovalRequest {
+            id: &id,
+            thread_id,
+            request,
+        },
+    };
+    serialize(&line)
+}
+
+fn error_line(id: Option<&str>, message: String) -> String {
+    serialize(&OutgoingLine::Error { id, message })
+}
+
+fn serialize(line: &OutgoingLine<'_>) -> String {
+    serde_json::to_string(line).unwrap_or_else(|err| {
+        warn!("failed to serialize approval socket line: {err}");
+        String::from("{}")
+    })
+}
+
+async fn next_line(client: &mut Option<Client>) -> std::io::Result<Option<String>> {
+    match client {
+        Some(client) => client.lines.next_line().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Writes `line` to the client, failing if the client does not take it
+/// within [`WRITE_TIMEOUT`].
+async fn write_line(writer: &mut OwnedWriteHalf, line: &str) -> std::io::Result<()> {
+    let write = async {
+        writer.write_all(line.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+        writer.flush().await
+    };
+    tokio::time::timeout(WRITE_TIMEOUT, write)
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn exec_request_line_includes_id_and_command() {
+        let request = ExecApprovalRequestEvent {
+            call_id: "call-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            command: vec!["cargo".to_string(), "publish".to_string()],
+            cwd: PathBuf::from("/repo"),
+            reason: Some("needs network".to_string()),
+            proposed_execpolicy_amendment: None,
+            parsed_cmd: Vec::new(),
+        };
+        let line = serialize(&OutgoingLine::ExecApprovalRequest {
+            id: "3",
+            thread_id: "thread".to_string(),
+            request: &request,
+        });
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&line).unwrap(),
+            json!({
+                "type": "exec_approval_request",
+                "id": "3",
+                "thread_id": "thread",
+                "call_id": "call-1",
+                "turn_id": "turn-1",
+                "command": ["cargo", "publish"],
+                "cwd": "/repo",
+                "reason": "needs network",
+                "parsed_cmd": [],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn bind_private_creates_a_user_only_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("approvals.sock");
+
+        let _listener = bind_private(&path).unwrap();
+
+        let metadata = std::fs::symlink_metadata(&path).unwrap();
+        assert!(metadata.file_type().is_socket());
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        let entries: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, vec![std::ffi::OsString::from("approvals.sock")]);
+    }
+
+    fn exec_request(event_id: &str, thread_id: ThreadId) -> SocketMessage {
+        SocketMessage::Request(PendingApproval {
+            thread_id,
+            event_id: event_id.to_string(),
+            request: ApprovalRequest::Exec(ExecApprovalRequestEvent {
+                call_id: format!("call-{event_id}"),
+                turn_id: "turn-1".to_string(),
+                command: vec!["cargo".to_string(), "publish".to_string()],
+                cwd: PathBuf::from("/repo"),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                parsed_cmd: Vec::new(),
+            }),
+        })
+    }
+
+    fn patch_request(event_id: &str, thread_id: ThreadId) -> SocketMessage {
+        SocketMessage::Request(PendingApproval {
+            thread_id,
+            event_id: event_id.to_string(),
+            request: ApprovalRequest::Patch(ApplyPatchApprovalRequestEvent {
+                call_id: format!("call-{event_id}"),
+                turn_id: "turn-1".to_string(),
+                changes: HashMap::new(),
+                reason: None,
+                grant_root: None,
+                overlay_review: false,
+            }),
+        })
+    }
+
+    struct TestClient {
+        lines: Lines<BufReader<OwnedReadHalf>>,
+        writer: OwnedWriteHalf,
+    }
+
+    impl TestClient {
+        async fn connect(path: &Path) -> Self {
+            let (reader, writer) = tokio::net::UnixStream::connect(path)
+                .await
+                .unwrap()
+                .into_split();
+            Self {
+                lines: BufReader::new(reader).lines(),
+                writer,
+            }
+        }
+
+        async fn read(&mut self) -> serde_json::Value {
+            let line = self.lines.next_line().await.unwrap().unwrap();
+            serde_json::from_str(&line).unwrap()
+        }
+
+        async fn answer(&mut self, id: &str) {
+            let line = json!({ "id": id, "decision": "approved" }).to_string();
+            write_line(&mut self.writer, &line).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn serve_replays_pending_requests_and_submits_answers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("approvals.sock");
+        let listener = bind_private(&path).unwrap();
+        let (messages_tx, messages_rx) = mpsc::unbounded_channel();
+        let (answers_tx, mut answers_rx) = mpsc::unbounded_channel();
+        tokio::spawn(serve(listener, messages_rx, answers_tx));
+        let thread_id = ThreadId::new();
+
+        messages_tx
+            .send(exec_request("event-1", thread_id))
+            .unwrap();
+        let mut client = TestClient::connect(&path).await;
+        let line = client.read().await;
+        assert_eq!(
+            (line["type"].clone(), line["id"].clone()),
+            (json!("exec_approval_request"), json!("1"))
+        );
+        messages_tx
+            .send(patch_request("event-2", thread_id))
+            .unwrap();
+        assert_eq!(client.read().await["id"], json!("2"));
+
+        // A reconnecting client first gets every request still pending.
+        drop(client);
+        let mut client = TestClient::connect(&path).await;
+        assert_eq!(client.read().await["id"], json!("1"));
+        assert_eq!(client.read().await["id"], json!("2"));
+
+        client.answer("2").await;
+        assert_eq!(
+            answers_rx.recv().await,
+            Some((
+                thread_id,
+                Op::PatchApproval {
+                    id: "event-2".to_string(),
+                    decision: ReviewDecision::Approved,
+                }
+            ))
+        );
+
+        messages_tx
+            .send(SocketMessage::TurnEnded {
+                thread_id,
+                event_id: "event-1".to_string(),
+            })
+            .unwrap();
+        messages_tx
+            .send(exec_request("event-3", thread_id))
+            .unwrap();
+        assert_eq!(client.read().await["id"], json!("3"));
+
+        // The ended turn's request is no longer offered or answerable.
+        drop(client);
+        let mut client = TestClient::connect(&path).await;
+        assert_eq!(client.read().await["id"], json!("3"));
+        client.answer("1").await;
+        assert_eq!(
+            client.read().await,
+            json!({ "type": "error", "id": "1", "message": "没有编号为 1 的待审批请求" })
+        );
+
+        client.answer("3").await;
+        assert_eq!(
+            answers_rx.recv().await,
+            Some((
+                thread_id,
+                Op::ExecApproval {
+                    id: "event-3".to_string(),
+                    decision: ReviewDecision::Approved,
+                }
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn bind_keeps_a_socket_that_is_still_served() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("approvals.sock");
+
+        let listener = bind_private(&path).unwrap();
+        assert!(ApprovalSocket::bind(path.clone()).is_err());
+        assert!(std::fs::symlink_metadata(&path).is_ok());
+
+        drop(listener);
+        let socket = ApprovalSocket::bind(path.clone()).unwrap();
+        assert!(tokio::net::UnixStream::connect(&path).await.is_ok());
+        drop(socket);
+    }
+
+    #[test]
+    fn parses_answers() {
+        assert_eq!(
+            parse_answer(r#"{"id": "3", "decision": "approved_for_session"}"#).unwrap(),
+            ApprovalAnswer {
+                id: "3".to_string(),
+                decision: ReviewDecision::ApprovedForSession,
+            }
+        );
+        assert!(parse_answer(r#"{"id": "3", "decision": "maybe"}"#).is_err());
+        assert_eq!(
+            error_line(Some("9"), "没有编号为 9 的待审批请求".to_string()),
+            r#"{"type":"error","id":"9","message":"没有编号为 9 的待审批请求"}"#
+        );
+    }
+}
@@ -0,0 +1,573 @@
+//! `codex exec batch`: runs every task listed in a JSONL file in a thread of
+//! its own, a bounded number at a time.
+//!
+//! Each finished task gets a `<id>.txt` file with the agent's last message and
+//! a line in `summary.jsonl`. Tasks that were interrupted get neither, so
+//! `--resume` reruns exactly the tasks without a summary line.
+
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use codex_core::NewThread;
+use codex_core::ThreadManager;
+use codex_core::config::Config;
+use codex_core::config::ConfigBuilder;
+use codex_core::git_info::get_git_repo_root;
+use codex_core::models_manager::manager::RefreshStrategy;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::approvals::ElicitationAction;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::TokenUsage;
+use codex_protocol::user_input::UserInput;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::Semaphore;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tracing::warn;
+
+use crate::TURN_LIMIT_EXIT_CODE;
+use crate::cli::BatchArgs;
+
+const SUMMARY_FILE_NAME: &str = "summary.jsonl";
+
+pub(crate) struct BatchContext {
+    pub(crate) thread_manager: Arc<ThreadManager>,
+    pub(crate) config: Config,
+    /// Carries the `-c` overrides and cloud requirements used for `config`, so
+    /// tasks with their own `cwd` or `model` get a config loaded the same way.
+    pub(crate) config_builder: ConfigBuilder,
+    pub(crate) skip_git_repo_check: bool,
+}
+
+/// One line of the tasks file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TaskLine {
+    id: String,
+    prompt: String,
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    #[serde(default)]
+    model: Option<String>,
+    /// Path to a JSON Schema file, or the schema itself.
+    #[serde(default)]
+    output_schema: Option<Value>,
+}
+
+#[derive(Debug, PartialEq)]
+struct BatchTask {
+    id: String,
+    prompt: String,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    output_schema: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TaskStatus {
+    Succeeded,
+    Failed,
+}
+
+/// One line of `summary.jsonl`.
+#[derive(Debug, Serialize)]
+struct TaskSummary {
+    id: String,
+    status: TaskStatus,
+    exit_code: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rollout_path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_message_file: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_usage: Option<TokenUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl TaskSummary {
+    fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            status: TaskStatus::Failed,
+            exit_code: 1,
+            thread_id: None,
+            rollout_path: None,
+            last_message_file: None,
+            token_usage: None,
+            error: None,
+        }
+    }
+
+    fn failed(id: &str, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::new(id)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RecordedTask {
+    id: String,
+}
+
+pub(crate) async fn run_batch(ctx: BatchContext, args: BatchArgs) -> anyhow::Result<()> {
+    let BatchArgs {
+        tasks: tasks_path,
+        concurrency,
+        output_dir,
+        resume,
+    } = args;
+
+    let contents = std::fs::read_to_string(&tasks_path)
+        .with_context(|| format!("读取任务文件 {} 失败", tasks_path.display()))?;
+    let base_dir = std::path::absolute(tasks_path.parent().unwrap_or(Path::new("")))?;
+    let tasks = parse_tasks(&contents, &base_dir)?;
+
+    let output_dir = output_dir.unwrap_or_else(|| default_output_dir(&tasks_path));
+    std::fs::create_dir_all(&output_dir)
+        .with_context(|| format!("创建输出目录 {} 失败", output_dir.display()))?;
+    let summary_path = output_dir.join(SUMMARY_FILE_NAME);
+    let completed = if resume {
+        load_completed(&summary_path)?
+    } else if summary_path.exists() {
+        anyhow::bail!(
+            "{} 已存在。使用 --resume 继续之前的批处理，或使用 --output-dir 指定其他目录。",
+            summary_path.display()
+        );
+    } else {
+        HashSet::new()
+    };
+
+    let total = tasks.len();
+    let pending: Vec<BatchTask> = tasks
+        .into_iter()
+        .filter(|task| !completed.contains(&task.id))
+        .collect();
+    eprintln!(
+        "批处理：共 {total} 个任务，{} 个已完成，{} 个待运行。",
+        total - pending.len(),
+        pending.len()
+    );
+
+    let mut summary_file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&summary_path)
+        .with_context(|| format!("打开 {} 失败", summary_path.display()))?;
+    if contents_end_mid_line(&summary_path)? {
+        // Start a fresh line after an entry cut short by an earlier run.
+        summary_file.write_all(b"\n")?;
+    }
+    let summary_file = Arc::new(std::sync::Mutex::new(summary_file));
+
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            tracing::debug!("Keyboard interrupt");
+            let _ = cancel_tx.send(true);
+        }
+    });
+
+    let ctx = Arc::new(ctx);
+    let semaphore = Arc::new(Semaphore::new(usize::from(concurrency)));
+    let output_dir = Arc::new(output_dir);
+    let mut join_set = JoinSet::new();
+    let mut unfinished = 0;
+    let pending_count = pending.len();
+    for (index, task) in pending.into_iter().enumerate() {
+        // Taking the permit here starts tasks in file order.
+        let permit = Arc::clone(&semaphore).acquire_owned().await?;
+        if *cancel_rx.borrow() {
+            unfinished += pending_count - index;
+            break;
+        }
+        let ctx = Arc::clone(&ctx);
+        let output_dir = Arc::clone(&output_dir);
+        let summary_file = Arc::clone(&summary_file);
+        let cancel_rx = cancel_rx.clone();
+        join_set.spawn(async move {
+            let _permit = permit;
+            eprintln!("[{}] 开始", task.id);
+            let Some(summary) = run_task(&ctx, &task, &output_dir, cancel_rx).await else {
+                eprintln!("[{}] 已中断", task.id);
+                return None;
+            };
+            match &summary.error {
+                Some(error) => eprintln!("[{}] 失败：{error}", task.id),
+                None => eprintln!("[{}] 完成", task.id),
+            }
+            if let Err(err) = append_summary(&summary_file, &summary) {
+                eprintln!("[{}] 写入 {SUMMARY_FILE_NAME} 失败：{err}", task.id);
+                return None;
+            }
+            Some(summary.status)
+        });
+    }
+
+    let mut succeeded = 0;
+    let mut failed = 0;
+    while let Some(result) = join_set.join_next().await {
+        match result {
+            Ok(Some(TaskStatus::Succeeded)) => succeeded += 1,
+            Ok(Some(TaskStatus::Failed)) => failed += 1,
+            Ok(None) => unfinished += 1,
+            Err(err) => {
+                warn!("batch task panicked: {err}");
+                unfinished += 1;
+            }
+        }
+    }
+
+    eprintln!("批处理结束：成功 {succeeded} 个，失败 {failed} 个，未完成 {unfinished} 个。");
+    if unfinished > 0 {
+        eprintln!("使用 --resume 运行同一命令可继续未完成的任务。");
+    }
+    if failed > 0 || unfinished > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Runs `task` to completion in a new thread. Returns `None` when the task
+/// was interrupted and should run again on `--resume`.
+async fn run_task(
+    ctx: &BatchContext,
+    task: &BatchTask,
+    output_dir: &Path,
+    mut cancel_rx: watch::Receiver<bool>,
+) -> Option<TaskSummary> {
+    let (config, model) = match task_config(ctx, task).await {
+        Ok(config) => config,
+        Err(err) => return Some(TaskSummary::failed(&task.id, err.to_string())),
+    };
+    let NewThread {
+        thread_id,
+        thread,
+        session_configured,
+    } = match ctx.thread_manager.start_thread(config.clone()).await {
+        Ok(new_thread) => new_thread,
+        Err(err) => return Some(TaskSummary::failed(&task.id, err.to_string())),
+    };
+
+    let mut summary = TaskSummary::new(&task.id);
+    summary.thread_id = Some(thread_id.to_string());
+    summary.rollout_path = session_configured.rollout_path;
+
+    let submitted = thread
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: task.prompt.clone(),
+                // Batch tasks don't track UI element ranges, so none are available here.
+                text_elements: Vec::new(),
+            }],
+            cwd: config.cwd.clone(),
+            approval_policy: config.approval_policy.value(),
+            sandbox_policy: config.sandbox_policy.get().clone(),
+            model,
+            effort: config.model_reasoning_effort,
+            summary: config.model_reasoning_summary,
+            final_output_json_schema: task.output_schema.clone(),
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await;
+    if let Err(err) = submitted {
+        ctx.thread_manager.remove_thread(&thread_id).await;
+        summary.error = Some(err.to_string());
+        return Some(summary);
+    }
+
+    let mut last_message = None;
+    let mut interrupted = false;
+    let mut interrupt_sent = false;
+    loop {
+        let event = tokio::select! {
+            event = thread.next_event() => event,
+            changed = cancel_rx.changed(), if !interrupt_sent => {
+                interrupt_sent = true;
+                if changed.is_ok() {
+                    thread.submit(Op::Interrupt).await.ok();
+                }
+                continue;
+            }
+        };
+        let event = match event {
+            Ok(event) => event,
+            Err(err) => {
+                summary.error = Some(err.to_string());
+                break;
+            }
+        };
+        match event.msg {
+            EventMsg::TokenCount(ev) => {
+                if let Some(info) = ev.info {
+                    summary.token_usage = Some(info.total_token_usage);
+                }
+            }
+            EventMsg::Error(ev) => {
+                if matches!(
+                    ev.codex_error_info,
+                    Some(CodexErrorInfo::TurnLimitReached { .. })
+                ) {
+                    summary.exit_code = TURN_LIMIT_EXIT_CODE;
+                }
+                summary.error = Some(ev.message);
+            }
+            EventMsg::ElicitationRequest(ev) => {
+                // Nobody can answer elicitations in batch mode either.
+                thread
+                    .submit(Op::ResolveElicitation {
+                        server_name: ev.server_name,
+                        request_id: ev.id,
+                        decision: ElicitationAction::Cancel,
+                    })
+                    .await
+                    .ok();
+            }
+            EventMsg::TurnComplete(ev) => {
+                last_message = ev.last_agent_message;
+                thread.submit(Op::Shutdown).await.ok();
+            }
+            EventMsg::TurnAborted(_) => {
+                interrupted = true;
+                thread.submit(Op::Shutdown).await.ok();
+            }
+            EventMsg::ShutdownComplete => break,
+            _ => {}
+        }
+    }
+    ctx.thread_manager.remove_thread(&thread_id).await;
+    if interrupted {
+        return None;
+    }
+
+    if let Some(message) = last_message {
+        let path = output_dir.join(format!("{}.txt", task.id));
+        match std::fs::write(&path, message) {
+            Ok(()) => summary.last_message_file = Some(path),
+            Err(err) if summary.error.is_none() => {
+                summary.error = Some(format!("写入最后一条消息到 {} 失败：{err}", path.display()));
+            }
+            Err(_) => {}
+        }
+    }
+    if summary.error.is_none() {
+        summary.status = TaskStatus::Succeeded;
+        summary.exit_code = 0;
+    }
+    Some(summary)
+}
+
+/// Loads the config for `task` and resolves the model its turn should use.
+async fn task_config(ctx: &BatchContext, task: &BatchTask) -> anyhow::Result<(Config, String)> {
+    let config = if task.cwd.is_none() && task.model.is_none() {
+        ctx.config.clone()
+    } else {
+        let mut overrides = ctx.config.harness_overrides.clone();
+        if let Some(cwd) = &task.cwd {
+            overrides.cwd = Some(cwd.clone());
+        }
+        if let Some(model) = &task.model {
+            overrides.model = Some(model.clone());
+        }
+        ctx.config_builder
+            .clone()
+            .harness_overrides(overrides)
+            .build()
+            .await?
+    };
+    if !ctx.skip_git_repo_check && get_git_repo_root(&config.cwd).is_none() {
+        anyhow::bail!(
+            "{} 不在受信任目录中，且未指定 --skip-git-repo-check。",
+            config.cwd.display()
+        );
+    }
+    let model = ctx
+        .thread_manager
+        .get_models_manager()
+        .get_default_model(&config.model, &config, RefreshStrategy::OnlineIfUncached)
+        .await;
+    Ok((config, model))
+}
+
+/// Parses the tasks file. Relative `cwd` and `output_schema` paths are
+/// resolved against `base_dir`, the directory holding the file.
+fn parse_tasks(contents: &str, base_dir: &Path) -> anyhow::Result<Vec<BatchTask>> {
+    let mut tasks = Vec::new();
+    let mut ids = HashSet::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let TaskLine {
+            id,
+            prompt,
+            cwd,
+            model,
+            output_schema,
+        } = serde_json::from_str(line)
+            .with_context(|| format!("任务文件第 {line_number} 行无效"))?;
+        if !is_valid_task_id(&id) {
+            anyhow::bail!(
+                "任务文件第 {line_number} 行：id `{id}` 只能包含字母、数字、`-`、`_` 和 `.`，且不能以 `.` 开头"
+            );
+        }
+        if !ids.insert(id.clone()) {
+            anyhow::bail!("任务文件第 {line_number} 行：id `{id}` 重复");
+        }
+        let output_schema = match output_schema {
+            None => None,
+            Some(Value::String(path)) => {
+                let path = base_dir.join(path);
+                let schema = std::fs::read_to_string(&path)
+                    .with_context(|| format!("读取输出 schema 文件 {} 失败", path.display()))?;
+                Some(serde_json::from_str(&schema).with_context(|| {
+                    format!("输出 schema 文件 {} 不是有效的 JSON", path.display())
+                })?)
+            }
+            Some(schema @ Value::Object(_)) => Some(schema),
+            Some(_) => anyhow::bail!(
+                "任务文件第 {line_number} 行：output_schema 必须是文件路径或 JSON 对象"
+            ),
+        };
+        tasks.push(BatchTask {
+            id,
+            prompt,
+            cwd: cwd.map(|cwd| base_dir.join(cwd)),
+            model,
+            output_schema,
+        });
+    }
+    Ok(tasks)
+}
+
+/// Task ids double as file names in the output directory.
+fn is_valid_task_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// `tasks.jsonl` writes its results to `tasks.results/` next to it.
+fn default_output_dir(tasks_path: &Path) -> PathBuf {
+    tasks_path.with_extension("results")
+}
+
+/// Ids of the tasks already recorded in `summary_path`. A line cut short by
+/// an interruption is skipped, so its task runs again.
+fn load_completed(summary_path: &Path) -> anyhow::Result<HashSet<String>> {
+    let contents = match std::fs::read_to_string(summary_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(HashSet::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("读取 {} 失败", summary_path.display()));
+        }
+    };
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<RecordedTask>(line).ok())
+        .map(|recorded| recorded.id)
+        .collect())
+}
+
+fn contents_end_mid_line(path: &Path) -> std::io::Result<bool> {
+    let contents = std::fs::read(path)?;
+    Ok(contents.last().is_some_and(|byte| *byte != b'\n'))
+}
+
+fn append_summary(
+    summary_file: &std::sync::Mutex<std::fs::File>,
+    summary: &TaskSummary,
+) -> anyhow::Result<()> {
+    let mut line = serde_json::to_string(summary)?;
+    line.push('\n');
+    let mut file = summary_file
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    file.write_all(line.as_bytes())?;
+    file.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_tasks_resolves_paths_and_inline_schemas() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("schema.json"), r#"{"type":"object"}"#)?;
+        let contents = [
+            r#"{"id":"fix-1","prompt":"fix the bug","cwd":"repo","model":"gpt-5.1","output_schema":"schema.json"}"#,
+            "",
+            r#"{"id":"docs","prompt":"write docs","output_schema":{"type":"string"}}"#,
+        ]
+        .join("\n");
+
+        let tasks = parse_tasks(&contents, dir.path())?;
+
+        assert_eq!(
+            tasks,
+            vec![
+                BatchTask {
+                    id: "fix-1".to_string(),
+                    prompt: "fix the bug".to_string(),
+                    cwd: Some(dir.path().join("repo")),
+                    model: Some("gpt-5.1".to_string()),
+                    output_schema: Some(serde_json::json!({"type": "object"})),
+                },
+                BatchTask {
+                    id: "docs".to_string(),
+                    prompt: "write docs".to_string(),
+                    cwd: None,
+                    model: None,
+                    output_schema: Some(serde_json::json!({"type": "string"})),
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_tasks_rejects_duplicate_and_unsafe_ids() {
+        let base_dir = Path::new("/tmp");
+        let duplicate = "{\"id\":\"a\",\"prompt\":\"x\"}\n{\"id\":\"a\",\"prompt\":\"y\"}";
+        assert!(parse_tasks(duplicate, base_dir).is_err());
+        assert!(parse_tasks(r#"{"id":"../a","prompt":"x"}"#, base_dir).is_err());
+        assert!(parse_tasks(r#"{"id":"a","prompt":"x","extra":1}"#, base_dir).is_err());
+    }
+
+    #[test]
+    fn load_completed_skips_truncated_lines() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let summary_path = dir.path().join(SUMMARY_FILE_NAME);
+        assert_eq!(load_completed(&summary_path)?, HashSet::new());
+
+        std::fs::write(
+            &summary_path,
+            "{\"id\":\"a\",\"status\":\"succeeded\",\"exit_code\":0}\n{\"id\":\"b\",\"sta",
+        )?;
+        assert_eq!(
+            load_completed(&summary_path)?,
+            HashSet::from(["a".to_string()])
+        );
+        Ok(())
+    }
+}
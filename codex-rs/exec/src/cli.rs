@@ -70,10 +70,36 @@ pub struct Cli {
     #[arg(long = "add-dir", value_name = "目录", value_hint = clap::ValueHint::DirPath)]
     pub add_dir: Vec<PathBuf>,
 
+    /// 在该路径创建 Unix 套接字，通过 JSONL 发出命令和补丁的审批请求并读取回复，
+    /// 由其他进程决定是否批准。启用后，需要提升权限的命令会等待审批。
+    #[arg(
+        long = "approval-socket",
+        value_name = "路径",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with = "dangerously_bypass_approvals_and_sandbox"
+    )]
+    pub approval_socket: Option<PathBuf>,
+
     /// 以临时模式运行：不会将会话文件持久化到磁盘。
     #[arg(long = "ephemeral", global = true, default_value_t = false)]
     pub ephemeral: bool,
 
+    /// 每轮最多向模型发起的请求数，超出后终止本轮。
+    #[arg(long = "max-turns", value_name = "次数", global = true)]
+    pub max_turns: Option<u32>,
+
+    /// 每轮最多执行的工具调用数，超出后终止本轮。
+    #[arg(long = "max-tool-calls", value_name = "次数", global = true)]
+    pub max_tool_calls: Option<u32>,
+
+    /// 每轮的运行时长上限（秒），超时后终止本轮。
+    #[arg(long = "timeout", value_name = "秒", global = true)]
+    pub timeout: Option<u64>,
+
+    /// 工具调用连续失败达到该次数后终止本轮。
+    #[arg(long = "max-consecutive-failures", value_name = "次数", global = true)]
+    pub max_consecutive_failures: Option<u32>,
+
     /// JSON Schema 文件路径，用于描述模型最终响应的结构。
     #[arg(long = "output-schema", value_name = "文件")]
     pub output_schema: Option<PathBuf>,
@@ -110,6 +136,9 @@ pub enum Command {
 
     /// 针对当前仓库运行代码评审。
     Review(ReviewArgs),
+
+    /// 依次运行 JSONL 文件中列出的任务，可并发执行，中断后可继续。
+    Batch(BatchArgs),
 }
 
 #[derive(Args, Debug)]
@@ -209,7 +238,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "uncommitted",
         default_value_t = false,
-        conflicts_with_all = ["base", "commit", "prompt"]
+        conflicts_with_all = ["base", "commit", "range", "patch", "prompt"]
     )]
     pub uncommitted: bool,
 
@@ -217,7 +246,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "base",
         value_name = "分支",
-        conflicts_with_all = ["uncommitted", "commit", "prompt"]
+        conflicts_with_all = ["uncommitted", "commit", "range", "patch", "prompt"]
     )]
     pub base: Option<String>,
 
@@ -225,7 +254,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "commit",
         value_name = "提交SHA",
-        conflicts_with_all = ["uncommitted", "base", "prompt"]
+        conflicts_with_all = ["uncommitted", "base", "range", "path", "patch", "prompt"]
     )]
     pub commit: Option<String>,
 
@@ -233,9 +262,87 @@ pub struct ReviewArgs {
     #[arg(long = "title", value_name = "标题", requires = "commit")]
     pub commit_title: Option<String>,
 
+    /// 评审某个提交范围（`基准..目标`）内的全部变更；省略目标时为 `HEAD`。
+    #[arg(
+        long = "range",
+        value_name = "范围",
+        conflicts_with_all = ["uncommitted", "base", "commit", "path", "patch", "prompt"]
+    )]
+    pub range: Option<String>,
+
+    /// 仅评审指定路径下的变更，可重复使用；与 `--base` 搭配时相对于该分支评审。
+    #[arg(
+        long = "path",
+        value_name = "路径",
+        conflicts_with_all = ["commit", "range", "patch", "prompt"]
+    )]
+    pub paths: Vec<String>,
+
+    /// 评审补丁文件中的变更，无需先应用补丁。
+    #[arg(
+        long = "patch",
+        value_name = "文件",
+        conflicts_with_all = ["uncommitted", "base", "commit", "range", "path", "prompt"]
+    )]
+    pub patch: Option<PathBuf>,
+
     /// 自定义评审指令。若使用 `-`，则从 stdin 读取。
     #[arg(value_name = "提示", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
+
+    /// 评审结果输出到 stdout 的格式，便于 CI 生成 PR 注解。若评审未产生结果，
+    /// 非文本格式不向 stdout 输出任何内容，并以非零状态退出。
+    #[arg(long = "format", value_enum, default_value_t = ReviewOutputFormat::Text)]
+    pub format: ReviewOutputFormat,
+
+    /// 若存在优先级不低于该级别的评审意见（0 = P0 最紧急），则以非零状态退出。
+    #[arg(
+        long = "fail-on-priority",
+        value_name = "优先级",
+        value_parser = clap::value_parser!(i32).range(0..=3)
+    )]
+    pub fail_on_priority: Option<i32>,
+}
+
+#[derive(Parser, Debug)]
+pub struct BatchArgs {
+    /// 任务文件，每行一个 JSON 对象：`id`、`prompt`，以及可选的 `cwd`、`model`、`output_schema`。
+    #[arg(value_name = "任务文件")]
+    pub tasks: PathBuf,
+
+    /// 同时运行的任务数上限。
+    #[arg(
+        long = "concurrency",
+        short = 'j',
+        value_name = "数量",
+        default_value_t = 4,
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    pub concurrency: u16,
+
+    /// 写入每个任务最后一条消息和 `summary.jsonl` 的目录；默认为任务文件旁的 `<任务文件名>.results`。
+    #[arg(long = "output-dir", value_name = "目录")]
+    pub output_dir: Option<PathBuf>,
+
+    /// 继续之前中断的批处理：跳过 `summary.jsonl` 中已记录的任务。
+    #[arg(long = "resume", default_value_t = false)]
+    pub resume: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewOutputFormat {
+    /// 人类可读的文本（默认）。
+    #[default]
+    Text,
+    /// 包含全部评审意见的 JSON 文档。
+    Json,
+    /// SARIF 2.1.0，可上传到代码扫描。
+    Sarif,
+    /// GitHub Actions 工作流命令注解。
+    Github,
+    /// GitLab Code Quality 报告。
+    GitlabCodequality,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
@@ -281,4 +388,41 @@ mod tests {
         });
         assert_eq!(effective_prompt.as_deref(), Some(PROMPT));
     }
+
+    #[test]
+    fn batch_parses_tasks_file_and_options() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "batch",
+            "tasks.jsonl",
+            "-j",
+            "2",
+            "--resume",
+            "--skip-git-repo-check",
+        ]);
+
+        assert!(cli.skip_git_repo_check);
+        let Some(Command::Batch(args)) = cli.command else {
+            panic!("expected batch command");
+        };
+        assert_eq!(args.tasks, PathBuf::from("tasks.jsonl"));
+        assert_eq!(args.concurrency, 2);
+        assert_eq!(args.output_dir, None);
+        assert!(args.resume);
+    }
+
+    #[test]
+    fn approval_socket_conflicts_with_bypass() {
+        let cli = Cli::parse_from(["codex-exec", "--approval-socket", "/tmp/codex.sock", "hi"]);
+        assert_eq!(cli.approval_socket, Some(PathBuf::from("/tmp/codex.sock")));
+
+        let result = Cli::try_parse_from([
+            "codex-exec",
+            "--approval-socket",
+            "/tmp/codex.sock",
+            "--dangerously-bypass-approvals-and-sandbox",
+            "hi",
+        ]);
+        assert!(result.is_err());
+    }
 }
@@ -253,6 +253,32 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 );
                 ts_msg!(self, "{}", "自动取消（exec 模式不支持）".style(self.dimmed));
             }
+            EventMsg::ExecApprovalRequest(ev) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    "审批请求".style(self.magenta),
+                    escape_command(&ev.command).style(self.bold)
+                );
+                ts_msg!(
+                    self,
+                    "{}",
+                    "等待通过 --approval-socket 回复".style(self.dimmed)
+                );
+            }
+            EventMsg::ApplyPatchApprovalRequest(ev) => {
+                ts_msg!(
+                    self,
+                    "{} 修改 {} 个文件",
+                    "审批请求".style(self.magenta),
+                    ev.changes.len()
+                );
+                ts_msg!(
+                    self,
+                    "{}",
+                    "等待通过 --approval-socket 回复".style(self.dimmed)
+                );
+            }
             EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }) => {
                 let last_message = last_agent_message
                     .as_deref()
@@ -757,8 +783,6 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             }
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::ThreadNameUpdated(_)
-            | EventMsg::ExecApprovalRequest(_)
-            | EventMsg::ApplyPatchApprovalRequest(_)
             | EventMsg::TerminalInteraction(_)
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
@@ -767,6 +791,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::ListRemoteSkillsResponse(_)
             | EventMsg::RemoteSkillDownloaded(_)
+            | EventMsg::ContextBreakdown(_)
+            | EventMsg::ContextItemsDropped(_)
             | EventMsg::RawResponseItem(_)
             | EventMsg::UserMessage(_)
             | EventMsg::EnteredReviewMode(_)
@@ -781,6 +807,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ReasoningContentDelta(_)
             | EventMsg::ReasoningRawContentDelta(_)
             | EventMsg::SkillsUpdateAvailable
+            | EventMsg::ConfigReloaded(_)
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_)
             | EventMsg::ThreadRolledBack(_)
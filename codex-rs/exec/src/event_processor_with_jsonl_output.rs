@@ -35,12 +35,14 @@ use crate::exec_events::TodoItem;
 use crate::exec_events::TodoListItem;
 use crate::exec_events::TurnCompletedEvent;
 use crate::exec_events::TurnFailedEvent;
+use crate::exec_events::TurnFailedReason;
 use crate::exec_events::TurnStartedEvent;
 use crate::exec_events::Usage;
 use crate::exec_events::WebSearchItem;
 use codex_core::config::Config;
 use codex_core::protocol;
 use codex_core::protocol::AgentStatus as CoreAgentStatus;
+use codex_core::protocol::CodexErrorInfo;
 use codex_core::protocol::CollabAgentInteractionBeginEvent;
 use codex_core::protocol::CollabAgentInteractionEndEvent;
 use codex_core::protocol::CollabAgentSpawnBeginEvent;
@@ -49,6 +51,7 @@ use codex_core::protocol::CollabCloseBeginEvent;
 use codex_core::protocol::CollabCloseEndEvent;
 use codex_core::protocol::CollabWaitingBeginEvent;
 use codex_core::protocol::CollabWaitingEndEvent;
+use codex_core::protocol::TurnLimit;
 use codex_protocol::models::WebSearchAction;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -70,6 +73,7 @@ pub struct EventProcessorWithJsonOutput {
     running_collab_tool_calls: HashMap<String, RunningCollabToolCall>,
     running_web_search_calls: HashMap<String, String>,
     last_critical_error: Option<ThreadErrorEvent>,
+    last_turn_limit: Option<TurnFailedReason>,
 }
 
 #[derive(Debug, Clone)]
@@ -113,6 +117,7 @@ impl EventProcessorWithJsonOutput {
             running_collab_tool_calls: HashMap::new(),
             running_web_search_calls: HashMap::new(),
             last_critical_error: None,
+            last_turn_limit: None,
         }
     }
 
@@ -166,6 +171,16 @@ impl EventProcessorWithJsonOutput {
                     message: ev.message.clone(),
                 };
                 self.last_critical_error = Some(error.clone());
+                if let Some(CodexErrorInfo::TurnLimitReached { limit }) = &ev.codex_error_info {
+                    self.last_turn_limit = Some(match limit {
+                        TurnLimit::MaxTurns => TurnFailedReason::MaxTurns,
+                        TurnLimit::MaxToolCalls => TurnFailedReason::MaxToolCalls,
+                        TurnLimit::Timeout => TurnFailedReason::Timeout,
+                        TurnLimit::MaxConsecutiveFailures => {
+                            TurnFailedReason::MaxConsecutiveFailures
+                        }
+                    });
+                }
                 vec![ThreadEvent::Error(error)]
             }
             protocol::EventMsg::Warning(ev) => {
@@ -746,6 +761,7 @@ impl EventProcessorWithJsonOutput {
 
     fn handle_task_started(&mut self, _: &protocol::TurnStartedEvent) -> Vec<ThreadEvent> {
         self.last_critical_error = None;
+        self.last_turn_limit = None;
         vec![ThreadEvent::TurnStarted(TurnStartedEvent {})]
     }
 
@@ -788,7 +804,10 @@ impl EventProcessorWithJsonOutput {
         }
 
         if let Some(error) = self.last_critical_error.take() {
-            items.push(ThreadEvent::TurnFailed(TurnFailedEvent { error }));
+            items.push(ThreadEvent::TurnFailed(TurnFailedEvent {
+                error,
+                reason: self.last_turn_limit.take(),
+            }));
         } else {
             items.push(ThreadEvent::TurnCompleted(TurnCompletedEvent { usage }));
         }
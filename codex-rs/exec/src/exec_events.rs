@@ -54,6 +54,20 @@ pub struct TurnCompletedEvent {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct TurnFailedEvent {
     pub error: ThreadErrorEvent,
+    /// Set when the turn was stopped by a configured limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub reason: Option<TurnFailedReason>,
+}
+
+/// The limit that stopped a turn.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum TurnFailedReason {
+    MaxTurns,
+    MaxToolCalls,
+    Timeout,
+    MaxConsecutiveFailures,
 }
 
 /// Describes the usage of tokens during a turn.
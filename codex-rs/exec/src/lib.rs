@@ -4,15 +4,21 @@
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+#[cfg(unix)]
+mod approval_socket;
+mod batch;
 mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod review_report;
 
+pub use cli::BatchArgs;
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
+pub use cli::ReviewOutputFormat;
 use codex_cloud_requirements::cloud_requirements_loader;
 use codex_common::oss::ensure_oss_provider_ready;
 use codex_common::oss::get_default_model_for_oss_provider;
@@ -28,11 +34,13 @@ use codex_core::config::ConfigOverrides;
 use codex_core::config::find_codex_home;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config::types::TurnLimits;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::models_manager::manager::RefreshStrategy;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::CodexErrorInfo;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
@@ -69,6 +77,10 @@ use codex_core::default_client::set_default_originator;
 use codex_core::find_thread_path_by_id_str;
 use codex_core::find_thread_path_by_name_str;
 
+/// Exit status when a turn was stopped by `--max-turns`, `--max-tool-calls`,
+/// `--timeout` or `--max-consecutive-failures`.
+pub(crate) const TURN_LIMIT_EXIT_CODE: i32 = 3;
+
 enum InitialOperation {
     UserTurn {
         items: Vec<UserInput>,
@@ -103,7 +115,12 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         cwd,
         skip_git_repo_check,
         add_dir,
+        approval_socket: approval_socket_path,
         ephemeral,
+        max_turns,
+        max_tool_calls,
+        timeout,
+        max_consecutive_failures,
         color,
         last_message_file,
         json: json_mode,
@@ -153,6 +170,12 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     };
 
+    #[cfg(not(unix))]
+    if approval_socket_path.is_some() {
+        eprintln!("--approval-socket 仅支持 Unix 平台。");
+        std::process::exit(1);
+    }
+
     let resolved_cwd = cwd.clone();
     let config_cwd = match resolved_cwd.as_deref() {
         Some(path) => AbsolutePathBuf::from_absolute_path(path.canonicalize()?)?,
@@ -242,8 +265,13 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         model,
         review_model: None,
         config_profile,
-        // Default to never ask for approvals in headless mode. Feature flags can override.
-        approval_policy: Some(AskForApproval::Never),
+        // Default to never ask for approvals in headless mode, unless another
+        // process answers them over --approval-socket. Feature flags can override.
+        approval_policy: Some(if approval_socket_path.is_some() {
+            AskForApproval::OnRequest
+        } else {
+            AskForApproval::Never
+        }),
         sandbox_mode,
         cwd: resolved_cwd,
         model_provider: model_provider.clone(),
@@ -257,12 +285,20 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         tools_web_search_request: None,
         ephemeral: ephemeral.then_some(true),
         additional_writable_roots: add_dir,
+        turn_limits: TurnLimits {
+            max_turns,
+            max_tool_calls,
+            timeout_secs: timeout,
+            max_consecutive_failures,
+        },
     };
 
-    let config = ConfigBuilder::default()
+    let config_builder = ConfigBuilder::default()
         .cli_overrides(cli_kv_overrides)
+        .cloud_requirements(cloud_requirements);
+    let config = config_builder
+        .clone()
         .harness_overrides(overrides)
-        .cloud_requirements(cloud_requirements)
         .build()
         .await?;
     set_default_client_residency_requirement(config.enforce_residency.value());
@@ -346,11 +382,43 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         auth_manager.clone(),
         SessionSource::Exec,
     ));
+
+    if let Some(ExecCommand::Batch(args)) = command {
+        if prompt.is_some()
+            || !images.is_empty()
+            || last_message_file.is_some()
+            || output_schema_path.is_some()
+            || approval_socket_path.is_some()
+        {
+            eprintln!(
+                "batch 不接受提示、--image、--output-last-message、--output-schema 或 --approval-socket 参数；请在任务文件中为每个任务设置。"
+            );
+            std::process::exit(1);
+        }
+        let ctx = batch::BatchContext {
+            thread_manager,
+            config,
+            config_builder,
+            skip_git_repo_check: skip_git_repo_check || dangerously_bypass_approvals_and_sandbox,
+        };
+        return batch::run_batch(ctx, args).await;
+    }
+
     let default_model = thread_manager
         .get_models_manager()
         .get_default_model(&config.model, &config, RefreshStrategy::OnlineIfUncached)
         .await;
 
+    let (review_format, fail_on_priority) = match command.as_ref() {
+        Some(ExecCommand::Review(args)) => (args.format, args.fail_on_priority),
+        _ => (ReviewOutputFormat::Text, None),
+    };
+    if json_mode && review_format != ReviewOutputFormat::Text {
+        eprintln!("--format 不能与 --json 同时使用。");
+        std::process::exit(1);
+    }
+    let review_root = get_git_repo_root(&default_cwd).unwrap_or_else(|| default_cwd.clone());
+
     // Handle resume subcommand by resolving a rollout path and using explicit resume API.
     let NewThread {
         thread_id: primary_thread_id,
@@ -435,6 +503,16 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
 
     info!("Codex initialized with event: {session_configured:?}");
 
+    #[cfg(unix)]
+    let approval_socket = match approval_socket_path.map(approval_socket::ApprovalSocket::bind) {
+        Some(Ok(approval_socket)) => Some(approval_socket),
+        Some(Err(err)) => {
+            eprintln!("{err:#}");
+            std::process::exit(1);
+        }
+        None => None,
+    };
+
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ThreadEventEnvelope>();
     let attached_threads = Arc::new(Mutex::new(HashSet::from([primary_thread_id])));
     spawn_thread_listener(primary_thread_id, thread.clone(), tx.clone());
@@ -514,6 +592,8 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
+    let mut turn_limit_reached = false;
+    let mut review_output = None;
     while let Some(envelope) = rx.recv().await {
         let ThreadEventEnvelope {
             thread_id,
@@ -530,8 +610,21 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 })
                 .await?;
         }
-        if matches!(event.msg, EventMsg::Error(_)) {
+        #[cfg(unix)]
+        if let Some(approval_socket) = &approval_socket {
+            approval_socket.forward(thread_id, &thread, &event);
+        }
+        if let EventMsg::Error(ev) = &event.msg {
             error_seen = true;
+            turn_limit_reached |= matches!(
+                ev.codex_error_info,
+                Some(CodexErrorInfo::TurnLimitReached { .. })
+            );
+        }
+        if thread_id == primary_thread_id
+            && let EventMsg::ExitedReviewMode(ev) = &event.msg
+        {
+            review_output = ev.review_output.clone();
         }
         if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
             continue;
@@ -549,8 +642,34 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             CodexStatus::Shutdown => continue,
         }
     }
-    event_processor.print_final_output();
-    if error_seen {
+    // Remove the socket before any of the exits below.
+    #[cfg(unix)]
+    drop(approval_socket);
+    let review_report = review_output.as_ref().and_then(|output| {
+        review_report::render_review_report(review_format, output, &review_root)
+    });
+    // A structured format without a review result leaves stdout empty rather
+    // than falling back to plain text a CI parser would choke on.
+    let report_missing = review_format != ReviewOutputFormat::Text && review_report.is_none();
+    match review_report {
+        // Structured formats replace the final message so stdout stays parseable.
+        #[allow(clippy::print_stdout)]
+        Some(report) => println!("{report}"),
+        None if report_missing => eprintln!("评审未产生结果，没有可输出的报告。"),
+        None => event_processor.print_final_output(),
+    }
+    if turn_limit_reached {
+        std::process::exit(TURN_LIMIT_EXIT_CODE);
+    }
+    if error_seen || report_missing {
+        std::process::exit(1);
+    }
+    if let Some(threshold) = fail_on_priority
+        && review_output
+            .as_ref()
+            .is_some_and(|output| review_report::has_finding_at_or_above(output, threshold))
+    {
+        eprintln!("评审发现了优先级为 P{threshold} 或更高的问题。");
         std::process::exit(1);
     }
 
@@ -766,7 +885,12 @@ fn resolve_prompt(prompt_arg: Option<String>) -> String {
 }
 
 fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
-    let target = if args.uncommitted {
+    let target = if !args.paths.is_empty() {
+        ReviewTarget::Paths {
+            paths: args.paths,
+            base: args.base,
+        }
+    } else if args.uncommitted {
         ReviewTarget::UncommittedChanges
     } else if let Some(branch) = args.base {
         ReviewTarget::BaseBranch { branch }
@@ -775,6 +899,29 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
             sha,
             title: args.commit_title,
         }
+    } else if let Some(range) = args.range {
+        // `A...B` has to be checked before `A..B`, which would otherwise leave
+        // a leading `.` on the target.
+        if range.contains("...") {
+            anyhow::bail!(
+                "--range 不支持三点范围 `基准...目标`；请使用 `基准..目标`，或用 --base 评审相对于合并基准的改动"
+            );
+        }
+        let (base, head) = range.split_once("..").unwrap_or((range.as_str(), ""));
+        let base = base.trim();
+        if base.is_empty() {
+            anyhow::bail!("--range 需要 `基准..目标` 形式，例如 main..HEAD");
+        }
+        let head = match head.trim() {
+            "" => "HEAD",
+            head => head,
+        };
+        ReviewTarget::CommitRange {
+            base: base.to_string(),
+            head: head.to_string(),
+        }
+    } else if let Some(path) = args.patch {
+        ReviewTarget::PatchFile { path }
     } else if let Some(prompt_arg) = args.prompt {
         let prompt = resolve_prompt(Some(prompt_arg)).trim().to_string();
         if prompt.is_empty() {
@@ -784,7 +931,9 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
             instructions: prompt,
         }
     } else {
-        anyhow::bail!("请指定 --uncommitted、--base、--commit，或提供自定义评审指令");
+        anyhow::bail!(
+            "请指定 --uncommitted、--base、--commit、--range、--path、--patch，或提供自定义评审指令"
+        );
     };
 
     Ok(ReviewRequest {
@@ -805,7 +954,12 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            paths: Vec::new(),
+            patch: None,
             prompt: None,
+            format: ReviewOutputFormat::Text,
+            fail_on_priority: None,
         })
         .expect("builds uncommitted review request");
 
@@ -824,7 +978,12 @@ mod tests {
             base: None,
             commit: Some("123456789".to_string()),
             commit_title: Some("Add review command".to_string()),
+            range: None,
+            paths: Vec::new(),
+            patch: None,
             prompt: None,
+            format: ReviewOutputFormat::Text,
+            fail_on_priority: None,
         })
         .expect("builds commit review request");
 
@@ -846,7 +1005,12 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            paths: Vec::new(),
+            patch: None,
             prompt: Some("  custom review instructions  ".to_string()),
+            format: ReviewOutputFormat::Text,
+            fail_on_priority: None,
         })
         .expect("builds custom review request");
 
@@ -860,6 +1024,44 @@ mod tests {
         assert_eq!(request, expected);
     }
 
+    #[test]
+    fn builds_range_and_path_review_requests() {
+        let args = |range: Option<&str>, paths: &[&str], base: Option<&str>| ReviewArgs {
+            uncommitted: false,
+            base: base.map(str::to_string),
+            commit: None,
+            commit_title: None,
+            range: range.map(str::to_string),
+            paths: paths.iter().map(|path| path.to_string()).collect(),
+            patch: None,
+            prompt: None,
+            format: ReviewOutputFormat::Text,
+            fail_on_priority: None,
+        };
+
+        let request = build_review_request(args(Some("v1.0.."), &[], None)).expect("range");
+        assert_eq!(
+            request.target,
+            ReviewTarget::CommitRange {
+                base: "v1.0".to_string(),
+                head: "HEAD".to_string(),
+            }
+        );
+
+        let request =
+            build_review_request(args(None, &["crates/foo"], Some("main"))).expect("paths");
+        assert_eq!(
+            request.target,
+            ReviewTarget::Paths {
+                paths: vec!["crates/foo".to_string()],
+                base: Some("main".to_string()),
+            }
+        );
+
+        assert!(build_review_request(args(Some("..HEAD"), &[], None)).is_err());
+        assert!(build_review_request(args(Some("main...feature"), &[], None)).is_err());
+    }
+
     #[test]
     fn decode_prompt_bytes_strips_utf8_bom() {
         let input = [0xEF, 0xBB, 0xBF, b'h', b'i', b'\n'];
@@ -0,0 +1,354 @@
+//! Machine-readable renderings of review findings for CI systems.
+//!
+//! Every format carries a fingerprint per finding derived from the
+//! repository-relative path and the title (without the `[P1]` tag), so the
+//! same finding reported on a later run dedupes even if its lines moved.
+
+use std::path::Path;
+
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewOutputEvent;
+use codex_core::review_format::relative_finding_path;
+use codex_core::review_format::review_finding_fingerprint;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::cli::ReviewOutputFormat;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_RULE_ID: &str = "codex-review";
+const FINGERPRINT_KEY: &str = "codexReviewFinding/v1";
+
+/// Renders `output` in `format`, with paths relative to `root`.
+///
+/// Returns `None` for [`ReviewOutputFormat::Text`], which is printed by the
+/// regular event processor.
+pub(crate) fn render_review_report(
+    format: ReviewOutputFormat,
+    output: &ReviewOutputEvent,
+    root: &Path,
+) -> Option<String> {
+    let findings: Vec<ReportFinding> = output
+        .findings
+        .iter()
+        .map(|finding| ReportFinding::new(finding, root))
+        .collect();
+    let rendered = match format {
+        ReviewOutputFormat::Text => return None,
+        ReviewOutputFormat::Json => pretty_json(&render_json(output, &findings)),
+        ReviewOutputFormat::Sarif => pretty_json(&render_sarif(&findings)),
+        ReviewOutputFormat::Github => render_github(&findings),
+        ReviewOutputFormat::GitlabCodequality => pretty_json(&render_gitlab(&findings)),
+    };
+    Some(rendered)
+}
+
+/// Whether any finding is at least as urgent as `threshold` (P0 is the most
+/// urgent).
+pub(crate) fn has_finding_at_or_above(output: &ReviewOutputEvent, threshold: i32) -> bool {
+    output
+        .findings
+        .iter()
+        .any(|finding| finding.priority <= threshold)
+}
+
+struct ReportFinding<'a> {
+    finding: &'a ReviewFinding,
+    path: String,
+    fingerprint: String,
+}
+
+impl<'a> ReportFinding<'a> {
+    fn new(finding: &'a ReviewFinding, root: &Path) -> Self {
+        let path = relative_finding_path(&finding.code_location.absolute_file_path, root);
+        let fingerprint = review_finding_fingerprint(&path, &finding.title);
+        Self {
+            finding,
+            path,
+            fingerprint,
+        }
+    }
+
+    fn start_line(&self) -> u32 {
+        self.finding.code_location.line_range.start.max(1)
+    }
+
+    fn end_line(&self) -> u32 {
+        self.finding
+            .code_location
+            .line_range
+            .end
+            .max(self.start_line())
+    }
+}
+
+fn pretty_json(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+fn render_json(output: &ReviewOutputEvent, findings: &[ReportFinding<'_>]) -> Value {
+    json!({
+        "overall_correctness": output.overall_correctness,
+        "overall_explanation": output.overall_explanation,
+        "overall_confidence_score": output.overall_confidence_score,
+        "findings": findings
+            .iter()
+            .map(|finding| {
+                json!({
+                    "title": finding.finding.title,
+                    "body": finding.finding.body,
+                    "priority": finding.finding.priority,
+                    "confidence_score": finding.finding.confidence_score,
+                    "path": finding.path,
+                    "start_line": finding.start_line(),
+                    "end_line": finding.end_line(),
+                    "fingerprint": finding.fingerprint,
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn sarif_level(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
+
+fn render_sarif(findings: &[ReportFinding<'_>]) -> Value {
+    let results = findings
+        .iter()
+        .map(|finding| {
+            json!({
+                "ruleId": SARIF_RULE_ID,
+                "level": sarif_level(finding.finding.priority),
+                "message": {
+                    "text": format!("{}\n\n{}", finding.finding.title, finding.finding.body),
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": finding.path },
+                        "region": {
+                            "startLine": finding.start_line(),
+                            "endLine": finding.end_line(),
+                        },
+                    },
+                }],
+                "partialFingerprints": { FINGERPRINT_KEY: finding.fingerprint },
+                "properties": {
+                    "priority": finding.finding.priority,
+                    "confidence": finding.finding.confidence_score,
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "codex",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/openai/codex",
+                    "rules": [{
+                        "id": SARIF_RULE_ID,
+                        "shortDescription": { "text": "Codex review finding" },
+                    }],
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn github_level(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=1 => "error",
+        2 => "warning",
+        _ => "notice",
+    }
+}
+
+/// Escapes a workflow command message.
+fn github_escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a workflow command property value.
+fn github_escape_property(value: &str) -> String {
+    github_escape_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+fn render_github(findings: &[ReportFinding<'_>]) -> String {
+    findings
+        .iter()
+        .map(|finding| {
+            format!(
+                "::{level} file={file},line={line},endLine={end_line},title={title}::{body}",
+                level = github_level(finding.finding.priority),
+                file = github_escape_property(&finding.path),
+                line = finding.start_line(),
+                end_line = finding.end_line(),
+                title = github_escape_property(&finding.finding.title),
+                body = github_escape_data(&finding.finding.body),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn gitlab_severity(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=0 => "critical",
+        1 => "major",
+        2 => "minor",
+        _ => "info",
+    }
+}
+
+fn render_gitlab(findings: &[ReportFinding<'_>]) -> Value {
+    Value::Array(
+        findings
+            .iter()
+            .map(|finding| {
+                json!({
+                    "description": finding.finding.title,
+                    "content": { "body": finding.finding.body },
+                    "check_name": SARIF_RULE_ID,
+                    "fingerprint": finding.fingerprint,
+                    "severity": gitlab_severity(finding.finding.priority),
+                    "location": {
+                        "path": finding.path,
+                        "lines": {
+                            "begin": finding.start_line(),
+                            "end": finding.end_line(),
+                        },
+                    },
+                })
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ReviewCodeLocation;
+    use codex_core::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(title: &str, priority: i32, start: u32) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: "Line one\nline two, with 100% detail".to_string(),
+            confidence_score: 0.8,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                line_range: ReviewLineRange {
+                    start,
+                    end: start + 2,
+                },
+            },
+        }
+    }
+
+    fn output(findings: Vec<ReviewFinding>) -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings,
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "One bug.".to_string(),
+            overall_confidence_score: 0.7,
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_priority_tag_and_line_moves() {
+        let root = Path::new("/repo");
+        let first = finding("[P1] Off-by-one in  loop", 1, 10);
+        let moved = finding("[P2] off-by-one in loop", 2, 40);
+        assert_eq!(
+            ReportFinding::new(&first, root).fingerprint,
+            ReportFinding::new(&moved, root).fingerprint
+        );
+        assert_ne!(
+            ReportFinding::new(&first, root).fingerprint,
+            ReportFinding::new(&finding("[P1] Different bug", 1, 10), root).fingerprint
+        );
+    }
+
+    #[test]
+    fn renders_github_annotations() {
+        let report = render_review_report(
+            ReviewOutputFormat::Github,
+            &output(vec![finding("[P1] Bad: thing", 1, 3)]),
+            Path::new("/repo"),
+        )
+        .expect("github report");
+        assert_eq!(
+            report,
+            "::error file=src/lib.rs,line=3,endLine=5,title=[P1] Bad%3A thing::Line one%0Aline two, with 100%25 detail"
+        );
+    }
+
+    #[test]
+    fn renders_sarif_and_gitlab_with_relative_paths() {
+        let review = output(vec![finding("[P3] Nit", 3, 7)]);
+        let root = Path::new("/repo");
+        let expected_fingerprint = review_finding_fingerprint("src/lib.rs", "[P3] Nit");
+
+        let sarif: Value = serde_json::from_str(
+            &render_review_report(ReviewOutputFormat::Sarif, &review, root).expect("sarif"),
+        )
+        .expect("valid json");
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["level"], "note");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/lib.rs"
+        );
+        assert_eq!(
+            result["partialFingerprints"][FINGERPRINT_KEY],
+            expected_fingerprint.as_str()
+        );
+
+        let gitlab: Value = serde_json::from_str(
+            &render_review_report(ReviewOutputFormat::GitlabCodequality, &review, root)
+                .expect("gitlab"),
+        )
+        .expect("valid json");
+        assert_eq!(
+            gitlab,
+            json!([{
+                "description": "[P3] Nit",
+                "content": { "body": "Line one\nline two, with 100% detail" },
+                "check_name": SARIF_RULE_ID,
+                "fingerprint": expected_fingerprint,
+                "severity": "info",
+                "location": { "path": "src/lib.rs", "lines": { "begin": 7, "end": 9 } },
+            }])
+        );
+
+        assert_eq!(
+            render_review_report(ReviewOutputFormat::Text, &review, root),
+            None
+        );
+    }
+
+    #[test]
+    fn priority_threshold_counts_more_urgent_findings() {
+        let review = output(vec![finding("[P2] Medium", 2, 1)]);
+        assert!(has_finding_at_or_above(&review, 2));
+        assert!(has_finding_at_or_above(&review, 3));
+        assert!(!has_finding_at_or_above(&review, 1));
+    }
+}
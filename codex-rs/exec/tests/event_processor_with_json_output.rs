@@ -19,6 +19,7 @@ use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::TurnLimit;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
@@ -51,6 +52,7 @@ use codex_exec::exec_events::TodoItem as ExecTodoItem;
 use codex_exec::exec_events::TodoListItem as ExecTodoListItem;
 use codex_exec::exec_events::TurnCompletedEvent;
 use codex_exec::exec_events::TurnFailedEvent;
+use codex_exec::exec_events::TurnFailedReason;
 use codex_exec::exec_events::TurnStartedEvent;
 use codex_exec::exec_events::Usage;
 use codex_exec::exec_events::WebSearchItem;
@@ -837,10 +839,49 @@ fn error_followed_by_task_complete_produces_turn_failed() {
             error: ThreadErrorEvent {
                 message: "boom".to_string(),
             },
+            reason: None,
         })]
     );
 }
 
+#[test]
+fn turn_limit_error_sets_turn_failed_reason() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+
+    ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::Error(ErrorEvent {
+            message: "Stopped after 3 tool calls (max_tool_calls).".to_string(),
+            codex_error_info: Some(CodexErrorInfo::TurnLimitReached {
+                limit: TurnLimit::MaxToolCalls,
+            }),
+        }),
+    ));
+    let out = ep.collect_thread_events(&event(
+        "e2",
+        EventMsg::TurnComplete(codex_core::protocol::TurnCompleteEvent {
+            last_agent_message: Some("partial".to_string()),
+        }),
+    ));
+    assert_eq!(
+        out,
+        vec![ThreadEvent::TurnFailed(TurnFailedEvent {
+            error: ThreadErrorEvent {
+                message: "Stopped after 3 tool calls (max_tool_calls).".to_string(),
+            },
+            reason: Some(TurnFailedReason::MaxToolCalls),
+        })]
+    );
+    assert_eq!(
+        serde_json::to_value(&out[0]).unwrap(),
+        json!({
+            "type": "turn.failed",
+            "error": { "message": "Stopped after 3 tool calls (max_tool_calls)." },
+            "reason": "max_tool_calls",
+        })
+    );
+}
+
 #[test]
 fn exec_command_end_success_produces_completed_command_item() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
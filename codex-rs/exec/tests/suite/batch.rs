@@ -0,0 +1,105 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn batch_runs_tasks_and_resumes_unfinished_ones() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let tasks_path = test.cwd_path().join("tasks.jsonl");
+    std::fs::write(
+        &tasks_path,
+        concat!(
+            r#"{"id":"first","prompt":"say one"}"#,
+            "\n",
+            r#"{"id":"second","prompt":"say two","output_schema":{"type":"object"}}"#,
+            "\n",
+        ),
+    )?;
+
+    let server = responses::start_mock_server().await;
+    let response_mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp1"),
+                responses::ev_assistant_message("m1", "one"),
+                responses::ev_completed("resp1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_response_created("resp2"),
+                responses::ev_assistant_message("m2", "two"),
+                responses::ev_completed("resp2"),
+            ]),
+        ],
+    )
+    .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("batch")
+        .arg(&tasks_path)
+        .arg("--concurrency")
+        .arg("1")
+        .assert()
+        .success();
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[1].body_json()["text"]["format"]["schema"],
+        serde_json::json!({"type": "object"})
+    );
+
+    let output_dir = test.cwd_path().join("tasks.results");
+    assert_eq!(
+        std::fs::read_to_string(output_dir.join("first.txt"))?,
+        "one"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output_dir.join("second.txt"))?,
+        "two"
+    );
+    let summary: Vec<Value> = std::fs::read_to_string(output_dir.join("summary.jsonl"))?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    let statuses: Vec<(&str, &str, i64)> = summary
+        .iter()
+        .map(|line| {
+            (
+                line["id"].as_str().unwrap(),
+                line["status"].as_str().unwrap(),
+                line["exit_code"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![("first", "succeeded", 0), ("second", "succeeded", 0)]
+    );
+    assert!(summary.iter().all(|line| line["rollout_path"].is_string()));
+
+    // Every task is already recorded, so resuming sends no further requests.
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("batch")
+        .arg(&tasks_path)
+        .arg("--resume")
+        .assert()
+        .success();
+    assert_eq!(response_mock.requests().len(), 2);
+
+    // Without --resume, existing results are not overwritten.
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("batch")
+        .arg(&tasks_path)
+        .assert()
+        .failure();
+
+    Ok(())
+}
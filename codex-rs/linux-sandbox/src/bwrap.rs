@@ -6,6 +6,10 @@
 //! - sensitive subpaths such as `.git` and `.codex` remain read-only even when
 //!   their parent root is writable.
 //!
+//! The `workspace-overlay` policy instead mounts the workspace through a
+//! copy-on-write overlay, so writes land in a scratch layer that Codex reviews
+//! after the command exits.
+//!
 //! The overall Linux sandbox is composed of:
 //! - seccomp + `PR_SET_NO_NEW_PRIVS` applied in-process, and
 //! - bubblewrap used to construct the filesystem view before exec.
@@ -19,18 +23,28 @@ use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::WritableRoot;
 
 /// Options that control how bubblewrap is invoked.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct BwrapOptions {
     /// Whether to mount a fresh `/proc` inside the PID namespace.
     ///
     /// This is the secure default, but some restrictive container environments
     /// deny `--proc /proc` even when PID namespaces are available.
     pub mount_proc: bool,
+    /// Scratch directory that receives the upper and work layers of the
+    /// workspace overlay. Without one, overlay writes go to a tmpfs and are
+    /// discarded when the command exits.
+    pub overlay_dir: Option<PathBuf>,
+    /// Paths masked with empty mounts so the command cannot read them.
+    pub deny_read: Vec<PathBuf>,
 }
 
 impl Default for BwrapOptions {
     fn default() -> Self {
-        Self { mount_proc: true }
+        Self {
+            mount_proc: true,
+            overlay_dir: None,
+            deny_read: Vec::new(),
+        }
     }
 }
 
@@ -62,7 +76,12 @@ fn create_bwrap_flags(
     let mut args = Vec::new();
     args.push("--new-session".to_string());
     args.push("--die-with-parent".to_string());
-    args.extend(create_filesystem_args(sandbox_policy, cwd)?);
+    args.extend(create_filesystem_args(
+        sandbox_policy,
+        cwd,
+        options.overlay_dir.as_deref(),
+        &options.deny_read,
+    )?);
     // Isolate the PID namespace.
     args.push("--unshare-pid".to_string());
     // Mount a fresh /proc unless the caller explicitly disables it.
@@ -82,9 +101,17 @@ fn create_bwrap_flags(
 /// 2. `--bind <root> <root>` re-enables writes for allowed roots.
 /// 3. `--ro-bind <subpath> <subpath>` re-applies read-only protections under
 ///    those writable roots so protected subpaths win.
-/// 4. `--dev-bind /dev/null /dev/null` preserves the common sink even under a
+/// 4. For the overlay policy, the overlay mounts from [`create_overlay_args`].
+/// 5. Deny-read masks from [`create_deny_read_args`], last so no later mount
+///    exposes the masked paths again.
+/// 6. `--dev-bind /dev/null /dev/null` preserves the common sink even under a
 ///    read-only root.
-fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<Vec<String>> {
+fn create_filesystem_args(
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+    overlay_dir: Option<&Path>,
+    deny_read: &[PathBuf],
+) -> Result<Vec<String>> {
     let writable_roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
     ensure_mount_targets_exist(&writable_roots)?;
 
@@ -134,6 +161,12 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
         }
     }
 
+    if let Some(overlay_root) = sandbox_policy.get_overlay_root_with_cwd(cwd) {
+        args.extend(create_overlay_args(&overlay_root, overlay_dir)?);
+    }
+
+    args.extend(create_deny_read_args(deny_read));
+
     // Ensure `/dev/null` remains usable regardless of the root bind.
     args.push("--dev-bind".to_string());
     args.push("/dev/null".to_string());
@@ -142,6 +175,83 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
     Ok(args)
 }
 
+/// Mount `overlay_root` through a copy-on-write overlay.
+///
+/// Writes land in `<overlay_dir>/upper` so the caller can review them once the
+/// command exits, or in an invisible tmpfs when no scratch directory is given.
+/// `/tmp` gets a private tmpfs so build tools have somewhere to write that is
+/// discarded with the sandbox, and read-only subpaths are bound from the real
+/// workspace on top of the overlay.
+fn create_overlay_args(
+    overlay_root: &WritableRoot,
+    overlay_dir: Option<&Path>,
+) -> Result<Vec<String>> {
+    let root = overlay_root.root.as_path();
+    if !root.is_dir() {
+        return Err(CodexErr::UnsupportedOperation(format!(
+            "Sandbox expected overlay root {root}, but it is not a directory.",
+            root = root.display()
+        )));
+    }
+
+    let mut args = Vec::new();
+    let slash_tmp = Path::new("/tmp");
+    if slash_tmp.is_dir() && !slash_tmp.starts_with(root) {
+        args.push("--tmpfs".to_string());
+        args.push(path_to_string(slash_tmp));
+    }
+
+    args.push("--overlay-src".to_string());
+    args.push(path_to_string(root));
+    match overlay_dir {
+        Some(overlay_dir) => {
+            let upper = overlay_dir.join("upper");
+            let work = overlay_dir.join("work");
+            std::fs::create_dir_all(&upper)?;
+            std::fs::create_dir_all(&work)?;
+            args.push("--overlay".to_string());
+            args.push(path_to_string(&upper));
+            args.push(path_to_string(&work));
+        }
+        None => args.push("--tmp-overlay".to_string()),
+    }
+    args.push(path_to_string(root));
+
+    for subpath in &overlay_root.read_only_subpaths {
+        let subpath = subpath.as_path();
+        if subpath.exists() {
+            args.push("--ro-bind".to_string());
+            args.push(path_to_string(subpath));
+            args.push(path_to_string(subpath));
+        }
+    }
+
+    Ok(args)
+}
+
+/// Mask each existing `deny_read` path: directories get an empty read-only
+/// tmpfs and files get `/dev/null`, which the sandbox cannot open because the
+/// bind is `nodev`.
+fn create_deny_read_args(deny_read: &[PathBuf]) -> Vec<String> {
+    let mut args = Vec::new();
+    for path in deny_read {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            args.push("--tmpfs".to_string());
+            args.push(path_to_string(path));
+            args.push("--remount-ro".to_string());
+            args.push(path_to_string(path));
+        } else {
+            args.push("--ro-bind".to_string());
+            args.push("/dev/null".to_string());
+            args.push(path_to_string(path));
+        }
+    }
+    args
+}
+
 /// Collect unique read-only subpaths across all writable roots.
 fn collect_read_only_subpaths(writable_roots: &[WritableRoot]) -> Vec<PathBuf> {
     let mut subpaths: BTreeSet<PathBuf> = BTreeSet::new();
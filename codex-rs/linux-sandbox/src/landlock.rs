@@ -4,6 +4,7 @@
 //! Landlock helpers remain available here as legacy/backup utilities.
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
@@ -37,11 +38,13 @@ use seccompiler::apply_filter;
 /// - enabling `PR_SET_NO_NEW_PRIVS` when restrictions apply, and
 /// - installing the network seccomp filter when network access is disabled.
 ///
-/// Filesystem restrictions are intentionally handled by bubblewrap.
+/// Filesystem restrictions are intentionally handled by bubblewrap. On the
+/// legacy Landlock pipeline, `deny_read` paths are left out of the read rules.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
     apply_landlock_fs: bool,
+    deny_read: &[PathBuf],
 ) -> Result<()> {
     // `PR_SET_NO_NEW_PRIVS` is required for seccomp, but it also prevents
     // setuid privilege elevation. Many `bwrap` deployments rely on setuid, so
@@ -63,7 +66,7 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
             .into_iter()
             .map(|writable_root| writable_root.root)
             .collect();
-        install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
+        install_filesystem_landlock_rules_on_current_thread(writable_roots, deny_read)?;
     }
 
     // TODO(ragona): Add appropriate restrictions if
@@ -85,6 +88,12 @@ fn set_no_new_privs() -> Result<()> {
 /// access to the entire file-system while restricting write access to
 /// `/dev/null` and the provided list of `writable_roots`.
 ///
+/// Landlock rules only ever grant access, so when `deny_read` is not empty
+/// the read rules are spelled out for everything around the denied paths
+/// (see [`readable_paths_excluding`]). Ancestors of denied paths can then only
+/// be listed, which would hide files the command creates in them, so writable
+/// roots keep full access and denied paths inside them stay readable.
+///
 /// # Errors
 /// Returns [`CodexErr::Sandbox`] variants when the ruleset fails to apply.
 ///
@@ -92,6 +101,7 @@ fn set_no_new_privs() -> Result<()> {
 /// via bubblewrap. It is kept for reference and potential fallback use.
 fn install_filesystem_landlock_rules_on_current_thread(
     writable_roots: Vec<AbsolutePathBuf>,
+    deny_read: &[PathBuf],
 ) -> Result<()> {
     let abi = ABI::V5;
     let access_rw = AccessFs::from_all(abi);
@@ -100,8 +110,27 @@ fn install_filesystem_landlock_rules_on_current_thread(
     let mut ruleset = Ruleset::default()
         .set_compatibility(CompatLevel::BestEffort)
         .handle_access(access_rw)?
-        .create()?
-        .add_rules(landlock::path_beneath_rules(&["/"], access_ro))?
+        .create()?;
+
+    let deny_read: Vec<PathBuf> = deny_read
+        .iter()
+        .filter(|denied| {
+            !writable_roots
+                .iter()
+                .any(|root| denied.starts_with(root.as_path()))
+        })
+        .cloned()
+        .collect();
+    if deny_read.is_empty() {
+        ruleset = ruleset.add_rules(landlock::path_beneath_rules(&["/"], access_ro))?;
+    } else {
+        let (readable, list_only) = readable_paths_excluding(&deny_read);
+        ruleset = ruleset
+            .add_rules(landlock::path_beneath_rules(&readable, access_ro))?
+            .add_rules(landlock::path_beneath_rules(&list_only, AccessFs::ReadDir))?;
+    }
+
+    let mut ruleset = ruleset
         .add_rules(landlock::path_beneath_rules(&["/dev/null"], access_rw))?
         .set_no_new_privs(true);
 
@@ -118,6 +147,46 @@ fn install_filesystem_landlock_rules_on_current_thread(
     Ok(())
 }
 
+/// Splits the filesystem into paths that can be read in full and ancestors of
+/// `deny_read` paths, which can only be listed. Every other path is readable
+/// through one of the returned entries; the denied paths are not.
+fn readable_paths_excluding(deny_read: &[PathBuf]) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut readable = Vec::new();
+    let mut list_only = Vec::new();
+    collect_readable_paths(Path::new("/"), deny_read, &mut readable, &mut list_only);
+    (readable, list_only)
+}
+
+fn collect_readable_paths(
+    path: &Path,
+    deny_read: &[PathBuf],
+    readable: &mut Vec<PathBuf>,
+    list_only: &mut Vec<PathBuf>,
+) {
+    if deny_read.iter().any(|denied| path.starts_with(denied)) {
+        return;
+    }
+    // Follow symlinks so a link to a denied path is not granted either.
+    if let Ok(target) = path.canonicalize()
+        && deny_read.iter().any(|denied| target.starts_with(denied))
+    {
+        return;
+    }
+    let is_real_dir = std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_dir());
+    if !is_real_dir || !deny_read.iter().any(|denied| denied.starts_with(path)) {
+        readable.push(path.to_path_buf());
+        return;
+    }
+
+    list_only.push(path.to_path_buf());
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        collect_readable_paths(&entry.path(), deny_read, readable, list_only);
+    }
+}
+
 /// Installs a seccomp filter that blocks outbound network access except for
 /// AF_UNIX domain sockets.
 ///
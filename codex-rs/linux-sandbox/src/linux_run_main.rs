@@ -6,6 +6,8 @@ use std::os::fd::FromRawFd;
 use std::path::Path;
 use std::path::PathBuf;
 
+use codex_core::protocol::SandboxPolicy;
+
 use crate::bwrap::BwrapOptions;
 use crate::bwrap::create_bwrap_command_args;
 use crate::landlock::apply_sandbox_policy_to_current_thread;
@@ -46,6 +48,20 @@ pub struct LandlockCommand {
     #[arg(long = "no-proc", default_value_t = false)]
     pub no_proc: bool,
 
+    /// Scratch directory for the copy-on-write overlay used by the
+    /// `workspace-overlay` policy. Writes to the workspace land in
+    /// `<overlay-dir>/upper`; without it they are discarded when the command
+    /// exits.
+    #[arg(long = "overlay-dir")]
+    pub overlay_dir: Option<PathBuf>,
+
+    /// Path the command must not be able to read. Directories are replaced by
+    /// an empty tmpfs and files by `/dev/null`; the legacy Landlock pipeline
+    /// leaves them out of its read rules instead, except inside writable
+    /// roots. May be repeated.
+    #[arg(long = "deny-read")]
+    pub deny_read: Vec<PathBuf>,
+
     /// Full command args to run under the Linux sandbox helper.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -65,6 +81,8 @@ pub fn run_main() -> ! {
         use_bwrap_sandbox,
         apply_seccomp_then_exec,
         no_proc,
+        overlay_dir,
+        deny_read,
         command,
     } = LandlockCommand::parse();
 
@@ -76,7 +94,7 @@ pub fn run_main() -> ! {
     // established the filesystem view.
     if apply_seccomp_then_exec {
         if let Err(e) =
-            apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, false)
+            apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, false, &[])
         {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
@@ -85,13 +103,17 @@ pub fn run_main() -> ! {
 
     if sandbox_policy.has_full_disk_write_access() {
         if let Err(e) =
-            apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, false)
+            apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, false, &[])
         {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
         exec_or_panic(command);
     }
 
+    // Landlock cannot express an overlay, so the overlay policy always uses
+    // bubblewrap.
+    let use_bwrap_sandbox =
+        use_bwrap_sandbox || matches!(sandbox_policy, SandboxPolicy::WorkspaceOverlay { .. });
     if use_bwrap_sandbox {
         // Outer stage: bubblewrap first, then re-enter this binary in the
         // sandboxed environment to apply seccomp. This path never falls back
@@ -102,13 +124,21 @@ pub fn run_main() -> ! {
             use_bwrap_sandbox,
             command,
         );
-        run_bwrap_with_proc_fallback(&sandbox_policy_cwd, &sandbox_policy, inner, !no_proc);
+        let options = BwrapOptions {
+            mount_proc: !no_proc,
+            overlay_dir,
+            deny_read,
+        };
+        run_bwrap_with_proc_fallback(&sandbox_policy_cwd, &sandbox_policy, inner, options);
     }
 
     // Legacy path: Landlock enforcement only, when bwrap sandboxing is not enabled.
-    if let Err(e) =
-        apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, true)
-    {
+    if let Err(e) = apply_sandbox_policy_to_current_thread(
+        &sandbox_policy,
+        &sandbox_policy_cwd,
+        true,
+        &deny_read,
+    ) {
         panic!("error applying legacy Linux sandbox restrictions: {e:?}");
     }
     exec_or_panic(command);
@@ -118,16 +148,15 @@ fn run_bwrap_with_proc_fallback(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
     inner: Vec<String>,
-    mount_proc: bool,
+    mut options: BwrapOptions,
 ) -> ! {
-    let mut mount_proc = mount_proc;
-
-    if mount_proc && !preflight_proc_mount_support(sandbox_policy_cwd, sandbox_policy) {
+    if options.mount_proc
+        && !preflight_proc_mount_support(sandbox_policy_cwd, sandbox_policy, &options)
+    {
         eprintln!("codex-linux-sandbox: bwrap could not mount /proc; retrying with --no-proc");
-        mount_proc = false;
+        options.mount_proc = false;
     }
 
-    let options = BwrapOptions { mount_proc };
     let argv = build_bwrap_argv(inner, sandbox_policy, sandbox_policy_cwd, options);
     exec_vendored_bwrap(argv);
 }
@@ -158,13 +187,17 @@ fn build_bwrap_argv(
 fn preflight_proc_mount_support(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
+    options: &BwrapOptions,
 ) -> bool {
     let preflight_command = vec![resolve_true_command()];
     let preflight_argv = build_bwrap_argv(
         preflight_command,
         sandbox_policy,
         sandbox_policy_cwd,
-        BwrapOptions { mount_proc: true },
+        BwrapOptions {
+            mount_proc: true,
+            ..options.clone()
+        },
     );
     let stderr = run_bwrap_in_child_capture_stderr(preflight_argv);
     !is_proc_mount_failure(stderr.as_str())
@@ -342,7 +375,7 @@ mod tests {
             vec!["/bin/true".to_string()],
             &SandboxPolicy::ReadOnly,
             Path::new("/"),
-            BwrapOptions { mount_proc: true },
+            BwrapOptions::default(),
         );
         assert_eq!(
             argv,
@@ -366,4 +399,84 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn mounts_workspace_through_overlay() {
+        let workspace = tempfile::tempdir().unwrap();
+        let scratch = tempfile::tempdir().unwrap();
+        let workspace_path = workspace.path().to_string_lossy().to_string();
+        let argv = build_bwrap_argv(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::WorkspaceOverlay {
+                network_access: false,
+            },
+            workspace.path(),
+            BwrapOptions {
+                mount_proc: false,
+                overlay_dir: Some(scratch.path().to_path_buf()),
+                deny_read: Vec::new(),
+            },
+        );
+
+        let upper = scratch.path().join("upper");
+        let work = scratch.path().join("work");
+        assert_eq!(
+            argv[3..argv.len() - 4].to_vec(),
+            vec![
+                "--ro-bind".to_string(),
+                "/".to_string(),
+                "/".to_string(),
+                "--tmpfs".to_string(),
+                "/tmp".to_string(),
+                "--overlay-src".to_string(),
+                workspace_path.clone(),
+                "--overlay".to_string(),
+                upper.to_string_lossy().to_string(),
+                work.to_string_lossy().to_string(),
+                workspace_path,
+                "--dev-bind".to_string(),
+                "/dev/null".to_string(),
+                "/dev/null".to_string(),
+                "--unshare-pid".to_string(),
+            ]
+        );
+        assert_eq!(upper.is_dir() && work.is_dir(), true);
+    }
+
+    #[test]
+    fn masks_deny_read_paths() {
+        let home = tempfile::tempdir().unwrap();
+        let ssh = home.path().join(".ssh");
+        let env_file = home.path().join(".env");
+        std::fs::create_dir(&ssh).unwrap();
+        std::fs::write(&env_file, "TOKEN=secret\n").unwrap();
+        let argv = build_bwrap_argv(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::ReadOnly,
+            Path::new("/"),
+            BwrapOptions {
+                mount_proc: false,
+                overlay_dir: None,
+                deny_read: vec![ssh.clone(), env_file.clone(), home.path().join("missing")],
+            },
+        );
+
+        let ssh = ssh.to_string_lossy().to_string();
+        assert_eq!(
+            argv[6..argv.len() - 4].to_vec(),
+            vec![
+                "--tmpfs".to_string(),
+                ssh.clone(),
+                "--remount-ro".to_string(),
+                ssh,
+                "--ro-bind".to_string(),
+                "/dev/null".to_string(),
+                env_file.to_string_lossy().to_string(),
+                "--dev-bind".to_string(),
+                "/dev/null".to_string(),
+                "/dev/null".to_string(),
+                "--unshare-pid".to_string(),
+            ]
+        );
+    }
 }
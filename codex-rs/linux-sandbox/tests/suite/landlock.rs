@@ -13,6 +13,7 @@ use codex_core::sandboxing::SandboxPermissions;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 use tempfile::NamedTempFile;
 
@@ -79,6 +80,7 @@ async fn run_cmd_result_with_writable_roots(
         windows_sandbox_level: WindowsSandboxLevel::Disabled,
         justification: None,
         arg0: None,
+        stdin: None,
     };
 
     let sandbox_policy = SandboxPolicy::WorkspaceWrite {
@@ -235,6 +237,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
         windows_sandbox_level: WindowsSandboxLevel::Disabled,
         justification: None,
         arg0: None,
+        stdin: None,
     };
 
     let sandbox_policy = SandboxPolicy::new_read_only_policy();
@@ -407,3 +410,64 @@ async fn sandbox_blocks_dev_tcp_redirection() {
     // all images ship bash, so we guard against 127 as well.
     assert_network_blocked(&["bash", "-c", "echo hi > /dev/tcp/127.0.0.1/80"]).await;
 }
+
+/// Runs `script` through the helper's legacy Landlock pipeline with
+/// `--deny-read` entries, which `process_exec_tool_call` does not pass.
+async fn run_legacy_landlock_with_deny_read(
+    script: &str,
+    writable_root: &Path,
+    deny_read: &[PathBuf],
+) -> std::process::Output {
+    let cwd = std::env::current_dir().unwrap();
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![AbsolutePathBuf::try_from(writable_root).unwrap()],
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+    };
+    let mut command = tokio::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"));
+    command
+        .arg("--sandbox-policy-cwd")
+        .arg(&cwd)
+        .arg("--sandbox-policy")
+        .arg(serde_json::to_string(&sandbox_policy).unwrap());
+    for path in deny_read {
+        command.arg("--deny-read").arg(path);
+    }
+    command.arg("--").args(["bash", "-c", script]);
+    command.output().await.unwrap()
+}
+
+#[tokio::test]
+async fn legacy_landlock_deny_read_keeps_new_files_in_writable_roots_readable() {
+    let workspace = tempfile::tempdir().unwrap();
+    let secrets = tempfile::tempdir().unwrap();
+    let secret = secrets.path().join("token");
+    std::fs::write(&secret, "secret").unwrap();
+    let new_file = workspace.path().join("created-later.txt");
+
+    let output = run_legacy_landlock_with_deny_read(
+        &format!(
+            "echo hello > {new} && mv {new} {new}.moved && cat {new}.moved",
+            new = new_file.to_string_lossy()
+        ),
+        workspace.path(),
+        std::slice::from_ref(&secret),
+    )
+    .await;
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "hello\n",
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = run_legacy_landlock_with_deny_read(
+        &format!("cat {}", secret.to_string_lossy()),
+        workspace.path(),
+        std::slice::from_ref(&secret),
+    )
+    .await;
+    assert_ne!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -38,6 +39,8 @@ pub struct OtelSettings {
     pub trace_exporter: OtelExporter,
     pub metrics_exporter: OtelExporter,
     pub runtime_metrics: bool,
+    /// Serve a Prometheus `/metrics` scrape endpoint on this address.
+    pub prometheus_listen: Option<SocketAddr>,
 }
 
 #[derive(Clone, Debug)]
@@ -73,4 +76,9 @@ pub enum OtelExporter {
         protocol: OtelHttpProtocol,
         tls: Option<OtelTlsConfig>,
     },
+    /// Append OTLP-JSON export requests to rotating files in `directory`,
+    /// one file set per signal, for local analysis without a collector.
+    File {
+        directory: PathBuf,
+    },
 }
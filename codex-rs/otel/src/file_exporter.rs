@@ -0,0 +1,274 @@
+//! Exporters that append OTLP-JSON to local files instead of a collector.
+//!
+//! Each signal writes one export request per line to `<signal>.jsonl` in the
+//! configured directory, in the same JSON encoding OTLP/HTTP uses, so the
+//! files can be replayed into a collector or inspected with `jq`. When the
+//! active file grows past [`MAX_FILE_BYTES`] it is rotated to
+//! `<signal>.1.jsonl`, shifting older files up to [`MAX_ROTATED_FILES`].
+
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::transform::common::tonic::ResourceAttributesWithSchema;
+use opentelemetry_proto::transform::logs::tonic::group_logs_by_resource_and_scope;
+use opentelemetry_proto::transform::trace::tonic::group_spans_by_resource_and_scope;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkError;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::logs::LogBatch;
+use opentelemetry_sdk::logs::LogExporter;
+use opentelemetry_sdk::metrics::Temporality;
+use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use opentelemetry_sdk::metrics::exporter::PushMetricExporter;
+use opentelemetry_sdk::trace::SpanData;
+use opentelemetry_sdk::trace::SpanExporter;
+use serde::Serialize;
+
+/// Size at which the active file is rotated.
+pub(crate) const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Number of rotated files kept per signal, in addition to the active one.
+pub(crate) const MAX_ROTATED_FILES: usize = 4;
+
+/// Appends JSON lines to `<directory>/<signal>.jsonl`, rotating by size.
+struct RotatingFile {
+    directory: PathBuf,
+    signal: &'static str,
+    max_bytes: u64,
+    max_rotated: usize,
+    state: Mutex<Option<(File, u64)>>,
+}
+
+impl fmt::Debug for RotatingFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RotatingFile")
+            .field("directory", &self.directory)
+            .field("signal", &self.signal)
+            .finish()
+    }
+}
+
+impl RotatingFile {
+    fn new(directory: &Path, signal: &'static str) -> io::Result<Self> {
+        fs::create_dir_all(directory)?;
+        Ok(Self {
+            directory: directory.to_path_buf(),
+            signal,
+            max_bytes: MAX_FILE_BYTES,
+            max_rotated: MAX_ROTATED_FILES,
+            state: Mutex::new(None),
+        })
+    }
+
+    fn path(&self, generation: usize) -> PathBuf {
+        if generation == 0 {
+            self.directory.join(format!("{}.jsonl", self.signal))
+        } else {
+            self.directory
+                .join(format!("{}.{generation}.jsonl", self.signal))
+        }
+    }
+
+    fn write_json<T: Serialize>(&self, value: &T) -> OTelSdkResult {
+        let mut line = serde_json::to_vec(value)
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?;
+        line.push(b'\n');
+        self.append(&line)
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))
+    }
+
+    fn append(&self, line: &[u8]) -> io::Result<()> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some((_, size)) = state.as_ref()
+            && *size > 0
+            && size.saturating_add(line.len() as u64) > self.max_bytes
+        {
+            *state = None;
+            self.rotate()?;
+        }
+        if state.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.path(0))?;
+            let size = file.metadata()?.len();
+            *state = Some((file, size));
+        }
+        if let Some((file, size)) = state.as_mut() {
+            file.write_all(line)?;
+            *size = size.saturating_add(line.len() as u64);
+        }
+        Ok(())
+    }
+
+    /// Shifts `<signal>.N.jsonl` to `N + 1`, dropping the oldest file.
+    fn rotate(&self) -> io::Result<()> {
+        if self.max_rotated == 0 {
+            return fs::remove_file(self.path(0));
+        }
+        let oldest = self.path(self.max_rotated);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for generation in (0..self.max_rotated).rev() {
+            let from = self.path(generation);
+            if from.exists() {
+                fs::rename(from, self.path(generation + 1))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&self) -> OTelSdkResult {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match state.as_mut() {
+            Some((file, _)) => file
+                .flush()
+                .map_err(|err| OTelSdkError::InternalFailure(err.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct FileSpanExporter {
+    file: RotatingFile,
+    resource: ResourceAttributesWithSchema,
+}
+
+impl FileSpanExporter {
+    pub(crate) fn new(directory: &Path) -> io::Result<Self> {
+        Ok(Self {
+            file: RotatingFile::new(directory, "traces")?,
+            resource: ResourceAttributesWithSchema::default(),
+        })
+    }
+}
+
+impl SpanExporter for FileSpanExporter {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let request = ExportTraceServiceRequest {
+            resource_spans: group_spans_by_resource_and_scope(batch, &self.resource),
+        };
+        self.file.write_json(&request)
+    }
+
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.file.flush()
+    }
+
+    fn shutdown_with_timeout(&mut self, _timeout: Duration) -> OTelSdkResult {
+        self.file.flush()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.resource = resource.into();
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct FileLogExporter {
+    file: RotatingFile,
+    resource: ResourceAttributesWithSchema,
+}
+
+impl FileLogExporter {
+    pub(crate) fn new(directory: &Path) -> io::Result<Self> {
+        Ok(Self {
+            file: RotatingFile::new(directory, "logs")?,
+            resource: ResourceAttributesWithSchema::default(),
+        })
+    }
+}
+
+impl LogExporter for FileLogExporter {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        let request = ExportLogsServiceRequest {
+            resource_logs: group_logs_by_resource_and_scope(&batch, &self.resource),
+        };
+        self.file.write_json(&request)
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        self.file.flush()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.resource = resource.into();
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct FileMetricExporter {
+    file: RotatingFile,
+}
+
+impl FileMetricExporter {
+    pub(crate) fn new(directory: &Path) -> io::Result<Self> {
+        Ok(Self {
+            file: RotatingFile::new(directory, "metrics")?,
+        })
+    }
+}
+
+impl PushMetricExporter for FileMetricExporter {
+    async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+        let request = ExportMetricsServiceRequest::from(metrics);
+        self.file.write_json(&request)
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.file.flush()
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        self.file.flush()
+    }
+
+    fn temporality(&self) -> Temporality {
+        Temporality::Delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn rotates_by_size_and_keeps_bounded_history() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = RotatingFile {
+            max_bytes: 10,
+            max_rotated: 2,
+            ..RotatingFile::new(dir.path(), "metrics").expect("rotating file")
+        };
+
+        for line in ["aaaaaaa\n", "bbbbbbb\n", "ccccccc\n", "ddddddd\n"] {
+            file.append(line.as_bytes()).expect("append");
+        }
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).expect(name);
+        assert_eq!(read("metrics.jsonl"), "ddddddd\n");
+        assert_eq!(read("metrics.1.jsonl"), "ccccccc\n");
+        assert_eq!(read("metrics.2.jsonl"), "bbbbbbb\n");
+        assert!(!dir.path().join("metrics.3.jsonl").exists());
+    }
+}
@@ -3,6 +3,7 @@ pub mod metrics;
 pub mod otel_provider;
 pub mod traces;
 
+mod file_exporter;
 mod otlp;
 
 use crate::metrics::MetricsClient;
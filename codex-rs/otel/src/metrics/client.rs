@@ -1,9 +1,11 @@
 use crate::config::OtelExporter;
 use crate::config::OtelHttpProtocol;
+use crate::file_exporter::FileMetricExporter;
 use crate::metrics::MetricsError;
 use crate::metrics::Result;
 use crate::metrics::config::MetricsConfig;
 use crate::metrics::config::MetricsExporter;
+use crate::metrics::prometheus::render_prometheus_text;
 use crate::metrics::timer::Timer;
 use crate::metrics::validation::validate_metric_name;
 use crate::metrics::validation::validate_tag_key;
@@ -25,6 +27,7 @@ use opentelemetry_otlp::tonic_types::transport::ClientTlsConfig;
 use opentelemetry_sdk::Resource;
 use opentelemetry_sdk::metrics::InstrumentKind;
 use opentelemetry_sdk::metrics::ManualReader;
+use opentelemetry_sdk::metrics::MeterProviderBuilder;
 use opentelemetry_sdk::metrics::PeriodicReader;
 use opentelemetry_sdk::metrics::Pipeline;
 use opentelemetry_sdk::metrics::SdkMeterProvider;
@@ -86,6 +89,7 @@ struct MetricsClientInner {
     histograms: Mutex<HashMap<String, Histogram<f64>>>,
     duration_histograms: Mutex<HashMap<String, Histogram<f64>>>,
     runtime_reader: Option<Arc<ManualReader>>,
+    prometheus_reader: Option<Arc<ManualReader>>,
     default_tags: BTreeMap<String, String>,
 }
 
@@ -193,6 +197,7 @@ impl MetricsClient {
             exporter,
             export_interval,
             runtime_reader,
+            prometheus_reader,
             default_tags,
         } = config;
 
@@ -219,15 +224,45 @@ impl MetricsClient {
             )
         });
 
-        let (meter_provider, meter) = match exporter {
+        // Scrapes report running totals, so this reader is cumulative.
+        let prometheus_reader = prometheus_reader.then(|| {
+            Arc::new(
+                ManualReader::builder()
+                    .with_temporality(Temporality::Cumulative)
+                    .build(),
+            )
+        });
+
+        let mut provider_builder = SdkMeterProvider::builder().with_resource(resource);
+        for reader in [&runtime_reader, &prometheus_reader].into_iter().flatten() {
+            provider_builder =
+                provider_builder.with_reader(SharedManualReader::new(reader.clone()));
+        }
+        let provider_builder = match exporter {
             MetricsExporter::InMemory(exporter) => {
-                build_provider(resource, exporter, export_interval, runtime_reader.clone())
+                with_periodic_reader(provider_builder, exporter, export_interval)
+            }
+            MetricsExporter::Otlp(OtelExporter::None) if prometheus_reader.is_some() => {
+                provider_builder
+            }
+            MetricsExporter::Otlp(OtelExporter::File { directory }) => {
+                let exporter = FileMetricExporter::new(&directory).map_err(|err| {
+                    MetricsError::InvalidConfig {
+                        message: format!(
+                            "failed to open metrics directory {}: {err}",
+                            directory.display()
+                        ),
+                    }
+                })?;
+                with_periodic_reader(provider_builder, exporter, export_interval)
             }
             MetricsExporter::Otlp(exporter) => {
                 let exporter = build_otlp_metric_exporter(exporter, Temporality::Delta)?;
-                build_provider(resource, exporter, export_interval, runtime_reader.clone())
+                with_periodic_reader(provider_builder, exporter, export_interval)
             }
         };
+        let meter_provider = provider_builder.build();
+        let meter = meter_provider.meter(METER_NAME);
 
         Ok(Self(std::sync::Arc::new(MetricsClientInner {
             meter_provider,
@@ -236,6 +271,7 @@ impl MetricsClient {
             histograms: Mutex::new(HashMap::new()),
             duration_histograms: Mutex::new(HashMap::new()),
             runtime_reader,
+            prometheus_reader,
             default_tags,
         })))
     }
@@ -284,6 +320,18 @@ impl MetricsClient {
         Ok(snapshot)
     }
 
+    /// Render cumulative totals in the Prometheus text exposition format.
+    pub fn prometheus_text(&self) -> Result<String> {
+        let Some(reader) = &self.0.prometheus_reader else {
+            return Err(MetricsError::PrometheusUnavailable);
+        };
+        let mut snapshot = ResourceMetrics::default();
+        reader
+            .collect(&mut snapshot)
+            .map_err(|source| MetricsError::PrometheusCollect { source })?;
+        Ok(render_prometheus_text(&snapshot))
+    }
+
     /// Flush metrics and stop the underlying OTEL meter provider.
     pub fn shutdown(&self) -> Result<()> {
         self.0.shutdown()
@@ -306,12 +354,11 @@ fn os_resource_attributes() -> Vec<KeyValue> {
     attributes
 }
 
-fn build_provider<E>(
-    resource: Resource,
+fn with_periodic_reader<E>(
+    builder: MeterProviderBuilder,
     exporter: E,
     interval: Option<Duration>,
-    runtime_reader: Option<Arc<ManualReader>>,
-) -> (SdkMeterProvider, Meter)
+) -> MeterProviderBuilder
 where
     E: opentelemetry_sdk::metrics::exporter::PushMetricExporter + 'static,
 {
@@ -319,14 +366,7 @@ where
     if let Some(interval) = interval {
         reader_builder = reader_builder.with_interval(interval);
     }
-    let reader = reader_builder.build();
-    let mut provider_builder = SdkMeterProvider::builder().with_resource(resource);
-    if let Some(reader) = runtime_reader {
-        provider_builder = provider_builder.with_reader(SharedManualReader::new(reader));
-    }
-    let provider = provider_builder.with_reader(reader).build();
-    let meter = provider.meter(METER_NAME);
-    (provider, meter)
+    builder.with_reader(reader_builder.build())
 }
 
 fn build_otlp_metric_exporter(
@@ -335,6 +375,9 @@ fn build_otlp_metric_exporter(
 ) -> Result<opentelemetry_otlp::MetricExporter> {
     match exporter {
         OtelExporter::None => Err(MetricsError::ExporterDisabled),
+        OtelExporter::File { .. } => Err(MetricsError::InvalidConfig {
+            message: "the file exporter is not an OTLP endpoint".to_string(),
+        }),
         OtelExporter::Statsig => build_otlp_metric_exporter(
             crate::config::resolve_exporter(&OtelExporter::Statsig),
             temporality,
@@ -43,4 +43,13 @@ pub enum MetricsError {
         #[source]
         source: opentelemetry_sdk::error::OTelSdkError,
     },
+
+    #[error("Prometheus metrics reader is not enabled")]
+    PrometheusUnavailable,
+
+    #[error("failed to collect metrics for Prometheus")]
+    PrometheusCollect {
+        #[source]
+        source: opentelemetry_sdk::error::OTelSdkError,
+    },
 }
@@ -2,6 +2,7 @@ mod client;
 mod config;
 mod error;
 pub(crate) mod names;
+pub(crate) mod prometheus;
 pub(crate) mod runtime_metrics;
 pub(crate) mod timer;
 pub(crate) mod validation;
@@ -0,0 +1,363 @@
+//! Prometheus text exposition for Codex metrics.
+//!
+//! Long-running servers can expose a `/metrics` scrape endpoint backed by a
+//! cumulative pull reader, so metrics are available without an OTLP
+//! collector. Metric names are the OpenTelemetry names with `.` replaced by
+//! `_`; monotonic counters gain the conventional `_total` suffix.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::metrics::data::AggregatedMetrics;
+use opentelemetry_sdk::metrics::data::Metric;
+use opentelemetry_sdk::metrics::data::MetricData;
+use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use opentelemetry_sdk::metrics::data::ScopeMetrics;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::sync::Semaphore;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::warn;
+
+use crate::metrics::MetricsClient;
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Upper bound on the request head we read before answering.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Time a scrape gets to send its request and read the response.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections served at once; further ones are closed right away.
+const MAX_CONNECTIONS: usize = 16;
+
+/// Binds `addr` and serves `GET /metrics` from `metrics` on the current tokio
+/// runtime until the process exits.
+pub(crate) fn spawn_prometheus_server(addr: SocketAddr, metrics: MetricsClient) -> io::Result<()> {
+    let handle = tokio::runtime::Handle::try_current().map_err(|_| {
+        io::Error::other("the Prometheus metrics endpoint requires a tokio runtime")
+    })?;
+    // Bind synchronously so a port conflict is reported at startup.
+    let listener = std::net::TcpListener::bind(addr)?;
+    listener.set_nonblocking(true)?;
+    let local_addr = listener.local_addr()?;
+    handle.spawn(async move {
+        let listener = match TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(err) => {
+                warn!("failed to start Prometheus metrics endpoint on {local_addr}: {err}");
+                return;
+            }
+        };
+        debug!("serving Prometheus metrics on http://{local_addr}/metrics");
+        let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let Ok(permit) = Arc::clone(&connections).try_acquire_owned() else {
+                        debug!("closing Prometheus scrape: too many open connections");
+                        continue;
+                    };
+                    let metrics = metrics.clone();
+                    tokio::spawn(async move {
+                        let _permit = permit;
+                        if let Err(err) = serve_connection(stream, &metrics).await {
+                            debug!("Prometheus scrape failed: {err}");
+                        }
+                    });
+                }
+                Err(err) => warn!("failed to accept Prometheus scrape: {err}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Answers one scrape, giving up on clients that stall while sending the
+/// request or reading the response.
+async fn serve_connection<S>(stream: S, metrics: &MetricsClient) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    timeout(CONNECTION_TIMEOUT, handle_connection(stream, metrics))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "scrape timed out"))?
+}
+
+async fn handle_connection<S>(mut stream: S, metrics: &MetricsClient) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..read]);
+        if request.len() > MAX_REQUEST_BYTES {
+            break;
+        }
+    }
+
+    let request_line = String::from_utf8_lossy(&request);
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default();
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => match metrics.prometheus_text() {
+            Ok(body) => ("200 OK", CONTENT_TYPE, body),
+            Err(err) => (
+                "500 Internal Server Error",
+                "text/plain; charset=utf-8",
+                format!("{err}\n"),
+            ),
+        },
+        ("GET", _) => ("404 Not Found", "text/plain; charset=utf-8", String::new()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            String::new(),
+        ),
+    };
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Renders `snapshot` in the Prometheus text exposition format (0.0.4).
+pub(crate) fn render_prometheus_text(snapshot: &ResourceMetrics) -> String {
+    let mut families: BTreeMap<String, Family> = BTreeMap::new();
+    for metric in snapshot.scope_metrics().flat_map(ScopeMetrics::metrics) {
+        add_metric(&mut families, metric);
+    }
+
+    let mut out = String::new();
+    for (name, family) in families {
+        if !family.help.is_empty() {
+            let _ = writeln!(out, "# HELP {name} {}", escape_help(&family.help));
+        }
+        let _ = writeln!(out, "# TYPE {name} {}", family.kind);
+        for sample in family.samples {
+            out.push_str(&sample);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+struct Family {
+    kind: &'static str,
+    help: String,
+    samples: Vec<String>,
+}
+
+fn add_metric(families: &mut BTreeMap<String, Family>, metric: &Metric) {
+    let base = sanitize_metric_name(metric.name());
+    let help = metric.description().to_string();
+    match metric.data() {
+        AggregatedMetrics::F64(data) => add_data(families, &base, help, data, |v| *v),
+        AggregatedMetrics::U64(data) => add_data(families, &base, help, data, |v| *v as f64),
+        AggregatedMetrics::I64(data) => add_data(families, &base, help, data, |v| *v as f64),
+    }
+}
+
+fn add_data<T>(
+    families: &mut BTreeMap<String, Family>,
+    base: &str,
+    help: String,
+    data: &MetricData<T>,
+    to_f64: impl Fn(&T) -> f64,
+) {
+    match data {
+        MetricData::Sum(sum) => {
+            let (name, kind) = if sum.is_monotonic() {
+                (format!("{base}_total"), "counter")
+            } else {
+                (base.to_string(), "gauge")
+            };
+            let family = family(families, &name, kind, help);
+            for point in sum.data_points() {
+                family.samples.push(sample(
+                    &name,
+                    &labels(point.attributes(), None),
+                    to_f64(&point.value()),
+                ));
+            }
+        }
+        MetricData::Gauge(gauge) => {
+            let family = family(families, base, "gauge", help);
+            for point in gauge.data_points() {
+                family.samples.push(sample(
+                    base,
+                    &labels(point.attributes(), None),
+                    to_f64(&point.value()),
+                ));
+            }
+        }
+        MetricData::Histogram(histogram) => {
+            let family = family(families, base, "histogram", help);
+            for point in histogram.data_points() {
+                let attributes: Vec<&KeyValue> = point.attributes().collect();
+                let mut cumulative = 0u64;
+                for (bound, count) in point.bounds().zip(point.bucket_counts()) {
+                    cumulative = cumulative.saturating_add(count);
+                    family.samples.push(sample(
+                        &format!("{base}_bucket"),
+                        &labels(attributes.iter().copied(), Some(&format_value(bound))),
+                        cumulative as f64,
+                    ));
+                }
+                family.samples.push(sample(
+                    &format!("{base}_bucket"),
+                    &labels(attributes.iter().copied(), Some("+Inf")),
+                    point.count() as f64,
+                ));
+                family.samples.push(sample(
+                    &format!("{base}_sum"),
+                    &labels(attributes.iter().copied(), None),
+                    to_f64(&point.sum()),
+                ));
+                family.samples.push(sample(
+                    &format!("{base}_count"),
+                    &labels(attributes.iter().copied(), None),
+                    point.count() as f64,
+                ));
+            }
+        }
+        // Codex does not record exponential histograms.
+        MetricData::ExponentialHistogram(_) => {}
+    }
+}
+
+fn family<'a>(
+    families: &'a mut BTreeMap<String, Family>,
+    name: &str,
+    kind: &'static str,
+    help: String,
+) -> &'a mut Family {
+    families.entry(name.to_string()).or_insert_with(|| Family {
+        kind,
+        help,
+        samples: Vec::new(),
+    })
+}
+
+fn sample(name: &str, labels: &str, value: f64) -> String {
+    format!("{name}{labels} {}", format_value(value))
+}
+
+fn labels<'a>(attributes: impl Iterator<Item = &'a KeyValue>, le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = attributes
+        .map(|kv| {
+            format!(
+                "{}=\"{}\"",
+                sanitize_label_name(kv.key.as_str()),
+                escape_label_value(&kv.value.to_string())
+            )
+        })
+        .collect();
+    pairs.sort();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{le}\""));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn sanitize_metric_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn sanitize_label_name(name: &str) -> String {
+    sanitize_metric_name(name).replace(':', "_")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::MetricsConfig;
+    use opentelemetry_sdk::metrics::InMemoryMetricExporter;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sanitizes_names_and_label_values() {
+        assert_eq!(
+            sanitize_metric_name("codex.tool.call.duration_ms"),
+            "codex_tool_call_duration_ms"
+        );
+        assert_eq!(sanitize_metric_name("9lives"), "_9lives");
+        assert_eq!(escape_label_value("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stalled_scrapes_time_out() {
+        let metrics = MetricsClient::new(MetricsConfig::in_memory(
+            "test",
+            "codex-cli",
+            env!("CARGO_PKG_VERSION"),
+            InMemoryMetricExporter::default(),
+        ))
+        .expect("metrics client");
+        let (_client, server) = tokio::io::duplex(64);
+
+        let err = serve_connection(server, &metrics)
+            .await
+            .expect_err("stalled scrape");
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::config::OtelExporter;
 use crate::config::OtelHttpProtocol;
 use crate::config::OtelSettings;
+use crate::file_exporter::FileLogExporter;
+use crate::file_exporter::FileSpanExporter;
 use crate::metrics::MetricsClient;
 use crate::metrics::MetricsConfig;
 use opentelemetry::Context;
@@ -72,7 +74,9 @@ impl OtelProvider {
         let trace_enabled = !matches!(settings.trace_exporter, OtelExporter::None);
 
         let metric_exporter = crate::config::resolve_exporter(&settings.metrics_exporter);
-        let metrics = if matches!(metric_exporter, OtelExporter::None) {
+        let metrics = if matches!(metric_exporter, OtelExporter::None)
+            && settings.prometheus_listen.is_none()
+        {
             None
         } else {
             let mut config = MetricsConfig::otlp(
@@ -84,11 +88,23 @@ impl OtelProvider {
             if settings.runtime_metrics {
                 config = config.with_runtime_reader();
             }
+            if settings.prometheus_listen.is_some() {
+                config = config.with_prometheus_reader();
+            }
             Some(MetricsClient::new(config)?)
         };
 
         if let Some(metrics) = metrics.as_ref() {
             crate::metrics::install_global(metrics.clone());
+            if let Some(addr) = settings.prometheus_listen {
+                crate::metrics::prometheus::spawn_prometheus_server(addr, metrics.clone())
+                    .map_err(|err| {
+                        std::io::Error::new(
+                            err.kind(),
+                            format!("failed to serve Prometheus metrics on {addr}: {err}"),
+                        )
+                    })?;
+            }
         }
 
         if !log_enabled && !trace_enabled && metrics.is_none() {
@@ -242,6 +258,13 @@ fn build_logger(
     match crate::config::resolve_exporter(exporter) {
         OtelExporter::None => return Ok(builder.build()),
         OtelExporter::Statsig => unreachable!("statsig exporter should be resolved"),
+        OtelExporter::File { directory } => {
+            debug!(
+                "Using OTLP-JSON file exporter for logs: {}",
+                directory.display()
+            );
+            builder = builder.with_batch_exporter(FileLogExporter::new(&directory)?);
+        }
         OtelExporter::OtlpGrpc {
             endpoint,
             headers,
@@ -309,6 +332,17 @@ fn build_tracer_provider(
     let span_exporter = match crate::config::resolve_exporter(exporter) {
         OtelExporter::None => return Ok(SdkTracerProvider::builder().build()),
         OtelExporter::Statsig => unreachable!("statsig exporter should be resolved"),
+        OtelExporter::File { directory } => {
+            debug!(
+                "Using OTLP-JSON file exporter for traces: {}",
+                directory.display()
+            );
+            let processor = BatchSpanProcessor::builder(FileSpanExporter::new(&directory)?).build();
+            return Ok(SdkTracerProvider::builder()
+                .with_resource(resource.clone())
+                .with_span_processor(processor)
+                .build());
+        }
         OtelExporter::OtlpGrpc {
             endpoint,
             headers,
@@ -0,0 +1,60 @@
+use codex_otel::metrics::MetricsClient;
+use codex_otel::metrics::MetricsConfig;
+use codex_otel::metrics::MetricsError;
+use codex_otel::metrics::Result;
+use opentelemetry_sdk::metrics::InMemoryMetricExporter;
+use std::time::Duration;
+
+// Scrapes report running totals in the Prometheus text format.
+#[test]
+fn prometheus_text_renders_cumulative_counters_and_histograms() -> Result<()> {
+    let config = MetricsConfig::in_memory(
+        "test",
+        "codex-app-server",
+        env!("CARGO_PKG_VERSION"),
+        InMemoryMetricExporter::default(),
+    )
+    .with_tag("service", "codex-app-server")?
+    .with_prometheus_reader();
+    let metrics = MetricsClient::new(config)?;
+
+    metrics.counter("codex.tool.call", 1, &[("tool", "shell")])?;
+    metrics.counter("codex.tool.call", 2, &[("tool", "shell")])?;
+    metrics.record_duration(
+        "codex.tool.call.duration_ms",
+        Duration::from_millis(40),
+        &[("tool", "shell")],
+    )?;
+
+    // Collecting twice must not reset the cumulative totals.
+    let _ = metrics.prometheus_text()?;
+    let text = metrics.prometheus_text()?;
+
+    assert!(text.contains("# TYPE codex_tool_call_total counter\n"));
+    assert!(
+        text.contains("codex_tool_call_total{service=\"codex-app-server\",tool=\"shell\"} 3\n")
+    );
+    assert!(text.contains("# TYPE codex_tool_call_duration_ms histogram\n"));
+    assert!(text.contains(
+        "codex_tool_call_duration_ms_count{service=\"codex-app-server\",tool=\"shell\"} 1\n"
+    ));
+    assert!(text.contains(
+        "codex_tool_call_duration_ms_bucket{service=\"codex-app-server\",tool=\"shell\",le=\"+Inf\"} 1\n"
+    ));
+    Ok(())
+}
+
+#[test]
+fn prometheus_text_requires_reader() -> Result<()> {
+    let metrics = MetricsClient::new(MetricsConfig::in_memory(
+        "test",
+        "codex-cli",
+        env!("CARGO_PKG_VERSION"),
+        InMemoryMetricExporter::default(),
+    ))?;
+    assert!(matches!(
+        metrics.prometheus_text(),
+        Err(MetricsError::PrometheusUnavailable)
+    ));
+    Ok(())
+}
@@ -93,4 +93,11 @@ pub struct ApplyPatchApprovalRequestEvent {
     /// When set, the agent is asking the user to allow writes under this root for the remainder of the session.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grant_root: Option<PathBuf>,
+    /// When set, `changes` were made by a command running in the copy-on-write
+    /// workspace overlay rather than proposed by `apply_patch`. `Approved`
+    /// copies them into the workspace and `Denied` discards them. When more
+    /// than one file changed, `ReviewEachFile` asks for each file to be
+    /// reviewed in its own request instead.
+    #[serde(default)]
+    pub overlay_review: bool,
 }
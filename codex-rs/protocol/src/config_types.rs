@@ -62,6 +62,9 @@ pub enum SandboxMode {
     #[serde(rename = "workspace-write")]
     WorkspaceWrite,
 
+    #[serde(rename = "workspace-overlay")]
+    WorkspaceOverlay,
+
     #[serde(rename = "danger-full-access")]
     DangerFullAccess,
 }
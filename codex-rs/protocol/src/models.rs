@@ -224,6 +224,8 @@ const SANDBOX_MODE_DANGER_FULL_ACCESS: &str =
     include_str!("prompts/permissions/sandbox_mode/danger_full_access.md");
 const SANDBOX_MODE_WORKSPACE_WRITE: &str =
     include_str!("prompts/permissions/sandbox_mode/workspace_write.md");
+const SANDBOX_MODE_WORKSPACE_OVERLAY: &str =
+    include_str!("prompts/permissions/sandbox_mode/workspace_overlay.md");
 const SANDBOX_MODE_READ_ONLY: &str = include_str!("prompts/permissions/sandbox_mode/read_only.md");
 
 impl DeveloperInstructions {
@@ -308,6 +310,7 @@ impl DeveloperInstructions {
                 let roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
                 (SandboxMode::WorkspaceWrite, Some(roots))
             }
+            SandboxPolicy::WorkspaceOverlay { .. } => (SandboxMode::WorkspaceOverlay, None),
         };
 
         DeveloperInstructions::from_permissions_with_network(
@@ -383,6 +386,7 @@ impl DeveloperInstructions {
         let template = match mode {
             SandboxMode::DangerFullAccess => SANDBOX_MODE_DANGER_FULL_ACCESS.trim_end(),
             SandboxMode::WorkspaceWrite => SANDBOX_MODE_WORKSPACE_WRITE.trim_end(),
+            SandboxMode::WorkspaceOverlay => SANDBOX_MODE_WORKSPACE_OVERLAY.trim_end(),
             SandboxMode::ReadOnly => SANDBOX_MODE_READ_ONLY.trim_end(),
         };
         let text = template.replace("{network_access}", &network_access.to_string());
@@ -465,7 +469,9 @@ impl From<SandboxMode> for DeveloperInstructions {
     fn from(mode: SandboxMode) -> Self {
         let network_access = match mode {
             SandboxMode::DangerFullAccess => NetworkAccess::Enabled,
-            SandboxMode::WorkspaceWrite | SandboxMode::ReadOnly => NetworkAccess::Restricted,
+            SandboxMode::WorkspaceWrite | SandboxMode::WorkspaceOverlay | SandboxMode::ReadOnly => {
+                NetworkAccess::Restricted
+            }
         };
 
         DeveloperInstructions::sandbox_text(mode, network_access)
@@ -297,6 +297,17 @@ pub enum Op {
     /// responsible for undoing any edits on disk.
     ThreadRollback { num_turns: u32 },
 
+    /// Request a breakdown of what currently occupies the context window.
+    /// Reply is delivered via `EventMsg::ContextBreakdown`.
+    GetContextBreakdown,
+
+    /// Replace specific tool outputs or images in the in-memory context with a
+    /// short placeholder before the next turn.
+    ///
+    /// `item_ids` are the `id` values reported in `ContextBreakdownEvent::largest_items`.
+    /// Reply is delivered via `EventMsg::ContextItemsDropped`.
+    DropContextItems { item_ids: Vec<String> },
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
@@ -423,6 +434,19 @@ pub enum SandboxPolicy {
         #[serde(default)]
         exclude_slash_tmp: bool,
     },
+
+    /// Same as `ReadOnly`, except that the current working directory is
+    /// mounted through a copy-on-write overlay. Commands write into a scratch
+    /// layer, and Codex asks the user which of the resulting changes to copy
+    /// into the workspace once the command exits. Only the Linux sandbox
+    /// supports overlays; on other platforms the workspace stays read-only.
+    #[serde(rename = "workspace-overlay")]
+    WorkspaceOverlay {
+        /// When set to `true`, outbound network access is allowed. `false` by
+        /// default.
+        #[serde(default)]
+        network_access: bool,
+    },
 }
 
 /// A writable root path accompanied by a list of subpaths that should remain
@@ -493,6 +517,7 @@ impl SandboxPolicy {
             SandboxPolicy::ExternalSandbox { .. } => true,
             SandboxPolicy::ReadOnly => false,
             SandboxPolicy::WorkspaceWrite { .. } => false,
+            SandboxPolicy::WorkspaceOverlay { .. } => false,
         }
     }
 
@@ -502,6 +527,29 @@ impl SandboxPolicy {
             SandboxPolicy::ExternalSandbox { network_access } => network_access.is_enabled(),
             SandboxPolicy::ReadOnly => false,
             SandboxPolicy::WorkspaceWrite { network_access, .. } => *network_access,
+            SandboxPolicy::WorkspaceOverlay { network_access } => *network_access,
+        }
+    }
+
+    /// Returns the directory that is mounted through a copy-on-write overlay
+    /// (tailored to the current working directory) together with subpaths
+    /// that should remain read-only inside it. Writes to the overlay never
+    /// reach the real directory, so it is not among the writable roots.
+    pub fn get_overlay_root_with_cwd(&self, cwd: &Path) -> Option<WritableRoot> {
+        match self {
+            SandboxPolicy::WorkspaceOverlay { .. } => {
+                match AbsolutePathBuf::from_absolute_path(cwd) {
+                    Ok(cwd) => Some(writable_root_with_read_only_subpaths(cwd)),
+                    Err(e) => {
+                        error!("Ignoring invalid cwd {:?} for sandbox overlay: {}", cwd, e);
+                        None
+                    }
+                }
+            }
+            SandboxPolicy::DangerFullAccess
+            | SandboxPolicy::ReadOnly
+            | SandboxPolicy::ExternalSandbox { .. }
+            | SandboxPolicy::WorkspaceWrite { .. } => None,
         }
     }
 
@@ -513,6 +561,7 @@ impl SandboxPolicy {
             SandboxPolicy::DangerFullAccess => Vec::new(),
             SandboxPolicy::ExternalSandbox { .. } => Vec::new(),
             SandboxPolicy::ReadOnly => Vec::new(),
+            SandboxPolicy::WorkspaceOverlay { .. } => Vec::new(),
             SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 exclude_tmpdir_env_var,
@@ -575,52 +624,53 @@ impl SandboxPolicy {
                 // For each root, compute subpaths that should remain read-only.
                 roots
                     .into_iter()
-                    .map(|writable_root| {
-                        let mut subpaths: Vec<AbsolutePathBuf> = Vec::new();
-                        #[allow(clippy::expect_used)]
-                        let top_level_git = writable_root
-                            .join(".git")
-                            .expect(".git is a valid relative path");
-                        // This applies to typical repos (directory .git), worktrees/submodules
-                        // (file .git with gitdir pointer), and bare repos when the gitdir is the
-                        // writable root itself.
-                        let top_level_git_is_file = top_level_git.as_path().is_file();
-                        let top_level_git_is_dir = top_level_git.as_path().is_dir();
-                        if top_level_git_is_dir || top_level_git_is_file {
-                            if top_level_git_is_file
-                                && is_git_pointer_file(&top_level_git)
-                                && let Some(gitdir) = resolve_gitdir_from_file(&top_level_git)
-                                && !subpaths
-                                    .iter()
-                                    .any(|subpath| subpath.as_path() == gitdir.as_path())
-                            {
-                                subpaths.push(gitdir);
-                            }
-                            subpaths.push(top_level_git);
-                        }
-
-                        // Make .agents/skills and .codex/config.toml and
-                        // related files read-only to the agent, by default.
-                        for subdir in &[".agents", ".codex"] {
-                            #[allow(clippy::expect_used)]
-                            let top_level_codex =
-                                writable_root.join(subdir).expect("valid relative path");
-                            if top_level_codex.as_path().is_dir() {
-                                subpaths.push(top_level_codex);
-                            }
-                        }
-
-                        WritableRoot {
-                            root: writable_root,
-                            read_only_subpaths: subpaths,
-                        }
-                    })
+                    .map(writable_root_with_read_only_subpaths)
                     .collect()
             }
         }
     }
 }
 
+fn writable_root_with_read_only_subpaths(writable_root: AbsolutePathBuf) -> WritableRoot {
+    let mut subpaths: Vec<AbsolutePathBuf> = Vec::new();
+    #[allow(clippy::expect_used)]
+    let top_level_git = writable_root
+        .join(".git")
+        .expect(".git is a valid relative path");
+    // This applies to typical repos (directory .git), worktrees/submodules
+    // (file .git with gitdir pointer), and bare repos when the gitdir is the
+    // writable root itself.
+    let top_level_git_is_file = top_level_git.as_path().is_file();
+    let top_level_git_is_dir = top_level_git.as_path().is_dir();
+    if top_level_git_is_dir || top_level_git_is_file {
+        if top_level_git_is_file
+            && is_git_pointer_file(&top_level_git)
+            && let Some(gitdir) = resolve_gitdir_from_file(&top_level_git)
+            && !subpaths
+                .iter()
+                .any(|subpath| subpath.as_path() == gitdir.as_path())
+        {
+            subpaths.push(gitdir);
+        }
+        subpaths.push(top_level_git);
+    }
+
+    // Make .agents/skills and .codex/config.toml and
+    // related files read-only to the agent, by default.
+    for subdir in &[".agents", ".codex"] {
+        #[allow(clippy::expect_used)]
+        let top_level_codex = writable_root.join(subdir).expect("valid relative path");
+        if top_level_codex.as_path().is_dir() {
+            subpaths.push(top_level_codex);
+        }
+    }
+
+    WritableRoot {
+        root: writable_root,
+        read_only_subpaths: subpaths,
+    }
+}
+
 fn is_git_pointer_file(path: &AbsolutePathBuf) -> bool {
     path.as_path().is_file() && path.as_path().file_name() == Some(OsStr::new(".git"))
 }
@@ -715,6 +765,9 @@ pub enum EventMsg {
     /// Conversation history was rolled back by dropping the last N user turns.
     ThreadRolledBack(ThreadRolledBackEvent),
 
+    /// Tool outputs or images were replaced with placeholders in the context.
+    ContextItemsDropped(ContextItemsDroppedEvent),
+
     /// Agent has started a turn.
     /// v1 wire format uses `task_started`; accept `turn_started` for v2 interop.
     #[serde(rename = "task_started", alias = "turn_started")]
@@ -837,9 +890,15 @@ pub enum EventMsg {
     /// Remote skill downloaded to local cache.
     RemoteSkillDownloaded(RemoteSkillDownloadedEvent),
 
+    /// Breakdown of what currently occupies the context window.
+    ContextBreakdown(ContextBreakdownEvent),
+
     /// Notification that skill data may have been updated and clients may want to reload.
     SkillsUpdateAvailable,
 
+    /// Config files changed on disk and were reloaded.
+    ConfigReloaded(ConfigReloadedEvent),
+
     PlanUpdate(UpdatePlanArgs),
 
     TurnAborted(TurnAbortedEvent),
@@ -980,9 +1039,28 @@ pub enum CodexErrorInfo {
         http_status_code: Option<u16>,
     },
     ThreadRollbackFailed,
+    /// The turn was stopped by one of the configured turn limits.
+    TurnLimitReached {
+        limit: TurnLimit,
+    },
     Other,
 }
 
+/// A configured limit that can stop a turn before the model is done.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum TurnLimit {
+    /// Too many model requests.
+    MaxTurns,
+    /// Too many tool calls.
+    MaxToolCalls,
+    /// The turn ran longer than its timeout.
+    Timeout,
+    /// Too many failed tool calls in a row.
+    MaxConsecutiveFailures,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, TS, JsonSchema)]
 pub struct RawResponseItemEvent {
     pub item: ResponseItem,
@@ -1125,6 +1203,27 @@ pub struct WarningEvent {
     pub message: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ConfigReloadedEvent {
+    /// Changed config keys that take effect on the next turn.
+    pub applied: Vec<String>,
+    /// Changed config keys that only take effect after a restart.
+    pub requires_restart: Vec<String>,
+    /// Changed config keys that would loosen the approval policy or the
+    /// sandbox. They are not applied; the user has to select the looser mode
+    /// explicitly.
+    pub needs_confirmation: Vec<String>,
+    /// Settings the session uses from the next turn on. Clients that send
+    /// them with every turn should adopt these values.
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffortConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub personality: Option<Personality>,
+    pub approval_policy: AskForApproval,
+    pub sandbox_policy: SandboxPolicy,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
@@ -1794,6 +1893,26 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the combined changes of a commit range, as in `git diff base..head`.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { base: String, head: String },
+
+    /// Review only the changes to the given repository-relative paths.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Paths {
+        paths: Vec<String>,
+        /// Branch or commit to compare against. When omitted, uncommitted
+        /// changes in the working tree are reviewed.
+        base: Option<String>,
+    },
+
+    /// Review a patch file, such as one received by email, without applying it.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
     /// Arbitrary instructions provided by the user.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -2001,6 +2120,15 @@ pub struct ThreadRolledBackEvent {
     pub num_turns: u32,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ContextItemsDroppedEvent {
+    /// Ids of the items that were replaced with placeholders.
+    pub item_ids: Vec<String>,
+    /// Estimated tokens freed by the replacement.
+    #[ts(type = "number")]
+    pub tokens_freed: i64,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -2136,6 +2264,63 @@ pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
 }
 
+/// Response payload for `Op::GetContextBreakdown`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct ContextBreakdownEvent {
+    /// Estimated tokens for everything sent with the next request.
+    #[ts(type = "number")]
+    pub total_tokens: i64,
+    #[ts(type = "number | null")]
+    pub model_context_window: Option<i64>,
+    /// Token totals per category, largest first. Tool outputs are reported
+    /// once per tool.
+    pub categories: Vec<ContextCategoryUsage>,
+    /// The largest individual items in the history, largest first.
+    pub largest_items: Vec<ContextItemUsage>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextCategory {
+    BaseInstructions,
+    /// AGENTS.md and other project docs.
+    ProjectDocs,
+    Skills,
+    /// Developer messages and environment context injected by Codex.
+    DeveloperContext,
+    UserMessages,
+    AssistantMessages,
+    Reasoning,
+    ToolCalls,
+    ToolOutputs,
+    Images,
+    /// Summaries produced by compaction.
+    Compaction,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct ContextCategoryUsage {
+    pub category: ContextCategory,
+    /// Tool name, for `tool_outputs` entries.
+    pub tool_name: Option<String>,
+    #[ts(type = "number")]
+    pub tokens: i64,
+    pub item_count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct ContextItemUsage {
+    /// Identifier accepted by `Op::DropContextItems`; only set for tool outputs
+    /// and images, which are the items that can be dropped.
+    pub id: Option<String>,
+    pub category: ContextCategory,
+    pub tool_name: Option<String>,
+    /// Short single-line preview of the item.
+    pub preview: String,
+    #[ts(type = "number")]
+    pub tokens: i64,
+}
+
 /// Response payload for `Op::ListSkills`.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ListSkillsResponseEvent {
@@ -2318,6 +2503,11 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// Only for a workspace overlay review of several files: the user wants to
+    /// review each changed file in its own request. Treated as `Denied` for
+    /// every other request.
+    ReviewEachFile,
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -2336,6 +2526,7 @@ impl ReviewDecision {
             ReviewDecision::Approved => "approved",
             ReviewDecision::ApprovedExecpolicyAmendment { .. } => "approved_with_amendment",
             ReviewDecision::ApprovedForSession => "approved_for_session",
+            ReviewDecision::ReviewEachFile => "review_each_file",
             ReviewDecision::Denied => "denied",
             ReviewDecision::Abort => "abort",
         }
@@ -2501,6 +2692,32 @@ mod tests {
         assert!(enabled.has_full_network_access());
     }
 
+    #[test]
+    fn workspace_overlay_mounts_cwd_without_granting_writes() -> Result<()> {
+        let cwd = tempfile::tempdir()?;
+        std::fs::create_dir(cwd.path().join(".git"))?;
+        let policy: SandboxPolicy = r#"{"type":"workspace-overlay"}"#.parse()?;
+        assert_eq!(
+            policy,
+            SandboxPolicy::WorkspaceOverlay {
+                network_access: false
+            }
+        );
+
+        assert!(!policy.has_full_disk_write_access());
+        assert_eq!(policy.get_writable_roots_with_cwd(cwd.path()), Vec::new());
+        let overlay_root = policy.get_overlay_root_with_cwd(cwd.path());
+        let cwd = AbsolutePathBuf::from_absolute_path(cwd.path())?;
+        assert_eq!(
+            overlay_root,
+            Some(WritableRoot {
+                read_only_subpaths: vec![cwd.join(".git")?],
+                root: cwd,
+            })
+        );
+        Ok(())
+    }
+
     #[test]
     fn item_started_event_from_web_search_emits_begin_event() {
         let event = ItemStartedEvent {
@@ -0,0 +1,32 @@
+CREATE TABLE thread_search_documents (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    thread_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    content TEXT NOT NULL,
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_thread_search_documents_unique
+    ON thread_search_documents(thread_id, kind, content);
+
+-- The trigram tokenizer matches substrings, which also works for text
+-- without word boundaries (for example Chinese).
+CREATE VIRTUAL TABLE thread_search USING fts5(
+    content,
+    content = 'thread_search_documents',
+    content_rowid = 'id',
+    tokenize = 'trigram'
+);
+
+CREATE TRIGGER thread_search_documents_ai AFTER INSERT ON thread_search_documents BEGIN
+    INSERT INTO thread_search(rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER thread_search_documents_ad AFTER DELETE ON thread_search_documents BEGIN
+    INSERT INTO thread_search(thread_search, rowid, content) VALUES ('delete', old.id, old.content);
+END;
+
+-- Re-run the rollout backfill so existing threads are indexed.
+UPDATE backfill_state
+SET status = 'pending', last_watermark = NULL
+WHERE id = 1;
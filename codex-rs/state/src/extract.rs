@@ -1,4 +1,7 @@
+use crate::model::SearchDocument;
+use crate::model::SearchDocumentKind;
 use crate::model::ThreadMetadata;
+use codex_protocol::models::LocalShellAction;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::RolloutItem;
@@ -11,6 +14,18 @@ use serde_json::Value;
 
 const IMAGE_ONLY_USER_MESSAGE_PLACEHOLDER: &str = "[Image]";
 
+/// Longest document stored in the search index, in bytes. Longer messages are
+/// truncated so a handful of huge transcripts cannot bloat the index.
+const MAX_SEARCH_DOCUMENT_BYTES: usize = 4096;
+
+/// Patch header prefixes that name the file a patch touches.
+const PATCH_PATH_PREFIXES: [&str; 4] = [
+    "*** Add File: ",
+    "*** Update File: ",
+    "*** Delete File: ",
+    "*** Move to: ",
+];
+
 /// Apply a rollout item to the metadata structure.
 pub fn apply_rollout_item(
     metadata: &mut ThreadMetadata,
@@ -85,6 +100,93 @@ fn apply_response_item(_metadata: &mut ThreadMetadata, _item: &ResponseItem) {
     // Title and first_user_message are derived from EventMsg::UserMessage only.
 }
 
+/// Extract the searchable content of a rollout item: user and assistant
+/// messages, commands the agent ran and files it patched.
+pub fn search_documents(item: &RolloutItem) -> Vec<SearchDocument> {
+    let mut documents = Vec::new();
+    let mut push = |kind: SearchDocumentKind, content: &str| {
+        let content = truncate_document(content.trim());
+        if !content.is_empty() {
+            documents.push(SearchDocument {
+                kind,
+                content: content.to_string(),
+            });
+        }
+    };
+    match item {
+        RolloutItem::EventMsg(EventMsg::UserMessage(user)) => push(
+            SearchDocumentKind::UserMessage,
+            strip_user_message_prefix(user.message.as_str()),
+        ),
+        RolloutItem::EventMsg(EventMsg::AgentMessage(agent)) => {
+            push(SearchDocumentKind::AgentMessage, agent.message.as_str());
+        }
+        RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+            action: LocalShellAction::Exec(exec),
+            ..
+        }) => push(SearchDocumentKind::Command, &exec.command.join(" ")),
+        RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+            name, arguments, ..
+        }) => {
+            let arguments = serde_json::from_str::<Value>(arguments).unwrap_or_default();
+            if name == "apply_patch" {
+                if let Some(patch) = arguments.get("input").and_then(Value::as_str) {
+                    for path in patched_paths(patch) {
+                        push(SearchDocumentKind::FilePath, path);
+                    }
+                }
+            } else if let Some(command) = command_from_arguments(&arguments) {
+                push(SearchDocumentKind::Command, &command);
+            }
+        }
+        RolloutItem::ResponseItem(ResponseItem::CustomToolCall { name, input, .. })
+            if name == "apply_patch" =>
+        {
+            for path in patched_paths(input) {
+                push(SearchDocumentKind::FilePath, path);
+            }
+        }
+        RolloutItem::SessionMeta(_)
+        | RolloutItem::TurnContext(_)
+        | RolloutItem::Compacted(_)
+        | RolloutItem::EventMsg(_)
+        | RolloutItem::ResponseItem(_) => {}
+    }
+    documents
+}
+
+/// Shell-like tools pass the command as `command` (argv or string) or `cmd`.
+fn command_from_arguments(arguments: &Value) -> Option<String> {
+    match arguments.get("command").or_else(|| arguments.get("cmd"))? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(argv) => {
+            let argv: Vec<&str> = argv.iter().filter_map(Value::as_str).collect();
+            (!argv.is_empty()).then(|| argv.join(" "))
+        }
+        _ => None,
+    }
+}
+
+fn patched_paths(patch: &str) -> impl Iterator<Item = &str> {
+    patch.lines().filter_map(|line| {
+        PATCH_PATH_PREFIXES
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix))
+            .map(str::trim)
+    })
+}
+
+fn truncate_document(content: &str) -> &str {
+    if content.len() <= MAX_SEARCH_DOCUMENT_BYTES {
+        return content;
+    }
+    let mut end = MAX_SEARCH_DOCUMENT_BYTES;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    &content[..end]
+}
+
 fn strip_user_message_prefix(text: &str) -> &str {
     match text.find(USER_MESSAGE_BEGIN) {
         Some(idx) => text[idx + USER_MESSAGE_BEGIN.len()..].trim(),
@@ -119,12 +221,16 @@ pub(crate) fn enum_to_string<T: Serialize>(value: &T) -> String {
 #[cfg(test)]
 mod tests {
     use super::apply_rollout_item;
+    use super::search_documents;
+    use crate::model::SearchDocument;
+    use crate::model::SearchDocumentKind;
     use crate::model::ThreadMetadata;
     use chrono::DateTime;
     use chrono::Utc;
     use codex_protocol::ThreadId;
     use codex_protocol::models::ContentItem;
     use codex_protocol::models::ResponseItem;
+    use codex_protocol::protocol::AgentMessageEvent;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::RolloutItem;
     use codex_protocol::protocol::USER_MESSAGE_BEGIN;
@@ -207,6 +313,51 @@ mod tests {
         assert_eq!(metadata.title, "");
     }
 
+    #[test]
+    fn search_documents_cover_messages_commands_and_patched_paths() {
+        let items = [
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: format!("{USER_MESSAGE_BEGIN} fix the flaky migration"),
+                images: None,
+                local_images: vec![],
+                text_elements: vec![],
+            })),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Done.".to_string(),
+            })),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["cargo","test","-p","db"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-2".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** Update File: db/migrate.rs\n@@\n-a\n+b\n*** End Patch"
+                    .to_string(),
+            }),
+        ];
+
+        let documents: Vec<SearchDocument> = items.iter().flat_map(search_documents).collect();
+
+        let document = |kind, content: &str| SearchDocument {
+            kind,
+            content: content.to_string(),
+        };
+        assert_eq!(
+            documents,
+            vec![
+                document(SearchDocumentKind::UserMessage, "fix the flaky migration"),
+                document(SearchDocumentKind::AgentMessage, "Done."),
+                document(SearchDocumentKind::Command, "cargo test -p db"),
+                document(SearchDocumentKind::FilePath, "db/migrate.rs"),
+            ]
+        );
+    }
+
     fn metadata_for_test() -> ThreadMetadata {
         let id = ThreadId::from_string(&Uuid::from_u128(42).to_string()).expect("thread id");
         let created_at = DateTime::<Utc>::from_timestamp(1_735_689_600, 0).expect("timestamp");
@@ -21,15 +21,20 @@ pub use runtime::StateRuntime;
 ///
 /// Most consumers should prefer [`StateRuntime`].
 pub use extract::apply_rollout_item;
+pub use extract::search_documents;
 pub use model::Anchor;
 pub use model::BackfillState;
 pub use model::BackfillStats;
 pub use model::BackfillStatus;
 pub use model::ExtractionOutcome;
+pub use model::SearchDocument;
+pub use model::SearchDocumentKind;
+pub use model::SearchSnippet;
 pub use model::SortKey;
 pub use model::ThreadMemory;
 pub use model::ThreadMetadata;
 pub use model::ThreadMetadataBuilder;
+pub use model::ThreadSearchHit;
 pub use model::ThreadsPage;
 pub use runtime::STATE_DB_FILENAME;
 pub use runtime::STATE_DB_VERSION;
@@ -2,6 +2,7 @@ mod backfill_state;
 mod log;
 mod thread_memory;
 mod thread_metadata;
+mod thread_search;
 
 pub use backfill_state::BackfillState;
 pub use backfill_state::BackfillStatus;
@@ -16,8 +17,14 @@ pub use thread_metadata::SortKey;
 pub use thread_metadata::ThreadMetadata;
 pub use thread_metadata::ThreadMetadataBuilder;
 pub use thread_metadata::ThreadsPage;
+pub use thread_search::SearchDocument;
+pub use thread_search::SearchDocumentKind;
+pub use thread_search::SearchSnippet;
+pub use thread_search::ThreadSearchHit;
 
 pub(crate) use thread_memory::ThreadMemoryRow;
 pub(crate) use thread_metadata::ThreadRow;
 pub(crate) use thread_metadata::anchor_from_item;
 pub(crate) use thread_metadata::datetime_to_epoch_seconds;
+pub(crate) use thread_search::SNIPPET_MATCH_END;
+pub(crate) use thread_search::SNIPPET_MATCH_START;
@@ -1,3 +1,4 @@
+use crate::model::SearchDocument;
 use anyhow::Result;
 use chrono::DateTime;
 use chrono::Timelike;
@@ -47,6 +48,8 @@ pub struct ExtractionOutcome {
     pub metadata: ThreadMetadata,
     /// The number of rollout lines that failed to parse.
     pub parse_errors: usize,
+    /// Searchable content of the rollout.
+    pub search_documents: Vec<SearchDocument>,
 }
 
 /// Canonical thread metadata derived from rollout files.
@@ -0,0 +1,99 @@
+use std::ops::Range;
+
+use crate::model::ThreadMetadata;
+
+/// Marks the start of a match inside snippets returned by SQLite.
+pub(crate) const SNIPPET_MATCH_START: char = '\u{e000}';
+/// Marks the end of a match inside snippets returned by SQLite.
+pub(crate) const SNIPPET_MATCH_END: char = '\u{e001}';
+
+/// Kind of rollout content indexed for full-text search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchDocumentKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    FilePath,
+}
+
+impl SearchDocumentKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            SearchDocumentKind::UserMessage => "user_message",
+            SearchDocumentKind::AgentMessage => "agent_message",
+            SearchDocumentKind::Command => "command",
+            SearchDocumentKind::FilePath => "file_path",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "user_message" => Some(SearchDocumentKind::UserMessage),
+            "agent_message" => Some(SearchDocumentKind::AgentMessage),
+            "command" => Some(SearchDocumentKind::Command),
+            "file_path" => Some(SearchDocumentKind::FilePath),
+            _ => None,
+        }
+    }
+}
+
+/// One searchable piece of a rollout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchDocument {
+    pub kind: SearchDocumentKind,
+    pub content: String,
+}
+
+/// Excerpt around a match, with the matched parts as byte ranges into `text`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchSnippet {
+    pub text: String,
+    pub highlights: Vec<Range<usize>>,
+}
+
+impl SearchSnippet {
+    /// Splits a snippet produced with the match marker characters into plain
+    /// text and highlight ranges.
+    pub(crate) fn from_marked(marked: &str) -> Self {
+        let mut text = String::with_capacity(marked.len());
+        let mut highlights = Vec::new();
+        let mut start = None;
+        for ch in marked.chars() {
+            match ch {
+                SNIPPET_MATCH_START => start = Some(text.len()),
+                SNIPPET_MATCH_END => {
+                    if let Some(start) = start.take()
+                        && start < text.len()
+                    {
+                        highlights.push(start..text.len());
+                    }
+                }
+                // Snippets are shown on one line.
+                '\n' | '\r' | '\t' => text.push(' '),
+                _ => text.push(ch),
+            }
+        }
+        Self { text, highlights }
+    }
+}
+
+/// Best match of a full-text query within one thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadSearchHit {
+    pub thread: ThreadMetadata,
+    pub kind: SearchDocumentKind,
+    pub snippet: SearchSnippet,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn snippet_markers_become_highlight_ranges() {
+        let snippet = SearchSnippet::from_marked("fix the \u{e000}flaky\u{e001}\nmigration");
+        assert_eq!(snippet.text, "fix the flaky migration");
+        assert_eq!(snippet.highlights, vec![8..13]);
+    }
+}
@@ -2,18 +2,25 @@ use crate::DB_ERROR_METRIC;
 use crate::LogEntry;
 use crate::LogQuery;
 use crate::LogRow;
+use crate::SearchDocument;
+use crate::SearchDocumentKind;
+use crate::SearchSnippet;
 use crate::SortKey;
 use crate::ThreadMemory;
 use crate::ThreadMetadata;
 use crate::ThreadMetadataBuilder;
+use crate::ThreadSearchHit;
 use crate::ThreadsPage;
 use crate::apply_rollout_item;
 use crate::migrations::MIGRATOR;
+use crate::model::SNIPPET_MATCH_END;
+use crate::model::SNIPPET_MATCH_START;
 use crate::model::ThreadMemoryRow;
 use crate::model::ThreadRow;
 use crate::model::anchor_from_item;
 use crate::model::datetime_to_epoch_seconds;
 use crate::paths::file_modified_time_utc;
+use crate::search_documents;
 use chrono::DateTime;
 use chrono::Utc;
 use codex_otel::OtelManager;
@@ -42,6 +49,9 @@ pub const STATE_DB_VERSION: u32 = 3;
 
 const METRIC_DB_INIT: &str = "codex.db.init";
 
+/// Approximate number of tokens shown around a full-text match.
+const SEARCH_SNIPPET_TOKENS: i64 = 16;
+
 #[derive(Clone)]
 pub struct StateRuntime {
     codex_home: PathBuf,
@@ -340,6 +350,172 @@ FROM threads
         })
     }
 
+    /// Full-text search over indexed rollout content.
+    ///
+    /// Returns at most `limit` threads, best match first, each with the
+    /// snippet of its best matching document. Every term must appear in the
+    /// same document (one message, command or file path); terms spread across
+    /// a session do not match. The index matches trigrams, so terms shorter
+    /// than three characters, such as two-character CJK words, are matched as
+    /// plain substrings instead.
+    pub async fn search_threads(
+        &self,
+        query: &str,
+        limit: usize,
+        allowed_sources: &[String],
+        model_providers: Option<&[String]>,
+        archived_only: bool,
+    ) -> anyhow::Result<Vec<ThreadSearchHit>> {
+        let (match_query, substring_terms) = search_terms(query);
+        if match_query.is_none() && substring_terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let match_start = SNIPPET_MATCH_START.to_string();
+        let match_end = SNIPPET_MATCH_END.to_string();
+
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+WITH matches AS (
+    SELECT
+        d.thread_id AS thread_id,
+        d.kind AS match_kind,
+        "#,
+        );
+        match &match_query {
+            Some(match_query) => {
+                builder
+                    .push("snippet(thread_search, 0, ")
+                    .push_bind(match_start.as_str())
+                    .push(", ")
+                    .push_bind(match_end.as_str())
+                    .push(", '…', ")
+                    .push_bind(SEARCH_SNIPPET_TOKENS)
+                    .push(
+                        r#") AS match_snippet,
+        bm25(thread_search) AS score
+    FROM thread_search
+    JOIN thread_search_documents AS d ON d.id = thread_search.rowid
+    WHERE thread_search MATCH "#,
+                    )
+                    .push_bind(match_query.as_str());
+            }
+            // Without a term the index can match, the snippet is cut from the
+            // whole document below.
+            None => {
+                builder.push(
+                    r#"d.content AS match_snippet,
+        0.0 AS score
+    FROM thread_search_documents AS d
+    WHERE 1 = 1"#,
+                );
+            }
+        }
+        for term in &substring_terms {
+            builder
+                .push(" AND d.content LIKE ")
+                .push_bind(like_pattern(term))
+                .push(" ESCAPE '\\'");
+        }
+        builder.push(
+            r#"
+),
+ranked AS (
+    SELECT
+        thread_id,
+        match_kind,
+        match_snippet,
+        score,
+        ROW_NUMBER() OVER (PARTITION BY thread_id ORDER BY score ASC) AS rank_in_thread
+    FROM matches
+)
+SELECT
+    threads.id,
+    threads.rollout_path,
+    threads.created_at,
+    threads.updated_at,
+    threads.source,
+    threads.model_provider,
+    threads.cwd,
+    threads.cli_version,
+    threads.title,
+    threads.sandbox_policy,
+    threads.approval_mode,
+    threads.tokens_used,
+    threads.first_user_message,
+    threads.archived_at,
+    threads.git_sha,
+    threads.git_branch,
+    threads.git_origin_url,
+    ranked.match_kind,
+    ranked.match_snippet
+FROM ranked
+JOIN threads ON threads.id = ranked.thread_id
+            "#,
+        );
+        push_thread_filters(
+            &mut builder,
+            archived_only,
+            allowed_sources,
+            model_providers,
+            None,
+            SortKey::UpdatedAt,
+        );
+        builder.push(" AND ranked.rank_in_thread = 1");
+        builder.push(" ORDER BY ranked.score ASC, threads.updated_at DESC LIMIT ");
+        builder.push_bind(limit as i64);
+
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter()
+            .map(|row| {
+                let kind: String = row.try_get("match_kind")?;
+                let kind = SearchDocumentKind::parse(kind.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("unknown search document kind: {kind}"))?;
+                let mut snippet: String = row.try_get("match_snippet")?;
+                if match_query.is_none()
+                    && let Some(term) = substring_terms.first()
+                {
+                    snippet = substring_snippet(&snippet, term);
+                }
+                Ok(ThreadSearchHit {
+                    thread: ThreadRow::try_from_row(&row).and_then(ThreadMetadata::try_from)?,
+                    kind,
+                    snippet: SearchSnippet::from_marked(snippet.as_str()),
+                })
+            })
+            .collect()
+    }
+
+    /// Add documents to a thread's search index, skipping ones already indexed.
+    pub async fn insert_search_documents(
+        &self,
+        thread_id: ThreadId,
+        documents: &[SearchDocument],
+    ) -> anyhow::Result<()> {
+        if documents.is_empty() {
+            return Ok(());
+        }
+        let mut tx = self.pool.begin().await?;
+        insert_search_documents(&mut tx, thread_id, documents).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Replace a thread's search index, e.g. after re-reading its whole rollout.
+    pub async fn replace_search_documents(
+        &self,
+        thread_id: ThreadId,
+        documents: &[SearchDocument],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM thread_search_documents WHERE thread_id = ?")
+            .bind(thread_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        insert_search_documents(&mut tx, thread_id, documents).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
     /// Insert one log entry into the logs table.
     pub async fn insert_log(&self, entry: &LogEntry) -> anyhow::Result<()> {
         self.insert_logs(std::slice::from_ref(entry)).await
@@ -656,6 +832,13 @@ ON CONFLICT(thread_id, position) DO NOTHING
             }
             return Err(err);
         }
+        let documents: Vec<SearchDocument> = items.iter().flat_map(search_documents).collect();
+        if let Err(err) = self.insert_search_documents(builder.id, &documents).await {
+            if let Some(otel) = otel {
+                otel.counter(DB_ERROR_METRIC, 1, &[("stage", "index_search_documents")]);
+            }
+            return Err(err);
+        }
         let dynamic_tools = extract_dynamic_tools(items);
         if let Some(dynamic_tools) = dynamic_tools
             && let Err(err) = self
@@ -794,6 +977,99 @@ fn push_like_filters<'a>(
     builder.push(")");
 }
 
+async fn insert_search_documents(
+    tx: &mut sqlx::Transaction<'_, Sqlite>,
+    thread_id: ThreadId,
+    documents: &[SearchDocument],
+) -> anyhow::Result<()> {
+    let thread_id = thread_id.to_string();
+    for document in documents {
+        sqlx::query(
+            r#"
+INSERT INTO thread_search_documents (thread_id, kind, content)
+VALUES (?, ?, ?)
+ON CONFLICT(thread_id, kind, content) DO NOTHING
+            "#,
+        )
+        .bind(thread_id.as_str())
+        .bind(document.kind.as_str())
+        .bind(document.content.as_str())
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Splits free text into an FTS5 query that requires every term long enough
+/// to match a trigram (each as a quoted phrase), and the shorter terms, which
+/// have to be matched as substrings of the document instead.
+fn search_terms(query: &str) -> (Option<String>, Vec<String>) {
+    let (trigram_terms, substring_terms): (Vec<&str>, Vec<&str>) = query
+        .split_whitespace()
+        .partition(|term| term.chars().count() >= 3);
+    let match_query = (!trigram_terms.is_empty()).then(|| {
+        trigram_terms
+            .iter()
+            .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ")
+    });
+    (
+        match_query,
+        substring_terms.into_iter().map(str::to_string).collect(),
+    )
+}
+
+/// A `LIKE` pattern matching `term` anywhere, with `\` as the escape
+/// character.
+fn like_pattern(term: &str) -> String {
+    let mut pattern = String::with_capacity(term.len() + 2);
+    pattern.push('%');
+    for ch in term.chars() {
+        if matches!(ch, '\\' | '%' | '_') {
+            pattern.push('\\');
+        }
+        pattern.push(ch);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// Cuts a snippet around the first occurrence of `term` in `content`, marked
+/// like the ones FTS5 returns. `LIKE` ignores ASCII case, so the search does
+/// too.
+fn substring_snippet(content: &str, term: &str) -> String {
+    const CONTEXT_CHARS: usize = 32;
+    let Some(start) = content
+        .to_ascii_lowercase()
+        .find(&term.to_ascii_lowercase())
+    else {
+        return content.chars().take(CONTEXT_CHARS * 2).collect();
+    };
+    let end = start + term.len();
+    let window_start = content[..start]
+        .char_indices()
+        .rev()
+        .nth(CONTEXT_CHARS - 1)
+        .map_or(0, |(idx, _)| idx);
+    let window_end = content[end..]
+        .char_indices()
+        .nth(CONTEXT_CHARS)
+        .map_or(content.len(), |(idx, _)| end + idx);
+    format!(
+        "{}{}{SNIPPET_MATCH_START}{}{SNIPPET_MATCH_END}{}{}",
+        if window_start > 0 { "…" } else { "" },
+        &content[window_start..start],
+        &content[start..end],
+        &content[end..window_end],
+        if window_end < content.len() {
+            "…"
+        } else {
+            ""
+        },
+    )
+}
+
 fn extract_dynamic_tools(items: &[RolloutItem]) -> Option<Option<Vec<DynamicToolSpec>>> {
     items.iter().find_map(|item| match item {
         RolloutItem::SessionMeta(meta_line) => Some(meta_line.meta.dynamic_tools.clone()),
@@ -964,6 +1240,8 @@ fn push_thread_order_and_limit(
 mod tests {
     use super::STATE_DB_FILENAME;
     use super::STATE_DB_VERSION;
+    use super::SearchDocument;
+    use super::SearchDocumentKind;
     use super::StateRuntime;
     use super::ThreadMetadata;
     use super::state_db_filename;
@@ -1382,6 +1660,150 @@ mod tests {
         let _ = tokio::fs::remove_dir_all(codex_home).await;
     }
 
+    #[tokio::test]
+    async fn search_threads_returns_best_snippet_per_thread() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+
+        let matching = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        let other = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        for thread_id in [matching, other] {
+            runtime
+                .upsert_thread(&test_thread_metadata(
+                    &codex_home,
+                    thread_id,
+                    codex_home.join("workspace"),
+                ))
+                .await
+                .expect("upsert thread");
+        }
+        let document = |kind, content: &str| SearchDocument {
+            kind,
+            content: content.to_string(),
+        };
+        let documents = [
+            document(
+                SearchDocumentKind::UserMessage,
+                "the migration test is flaky",
+            ),
+            document(SearchDocumentKind::Command, "cargo test -p db migration"),
+        ];
+        runtime
+            .insert_search_documents(matching, &documents)
+            .await
+            .expect("index documents");
+        // Re-indexing the same content must not duplicate documents.
+        runtime
+            .insert_search_documents(matching, &documents)
+            .await
+            .expect("index documents again");
+        runtime
+            .insert_search_documents(
+                other,
+                &[document(SearchDocumentKind::UserMessage, "migration notes")],
+            )
+            .await
+            .expect("index documents");
+
+        let hits = runtime
+            .search_threads("flaky migration", 10, &[], None, false)
+            .await
+            .expect("search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].thread.id, matching);
+        assert_eq!(hits[0].kind, SearchDocumentKind::UserMessage);
+        let highlighted: Vec<&str> = hits[0]
+            .snippet
+            .highlights
+            .iter()
+            .map(|range| &hits[0].snippet.text[range.clone()])
+            .collect();
+        assert_eq!(highlighted, vec!["migration", "flaky"]);
+
+        runtime
+            .replace_search_documents(matching, &[])
+            .await
+            .expect("clear documents");
+        let hits = runtime
+            .search_threads("flaky", 10, &[], None, false)
+            .await
+            .expect("search after clear");
+        assert_eq!(hits, Vec::new());
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
+    #[tokio::test]
+    async fn search_threads_matches_short_terms_as_substrings() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string(), None)
+            .await
+            .expect("initialize runtime");
+
+        let thread_id = ThreadId::from_string(&Uuid::new_v4().to_string()).expect("thread id");
+        runtime
+            .upsert_thread(&test_thread_metadata(
+                &codex_home,
+                thread_id,
+                codex_home.join("workspace"),
+            ))
+            .await
+            .expect("upsert thread");
+        runtime
+            .insert_search_documents(
+                thread_id,
+                &[
+                    SearchDocument {
+                        kind: SearchDocumentKind::UserMessage,
+                        content: "请帮我检查配置文件的加载顺序".to_string(),
+                    },
+                    SearchDocument {
+                        kind: SearchDocumentKind::Command,
+                        content: "cargo test -p db 100%".to_string(),
+                    },
+                ],
+            )
+            .await
+            .expect("index documents");
+
+        let hits = runtime
+            .search_threads("配置", 10, &[], None, false)
+            .await
+            .expect("search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SearchDocumentKind::UserMessage);
+        assert_eq!(hits[0].snippet.text, "请帮我检查配置文件的加载顺序");
+        let highlighted: Vec<&str> = hits[0]
+            .snippet
+            .highlights
+            .iter()
+            .map(|range| &hits[0].snippet.text[range.clone()])
+            .collect();
+        assert_eq!(highlighted, vec!["配置"]);
+
+        // Short terms narrow down trigram matches too.
+        let hits = runtime
+            .search_threads("cargo DB", 10, &[], None, false)
+            .await
+            .expect("search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SearchDocumentKind::Command);
+        let hits = runtime
+            .search_threads("cargo 0%", 10, &[], None, false)
+            .await
+            .expect("search");
+        assert_eq!(hits.len(), 1);
+        let hits = runtime
+            .search_threads("cargo _b", 10, &[], None, false)
+            .await
+            .expect("search");
+        assert_eq!(hits, Vec::new());
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+
     fn test_thread_metadata(
         codex_home: &Path,
         thread_id: ThreadId,
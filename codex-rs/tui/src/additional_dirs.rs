@@ -16,18 +16,21 @@ pub fn add_dir_warning_message(
         SandboxPolicy::WorkspaceWrite { .. }
         | SandboxPolicy::DangerFullAccess
         | SandboxPolicy::ExternalSandbox { .. } => None,
-        SandboxPolicy::ReadOnly => Some(format_warning(additional_dirs)),
+        SandboxPolicy::ReadOnly => Some(format_warning(additional_dirs, "只读")),
+        SandboxPolicy::WorkspaceOverlay { .. } => {
+            Some(format_warning(additional_dirs, "工作区覆盖层"))
+        }
     }
 }
 
-fn format_warning(additional_dirs: &[PathBuf]) -> String {
+fn format_warning(additional_dirs: &[PathBuf], mode: &str) -> String {
     let joined_paths = additional_dirs
         .iter()
         .map(|path| path.to_string_lossy())
         .collect::<Vec<_>>()
         .join(", ");
     format!(
-        "由于当前生效的沙箱模式为只读，已忽略 --add-dir（{joined_paths}）。请切换到 workspace-write 或 danger-full-access 以允许额外的可写目录。"
+        "由于当前生效的沙箱模式为{mode}，已忽略 --add-dir（{joined_paths}）。请切换到 workspace-write 或 danger-full-access 以允许额外的可写目录。"
     )
 }
 
@@ -74,6 +77,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn warns_for_workspace_overlay() {
+        let sandbox = SandboxPolicy::WorkspaceOverlay {
+            network_access: false,
+        };
+        let dirs = vec![PathBuf::from("/abs")];
+        let message = add_dir_warning_message(&dirs, &sandbox)
+            .expect("expected warning for workspace overlay");
+        assert_eq!(
+            message,
+            "由于当前生效的沙箱模式为工作区覆盖层，已忽略 --add-dir（/abs）。请切换到 workspace-write 或 danger-full-access 以允许额外的可写目录。"
+        );
+    }
+
     #[test]
     fn returns_none_when_no_additional_dirs() {
         let sandbox = SandboxPolicy::ReadOnly;
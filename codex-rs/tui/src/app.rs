@@ -57,6 +57,7 @@ use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::SkillErrorInfo;
 use codex_core::protocol::TokenUsage;
+use codex_core::review_dismissals::review_change_key;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_otel::OtelManager;
@@ -1087,7 +1088,8 @@ impl App {
 
         chat_widget.maybe_prompt_windows_sandbox_enable();
 
-        let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
+        let mut file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
+        file_search.set_symbol_search(config.features.enabled(Feature::SymbolIndex));
         #[cfg(not(debug_assertions))]
         let upgrade_version = crate::updates::get_upgrade_version(&config);
 
@@ -1340,6 +1342,7 @@ impl App {
                     &self.config.codex_home,
                     &self.config.model_provider_id,
                     false,
+                    None,
                 )
                 .await?
                 {
@@ -1580,6 +1583,9 @@ impl App {
             AppEvent::FileSearchResult { query, matches } => {
                 self.chat_widget.apply_file_search_result(query, matches);
             }
+            AppEvent::SymbolSearchResult { query, symbols } => {
+                self.chat_widget.apply_symbol_search_result(query, symbols);
+            }
             AppEvent::RateLimitSnapshotFetched(snapshot) => {
                 self.chat_widget.on_rate_limit_snapshot(Some(snapshot));
             }
@@ -2174,9 +2180,34 @@ impl App {
             AppEvent::OpenReviewCommitPicker(cwd) => {
                 self.chat_widget.show_review_commit_picker(&cwd).await;
             }
+            AppEvent::OpenReviewRangePicker(cwd) => {
+                self.chat_widget.show_review_range_picker(&cwd).await;
+            }
+            AppEvent::OpenReviewPathsPrompt => {
+                self.chat_widget.show_review_paths_prompt();
+            }
+            AppEvent::OpenReviewPatchPrompt => {
+                self.chat_widget.show_review_patch_prompt();
+            }
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::ReviewFindingsReceived {
+                target,
+                findings,
+                open_panel,
+            } => {
+                let change_key = review_change_key(&target, &self.config.cwd).await;
+                self.chat_widget
+                    .set_review_findings(findings, change_key, open_panel);
+            }
+            AppEvent::ReviewFinding {
+                fingerprint,
+                action,
+            } => {
+                self.chat_widget
+                    .handle_review_finding_action(&fingerprint, action);
+            }
             AppEvent::SubmitUserMessageWithMode {
                 text,
                 collaboration_mode,
@@ -2250,6 +2281,10 @@ impl App {
                 self.chat_widget.set_status_line_branch(cwd, branch);
                 self.refresh_status_line();
             }
+            AppEvent::StatusLineSegmentUpdated { name, value } => {
+                self.chat_widget.set_status_line_segment_value(&name, value);
+                self.refresh_status_line();
+            }
             AppEvent::StatusLineSetupCancelled => {
                 self.chat_widget.cancel_status_line_setup();
             }
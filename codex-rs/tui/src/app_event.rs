@@ -14,12 +14,15 @@ use codex_chatgpt::connectors::AppInfo;
 use codex_common::approval_presets::ApprovalPreset;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::ReviewFinding;
+use codex_core::protocol::ReviewTarget;
+use codex_core::symbol_index::Symbol;
 use codex_file_search::FileMatch;
 use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ModelPreset;
 
 use crate::bottom_pane::ApprovalRequest;
-use crate::bottom_pane::StatusLineItem;
+use crate::bottom_pane::StatusLineEntry;
 use crate::history_cell::HistoryCell;
 
 use codex_core::features::Feature;
@@ -93,6 +96,13 @@ pub(crate) enum AppEvent {
         matches: Vec<FileMatch>,
     },
 
+    /// Workspace symbols matching a file search query, sent alongside the
+    /// file results when the symbol index feature is enabled.
+    SymbolSearchResult {
+        query: String,
+        symbols: Vec<Symbol>,
+    },
+
     /// Result of refreshing rate limits
     RateLimitSnapshotFetched(RateLimitSnapshot),
 
@@ -268,9 +278,32 @@ pub(crate) enum AppEvent {
     /// Open the commit picker option from the review popup.
     OpenReviewCommitPicker(PathBuf),
 
+    /// Open the commit-range picker option from the review popup.
+    OpenReviewRangePicker(PathBuf),
+
+    /// Open the path-scoped review prompt from the review popup.
+    OpenReviewPathsPrompt,
+
+    /// Open the patch-file review prompt from the review popup.
+    OpenReviewPatchPrompt,
+
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
+    /// A review finished with findings; resolve the reviewed change so
+    /// earlier dismissals apply, then hand them to the triage panel.
+    ReviewFindingsReceived {
+        target: ReviewTarget,
+        findings: Vec<ReviewFinding>,
+        open_panel: bool,
+    },
+
+    /// Act on one finding from the review triage panel.
+    ReviewFinding {
+        fingerprint: String,
+        action: ReviewFindingAction,
+    },
+
     /// Submit a user message with an explicit collaboration mask.
     SubmitUserMessageWithMode {
         text: String,
@@ -299,14 +332,32 @@ pub(crate) enum AppEvent {
         cwd: PathBuf,
         branch: Option<String>,
     },
+    /// Async result of running a custom command-backed status line segment.
+    StatusLineSegmentUpdated {
+        name: String,
+        value: Option<String>,
+    },
     /// Apply a user-confirmed status-line item ordering/selection.
     StatusLineSetup {
-        items: Vec<StatusLineItem>,
+        items: Vec<StatusLineEntry>,
     },
     /// Dismiss the status-line setup UI without changing config.
     StatusLineSetupCancelled,
 }
 
+/// What to do with a finding picked in the review triage panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReviewFindingAction {
+    /// Show the actions available for the finding.
+    ShowActions,
+    /// Print the finding's location, source snippet and body.
+    Open,
+    /// Ask Codex to fix the finding in the main thread.
+    Fix,
+    /// Hide the finding for this change, including on later reviews.
+    Dismiss,
+}
+
 /// The exit strategy requested by the UI layer.
 ///
 /// Most user-initiated exits should use `ShutdownFirst` so core cleanup runs and the UI exits only
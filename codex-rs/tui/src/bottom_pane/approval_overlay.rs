@@ -50,6 +50,8 @@ pub(crate) enum ApprovalRequest {
         reason: Option<String>,
         cwd: PathBuf,
         changes: HashMap<PathBuf, FileChange>,
+        /// The changes were made by a command in the workspace overlay.
+        overlay_review: bool,
     },
     McpElicitation {
         server_name: String,
@@ -115,6 +117,13 @@ impl ApprovalOverlay {
                 exec_options(proposed_execpolicy_amendment.clone(), features),
                 "是否执行以下命令？".to_string(),
             ),
+            ApprovalVariant::ApplyPatch {
+                overlay_files: Some(file_count),
+                ..
+            } => (
+                overlay_options(*file_count),
+                "是否将以下更改应用到工作区？".to_string(),
+            ),
             ApprovalVariant::ApplyPatch { .. } => {
                 (patch_options(), "是否应用以下修改？".to_string())
             }
@@ -368,7 +377,9 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 reason,
                 cwd,
                 changes,
+                overlay_review,
             } => {
+                let overlay_files = overlay_review.then_some(changes.len());
                 let mut header: Vec<Box<dyn Renderable>> = Vec::new();
                 if let Some(reason) = reason
                     && !reason.is_empty()
@@ -381,7 +392,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 }
                 header.push(DiffSummary::new(changes, cwd).into());
                 Self {
-                    variant: ApprovalVariant::ApplyPatch { id },
+                    variant: ApprovalVariant::ApplyPatch { id, overlay_files },
                     header: Box::new(ColumnRenderable::with(header)),
                 }
             }
@@ -417,6 +428,9 @@ enum ApprovalVariant {
     },
     ApplyPatch {
         id: String,
+        /// Number of files under review when the changes come from the
+        /// workspace overlay.
+        overlay_files: Option<usize>,
     },
     McpElicitation {
         server_name: String,
@@ -510,6 +524,45 @@ fn patch_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn overlay_options(file_count: usize) -> Vec<ApprovalOption> {
+    if file_count <= 1 {
+        return vec![
+            ApprovalOption {
+                label: "是，应用此更改".to_string(),
+                decision: ApprovalDecision::Review(ReviewDecision::Approved),
+                display_shortcut: None,
+                additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+            },
+            ApprovalOption {
+                label: "否，丢弃此更改".to_string(),
+                decision: ApprovalDecision::Review(ReviewDecision::Denied),
+                display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+                additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+            },
+        ];
+    }
+    vec![
+        ApprovalOption {
+            label: "是，应用全部更改".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Approved),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: "逐个文件审查".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::ReviewEachFile),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('f'))],
+        },
+        ApprovalOption {
+            label: "否，丢弃全部更改".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Denied),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
 fn elicitation_options() -> Vec<ApprovalOption> {
     vec![
         ApprovalOption {
@@ -642,6 +695,54 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    #[test]
+    fn overlay_review_offers_per_file_review_for_several_files() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let changes = HashMap::from([
+            (
+                PathBuf::from("/tmp/a.txt"),
+                FileChange::Add {
+                    content: "a\n".to_string(),
+                },
+            ),
+            (
+                PathBuf::from("/tmp/b.txt"),
+                FileChange::Delete {
+                    content: "b\n".to_string(),
+                },
+            ),
+        ]);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::ApplyPatch {
+                id: "test".to_string(),
+                reason: None,
+                cwd: PathBuf::from("/tmp"),
+                changes,
+                overlay_review: true,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        assert_eq!(
+            view.options
+                .iter()
+                .map(|option| option.label.as_str())
+                .collect::<Vec<_>>(),
+            vec!["是，应用全部更改", "逐个文件审查", "否，丢弃全部更改"]
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE));
+        let mut saw_op = false;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::PatchApproval { decision, .. }) = ev {
+                assert_eq!(decision, ReviewDecision::ReviewEachFile);
+                saw_op = true;
+                break;
+            }
+        }
+        assert!(saw_op, "expected per-file review to emit a patch approval");
+    }
+
     #[test]
     fn header_includes_command_snippet() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
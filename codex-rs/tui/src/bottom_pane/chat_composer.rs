@@ -166,6 +166,7 @@ use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_chatgpt::connectors;
 use codex_chatgpt::connectors::AppInfo;
 use codex_core::skills::model::SkillMetadata;
+use codex_core::symbol_index::Symbol;
 use codex_file_search::FileMatch;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -931,6 +932,21 @@ impl ChatComposer {
         }
     }
 
+    /// Integrate workspace symbols matching the current `@` token.
+    pub(crate) fn on_symbol_search_result(&mut self, query: String, symbols: Vec<Symbol>) {
+        let Some(current_token) = Self::current_at_token(&self.textarea) else {
+            return;
+        };
+
+        if !current_token.starts_with(&query) {
+            return;
+        }
+
+        if let ActivePopup::File(popup) = &mut self.active_popup {
+            popup.set_symbols(&query, symbols);
+        }
+    }
+
     /// Show the transient "press again to quit" hint for `key`.
     ///
     /// The owner (`BottomPane`/`ChatWidget`) is responsible for scheduling a
@@ -1350,12 +1366,11 @@ impl ChatComposer {
                 modifiers: KeyModifiers::NONE,
                 ..
             } => {
-                let Some(sel) = popup.selected_match() else {
+                let Some(sel_path) = popup.selected_match() else {
                     self.active_popup = ActivePopup::None;
                     return (InputResult::None, true);
                 };
 
-                let sel_path = sel.to_string_lossy().to_string();
                 // If selected path looks like an image (png/jpeg), attach as image instead of inserting text.
                 let is_image = Self::is_image_path(&sel_path);
                 if is_image {
@@ -4137,6 +4152,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn selecting_symbol_suggestion_inserts_location() {
+        use codex_core::symbol_index::SymbolKind;
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer =
+            ChatComposer::new(true, sender, false, "让 Codex 做点什么".to_string(), false);
+        type_chars_humanlike(&mut composer, &['@', 'p', 'a', 'r', 's', 'e']);
+        assert!(matches!(composer.active_popup, ActivePopup::File(_)));
+
+        composer.on_file_search_result("parse".to_string(), Vec::new());
+        composer.on_symbol_search_result(
+            "parse".to_string(),
+            vec![Symbol {
+                name: "parse".to_string(),
+                kind: SymbolKind::Function,
+                container: None,
+                path: PathBuf::from("src/lib.rs"),
+                start_line: 3,
+                end_line: 9,
+                depth: 0,
+            }],
+        );
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(composer.current_text(), "src/lib.rs:3-9 ");
+        assert!(matches!(composer.active_popup, ActivePopup::None));
+    }
+
     /// Behavior: if the ASCII path has a pending first char (flicker suppression) and a non-ASCII
     /// char arrives next, the pending ASCII char should still be preserved and the overall input
     /// should submit normally (i.e. we should not misclassify this as a paste burst).
@@ -0,0 +1,99 @@
+//! Picker for dropping tool outputs and images from the context.
+//!
+//! Shown after `/context` when the breakdown contains droppable items. Selected
+//! ids are submitted as `Op::DropContextItems`; core replaces each item with a
+//! short placeholder before the next turn.
+
+use codex_core::protocol::ContextBreakdownEvent;
+use codex_core::protocol::Op;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::bottom_pane_view::BottomPaneView;
+use crate::bottom_pane::multi_select_picker::MultiSelectItem;
+use crate::bottom_pane::multi_select_picker::MultiSelectPicker;
+use crate::context_breakdown::category_label;
+use crate::context_breakdown::droppable_items;
+use crate::render::renderable::Renderable;
+use crate::status::format_tokens_compact;
+
+pub(crate) struct ContextDropView {
+    picker: MultiSelectPicker,
+}
+
+impl ContextDropView {
+    /// Returns `None` when the breakdown has nothing that can be dropped.
+    pub(crate) fn new(
+        breakdown: &ContextBreakdownEvent,
+        app_event_tx: AppEventSender,
+    ) -> Option<Self> {
+        let items = droppable_items(breakdown)
+            .into_iter()
+            .filter_map(|item| {
+                let id = item.id.clone()?;
+                let label = category_label(item.category, item.tool_name.as_deref());
+                Some(MultiSelectItem {
+                    id,
+                    name: format!("{}  {label}", format_tokens_compact(item.tokens)),
+                    description: (!item.preview.is_empty()).then(|| item.preview.clone()),
+                    enabled: false,
+                })
+            })
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            return None;
+        }
+
+        let picker = MultiSelectPicker::builder(
+            "从上下文中移除".to_string(),
+            Some("所选条目将在下一轮之前替换为简短的占位文本。".to_string()),
+            app_event_tx,
+        )
+        .instructions(vec!["使用 ↑↓ 移动，空格选择，回车确认，esc 取消。".into()])
+        .items(items)
+        .on_preview(|items| {
+            let selected = items.iter().filter(|item| item.enabled).count();
+            (selected > 0).then(|| Line::from(format!("已选择 {selected} 项")))
+        })
+        .on_confirm(|ids, app_event| {
+            if ids.is_empty() {
+                return;
+            }
+            app_event.send(AppEvent::CodexOp(Op::DropContextItems {
+                item_ids: ids.to_vec(),
+            }));
+        })
+        .build();
+
+        Some(Self { picker })
+    }
+}
+
+impl BottomPaneView for ContextDropView {
+    fn handle_key_event(&mut self, key_event: crossterm::event::KeyEvent) {
+        self.picker.handle_key_event(key_event);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.picker.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.picker.close();
+        CancellationEvent::Handled
+    }
+}
+
+impl Renderable for ContextDropView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.picker.render(area, buf)
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.picker.desired_height(width)
+    }
+}
@@ -1,5 +1,4 @@
-use std::path::PathBuf;
-
+use codex_core::symbol_index::Symbol;
 use codex_file_search::FileMatch;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -24,6 +23,8 @@ pub(crate) struct FileSearchPopup {
     waiting: bool,
     /// Cached matches; paths relative to the search dir.
     matches: Vec<FileMatch>,
+    /// Workspace symbols matching `display_query`, listed after the files.
+    symbols: Vec<Symbol>,
     /// Shared selection/scroll state.
     state: ScrollState,
 }
@@ -35,6 +36,7 @@ impl FileSearchPopup {
             pending_query: String::new(),
             waiting: true,
             matches: Vec::new(),
+            symbols: Vec::new(),
             state: ScrollState::new(),
         }
     }
@@ -58,6 +60,7 @@ impl FileSearchPopup {
         self.pending_query.clear();
         self.waiting = false;
         self.matches.clear();
+        self.symbols.clear();
         // Reset selection/scroll state when showing the empty prompt.
         self.state.reset();
     }
@@ -69,33 +72,67 @@ impl FileSearchPopup {
             return; // stale
         }
 
+        if query != self.display_query {
+            self.symbols.clear();
+        }
         self.display_query = query.to_string();
         self.matches = matches;
         self.waiting = false;
-        let len = self.matches.len();
+        let len = self.len();
         self.state.clamp_selection(len);
         self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
     }
 
+    /// Replace the symbol suggestions shown after the file matches. Only
+    /// applied when `query` matches `pending_query`.
+    pub(crate) fn set_symbols(&mut self, query: &str, symbols: Vec<Symbol>) {
+        if query != self.pending_query {
+            return; // stale
+        }
+
+        if query != self.display_query {
+            self.matches.clear();
+        }
+        self.display_query = query.to_string();
+        self.symbols = symbols;
+        let len = self.len();
+        self.state.clamp_selection(len);
+        self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
+    }
+
+    fn len(&self) -> usize {
+        self.matches.len() + self.symbols.len()
+    }
+
     /// Move selection cursor up.
     pub(crate) fn move_up(&mut self) {
-        let len = self.matches.len();
+        let len = self.len();
         self.state.move_up_wrap(len);
         self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
     }
 
     /// Move selection cursor down.
     pub(crate) fn move_down(&mut self) {
-        let len = self.matches.len();
+        let len = self.len();
         self.state.move_down_wrap(len);
         self.state.ensure_visible(len, len.min(MAX_POPUP_ROWS));
     }
 
-    pub(crate) fn selected_match(&self) -> Option<&PathBuf> {
-        self.state
-            .selected_idx
-            .and_then(|idx| self.matches.get(idx))
-            .map(|file_match| &file_match.path)
+    /// Text to insert for the selected row: the file path, or
+    /// `path:start-end` for a symbol.
+    pub(crate) fn selected_match(&self) -> Option<String> {
+        let idx = self.state.selected_idx?;
+        if let Some(file_match) = self.matches.get(idx) {
+            return Some(file_match.path.to_string_lossy().to_string());
+        }
+        self.symbols.get(idx - self.matches.len()).map(|symbol| {
+            format!(
+                "{}:{}-{}",
+                symbol.path.display(),
+                symbol.start_line,
+                symbol.end_line
+            )
+        })
     }
 
     pub(crate) fn calculate_required_height(&self) -> u16 {
@@ -105,32 +142,43 @@ impl FileSearchPopup {
         // up to MAX_RESULTS regardless of the waiting flag so the list
         // remains stable while a newer search is in-flight.
 
-        self.matches.len().clamp(1, MAX_POPUP_ROWS) as u16
+        self.len().clamp(1, MAX_POPUP_ROWS) as u16
     }
 }
 
 impl WidgetRef for &FileSearchPopup {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
         // Convert matches to GenericDisplayRow, translating indices to usize at the UI boundary.
-        let rows_all: Vec<GenericDisplayRow> = if self.matches.is_empty() {
-            Vec::new()
-        } else {
-            self.matches
-                .iter()
-                .map(|m| GenericDisplayRow {
-                    name: m.path.to_string_lossy().to_string(),
-                    match_indices: m
-                        .indices
-                        .as_ref()
-                        .map(|v| v.iter().map(|&i| i as usize).collect()),
-                    display_shortcut: None,
-                    description: None,
-                    wrap_indent: None,
-                    is_disabled: false,
-                    disabled_reason: None,
-                })
-                .collect()
-        };
+        let file_rows = self.matches.iter().map(|m| GenericDisplayRow {
+            name: m.path.to_string_lossy().to_string(),
+            match_indices: m
+                .indices
+                .as_ref()
+                .map(|v| v.iter().map(|&i| i as usize).collect()),
+            display_shortcut: None,
+            description: None,
+            wrap_indent: None,
+            is_disabled: false,
+            disabled_reason: None,
+        });
+        let symbol_rows = self.symbols.iter().map(|symbol| GenericDisplayRow {
+            name: match &symbol.container {
+                Some(container) => format!("{container}::{}", symbol.name),
+                None => symbol.name.clone(),
+            },
+            match_indices: None,
+            display_shortcut: None,
+            description: Some(format!(
+                "{} · {}:{}",
+                symbol.kind,
+                symbol.path.display(),
+                symbol.start_line
+            )),
+            wrap_indent: None,
+            is_disabled: false,
+            disabled_reason: None,
+        });
+        let rows_all: Vec<GenericDisplayRow> = file_rows.chain(symbol_rows).collect();
 
         let empty_message = if self.waiting {
             "加载中..."
@@ -16,11 +16,15 @@
 //! - Usage limits (5-hour, weekly)
 //! - Session info (ID, tokens used)
 //! - Application version
+//! - Custom segments declared under `tui.status_line_segments`
 
+use codex_core::config::types::StatusLineSegment;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::text::Line;
+use std::collections::BTreeMap;
 use std::collections::HashSet;
+use std::fmt;
 use strum::IntoEnumIterator;
 use strum_macros::Display;
 use strum_macros::EnumIter;
@@ -152,6 +156,49 @@ impl StatusLineItem {
     }
 }
 
+/// A configured status line entry: either a built-in item or a custom segment.
+///
+/// Custom segments are identified by their key under `tui.status_line_segments`;
+/// built-in item identifiers take precedence when a custom key collides with one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum StatusLineEntry {
+    Builtin(StatusLineItem),
+    Custom(String),
+}
+
+impl StatusLineEntry {
+    /// Resolves a configured identifier, returning `None` for unknown ids.
+    pub(crate) fn parse(
+        id: &str,
+        custom_segments: &BTreeMap<String, StatusLineSegment>,
+    ) -> Option<Self> {
+        if let Ok(item) = id.parse::<StatusLineItem>() {
+            return Some(Self::Builtin(item));
+        }
+        custom_segments
+            .contains_key(id)
+            .then(|| Self::Custom(id.to_string()))
+    }
+}
+
+impl fmt::Display for StatusLineEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusLineEntry::Builtin(item) => write!(f, "{item}"),
+            StatusLineEntry::Custom(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Describes a custom segment for the picker.
+fn custom_segment_description(segment: &StatusLineSegment) -> String {
+    match (&segment.command, &segment.template) {
+        (Some(command), _) => format!("Custom command: {}", command.join(" ")),
+        (None, Some(template)) => format!("Custom template: {template}"),
+        (None, None) => "Custom segment (no command or template configured)".to_string(),
+    }
+}
+
 /// Interactive view for configuring which items appear in the status line.
 ///
 /// Wraps a [`MultiSelectPicker`] with status-line-specific behavior:
@@ -171,35 +218,55 @@ impl StatusLineSetupView {
     ///
     /// * `status_line_items` - Currently configured item IDs (in display order),
     ///   or `None` to start with all items disabled
+    /// * `custom_segments` - Custom segments declared in config
     /// * `app_event_tx` - Event sender for dispatching configuration changes
     ///
     /// Items from `status_line_items` are shown first (in order) and marked as
-    /// enabled. Remaining items are appended and marked as disabled.
-    pub(crate) fn new(status_line_items: Option<&[String]>, app_event_tx: AppEventSender) -> Self {
+    /// enabled. Remaining built-in items and custom segments are appended and
+    /// marked as disabled.
+    pub(crate) fn new(
+        status_line_items: Option<&[String]>,
+        custom_segments: &BTreeMap<String, StatusLineSegment>,
+        app_event_tx: AppEventSender,
+    ) -> Self {
         let mut used_ids = HashSet::new();
         let mut items = Vec::new();
 
         if let Some(selected_items) = status_line_items.as_ref() {
             for id in *selected_items {
-                let Ok(item) = id.parse::<StatusLineItem>() else {
+                let Some(entry) = StatusLineEntry::parse(id, custom_segments) else {
                     continue;
                 };
-                let item_id = item.to_string();
+                let item_id = entry.to_string();
                 if !used_ids.insert(item_id.clone()) {
                     continue;
                 }
-                items.push(Self::status_line_select_item(item, true));
+                items.push(Self::status_line_select_item(&entry, custom_segments, true));
             }
         }
 
-        for item in StatusLineItem::iter() {
-            let item_id = item.to_string();
-            if used_ids.contains(&item_id) {
+        let remaining = StatusLineItem::iter()
+            .map(StatusLineEntry::Builtin)
+            .chain(
+                custom_segments
+                    .keys()
+                    .filter_map(|name| StatusLineEntry::parse(name, custom_segments)),
+            )
+            .collect::<Vec<_>>();
+        for entry in remaining {
+            if !used_ids.insert(entry.to_string()) {
                 continue;
             }
-            items.push(Self::status_line_select_item(item, false));
+            items.push(Self::status_line_select_item(
+                &entry,
+                custom_segments,
+                false,
+            ));
         }
 
+        let preview_segments = custom_segments.clone();
+        let confirm_segments = custom_segments.clone();
+
         Self {
             picker: MultiSelectPicker::builder(
                 "Configure Status Line".to_string(),
@@ -212,12 +279,15 @@ impl StatusLineSetupView {
             ])
             .items(items)
             .enable_ordering()
-            .on_preview(|items| {
+            .on_preview(move |items| {
                 let preview = items
                     .iter()
                     .filter(|item| item.enabled)
-                    .filter_map(|item| item.id.parse::<StatusLineItem>().ok())
-                    .map(|item| item.render())
+                    .filter_map(|item| StatusLineEntry::parse(&item.id, &preview_segments))
+                    .map(|entry| match entry {
+                        StatusLineEntry::Builtin(item) => item.render().to_string(),
+                        StatusLineEntry::Custom(name) => format!("<{name}>"),
+                    })
                     .collect::<Vec<_>>()
                     .join(" · ");
                 if preview.is_empty() {
@@ -226,12 +296,11 @@ impl StatusLineSetupView {
                     Some(Line::from(preview))
                 }
             })
-            .on_confirm(|ids, app_event| {
+            .on_confirm(move |ids, app_event| {
                 let items = ids
                     .iter()
-                    .map(|id| id.parse::<StatusLineItem>())
-                    .collect::<Result<Vec<_>, _>>()
-                    .unwrap_or_default();
+                    .filter_map(|id| StatusLineEntry::parse(id, &confirm_segments))
+                    .collect::<Vec<_>>();
                 app_event.send(AppEvent::StatusLineSetup { items });
             })
             .on_cancel(|app_event| {
@@ -241,12 +310,23 @@ impl StatusLineSetupView {
         }
     }
 
-    /// Converts a [`StatusLineItem`] into a [`MultiSelectItem`] for the picker.
-    fn status_line_select_item(item: StatusLineItem, enabled: bool) -> MultiSelectItem {
+    /// Converts a [`StatusLineEntry`] into a [`MultiSelectItem`] for the picker.
+    fn status_line_select_item(
+        entry: &StatusLineEntry,
+        custom_segments: &BTreeMap<String, StatusLineSegment>,
+        enabled: bool,
+    ) -> MultiSelectItem {
+        let description = match entry {
+            StatusLineEntry::Builtin(item) => item.description().to_string(),
+            StatusLineEntry::Custom(name) => custom_segments
+                .get(name)
+                .map(custom_segment_description)
+                .unwrap_or_default(),
+        };
         MultiSelectItem {
-            id: item.to_string(),
-            name: item.to_string(),
-            description: Some(item.description().to_string()),
+            id: entry.to_string(),
+            name: entry.to_string(),
+            description: Some(description),
             enabled,
         }
     }
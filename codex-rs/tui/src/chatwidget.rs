@@ -31,11 +31,16 @@ use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
+use crate::bottom_pane::StatusLineEntry;
 use crate::bottom_pane::StatusLineItem;
 use crate::bottom_pane::StatusLineSetupView;
 use crate::status::RateLimitWindowDisplay;
 use crate::status::format_directory_display;
 use crate::status::format_tokens_compact;
+use crate::status_line_segments::StatusLineSegmentState;
+use crate::status_line_segments::StatusLineTemplateContext;
+use crate::status_line_segments::render_template;
+use crate::status_line_segments::template_uses_placeholder;
 use crate::text_formatting::proper_join;
 use crate::version::CODEX_CLI_VERSION;
 use codex_app_server_protocol::ConfigLayerSource;
@@ -62,6 +67,9 @@ use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::ConfigReloadedEvent;
+use codex_core::protocol::ContextBreakdownEvent;
+use codex_core::protocol::ContextItemsDroppedEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -158,6 +166,7 @@ use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::CollaborationModeIndicator;
 use crate::bottom_pane::ColumnWidthMode;
+use crate::bottom_pane::ContextDropView;
 use crate::bottom_pane::DOUBLE_PRESS_QUIT_SHORTCUT_ENABLED;
 use crate::bottom_pane::ExperimentalFeatureItem;
 use crate::bottom_pane::ExperimentalFeaturesView;
@@ -173,6 +182,7 @@ use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::collab;
 use crate::collaboration_modes;
+use crate::context_breakdown;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
@@ -206,6 +216,8 @@ use self::agent::spawn_agent_from_existing;
 pub(crate) use self::agent::spawn_op_forwarder;
 mod session_header;
 use self::session_header::SessionHeader;
+mod review_findings;
+use self::review_findings::ReviewFindingsState;
 mod skills;
 use self::skills::collect_tool_mentions;
 use self::skills::find_app_mentions;
@@ -224,6 +236,7 @@ use codex_core::CodexAuth;
 use codex_core::ThreadManager;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
+use codex_core::symbol_index::Symbol;
 use codex_file_search::FileMatch;
 use codex_protocol::openai_models::InputModality;
 use codex_protocol::openai_models::ModelPreset;
@@ -564,6 +577,10 @@ pub(crate) struct ChatWidget {
     is_review_mode: bool,
     // Snapshot of token usage to restore after review mode exits.
     pre_review_token_info: Option<Option<TokenUsageInfo>>,
+    // Target of the review in progress, kept to key dismissed findings.
+    review_target: Option<ReviewTarget>,
+    // Pending findings of the most recent review, shown by `/findings`.
+    review_findings: Option<ReviewFindingsState>,
     // Whether the next streamed assistant content should be preceded by a final message separator.
     //
     // This is set whenever we insert a visible history cell that conceptually belongs to a turn.
@@ -608,6 +625,8 @@ pub(crate) struct ChatWidget {
     status_line_branch_pending: bool,
     // True once we've attempted a branch lookup for the current CWD.
     status_line_branch_lookup_complete: bool,
+    // Cached output and refresh bookkeeping for command-backed custom segments.
+    status_line_segments: HashMap<String, StatusLineSegmentState>,
     external_editor_state: ExternalEditorState,
 }
 
@@ -855,7 +874,8 @@ impl ChatWidget {
             );
             self.on_warning(message);
         }
-        if !items.contains(&StatusLineItem::GitBranch) {
+        let needs_branch = self.status_line_needs_branch(&items);
+        if !needs_branch {
             self.status_line_branch = None;
             self.status_line_branch_pending = false;
             self.status_line_branch_lookup_complete = false;
@@ -870,13 +890,21 @@ impl ChatWidget {
         let cwd = self.status_line_cwd().to_path_buf();
         self.sync_status_line_branch_state(&cwd);
 
-        if items.contains(&StatusLineItem::GitBranch) && !self.status_line_branch_lookup_complete {
-            self.request_status_line_branch(cwd);
+        if needs_branch && !self.status_line_branch_lookup_complete {
+            self.request_status_line_branch(cwd.clone());
         }
 
+        self.status_line_segments
+            .retain(|name, _| items.contains(&StatusLineEntry::Custom(name.clone())));
         let mut parts = Vec::new();
-        for item in items {
-            if let Some(value) = self.status_line_value_for_item(&item) {
+        for entry in items {
+            let value = match &entry {
+                StatusLineEntry::Builtin(item) => self.status_line_value_for_item(item),
+                StatusLineEntry::Custom(name) => {
+                    self.status_line_value_for_custom_segment(name, &cwd)
+                }
+            };
+            if let Some(value) = value {
                 parts.push(value);
             }
         }
@@ -901,7 +929,7 @@ impl ChatWidget {
     ///
     /// An empty selection is normalized to `None` so the status line is fully disabled and the
     /// behavior matches an unset `tui.status_line` config value.
-    pub(crate) fn setup_status_line(&mut self, items: Vec<StatusLineItem>) {
+    pub(crate) fn setup_status_line(&mut self, items: Vec<StatusLineEntry>) {
         tracing::info!("status line setup confirmed with items: {items:#?}");
         let ids = items.iter().map(ToString::to_string).collect::<Vec<_>>();
         self.config.tui_status_line = if ids.is_empty() { None } else { Some(ids) };
@@ -922,10 +950,19 @@ impl ChatWidget {
         self.status_line_branch_lookup_complete = true;
     }
 
-    /// Forces a new git-branch lookup when `GitBranch` is part of the configured status line.
+    /// Stores the latest output of a command-backed custom segment.
+    ///
+    /// Results for segments that are no longer configured are dropped.
+    pub(crate) fn set_status_line_segment_value(&mut self, name: &str, value: Option<String>) {
+        if let Some(state) = self.status_line_segments.get_mut(name) {
+            state.finish(value);
+        }
+    }
+
+    /// Forces a new git-branch lookup when the configured status line shows the branch.
     fn request_status_line_branch_refresh(&mut self) {
         let (items, _) = self.status_line_items_with_invalids();
-        if items.is_empty() || !items.contains(&StatusLineItem::GitBranch) {
+        if !self.status_line_needs_branch(&items) {
             return;
         }
         let cwd = self.status_line_cwd().to_path_buf();
@@ -2307,6 +2344,7 @@ impl ChatWidget {
             reason: ev.reason,
             changes: ev.changes.clone(),
             cwd: self.config.cwd.clone(),
+            overlay_review: ev.overlay_review,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -2559,6 +2597,8 @@ impl ChatWidget {
             quit_shortcut_key: None,
             is_review_mode: false,
             pre_review_token_info: None,
+            review_target: None,
+            review_findings: None,
             needs_final_message_separator: false,
             had_work_activity: false,
             saw_plan_update_this_turn: false,
@@ -2577,6 +2617,7 @@ impl ChatWidget {
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
+            status_line_segments: HashMap::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2725,6 +2766,8 @@ impl ChatWidget {
             quit_shortcut_key: None,
             is_review_mode: false,
             pre_review_token_info: None,
+            review_target: None,
+            review_findings: None,
             needs_final_message_separator: false,
             had_work_activity: false,
             last_separator_elapsed_secs: None,
@@ -2739,6 +2782,7 @@ impl ChatWidget {
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
+            status_line_segments: HashMap::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2872,6 +2916,8 @@ impl ChatWidget {
             quit_shortcut_key: None,
             is_review_mode: false,
             pre_review_token_info: None,
+            review_target: None,
+            review_findings: None,
             needs_final_message_separator: false,
             had_work_activity: false,
             saw_plan_update_this_turn: false,
@@ -2890,6 +2936,7 @@ impl ChatWidget {
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
+            status_line_segments: HashMap::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -3156,9 +3203,15 @@ impl ChatWidget {
                 self.clear_token_usage();
                 self.app_event_tx.send(AppEvent::CodexOp(Op::Compact));
             }
+            SlashCommand::Context => {
+                self.submit_op(Op::GetContextBreakdown);
+            }
             SlashCommand::Review => {
                 self.open_review_popup();
             }
+            SlashCommand::Findings => {
+                self.open_review_findings_panel();
+            }
             SlashCommand::Rename => {
                 self.otel_manager.counter("codex.thread.rename", 1, &[]);
                 self.show_rename_prompt();
@@ -3346,6 +3399,7 @@ impl ChatWidget {
                         ]),
                         reason: None,
                         grant_root: Some(PathBuf::from("/tmp")),
+                        overlay_review: false,
                     }),
                 }));
             }
@@ -3834,12 +3888,15 @@ impl ChatWidget {
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
             EventMsg::ListRemoteSkillsResponse(_) | EventMsg::RemoteSkillDownloaded(_) => {}
+            EventMsg::ContextBreakdown(ev) => self.on_context_breakdown(ev),
+            EventMsg::ContextItemsDropped(ev) => self.on_context_items_dropped(ev),
             EventMsg::SkillsUpdateAvailable => {
                 self.submit_op(Op::ListSkills {
                     cwds: Vec::new(),
                     force_reload: true,
                 });
             }
+            EventMsg::ConfigReloaded(ev) => self.on_config_reloaded(ev),
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
@@ -3861,7 +3918,7 @@ impl ChatWidget {
             EventMsg::EnteredReviewMode(review_request) => {
                 self.on_entered_review_mode(review_request, from_replay)
             }
-            EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
+            EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review, from_replay),
             EventMsg::ContextCompacted(_) => self.on_agent_message("上下文已压缩".to_owned()),
             EventMsg::CollabAgentSpawnBegin(_) => {}
             EventMsg::CollabAgentSpawnEnd(ev) => self.on_collab_event(collab::spawn_end(ev)),
@@ -3902,6 +3959,7 @@ impl ChatWidget {
             self.bottom_pane.set_task_running(true);
         }
         self.is_review_mode = true;
+        self.review_target = Some(review.target.clone());
         let hint = review
             .user_facing_hint
             .unwrap_or_else(|| codex_core::review_prompts::user_facing_hint(&review.target));
@@ -3910,7 +3968,7 @@ impl ChatWidget {
         self.request_redraw();
     }
 
-    fn on_exited_review_mode(&mut self, review: ExitedReviewModeEvent) {
+    fn on_exited_review_mode(&mut self, review: ExitedReviewModeEvent, from_replay: bool) {
         // Leave review mode; if output is present, flush pending stream + show results.
         if let Some(output) = review.review_output {
             self.flush_answer_stream_with_separator();
@@ -3932,11 +3990,19 @@ impl ChatWidget {
                     self.app_event_tx
                         .send(AppEvent::InsertHistoryCell(Box::new(body_cell)));
                 }
+            } else if let Some(target) = self.review_target.clone() {
+                // The app resolves the reviewed change before triage starts.
+                self.app_event_tx.send(AppEvent::ReviewFindingsReceived {
+                    target,
+                    findings: output.findings,
+                    open_panel: !from_replay,
+                });
             }
             // Final message is rendered as part of the AgentMessage.
         }
 
         self.is_review_mode = false;
+        self.review_target = None;
         self.restore_pre_review_token_info();
         // Append a finishing banner at the end of this turn.
         self.add_to_history(history_cell::new_review_status_line(
@@ -4074,6 +4140,7 @@ impl ChatWidget {
     fn open_status_line_setup(&mut self) {
         let view = StatusLineSetupView::new(
             self.config.tui_status_line.as_deref(),
+            &self.config.tui_status_line_segments,
             self.app_event_tx.clone(),
         );
         self.bottom_pane.show_view(Box::new(view));
@@ -4081,8 +4148,10 @@ impl ChatWidget {
 
     /// Parses configured status-line ids into known items and collects unknown ids.
     ///
-    /// Unknown ids are deduplicated in insertion order for warning messages.
-    fn status_line_items_with_invalids(&self) -> (Vec<StatusLineItem>, Vec<String>) {
+    /// Ids that are not built-in items resolve to custom segments when declared under
+    /// `tui.status_line_segments`. Unknown ids are deduplicated in insertion order for warning
+    /// messages.
+    fn status_line_items_with_invalids(&self) -> (Vec<StatusLineEntry>, Vec<String>) {
         let mut invalid = Vec::new();
         let mut invalid_seen = HashSet::new();
         let mut items = Vec::new();
@@ -4090,9 +4159,9 @@ impl ChatWidget {
             return (items, invalid);
         };
         for id in config_items {
-            match id.parse::<StatusLineItem>() {
-                Ok(item) => items.push(item),
-                Err(_) => {
+            match StatusLineEntry::parse(id, &self.config.tui_status_line_segments) {
+                Some(entry) => items.push(entry),
+                None => {
                     if invalid_seen.insert(id.clone()) {
                         invalid.push(format!(r#""{id}""#));
                     }
@@ -4237,6 +4306,68 @@ impl ChatWidget {
         }
     }
 
+    /// Whether the status line shows the git branch, either as the `GitBranch` item or through
+    /// a `{branch}` placeholder in a template segment.
+    fn status_line_needs_branch(&self, items: &[StatusLineEntry]) -> bool {
+        items.iter().any(|entry| match entry {
+            StatusLineEntry::Builtin(item) => *item == StatusLineItem::GitBranch,
+            StatusLineEntry::Custom(name) => self
+                .config
+                .tui_status_line_segments
+                .get(name)
+                .filter(|segment| segment.command.is_none())
+                .and_then(|segment| segment.template.as_deref())
+                .is_some_and(|template| template_uses_placeholder(template, "branch")),
+        })
+    }
+
+    /// Resolves a display string for a custom segment.
+    ///
+    /// Template segments render synchronously. Command segments return their cached output and
+    /// schedule another run once the refresh interval elapses.
+    fn status_line_value_for_custom_segment(&mut self, name: &str, cwd: &Path) -> Option<String> {
+        let segment = self.config.tui_status_line_segments.get(name)?;
+        if let Some(template) = segment.template.as_deref()
+            && segment.command.is_none()
+        {
+            return render_template(template, &self.status_line_template_context());
+        }
+        let state = self
+            .status_line_segments
+            .entry(name.to_string())
+            .or_default();
+        state.schedule(name, segment, cwd.to_path_buf(), &self.app_event_tx);
+        state.value.clone()
+    }
+
+    /// Collects session values exposed to template segments.
+    fn status_line_template_context(&self) -> StatusLineTemplateContext {
+        let usage = self.status_line_total_usage();
+        let sandbox = match self.config.sandbox_policy.get() {
+            SandboxPolicy::DangerFullAccess => "danger-full-access",
+            SandboxPolicy::ReadOnly => "read-only",
+            SandboxPolicy::ExternalSandbox { .. } => "external-sandbox",
+            SandboxPolicy::WorkspaceWrite { .. } => "workspace-write",
+            SandboxPolicy::WorkspaceOverlay { .. } => "workspace-overlay",
+        };
+        StatusLineTemplateContext {
+            model: Some(self.model_display_name().to_string()),
+            cwd: Some(format_directory_display(self.status_line_cwd(), None)),
+            project: self.status_line_project_root_name(),
+            branch: self.status_line_branch.clone(),
+            thread_name: self.thread_name.clone(),
+            session_id: self.thread_id.map(|id| id.to_string()),
+            input_tokens: Some(format_tokens_compact(usage.input_tokens)),
+            output_tokens: Some(format_tokens_compact(usage.output_tokens)),
+            total_tokens: Some(format_tokens_compact(usage.tokens_in_context_window())),
+            context_remaining: self
+                .status_line_context_remaining_percent()
+                .map(|remaining| format!("{remaining}%")),
+            mode: self.collaboration_mode_label().map(str::to_string),
+            sandbox: Some(sandbox.to_string()),
+        }
+    }
+
     fn status_line_context_window_size(&self) -> Option<i64> {
         self.token_info
             .as_ref()
@@ -6264,6 +6395,11 @@ impl ChatWidget {
         self.bottom_pane.on_file_search_result(query, matches);
     }
 
+    /// Forward symbol suggestions for the `@` popup to the bottom pane.
+    pub(crate) fn apply_symbol_search_result(&mut self, query: String, symbols: Vec<Symbol>) {
+        self.bottom_pane.on_symbol_search_result(query, symbols);
+    }
+
     /// Handles a Ctrl+C press at the chat-widget layer.
     ///
     /// The first press arms a time-bounded quit shortcut and shows a footer hint via the bottom
@@ -6432,6 +6568,59 @@ impl ChatWidget {
         ));
     }
 
+    fn on_context_breakdown(&mut self, ev: ContextBreakdownEvent) {
+        self.add_to_history(context_breakdown::new_context_breakdown_output(&ev));
+        if let Some(view) = ContextDropView::new(&ev, self.app_event_tx.clone()) {
+            self.bottom_pane.show_view(Box::new(view));
+        }
+        self.request_redraw();
+    }
+
+    fn on_context_items_dropped(&mut self, ev: ContextItemsDroppedEvent) {
+        let message = if ev.item_ids.is_empty() {
+            "没有可移除的条目，上下文未改变。".to_string()
+        } else {
+            format!(
+                "已从上下文中移除 {} 项，约释放 {} tokens。",
+                ev.item_ids.len(),
+                format_tokens_compact(ev.tokens_freed)
+            )
+        };
+        self.add_info_message(message, None);
+    }
+
+    fn on_config_reloaded(&mut self, ev: ConfigReloadedEvent) {
+        // Turns are submitted with the widget's own settings, so adopt the
+        // reloaded ones or they would be overridden again on the next turn.
+        if !ev.applied.is_empty() {
+            self.set_model(&ev.model);
+            self.set_reasoning_effort(ev.reasoning_effort);
+            self.config.personality = ev.personality;
+            self.set_approval_policy(ev.approval_policy);
+            if let Err(err) = self.set_sandbox_policy(ev.sandbox_policy.clone()) {
+                tracing::warn!(%err, "failed to set reloaded sandbox_policy on chat config");
+            }
+        }
+        let message = if ev.applied.is_empty() {
+            "配置文件已更新，没有可在当前会话中生效的变更。".to_string()
+        } else {
+            format!("配置文件已重新加载，下一轮生效：{}", ev.applied.join(", "))
+        };
+        let hint = (!ev.requires_restart.is_empty()).then(|| {
+            format!(
+                "以下配置需重启 Codex 后生效：{}",
+                ev.requires_restart.join(", ")
+            )
+        });
+        self.add_info_message(message, hint);
+        if !ev.needs_confirmation.is_empty() {
+            self.add_to_history(history_cell::new_warning_event(format!(
+                "以下配置会放宽审批或沙箱限制，未自动应用；如需启用，请通过 /permissions 手动选择：{}",
+                ev.needs_confirmation.join(", ")
+            )));
+        }
+    }
+
     fn on_list_custom_prompts(&mut self, ev: ListCustomPromptsResponseEvent) {
         let len = ev.custom_prompts.len();
         debug!("received {len} custom prompts");
@@ -6499,6 +6688,37 @@ impl ChatWidget {
             ..Default::default()
         });
 
+        items.push(SelectionItem {
+            name: "审查提交范围".to_string(),
+            description: Some("（从所选提交到 HEAD）".into()),
+            actions: vec![Box::new({
+                let cwd = self.config.cwd.clone();
+                move |tx| {
+                    tx.send(AppEvent::OpenReviewRangePicker(cwd.clone()));
+                }
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
+        items.push(SelectionItem {
+            name: "审查指定路径的更改".to_string(),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenReviewPathsPrompt);
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
+        items.push(SelectionItem {
+            name: "审查补丁文件".to_string(),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenReviewPatchPrompt);
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
         items.push(SelectionItem {
             name: "自定义审查指令".to_string(),
             actions: vec![Box::new(move |tx| {
@@ -6591,6 +6811,97 @@ impl ChatWidget {
         });
     }
 
+    /// Lists recent commits; the selected one becomes the exclusive base of
+    /// a `base..HEAD` range review.
+    pub(crate) async fn show_review_range_picker(&mut self, cwd: &Path) {
+        let commits = codex_core::git_info::recent_commits(cwd, 100).await;
+
+        let mut items: Vec<SelectionItem> = Vec::with_capacity(commits.len());
+        for entry in commits {
+            let subject = entry.subject.clone();
+            let sha = entry.sha.clone();
+            let search_val = format!("{subject} {sha}");
+
+            items.push(SelectionItem {
+                name: subject,
+                actions: vec![Box::new(move |tx3: &AppEventSender| {
+                    tx3.send(AppEvent::CodexOp(Op::Review {
+                        review_request: ReviewRequest {
+                            target: ReviewTarget::CommitRange {
+                                base: sha.clone(),
+                                head: "HEAD".to_string(),
+                            },
+                            user_facing_hint: None,
+                        },
+                    }));
+                })],
+                dismiss_on_select: true,
+                search_value: Some(search_val),
+                ..Default::default()
+            });
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("选择范围起点（不含该提交）".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("输入以搜索提交".to_string()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn show_review_paths_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "审查指定路径的更改".to_string(),
+            "输入以空格分隔的路径并按 Enter（含空格的路径请加引号）".to_string(),
+            None,
+            Box::new(move |input: String| {
+                let Some(paths) = shlex::split(&input) else {
+                    tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        history_cell::new_error_event("路径中的引号不匹配。".to_string()),
+                    )));
+                    return;
+                };
+                if paths.is_empty() {
+                    return;
+                }
+                tx.send(AppEvent::CodexOp(Op::Review {
+                    review_request: ReviewRequest {
+                        target: ReviewTarget::Paths { paths, base: None },
+                        user_facing_hint: None,
+                    },
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn show_review_patch_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "审查补丁文件".to_string(),
+            "输入补丁文件路径并按 Enter".to_string(),
+            None,
+            Box::new(move |input: String| {
+                let trimmed = input.trim();
+                if trimmed.is_empty() {
+                    return;
+                }
+                tx.send(AppEvent::CodexOp(Op::Review {
+                    review_request: ReviewRequest {
+                        target: ReviewTarget::PatchFile {
+                            path: PathBuf::from(trimmed),
+                        },
+                        user_facing_hint: None,
+                    },
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
     pub(crate) fn show_review_custom_prompt(&mut self) {
         let tx = self.app_event_tx.clone();
         let view = CustomPromptView::new(
@@ -0,0 +1,308 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::git_info::get_git_repo_root;
+use codex_core::protocol::ReviewFinding;
+use codex_core::review_dismissals::dismiss_review_finding;
+use codex_core::review_dismissals::load_dismissed_findings;
+use codex_core::review_format::relative_finding_path;
+use codex_core::review_format::review_finding_fingerprint;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use super::ChatWidget;
+use crate::app_event::AppEvent;
+use crate::app_event::ReviewFindingAction;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+
+/// Lines of context shown above and below a finding's range.
+const SNIPPET_CONTEXT_LINES: u32 = 3;
+
+/// Findings from the most recent review that have not been dismissed.
+pub(crate) struct ReviewFindingsState {
+    /// Identifies the reviewed change; `None` keeps dismissals in memory only.
+    change_key: Option<String>,
+    findings: Vec<TriageFinding>,
+}
+
+struct TriageFinding {
+    finding: ReviewFinding,
+    path: String,
+    fingerprint: String,
+}
+
+impl TriageFinding {
+    fn location(&self) -> String {
+        let range = &self.finding.code_location.line_range;
+        if range.end > range.start {
+            format!("{}:{}-{}", self.path, range.start, range.end)
+        } else {
+            format!("{}:{}", self.path, range.start)
+        }
+    }
+}
+
+impl ChatWidget {
+    /// Stores the findings of a finished review, hiding the ones dismissed for
+    /// the same change earlier, and opens the triage panel when `open_panel`.
+    pub(crate) fn set_review_findings(
+        &mut self,
+        findings: Vec<ReviewFinding>,
+        change_key: Option<String>,
+        open_panel: bool,
+    ) {
+        let root = get_git_repo_root(&self.config.cwd).unwrap_or_else(|| self.config.cwd.clone());
+        let dismissed = change_key
+            .as_deref()
+            .map(|key| load_dismissed_findings(&self.config.codex_home, key))
+            .unwrap_or_default();
+        let total = findings.len();
+        let findings: Vec<TriageFinding> = findings
+            .into_iter()
+            .map(|finding| {
+                let path = relative_finding_path(&finding.code_location.absolute_file_path, &root);
+                let fingerprint = review_finding_fingerprint(&path, &finding.title);
+                TriageFinding {
+                    finding,
+                    path,
+                    fingerprint,
+                }
+            })
+            .filter(|finding| !dismissed.contains(&finding.fingerprint))
+            .collect();
+        let hidden = total - findings.len();
+        let has_findings = !findings.is_empty();
+        self.review_findings = Some(ReviewFindingsState {
+            change_key,
+            findings,
+        });
+
+        if !open_panel {
+            return;
+        }
+        if hidden > 0 {
+            self.add_info_message(format!("已隐藏 {hidden} 条此前忽略的审查意见。"), None);
+        }
+        if has_findings {
+            self.open_review_findings_panel();
+        }
+    }
+
+    /// Lists the pending findings of the most recent review.
+    pub(crate) fn open_review_findings_panel(&mut self) {
+        let Some(state) = self
+            .review_findings
+            .as_ref()
+            .filter(|state| !state.findings.is_empty())
+        else {
+            self.add_info_message(
+                "暂无待处理的审查意见。".to_string(),
+                Some("使用 /review 开始审查。".to_string()),
+            );
+            return;
+        };
+
+        let items = state
+            .findings
+            .iter()
+            .map(|finding| {
+                let fingerprint = finding.fingerprint.clone();
+                SelectionItem {
+                    name: finding.finding.title.clone(),
+                    description: Some(finding.location()),
+                    actions: vec![Box::new(move |tx| {
+                        tx.send(AppEvent::ReviewFinding {
+                            fingerprint: fingerprint.clone(),
+                            action: ReviewFindingAction::ShowActions,
+                        });
+                    })],
+                    dismiss_on_select: true,
+                    search_value: Some(format!("{} {}", finding.finding.title, finding.path)),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("审查意见".to_string()),
+            subtitle: Some(format!("共 {} 条待处理", state.findings.len())),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("输入以搜索审查意见".to_string()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn handle_review_finding_action(
+        &mut self,
+        fingerprint: &str,
+        action: ReviewFindingAction,
+    ) {
+        match action {
+            ReviewFindingAction::ShowActions => self.open_review_finding_actions(fingerprint),
+            ReviewFindingAction::Open => {
+                let Some(finding) = self.review_finding(fingerprint) else {
+                    return;
+                };
+                let lines = finding_detail_lines(finding, &self.config.cwd);
+                self.add_plain_history_lines(lines);
+                self.open_review_findings_panel();
+            }
+            ReviewFindingAction::Fix => {
+                let Some(finding) = self.review_finding(fingerprint) else {
+                    return;
+                };
+                let message = fix_request_message(finding);
+                self.queue_user_message(message.into());
+            }
+            ReviewFindingAction::Dismiss => {
+                self.dismiss_review_finding(fingerprint);
+                if self
+                    .review_findings
+                    .as_ref()
+                    .is_some_and(|state| !state.findings.is_empty())
+                {
+                    self.open_review_findings_panel();
+                }
+            }
+        }
+    }
+
+    fn review_finding(&self, fingerprint: &str) -> Option<&TriageFinding> {
+        self.review_findings
+            .as_ref()?
+            .findings
+            .iter()
+            .find(|finding| finding.fingerprint == fingerprint)
+    }
+
+    fn open_review_finding_actions(&mut self, fingerprint: &str) {
+        let Some(finding) = self.review_finding(fingerprint) else {
+            return;
+        };
+        let title = finding.finding.title.clone();
+        let location = finding.location();
+        let action_item = |name: &str, description: &str, action: ReviewFindingAction| {
+            let fingerprint = fingerprint.to_string();
+            SelectionItem {
+                name: name.to_string(),
+                description: Some(description.to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::ReviewFinding {
+                        fingerprint: fingerprint.clone(),
+                        action,
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        };
+        let items = vec![
+            action_item(
+                "查看",
+                "在对话记录中显示代码片段与完整说明",
+                ReviewFindingAction::Open,
+            ),
+            action_item(
+                "修复",
+                "将这条意见作为任务发送给 Codex",
+                ReviewFindingAction::Fix,
+            ),
+            action_item(
+                "忽略",
+                "对同一改动再次审查时不再显示",
+                ReviewFindingAction::Dismiss,
+            ),
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(title),
+            subtitle: Some(location),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    fn dismiss_review_finding(&mut self, fingerprint: &str) {
+        let Some(state) = self.review_findings.as_mut() else {
+            return;
+        };
+        state
+            .findings
+            .retain(|finding| finding.fingerprint != fingerprint);
+        if let Some(change_key) = state.change_key.as_deref()
+            && let Err(err) =
+                dismiss_review_finding(&self.config.codex_home, change_key, fingerprint)
+        {
+            tracing::warn!("failed to persist dismissed review finding: {err}");
+            self.add_error_message(format!("无法保存已忽略的审查意见：{err}"));
+        }
+    }
+}
+
+/// Location header, source snippet and body of `finding`.
+fn finding_detail_lines(finding: &TriageFinding, cwd: &Path) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = vec![
+        Line::from(vec!["• ".dim(), finding.finding.title.clone().bold()]),
+        Line::from(format!("  {}", finding.location()).cyan()),
+    ];
+    let path = resolve_finding_path(&finding.finding.code_location.absolute_file_path, cwd);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => lines.extend(snippet_lines(&contents, finding)),
+        Err(err) => lines.push(Line::from(format!("  无法读取 {}：{err}", path.display())).dim()),
+    }
+    lines.push(Line::from(""));
+    lines.extend(
+        finding
+            .finding
+            .body
+            .lines()
+            .map(|line| Line::from(format!("  {line}"))),
+    );
+    lines
+}
+
+fn resolve_finding_path(path: &Path, cwd: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    }
+}
+
+fn snippet_lines(contents: &str, finding: &TriageFinding) -> Vec<Line<'static>> {
+    let range = &finding.finding.code_location.line_range;
+    let start = range.start.max(1);
+    let end = range.end.max(start);
+    let first = start.saturating_sub(SNIPPET_CONTEXT_LINES).max(1);
+    let last = end.saturating_add(SNIPPET_CONTEXT_LINES);
+    let width = last.to_string().len();
+    contents
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx as u32 + 1, line))
+        .skip_while(|(number, _)| *number < first)
+        .take_while(|(number, _)| *number <= last)
+        .map(|(number, line)| {
+            let gutter = format!("  {number:>width$} │ ");
+            if (start..=end).contains(&number) {
+                Line::from(vec![gutter.into(), line.to_string().into()])
+            } else {
+                Line::from(vec![gutter.dim(), line.to_string().dim()])
+            }
+        })
+        .collect()
+}
+
+fn fix_request_message(finding: &TriageFinding) -> String {
+    format!(
+        "Fix this review finding:\n\n{title}\nLocation: {location}\n\n{body}",
+        title = finding.finding.title,
+        location = finding.location(),
+        body = finding.finding.body.trim(),
+    )
+}
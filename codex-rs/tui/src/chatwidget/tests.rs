@@ -7,6 +7,7 @@
 use super::*;
 use crate::app_event::AppEvent;
 use crate::app_event::ExitMode;
+use crate::app_event::ReviewFindingAction;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::FeedbackAudience;
 use crate::bottom_pane::LocalImageAttachment;
@@ -30,6 +31,7 @@ use codex_core::protocol::AgentReasoningDeltaEvent;
 use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::ConfigReloadedEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -949,6 +951,8 @@ async fn make_chatwidget_manual(
         quit_shortcut_key: None,
         is_review_mode: false,
         pre_review_token_info: None,
+        review_target: None,
+        review_findings: None,
         needs_final_message_separator: false,
         had_work_activity: false,
         saw_plan_update_this_turn: false,
@@ -967,6 +971,7 @@ async fn make_chatwidget_manual(
         status_line_branch_cwd: None,
         status_line_branch_pending: false,
         status_line_branch_lookup_complete: false,
+        status_line_segments: HashMap::new(),
         external_editor_state: ExternalEditorState::Closed,
     };
     widget.set_model(&resolved_model);
@@ -2417,10 +2422,24 @@ async fn review_popup_custom_prompt_action_sends_event() {
     // Open the preset selection popup
     chat.open_review_popup();
 
-    // Move selection down to the fourth item: "自定义审查指令"
-    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
-    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
-    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    // Move selection down to "自定义审查指令", wherever it sits in the list.
+    // Wide characters render with padding cells, so compare without spaces.
+    let mut selected = false;
+    for _ in 0..16 {
+        let popup = render_bottom_popup(&chat, 80);
+        if popup
+            .lines()
+            .any(|line| line.contains('›') && line.replace(' ', "").contains("自定义审查指令"))
+        {
+            selected = true;
+            break;
+        }
+        chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    }
+    assert!(
+        selected,
+        "expected the custom prompt item in the review popup"
+    );
     // Activate
     chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
 
@@ -3063,6 +3082,83 @@ async fn custom_prompt_submit_sends_review_op() {
     }
 }
 
+/// Submitting the paths prompt splits the input like a shell into a
+/// path-scoped review.
+#[tokio::test]
+async fn paths_prompt_submit_sends_paths_review_op() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.show_review_paths_prompt();
+    chat.handle_paste(" crates/foo  'docs/my notes.md' ".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let evt = rx.try_recv().expect("expected one app event");
+    match evt {
+        AppEvent::CodexOp(Op::Review { review_request }) => {
+            assert_eq!(
+                review_request.target,
+                ReviewTarget::Paths {
+                    paths: vec!["crates/foo".to_string(), "docs/my notes.md".to_string()],
+                    base: None,
+                }
+            );
+        }
+        other => panic!("unexpected app event: {other:?}"),
+    }
+}
+
+/// Dismissed findings leave the triage panel and "fix" queues a task that
+/// carries the finding's location and body.
+#[tokio::test]
+async fn review_findings_dismiss_and_fix() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    let finding = |title: &str, start: u32| codex_core::protocol::ReviewFinding {
+        title: title.to_string(),
+        body: "Explain the problem.".to_string(),
+        confidence_score: 0.9,
+        priority: 1,
+        code_location: codex_core::protocol::ReviewCodeLocation {
+            absolute_file_path: chat.config.cwd.join("src/lib.rs"),
+            line_range: codex_core::protocol::ReviewLineRange {
+                start,
+                end: start + 1,
+            },
+        },
+    };
+    let findings = vec![finding("[P1] First bug", 3), finding("[P2] Second bug", 9)];
+    chat.set_review_findings(findings, None, false);
+
+    let root = codex_core::git_info::get_git_repo_root(&chat.config.cwd)
+        .unwrap_or_else(|| chat.config.cwd.clone());
+    let fingerprint = |title: &str| {
+        let path = codex_core::review_format::relative_finding_path(
+            &chat.config.cwd.join("src/lib.rs"),
+            &root,
+        );
+        codex_core::review_format::review_finding_fingerprint(&path, title)
+    };
+    let first = fingerprint("[P1] First bug");
+    let second = fingerprint("[P2] Second bug");
+
+    chat.handle_review_finding_action(&first, ReviewFindingAction::Dismiss);
+    // A dismissed finding can no longer be acted on.
+    chat.handle_review_finding_action(&first, ReviewFindingAction::Fix);
+    assert!(chat.queued_user_messages.is_empty());
+
+    chat.handle_review_finding_action(&second, ReviewFindingAction::Fix);
+    let queued = chat
+        .queued_user_messages
+        .front()
+        .expect("fix request queued");
+    assert!(
+        queued
+            .text
+            .starts_with("Fix this review finding:\n\n[P2] Second bug\nLocation: ")
+    );
+    assert!(queued.text.contains("src/lib.rs:9-10"));
+    assert!(queued.text.ends_with("Explain the problem."));
+}
+
 /// Hitting Enter on an empty custom prompt view does not submit.
 #[tokio::test]
 async fn custom_prompt_enter_empty_does_not_send() {
@@ -4036,6 +4132,7 @@ async fn approval_modal_patch_snapshot() -> anyhow::Result<()> {
         changes,
         reason: Some("The model wants to apply changes".into()),
         grant_root: Some(PathBuf::from("/tmp")),
+        overlay_review: false,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-patch".into(),
@@ -4421,6 +4518,7 @@ async fn apply_patch_events_emit_history_cells() {
         changes,
         reason: None,
         grant_root: None,
+        overlay_review: false,
     };
     chat.handle_codex_event(Event {
         id: "s1".into(),
@@ -4520,6 +4618,7 @@ async fn apply_patch_manual_approval_adjusts_header() {
             changes: proposed_changes,
             reason: None,
             grant_root: None,
+            overlay_review: false,
         }),
     });
     drain_insert_history(&mut rx);
@@ -4569,6 +4668,7 @@ async fn apply_patch_manual_flow_snapshot() {
             changes: proposed_changes,
             reason: Some("Manual review required".into()),
             grant_root: None,
+            overlay_review: false,
         }),
     });
     let history_before_apply = drain_insert_history(&mut rx);
@@ -4620,6 +4720,7 @@ async fn apply_patch_approval_sends_op_with_submission_id() {
         changes,
         reason: None,
         grant_root: None,
+        overlay_review: false,
     };
     chat.handle_codex_event(Event {
         id: "sub-123".into(),
@@ -4660,6 +4761,7 @@ async fn apply_patch_full_flow_integration_like() {
             changes,
             reason: None,
             grant_root: None,
+            overlay_review: false,
         }),
     });
 
@@ -4740,6 +4842,7 @@ async fn apply_patch_untrusted_shows_approval_modal() -> anyhow::Result<()> {
             changes,
             reason: None,
             grant_root: None,
+            overlay_review: false,
         }),
     });
 
@@ -4792,6 +4895,7 @@ async fn apply_patch_request_shows_diff_summary() -> anyhow::Result<()> {
             changes,
             reason: None,
             grant_root: None,
+            overlay_review: false,
         }),
     });
 
@@ -4900,6 +5004,40 @@ async fn stream_error_updates_status_indicator() {
     assert_eq!(status.details(), Some(details));
 }
 
+#[tokio::test]
+async fn config_reloaded_event_adopts_reloaded_settings() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::ConfigReloaded(ConfigReloadedEvent {
+            applied: vec!["approval_policy".to_string(), "model".to_string()],
+            requires_restart: vec!["model_providers.local.base_url".to_string()],
+            needs_confirmation: Vec::new(),
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: Some(ReasoningEffortConfig::High),
+            personality: None,
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+        }),
+    });
+
+    assert_eq!(chat.current_model(), "gpt-5.1-codex");
+    assert_eq!(
+        chat.current_reasoning_effort(),
+        Some(ReasoningEffortConfig::High)
+    );
+    assert_eq!(chat.config.approval_policy.value(), AskForApproval::Never);
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one info history cell");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("approval_policy, model")
+            && rendered.contains("model_providers.local.base_url"),
+        "config reload cell missing keys: {rendered}"
+    );
+}
+
 #[tokio::test]
 async fn warning_event_adds_warning_history_cell() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
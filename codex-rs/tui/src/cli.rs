@@ -32,6 +32,10 @@ pub struct Cli {
     #[clap(skip)]
     pub resume_show_all: bool,
 
+    /// Internal: open the resume picker with this full-text query pre-filled.
+    #[clap(skip)]
+    pub resume_search: Option<String>,
+
     // Internal controls set by the top-level `codex2 fork` subcommand.
     // These are not exposed as user flags on the base `codex` command.
     #[clap(skip)]
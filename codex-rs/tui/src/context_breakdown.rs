@@ -0,0 +1,195 @@
+//! Rendering for the `/context` command.
+//!
+//! Core answers `Op::GetContextBreakdown` with a [`ContextBreakdownEvent`]; this
+//! module turns it into a transcript cell that lists token usage per category
+//! and the largest individual items. Items that can be dropped (tool outputs
+//! and images) are offered in [`ContextDropView`](crate::bottom_pane::ContextDropView).
+
+use codex_core::protocol::ContextBreakdownEvent;
+use codex_core::protocol::ContextCategory;
+use codex_core::protocol::ContextItemUsage;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::history_cell::PlainHistoryCell;
+use crate::status::format_tokens_compact;
+
+/// User-visible label for a category row or item.
+pub(crate) fn category_label(category: ContextCategory, tool_name: Option<&str>) -> String {
+    let label = match category {
+        ContextCategory::BaseInstructions => "基础指令",
+        ContextCategory::ProjectDocs => "项目文档（AGENTS.md）",
+        ContextCategory::Skills => "技能",
+        ContextCategory::DeveloperContext => "开发者上下文",
+        ContextCategory::UserMessages => "用户消息",
+        ContextCategory::AssistantMessages => "助手消息",
+        ContextCategory::Reasoning => "推理",
+        ContextCategory::ToolCalls => "工具调用",
+        ContextCategory::ToolOutputs => "工具输出",
+        ContextCategory::Images => "图片",
+        ContextCategory::Compaction => "压缩摘要",
+    };
+    match (category, tool_name) {
+        (ContextCategory::ToolOutputs | ContextCategory::ToolCalls, Some(tool)) => {
+            format!("{label}（{tool}）")
+        }
+        _ => label.to_string(),
+    }
+}
+
+/// Items from the breakdown that can be dropped, largest first.
+pub(crate) fn droppable_items(ev: &ContextBreakdownEvent) -> Vec<&ContextItemUsage> {
+    ev.largest_items
+        .iter()
+        .filter(|item| item.id.is_some())
+        .collect()
+}
+
+pub(crate) fn new_context_breakdown_output(ev: &ContextBreakdownEvent) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec!["/context".magenta().into(), "".into()];
+
+    let total = format_tokens_compact(ev.total_tokens);
+    let summary = match ev.model_context_window.filter(|window| *window > 0) {
+        Some(window) => format!(
+            "约 {total} / {} tokens（{}%）",
+            format_tokens_compact(window),
+            percent(ev.total_tokens, window)
+        ),
+        None => format!("约 {total} tokens"),
+    };
+    lines.push(vec!["上下文占用：".bold(), summary.into()].into());
+    lines.push("".into());
+
+    lines.push("按类别".bold().into());
+    for usage in &ev.categories {
+        lines.push(
+            vec![
+                "  • ".into(),
+                category_label(usage.category, usage.tool_name.as_deref()).into(),
+                "  ".into(),
+                format_tokens_compact(usage.tokens).cyan(),
+                format!(
+                    "  {}% · {} 项",
+                    percent(usage.tokens, ev.total_tokens),
+                    usage.item_count
+                )
+                .dim(),
+            ]
+            .into(),
+        );
+    }
+
+    if !ev.largest_items.is_empty() {
+        lines.push("".into());
+        lines.push("最大条目".bold().into());
+        for item in &ev.largest_items {
+            let mut spans: Vec<Span<'static>> = vec![
+                "  • ".into(),
+                format_tokens_compact(item.tokens).cyan(),
+                "  ".into(),
+                category_label(item.category, item.tool_name.as_deref()).into(),
+            ];
+            if !item.preview.is_empty() {
+                spans.push(" — ".dim());
+                spans.push(item.preview.clone().dim());
+            }
+            lines.push(spans.into());
+        }
+    }
+
+    if !droppable_items(ev).is_empty() {
+        lines.push("".into());
+        lines.push(
+            "可在弹出的列表中选择要从上下文中移除的工具输出或图片。"
+                .dim()
+                .into(),
+        );
+    }
+
+    PlainHistoryCell::new(lines)
+}
+
+fn percent(part: i64, whole: i64) -> i64 {
+    if whole <= 0 {
+        return 0;
+    }
+    ((part.max(0) as f64 / whole as f64) * 100.0).round() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::HistoryCell;
+    use codex_core::protocol::ContextCategoryUsage;
+    use pretty_assertions::assert_eq;
+
+    fn breakdown() -> ContextBreakdownEvent {
+        ContextBreakdownEvent {
+            total_tokens: 20_000,
+            model_context_window: Some(200_000),
+            categories: vec![
+                ContextCategoryUsage {
+                    category: ContextCategory::ToolOutputs,
+                    tool_name: Some("shell".to_string()),
+                    tokens: 15_000,
+                    item_count: 3,
+                },
+                ContextCategoryUsage {
+                    category: ContextCategory::BaseInstructions,
+                    tool_name: None,
+                    tokens: 5_000,
+                    item_count: 1,
+                },
+            ],
+            largest_items: vec![
+                ContextItemUsage {
+                    id: Some("call-1".to_string()),
+                    category: ContextCategory::ToolOutputs,
+                    tool_name: Some("shell".to_string()),
+                    preview: "total 42".to_string(),
+                    tokens: 12_000,
+                },
+                ContextItemUsage {
+                    id: None,
+                    category: ContextCategory::UserMessages,
+                    tool_name: None,
+                    preview: "fix the build".to_string(),
+                    tokens: 50,
+                },
+            ],
+        }
+    }
+
+    fn render(ev: &ContextBreakdownEvent) -> Vec<String> {
+        new_context_breakdown_output(ev)
+            .display_lines(80)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn renders_categories_and_largest_items() {
+        let lines = render(&breakdown());
+        assert_eq!(lines[2], "上下文占用：约 20K / 200K tokens（10%）");
+        assert!(lines.contains(&"  • 工具输出（shell）  15K  75% · 3 项".to_string()));
+        assert!(lines.contains(&"  • 12K  工具输出（shell） — total 42".to_string()));
+        assert!(lines.contains(&"  • 50  用户消息 — fix the build".to_string()));
+    }
+
+    #[test]
+    fn only_items_with_ids_are_droppable() {
+        let ev = breakdown();
+        let ids = droppable_items(&ev)
+            .into_iter()
+            .filter_map(|item| item.id.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["call-1"]);
+    }
+}
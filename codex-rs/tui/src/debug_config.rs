@@ -32,6 +32,19 @@ fn render_debug_config_lines(stack: &ConfigLayerStack) -> Vec<Line<'static>> {
             if let Some(reason) = &layer.disabled_reason {
                 lines.push(format!("     原因：{reason}").dim().into());
             }
+            for include in &layer.includes {
+                lines.push(format!("     包含：{}", include.as_path().display()).into());
+                let mut keys: Vec<&str> = layer
+                    .included_key_files
+                    .iter()
+                    .filter(|(_, file)| *file == include)
+                    .map(|(key, _)| key.as_str())
+                    .collect();
+                if !keys.is_empty() {
+                    keys.sort_unstable();
+                    lines.push(format!("       提供：{}", keys.join(", ")).dim().into());
+                }
+            }
         }
     }
 
@@ -154,6 +167,7 @@ fn format_sandbox_mode_requirement(mode: SandboxModeRequirement) -> String {
     match mode {
         SandboxModeRequirement::ReadOnly => "read-only".to_string(),
         SandboxModeRequirement::WorkspaceWrite => "workspace-write".to_string(),
+        SandboxModeRequirement::WorkspaceOverlay => "workspace-overlay".to_string(),
         SandboxModeRequirement::DangerFullAccess => "danger-full-access".to_string(),
         SandboxModeRequirement::ExternalSandbox => "external-sandbox".to_string(),
     }
@@ -186,6 +200,7 @@ mod tests {
     use codex_utils_absolute_path::AbsolutePathBuf;
     use ratatui::text::Line;
     use std::collections::BTreeMap;
+    use std::collections::HashMap;
     use toml::Value as TomlValue;
 
     fn empty_toml_table() -> TomlValue {
@@ -250,6 +265,40 @@ mod tests {
         assert!(rendered.contains("  <无>"));
     }
 
+    #[test]
+    fn debug_config_output_lists_included_files_and_their_keys() {
+        let (user_file, team_file) = if cfg!(windows) {
+            (
+                absolute_path("C:\\users\\alice\\.codex\\config.toml"),
+                absolute_path("C:\\shared\\team.toml"),
+            )
+        } else {
+            (
+                absolute_path("/home/alice/.codex/config.toml"),
+                absolute_path("/shared/team.toml"),
+            )
+        };
+        let mut user_layer = ConfigLayerEntry::new(
+            ConfigLayerSource::User { file: user_file },
+            empty_toml_table(),
+        );
+        user_layer.includes = vec![team_file.clone()];
+        user_layer.included_key_files = HashMap::from([
+            ("model_provider".to_string(), team_file.clone()),
+            ("model".to_string(), team_file.clone()),
+        ]);
+        let stack = ConfigLayerStack::new(
+            vec![user_layer],
+            ConfigRequirements::default(),
+            ConfigRequirementsToml::default(),
+        )
+        .expect("config layer stack");
+
+        let rendered = render_to_text(&render_debug_config_lines(&stack));
+        assert!(rendered.contains(&format!("包含：{}", team_file.as_path().display())));
+        assert!(rendered.contains("提供：model, model_provider"));
+    }
+
     #[test]
     fn debug_config_output_lists_requirement_sources() {
         let requirements_file = if cfg!(windows) {
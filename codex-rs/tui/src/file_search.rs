@@ -4,19 +4,32 @@
 //! `AppEvent::StartFileSearch(query)`. This manager owns a single
 //! `codex-file-search` session for the current search root, updates the query
 //! on every keystroke, and drops the session when the query becomes empty.
+//! When symbol search is enabled, a query that stays unchanged for a short
+//! while also looks up matching definitions in the workspace symbol index on
+//! a blocking thread. The index lives as long as the manager and is kept up
+//! to date by its own file watcher, so lookups after the first one are cheap.
 
+use codex_core::symbol_index::SymbolIndex;
 use codex_file_search as file_search;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 
+/// Symbols suggested below the file matches for each query.
+const MAX_SYMBOL_MATCHES: usize = 8;
+
+/// How long the `@` token must stay unchanged before symbols are looked up.
+const SYMBOL_SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
 pub(crate) struct FileSearchManager {
     state: Arc<Mutex<SearchState>>,
     search_dir: PathBuf,
     app_tx: AppEventSender,
+    symbol_index: Option<Arc<SymbolIndex>>,
 }
 
 struct SearchState {
@@ -35,9 +48,15 @@ impl FileSearchManager {
             })),
             search_dir,
             app_tx: tx,
+            symbol_index: None,
         }
     }
 
+    /// Enables or disables symbol suggestions for `@` queries.
+    pub fn set_symbol_search(&mut self, enabled: bool) {
+        self.symbol_index = enabled.then(|| Arc::new(SymbolIndex::new()));
+    }
+
     /// Updates the directory used for file searches.
     /// This should be called when the session's CWD changes on resume.
     /// Drops the current session so it will be recreated with the new directory on next query.
@@ -70,6 +89,33 @@ impl FileSearchManager {
         if let Some(session) = st.session.as_ref() {
             session.update_query(&query);
         }
+        if let Some(index) = self.symbol_index.clone() {
+            self.start_symbol_search(index, query);
+        }
+    }
+
+    fn start_symbol_search(&self, index: Arc<SymbolIndex>, query: String) {
+        let state = self.state.clone();
+        let search_dir = self.search_dir.clone();
+        let app_tx = self.app_tx.clone();
+        tokio::spawn(async move {
+            let is_current = move |query: &str| {
+                #[expect(clippy::unwrap_used)]
+                let st = state.lock().unwrap();
+                st.latest_query == query
+            };
+            tokio::time::sleep(SYMBOL_SEARCH_DEBOUNCE).await;
+            if !is_current(&query) {
+                return;
+            }
+            let _ = tokio::task::spawn_blocking(move || {
+                let symbols = index.find(&search_dir, &query, None, MAX_SYMBOL_MATCHES);
+                if is_current(&query) {
+                    app_tx.send(AppEvent::SymbolSearchResult { query, symbols });
+                }
+            })
+            .await;
+        });
     }
 
     fn start_session_locked(&self, st: &mut SearchState) {
@@ -747,7 +747,7 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
-        Denied => {
+        Denied | ReviewEachFile => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
                 "✗ ".red(),
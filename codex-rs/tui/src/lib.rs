@@ -65,6 +65,7 @@ mod clipboard_paste;
 mod collab;
 mod collaboration_modes;
 mod color;
+mod context_breakdown;
 pub mod custom_terminal;
 mod cwd_prompt;
 mod debug_config;
@@ -97,6 +98,7 @@ mod skills_helpers;
 mod slash_command;
 mod status;
 mod status_indicator_widget;
+mod status_line_segments;
 mod streaming;
 mod style;
 mod terminal_palette;
@@ -630,6 +632,7 @@ async fn run_ratatui_app(
             &config.codex_home,
             &config.model_provider_id,
             cli.resume_show_all,
+            cli.resume_search.clone(),
         )
         .await?
         {
@@ -14,6 +14,9 @@ use codex_core::ThreadSortKey;
 use codex_core::ThreadsPage;
 use codex_core::find_thread_names_by_ids;
 use codex_core::path_utils;
+use codex_core::state_db;
+use codex_state::SearchSnippet;
+use codex_state::ThreadSearchHit;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -39,6 +42,7 @@ use codex_protocol::ThreadId;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+const CONTENT_SEARCH_LIMIT: usize = 50;
 #[derive(Debug, Clone)]
 pub enum SessionSelection {
     StartFresh,
@@ -88,12 +92,24 @@ struct PageLoadRequest {
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
 
+#[derive(Clone)]
+struct ContentSearchRequest {
+    query: String,
+    search_token: usize,
+}
+
+type ContentSearchLoader = Arc<dyn Fn(ContentSearchRequest) + Send + Sync>;
+
 enum BackgroundEvent {
     PageLoaded {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ThreadsPage>,
     },
+    ContentSearchLoaded {
+        search_token: usize,
+        hits: Vec<ThreadSearchHit>,
+    },
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
@@ -112,17 +128,24 @@ enum BackgroundEvent {
 /// 1. Provider and source filtering at the backend (only interactive CLI sessions
 ///    for the current model provider).
 /// 2. Working-directory filtering at the picker (unless `--all` is passed).
+///
+/// When the state database is available, a non-empty query also runs a
+/// full-text search over session content; those matches are listed after the
+/// title matches and show a snippet with the matched text highlighted.
+/// `initial_query` pre-fills the search box (`codex resume --search`).
 pub async fn run_resume_picker(
     tui: &mut Tui,
     codex_home: &Path,
     default_provider: &str,
     show_all: bool,
+    initial_query: Option<String>,
 ) -> Result<SessionSelection> {
     run_session_picker(
         tui,
         codex_home,
         default_provider,
         show_all,
+        initial_query,
         SessionPickerAction::Resume,
     )
     .await
@@ -139,6 +162,7 @@ pub async fn run_fork_picker(
         codex_home,
         default_provider,
         show_all,
+        None,
         SessionPickerAction::Fork,
     )
     .await
@@ -149,6 +173,7 @@ async fn run_session_picker(
     codex_home: &Path,
     default_provider: &str,
     show_all: bool,
+    initial_query: Option<String>,
     action: SessionPickerAction,
 ) -> Result<SessionSelection> {
     let alt = AltScreenGuard::enter(tui);
@@ -184,6 +209,35 @@ async fn run_session_picker(
         });
     });
 
+    let content_search_loader: Option<ContentSearchLoader> =
+        state_db::open_if_present(codex_home, default_provider.as_str())
+            .await
+            .map(|db| {
+                let loader_tx = bg_tx.clone();
+                let default_provider = default_provider.clone();
+                Arc::new(move |request: ContentSearchRequest| {
+                    let tx = loader_tx.clone();
+                    let db = db.clone();
+                    let provider_filter = vec![default_provider.clone()];
+                    tokio::spawn(async move {
+                        let hits = state_db::search_threads_db(
+                            Some(db.as_ref()),
+                            request.query.as_str(),
+                            CONTENT_SEARCH_LIMIT,
+                            INTERACTIVE_SESSION_SOURCES,
+                            Some(provider_filter.as_slice()),
+                            false,
+                        )
+                        .await
+                        .unwrap_or_default();
+                        let _ = tx.send(BackgroundEvent::ContentSearchLoaded {
+                            search_token: request.search_token,
+                            hits,
+                        });
+                    });
+                }) as ContentSearchLoader
+            });
+
     let mut state = PickerState::new(
         codex_home.to_path_buf(),
         alt.tui.frame_requester(),
@@ -193,6 +247,11 @@ async fn run_session_picker(
         filter_cwd,
         action,
     );
+    state.content_search_loader = content_search_loader;
+    if let Some(query) = initial_query.filter(|query| !query.trim().is_empty()) {
+        state.query = query;
+        state.start_content_search();
+    }
     state.start_initial_load();
     state.request_frame();
 
@@ -280,6 +339,8 @@ struct PickerState {
     action: SessionPickerAction,
     sort_key: ThreadSortKey,
     thread_name_cache: HashMap<ThreadId, Option<String>>,
+    content_search_loader: Option<ContentSearchLoader>,
+    content_search: ContentSearchState,
 }
 
 struct PaginationState {
@@ -301,6 +362,14 @@ struct PendingLoad {
     search_token: Option<usize>,
 }
 
+/// Full-text matches for the current query, fetched from the state database.
+#[derive(Default)]
+struct ContentSearchState {
+    /// Token of the request whose results we are waiting for.
+    pending: Option<usize>,
+    rows: Vec<Row>,
+}
+
 #[derive(Clone, Copy, Debug)]
 enum SearchState {
     Idle,
@@ -341,6 +410,7 @@ struct Row {
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
     git_branch: Option<String>,
+    snippet: Option<SearchSnippet>,
 }
 
 impl Row {
@@ -397,6 +467,8 @@ impl PickerState {
             action,
             sort_key: ThreadSortKey::CreatedAt,
             thread_name_cache: HashMap::new(),
+            content_search_loader: None,
+            content_search: ContentSearchState::default(),
         }
     }
 
@@ -532,6 +604,23 @@ impl PickerState {
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
+            BackgroundEvent::ContentSearchLoaded { search_token, hits } => {
+                if self.content_search.pending != Some(search_token) {
+                    return Ok(());
+                }
+                self.content_search.pending = None;
+                self.content_search.rows = hits
+                    .into_iter()
+                    .map(|hit| {
+                        let mut row = search_hit_to_row(hit);
+                        row.thread_name = row
+                            .thread_id
+                            .and_then(|id| self.thread_name_cache.get(&id).cloned().flatten());
+                        row
+                    })
+                    .collect();
+                self.apply_filter();
+            }
         }
         Ok(())
     }
@@ -618,7 +707,9 @@ impl PickerState {
             self.filtered_rows = base_iter.cloned().collect();
         } else {
             let q = self.query.to_lowercase();
-            self.filtered_rows = base_iter.filter(|r| r.matches_query(&q)).cloned().collect();
+            let mut rows: Vec<Row> = base_iter.filter(|r| r.matches_query(&q)).cloned().collect();
+            self.merge_content_matches(&mut rows);
+            self.filtered_rows = rows;
         }
         if self.selected >= self.filtered_rows.len() {
             self.selected = self.filtered_rows.len().saturating_sub(1);
@@ -630,6 +721,33 @@ impl PickerState {
         self.request_frame();
     }
 
+    /// Attaches snippets to title matches that also matched by content, then
+    /// appends the remaining content matches in relevance order.
+    fn merge_content_matches(&self, rows: &mut Vec<Row>) {
+        if self.content_search.rows.is_empty() {
+            return;
+        }
+        let snippets: HashMap<&Path, &SearchSnippet> = self
+            .content_search
+            .rows
+            .iter()
+            .filter_map(|row| Some((row.path.as_path(), row.snippet.as_ref()?)))
+            .collect();
+        for row in rows.iter_mut() {
+            if let Some(snippet) = snippets.get(row.path.as_path()) {
+                row.snippet = Some((*snippet).clone());
+            }
+        }
+        let listed: HashSet<PathBuf> = rows.iter().map(|row| row.path.clone()).collect();
+        rows.extend(
+            self.content_search
+                .rows
+                .iter()
+                .filter(|row| !listed.contains(&row.path) && self.row_matches_filter(row))
+                .cloned(),
+        );
+    }
+
     fn row_matches_filter(&self, row: &Row) -> bool {
         if self.show_all {
             return true;
@@ -649,6 +767,7 @@ impl PickerState {
         }
         self.query = new_query;
         self.selected = 0;
+        self.start_content_search();
         self.apply_filter();
         if self.query.is_empty() {
             self.search_state = SearchState::Idle;
@@ -667,6 +786,24 @@ impl PickerState {
         self.load_more_if_needed(LoadTrigger::Search { token });
     }
 
+    /// Drops matches for the previous query and asks the state database for
+    /// content matches of the current one.
+    fn start_content_search(&mut self) {
+        self.content_search = ContentSearchState::default();
+        if self.query.trim().is_empty() {
+            return;
+        }
+        let Some(loader) = self.content_search_loader.clone() else {
+            return;
+        };
+        let search_token = self.allocate_search_token();
+        self.content_search.pending = Some(search_token);
+        loader(ContentSearchRequest {
+            query: self.query.clone(),
+            search_token,
+        });
+    }
+
     fn continue_search_if_needed(&mut self) {
         let Some(token) = self.search_state.active_token() else {
             return;
@@ -837,6 +974,31 @@ fn head_to_row(item: &ThreadItem) -> Row {
         updated_at,
         cwd: item.cwd.clone(),
         git_branch: item.git_branch.clone(),
+        snippet: None,
+    }
+}
+
+fn search_hit_to_row(hit: ThreadSearchHit) -> Row {
+    let ThreadSearchHit {
+        thread, snippet, ..
+    } = hit;
+    let preview = thread
+        .first_user_message
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| String::from("(no message yet)"));
+    Row {
+        path: thread.rollout_path,
+        preview,
+        thread_id: Some(thread.id),
+        thread_name: None,
+        created_at: Some(thread.created_at),
+        updated_at: Some(thread.updated_at),
+        cwd: Some(thread.cwd),
+        git_branch: thread.git_branch,
+        snippet: Some(snippet),
     }
 }
 
@@ -1017,7 +1179,6 @@ fn render_list(
         if add_leading_gap {
             preview_width = preview_width.saturating_sub(2);
         }
-        let preview = truncate_text(row.display_preview(), preview_width);
         let mut spans: Vec<Span> = vec![marker];
         if let Some(created) = created_span {
             spans.push(created);
@@ -1038,7 +1199,10 @@ fn render_list(
         if add_leading_gap {
             spans.push("  ".into());
         }
-        spans.push(preview.into());
+        match row.snippet.as_ref() {
+            Some(snippet) => spans.extend(snippet_spans(snippet, preview_width)),
+            None => spans.push(truncate_text(row.display_preview(), preview_width).into()),
+        }
 
         let line: Line = spans.into();
         let rect = Rect::new(area.x, y, area.width, 1);
@@ -1056,6 +1220,7 @@ fn render_list(
 fn render_empty_state_line(state: &PickerState) -> Line<'static> {
     if !state.query.is_empty() {
         if state.search_state.is_active()
+            || state.content_search.pending.is_some()
             || (state.pagination.loading.is_pending() && state.pagination.next_cursor.is_some())
         {
             return vec!["正在搜索…".italic().dim()].into();
@@ -1081,6 +1246,39 @@ fn render_empty_state_line(state: &PickerState) -> Line<'static> {
     vec!["暂无会话记录".italic().dim()].into()
 }
 
+/// Renders a search snippet within `max_graphemes`, emphasizing the matched
+/// ranges. Truncation follows `truncate_text`.
+fn snippet_spans(snippet: &SearchSnippet, max_graphemes: usize) -> Vec<Span<'static>> {
+    let text = snippet.text.as_str();
+    let truncated = truncate_text(text, max_graphemes);
+    let (visible_len, ellipsis) = match truncated.strip_suffix("...") {
+        Some(prefix) if truncated.len() != text.len() => (prefix.len(), Some("...")),
+        _ => (truncated.len(), None),
+    };
+
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+    for range in &snippet.highlights {
+        let start = range.start.min(visible_len);
+        let end = range.end.min(visible_len);
+        if start < cursor || start >= end {
+            continue;
+        }
+        if start > cursor {
+            spans.push(Span::from(text[cursor..start].to_string()).dim());
+        }
+        spans.push(Span::from(text[start..end].to_string()).bold());
+        cursor = end;
+    }
+    if cursor < visible_len {
+        spans.push(Span::from(text[cursor..visible_len].to_string()).dim());
+    }
+    if let Some(ellipsis) = ellipsis {
+        spans.push(ellipsis.dim());
+    }
+    spans
+}
+
 fn human_time_ago(ts: DateTime<Utc>) -> String {
     let now = Utc::now();
     let delta = now - ts;
@@ -1323,6 +1521,7 @@ mod tests {
     use crossterm::event::KeyModifiers;
     use insta::assert_snapshot;
     use pretty_assertions::assert_eq;
+    use ratatui::style::Modifier;
     use serde_json::json;
     use std::fs::FileTimes;
     use std::fs::OpenOptions;
@@ -1561,6 +1760,7 @@ mod tests {
             updated_at: None,
             cwd: None,
             git_branch: None,
+            snippet: None,
         };
 
         assert_eq!(row.display_preview(), "My session");
@@ -1595,6 +1795,7 @@ mod tests {
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1605,6 +1806,7 @@ mod tests {
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
@@ -1615,6 +1817,7 @@ mod tests {
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -1872,6 +2075,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(2)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1882,6 +2086,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(3)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -2297,4 +2502,102 @@ mod tests {
         assert!(!state.search_state.is_active());
         assert!(state.pagination.reached_scan_cap);
     }
+
+    #[tokio::test]
+    async fn content_search_matches_are_appended_with_snippets() {
+        let page_loader: PageLoader = Arc::new(|_| {});
+        let requests: Arc<Mutex<Vec<ContentSearchRequest>>> = Arc::new(Mutex::new(Vec::new()));
+        let request_sink = requests.clone();
+        let content_loader: ContentSearchLoader = Arc::new(move |req: ContentSearchRequest| {
+            request_sink.lock().unwrap().push(req);
+        });
+
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            page_loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::Resume,
+        );
+        state.content_search_loader = Some(content_loader);
+        state.all_rows = vec![head_to_row(&make_item(
+            "/tmp/title-match.jsonl",
+            "2025-01-01T00:00:00Z",
+            "flaky test triage",
+        ))];
+
+        state.set_query("flaky".to_string());
+        let search_token = {
+            let guard = requests.lock().unwrap();
+            assert_eq!(guard.len(), 1);
+            assert_eq!(guard[0].query, "flaky");
+            guard[0].search_token
+        };
+        assert_eq!(state.filtered_rows.len(), 1);
+
+        let mut thread = codex_state::ThreadMetadataBuilder::new(
+            ThreadId::new(),
+            PathBuf::from("/tmp/content-match.jsonl"),
+            Utc::now(),
+            SessionSource::Cli,
+        )
+        .build("openai");
+        thread.first_user_message = Some(String::from("fix the migration"));
+        let snippet = SearchSnippet {
+            text: String::from("the flaky migration fails"),
+            highlights: vec![4..9],
+        };
+        state
+            .handle_background_event(BackgroundEvent::ContentSearchLoaded {
+                search_token,
+                hits: vec![ThreadSearchHit {
+                    thread,
+                    kind: codex_state::SearchDocumentKind::UserMessage,
+                    snippet: snippet.clone(),
+                }],
+            })
+            .await
+            .unwrap();
+
+        let paths: Vec<_> = state
+            .filtered_rows
+            .iter()
+            .map(|row| row.path.clone())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/tmp/title-match.jsonl"),
+                PathBuf::from("/tmp/content-match.jsonl"),
+            ]
+        );
+        assert_eq!(state.filtered_rows[1].snippet, Some(snippet));
+    }
+
+    #[test]
+    fn snippet_spans_bold_matches_and_truncate() {
+        let snippet = SearchSnippet {
+            text: String::from("the flaky migration fails"),
+            highlights: vec![4..9, 10..19],
+        };
+        let rendered: Vec<(String, bool)> = snippet_spans(&snippet, 15)
+            .into_iter()
+            .map(|span| {
+                let bold = span.style.add_modifier.contains(Modifier::BOLD);
+                (span.content.into_owned(), bold)
+            })
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                ("the ".to_string(), false),
+                ("flaky".to_string(), true),
+                (" ".to_string(), false),
+                ("mi".to_string(), true),
+                ("...".to_string(), false),
+            ]
+        );
+    }
 }
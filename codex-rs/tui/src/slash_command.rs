@@ -20,12 +20,14 @@ pub enum SlashCommand {
     Experimental,
     Skills,
     Review,
+    Findings,
     Rename,
     New,
     Resume,
     Fork,
     Init,
     Compact,
+    Context,
     Plan,
     Collab,
     Agent,
@@ -55,7 +57,9 @@ impl SlashCommand {
             SlashCommand::New => "在对话中开启新聊天",
             SlashCommand::Init => "创建包含 Codex 指令的 AGENTS.md 文件",
             SlashCommand::Compact => "总结对话以避免触及上下文上限",
+            SlashCommand::Context => "查看上下文窗口的占用情况并移除旧的工具输出",
             SlashCommand::Review => "审查当前改动并找出问题",
+            SlashCommand::Findings => "查看并处理最近一次审查的意见",
             SlashCommand::Rename => "重命名当前会话",
             SlashCommand::Resume => "恢复已保存的聊天",
             SlashCommand::Fork => "分叉当前聊天",
@@ -122,6 +126,8 @@ impl SlashCommand {
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
+            | SlashCommand::Context
+            | SlashCommand::Findings
             | SlashCommand::DebugConfig
             | SlashCommand::Ps
             | SlashCommand::Mcp
@@ -169,6 +169,13 @@ impl StatusHistoryCell {
             SandboxPolicy::DangerFullAccess => "危险·全权限".to_string(),
             SandboxPolicy::ReadOnly => "只读".to_string(),
             SandboxPolicy::WorkspaceWrite { .. } => "工作区可写".to_string(),
+            SandboxPolicy::WorkspaceOverlay { network_access } => {
+                if *network_access {
+                    "工作区覆盖层（启用网络访问）".to_string()
+                } else {
+                    "工作区覆盖层".to_string()
+                }
+            }
             SandboxPolicy::ExternalSandbox { network_access } => {
                 if matches!(network_access, NetworkAccess::Enabled) {
                     "外部沙箱（启用网络访问）".to_string()
@@ -0,0 +1,311 @@
+//! Custom status line segments declared under `tui.status_line_segments`.
+//!
+//! Segments come in two flavors:
+//!
+//! - **Command segments** run a program on a refresh interval (with a timeout)
+//!   and render the first non-empty line of its stdout, e.g. the current
+//!   Kubernetes context.
+//! - **Template segments** substitute `{placeholder}` names with values from
+//!   the current session, e.g. `"{thread_name} · {total_tokens} tok"`.
+//!
+//! Command execution is asynchronous: the chat widget keeps the last rendered
+//! value per segment and schedules a new run once the refresh interval has
+//! elapsed. Results arrive as [`AppEvent::StatusLineSegmentUpdated`].
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::config::types::StatusLineSegment;
+use tokio::process::Command;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+/// Placeholders recognized by template segments.
+pub(crate) const TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "model",
+    "cwd",
+    "project",
+    "branch",
+    "thread_name",
+    "session_id",
+    "input_tokens",
+    "output_tokens",
+    "total_tokens",
+    "context_remaining",
+    "mode",
+    "sandbox",
+];
+
+/// Session values available to template segments.
+///
+/// Fields are `None` when the value is not known yet; such placeholders render
+/// as an empty string.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct StatusLineTemplateContext {
+    pub(crate) model: Option<String>,
+    pub(crate) cwd: Option<String>,
+    pub(crate) project: Option<String>,
+    pub(crate) branch: Option<String>,
+    pub(crate) thread_name: Option<String>,
+    pub(crate) session_id: Option<String>,
+    pub(crate) input_tokens: Option<String>,
+    pub(crate) output_tokens: Option<String>,
+    pub(crate) total_tokens: Option<String>,
+    pub(crate) context_remaining: Option<String>,
+    pub(crate) mode: Option<String>,
+    pub(crate) sandbox: Option<String>,
+}
+
+impl StatusLineTemplateContext {
+    fn lookup(&self, name: &str) -> Option<&str> {
+        let value = match name {
+            "model" => &self.model,
+            "cwd" => &self.cwd,
+            "project" => &self.project,
+            "branch" => &self.branch,
+            "thread_name" => &self.thread_name,
+            "session_id" => &self.session_id,
+            "input_tokens" => &self.input_tokens,
+            "output_tokens" => &self.output_tokens,
+            "total_tokens" => &self.total_tokens,
+            "context_remaining" => &self.context_remaining,
+            "mode" => &self.mode,
+            "sandbox" => &self.sandbox,
+            _ => return None,
+        };
+        value.as_deref()
+    }
+}
+
+/// Whether `template` references the `{name}` placeholder.
+pub(crate) fn template_uses_placeholder(template: &str, name: &str) -> bool {
+    let mut rest = template;
+    while let Some(idx) = rest.find(['{', '}']) {
+        let tail = &rest[idx..];
+        if let Some(after) = tail.strip_prefix("{{").or_else(|| tail.strip_prefix("}}")) {
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix('{')
+            && let Some(end) = after.find('}')
+        {
+            if after[..end].trim() == name {
+                return true;
+            }
+            rest = &after[end + 1..];
+        } else {
+            rest = &tail[1..];
+        }
+    }
+    false
+}
+
+/// Renders a template segment, returning `None` when the result is blank.
+///
+/// `{{` and `}}` produce literal braces. Unknown placeholders are kept verbatim
+/// so typos are visible in the status line instead of silently disappearing.
+pub(crate) fn render_template(
+    template: &str,
+    context: &StatusLineTemplateContext,
+) -> Option<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(idx) = rest.find(['{', '}']) {
+        out.push_str(&rest[..idx]);
+        let tail = &rest[idx..];
+        if let Some(after) = tail.strip_prefix("{{") {
+            out.push('{');
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("}}") {
+            out.push('}');
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix('{')
+            && let Some(end) = after.find('}')
+        {
+            let name = after[..end].trim();
+            if TEMPLATE_PLACEHOLDERS.contains(&name) {
+                out.push_str(context.lookup(name).unwrap_or_default());
+            } else {
+                out.push_str(&tail[..end + 2]);
+            }
+            rest = &after[end + 1..];
+        } else {
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+
+    let trimmed = out.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Cached runtime state for one command segment.
+#[derive(Debug, Default)]
+pub(crate) struct StatusLineSegmentState {
+    /// Output of the most recent run, if it succeeded.
+    pub(crate) value: Option<String>,
+    /// When the most recent run was scheduled to start.
+    last_started: Option<Instant>,
+    /// Whether a run is scheduled or in flight.
+    pending: bool,
+}
+
+impl StatusLineSegmentState {
+    /// Returns how long to wait before the next run, or `None` if one is
+    /// already pending.
+    fn next_run_delay(&self, interval: Duration, now: Instant) -> Option<Duration> {
+        if self.pending {
+            return None;
+        }
+        Some(match self.last_started {
+            Some(started) => interval.saturating_sub(now.saturating_duration_since(started)),
+            None => Duration::ZERO,
+        })
+    }
+
+    /// Records a finished run. Failed runs clear the value so stale output is
+    /// not shown indefinitely.
+    pub(crate) fn finish(&mut self, value: Option<String>) {
+        self.value = value;
+        self.pending = false;
+    }
+
+    /// Schedules the next run of `segment` if it is not already pending.
+    pub(crate) fn schedule(
+        &mut self,
+        name: &str,
+        segment: &StatusLineSegment,
+        cwd: PathBuf,
+        app_event_tx: &AppEventSender,
+    ) {
+        let Some(command) = segment.command.clone().filter(|argv| !argv.is_empty()) else {
+            return;
+        };
+        let now = Instant::now();
+        let Some(delay) = self.next_run_delay(segment.refresh_interval(), now) else {
+            return;
+        };
+        self.pending = true;
+        self.last_started = Some(now + delay);
+
+        let name = name.to_string();
+        let timeout = segment.timeout();
+        let tx = app_event_tx.clone();
+        tokio::spawn(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            let value = run_segment_command(&command, &cwd, timeout).await;
+            tx.send(AppEvent::StatusLineSegmentUpdated { name, value });
+        });
+    }
+}
+
+/// Runs a segment command and returns the first non-empty line of stdout.
+///
+/// Non-zero exits, spawn failures and timeouts all yield `None`; the error is
+/// logged rather than surfaced because segments refresh continuously.
+async fn run_segment_command(command: &[String], cwd: &Path, timeout: Duration) -> Option<String> {
+    let (program, args) = command.split_first()?;
+    let child = Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(timeout, child).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => {
+            tracing::debug!(error = %err, ?command, "status line segment failed to start");
+            return None;
+        }
+        Err(_) => {
+            tracing::debug!(?command, ?timeout, "status line segment timed out");
+            return None;
+        }
+    };
+    if !output.status.success() {
+        tracing::debug!(status = %output.status, ?command, "status line segment exited with error");
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn context() -> StatusLineTemplateContext {
+        StatusLineTemplateContext {
+            thread_name: Some("fix-migration".to_string()),
+            total_tokens: Some("27.3K".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renders_known_placeholders() {
+        assert_eq!(
+            render_template("{thread_name} · {total_tokens} tok", &context()),
+            Some("fix-migration · 27.3K tok".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_values_render_empty_and_blank_results_are_omitted() {
+        assert_eq!(
+            render_template("{branch} {thread_name}", &context()),
+            Some("fix-migration".to_string())
+        );
+        assert_eq!(render_template("  {branch} ", &context()), None);
+    }
+
+    #[test]
+    fn unknown_placeholders_and_escapes_are_literal() {
+        assert_eq!(
+            render_template("{{x}} {nope} {", &context()),
+            Some("{x} {nope} {".to_string())
+        );
+    }
+
+    #[test]
+    fn finds_placeholders_outside_escapes() {
+        assert!(template_uses_placeholder("on { branch }", "branch"));
+        assert!(!template_uses_placeholder("{{branch}} {model}", "branch"));
+    }
+
+    #[test]
+    fn next_run_delay_respects_interval_and_pending() {
+        let interval = Duration::from_secs(5);
+        let now = Instant::now();
+        let mut state = StatusLineSegmentState::default();
+        assert_eq!(state.next_run_delay(interval, now), Some(Duration::ZERO));
+
+        state.last_started = Some(now);
+        assert_eq!(
+            state.next_run_delay(interval, now + Duration::from_secs(2)),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            state.next_run_delay(interval, now + Duration::from_secs(9)),
+            Some(Duration::ZERO)
+        );
+
+        state.pending = true;
+        assert_eq!(state.next_run_delay(interval, now), None);
+    }
+}
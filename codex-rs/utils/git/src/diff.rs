@@ -0,0 +1,164 @@
+use std::ffi::OsString;
+use std::path::Path;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_stdout_all;
+
+/// Returns the combined diff of the commits in `base..head`, limited to
+/// `paths` (relative to the repository root) when any are given.
+///
+/// Mirrors `git diff <base>..<head> -- <paths>`.
+pub fn diff_commit_range(
+    repo_path: &Path,
+    base: &str,
+    head: &str,
+    paths: &[String],
+) -> Result<String, GitToolingError> {
+    ensure_revision(base)?;
+    ensure_revision(head)?;
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    run_diff(
+        repo_root.as_path(),
+        OsString::from(format!("{base}..{head}")),
+        paths,
+    )
+}
+
+/// Returns the diff between the working tree (including staged changes) and
+/// `base`, or `HEAD` when `base` is `None`, limited to `paths` (relative to
+/// the repository root) when any are given.
+///
+/// Mirrors `git diff <base> -- <paths>`. Untracked files are not included.
+pub fn diff_working_tree(
+    repo_path: &Path,
+    base: Option<&str>,
+    paths: &[String],
+) -> Result<String, GitToolingError> {
+    let base = base.unwrap_or("HEAD");
+    ensure_revision(base)?;
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    run_diff(repo_root.as_path(), OsString::from(base), paths)
+}
+
+/// Rejects revisions that `git diff` would parse as options, such as
+/// `--output=<file>`.
+fn ensure_revision(revision: &str) -> Result<(), GitToolingError> {
+    if revision.starts_with('-') {
+        return Err(GitToolingError::InvalidRevision {
+            revision: revision.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn run_diff(
+    repo_root: &Path,
+    revision: OsString,
+    paths: &[String],
+) -> Result<String, GitToolingError> {
+    let mut args = vec![
+        OsString::from("diff"),
+        OsString::from("--no-color"),
+        OsString::from("--no-ext-diff"),
+        revision,
+        OsString::from("--"),
+    ];
+    args.extend(paths.iter().map(OsString::from));
+    run_git_for_stdout_all(repo_root, args, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diff_commit_range;
+    use super::diff_working_tree;
+    use crate::GitToolingError;
+    use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn run_git_in(repo_path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    }
+
+    fn commit_all(repo_path: &Path, message: &str) {
+        run_git_in(repo_path, &["add", "-A"]);
+        run_git_in(
+            repo_path,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                message,
+            ],
+        );
+    }
+
+    #[test]
+    fn diffs_commit_range_and_working_tree_limited_to_paths() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path();
+        run_git_in(repo, &["init", "--initial-branch=main"]);
+        run_git_in(repo, &["config", "core.autocrlf", "false"]);
+        std::fs::create_dir(repo.join("crates"))?;
+        std::fs::write(repo.join("crates/foo.txt"), "one\n")?;
+        std::fs::write(repo.join("other.txt"), "one\n")?;
+        commit_all(repo, "base");
+        run_git_in(repo, &["tag", "base"]);
+
+        std::fs::write(repo.join("crates/foo.txt"), "two\n")?;
+        std::fs::write(repo.join("other.txt"), "two\n")?;
+        commit_all(repo, "change both");
+
+        let full = diff_commit_range(repo, "base", "HEAD", &[])?;
+        assert!(full.contains("crates/foo.txt"));
+        assert!(full.contains("other.txt"));
+
+        let scoped = diff_commit_range(repo, "base", "HEAD", &["crates".to_string()])?;
+        assert!(scoped.contains("+two"));
+        assert!(!scoped.contains("other.txt"));
+
+        std::fs::write(repo.join("other.txt"), "three\n")?;
+        let working = diff_working_tree(repo, None, &[])?;
+        assert!(working.contains("+three"));
+        assert!(!working.contains("crates/foo.txt"));
+        assert_eq!(
+            diff_working_tree(repo, None, &["crates".to_string()])?,
+            String::new()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_revisions_that_look_like_options() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path();
+        run_git_in(repo, &["init", "--initial-branch=main"]);
+
+        assert_matches!(
+            diff_commit_range(repo, "--output=/tmp/pwned", "HEAD", &[]),
+            Err(GitToolingError::InvalidRevision { .. })
+        );
+        assert_matches!(
+            diff_working_tree(repo, Some("-p"), &[]),
+            Err(GitToolingError::InvalidRevision { .. })
+        );
+        assert!(!Path::new("/tmp/pwned").exists());
+
+        Ok(())
+    }
+}
@@ -26,6 +26,8 @@ pub enum GitToolingError {
     NonRelativePath { path: PathBuf },
     #[error("path {path:?} escapes the repository root")]
     PathEscapesRepository { path: PathBuf },
+    #[error("revision {revision:?} must not start with `-`")]
+    InvalidRevision { revision: String },
     #[error("failed to process path inside worktree")]
     PathPrefix(#[from] std::path::StripPrefixError),
     #[error(transparent)]
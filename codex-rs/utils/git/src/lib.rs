@@ -3,6 +3,7 @@ use std::path::PathBuf;
 
 mod apply;
 mod branch;
+mod diff;
 mod errors;
 mod ghost_commits;
 mod operations;
@@ -15,6 +16,8 @@ pub use apply::extract_paths_from_patch;
 pub use apply::parse_git_apply_output;
 pub use apply::stage_paths;
 pub use branch::merge_base_with_head;
+pub use diff::diff_commit_range;
+pub use diff::diff_working_tree;
 pub use errors::GitToolingError;
 pub use ghost_commits::CreateGhostCommitOptions;
 pub use ghost_commits::GhostSnapshotConfig;
@@ -1,14 +1,21 @@
-//! Text encoding detection and conversion utilities for shell output.
+//! Text encoding detection and conversion utilities for shell output and source files.
 //!
 //! Windows users frequently run into code pages such as CP1251 or CP866 when invoking commands
 //! through VS Code. Those bytes show up as invalid UTF-8 and used to be replaced with the standard
 //! Unicode replacement character. We now lean on `chardetng` and `encoding_rs` so we can
 //! automatically detect and decode the vast majority of legacy encodings before falling back to
 //! lossy UTF-8 decoding.
+//!
+//! Files are handled more strictly: [`decode_text_file`] only succeeds when the bytes can be
+//! written back unchanged with the returned [`FileEncoding`], so tools that edit files never
+//! silently change their encoding.
 
 use chardetng::EncodingDetector;
 use encoding_rs::Encoding;
 use encoding_rs::IBM866;
+use encoding_rs::UTF_8;
+use encoding_rs::UTF_16BE;
+use encoding_rs::UTF_16LE;
 use encoding_rs::WINDOWS_1252;
 
 /// Attempts to convert arbitrary bytes to UTF-8 with best-effort encoding detection.
@@ -25,6 +32,86 @@ pub fn bytes_to_string_smart(bytes: &[u8]) -> String {
     decode_bytes(bytes, encoding)
 }
 
+/// Encoding of a text file on disk, including whether it starts with a byte order mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileEncoding {
+    encoding: &'static Encoding,
+    bom: bool,
+}
+
+impl FileEncoding {
+    /// UTF-8 without a byte order mark, used for new files.
+    pub const UTF8: Self = Self {
+        encoding: UTF_8,
+        bom: false,
+    };
+
+    /// The WHATWG name of the encoding, e.g. `Shift_JIS`.
+    pub fn name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    /// Encodes `text` in this encoding, returning `None` when it contains characters the
+    /// encoding cannot represent.
+    pub fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(text.len() + 3);
+        if self.encoding == UTF_16LE || self.encoding == UTF_16BE {
+            // encoding_rs only encodes to UTF-8 compatible encodings, so UTF-16 is done by hand.
+            let little_endian = self.encoding == UTF_16LE;
+            let units = std::iter::once(0xFEFF)
+                .filter(|_| self.bom)
+                .chain(text.encode_utf16());
+            for unit in units {
+                if little_endian {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                } else {
+                    bytes.extend_from_slice(&unit.to_be_bytes());
+                }
+            }
+            return Some(bytes);
+        }
+
+        if self.bom {
+            bytes.extend_from_slice(b"\xEF\xBB\xBF");
+        }
+        let (encoded, _, had_unmappable) = self.encoding.encode(text);
+        if had_unmappable {
+            return None;
+        }
+        bytes.extend_from_slice(&encoded);
+        Some(bytes)
+    }
+}
+
+/// Decodes the contents of a text file, detecting its encoding the same way as
+/// [`bytes_to_string_smart`]. Returns `None` for binary content and for bytes that would not
+/// survive a decode/encode round trip, since editing such a file would corrupt it.
+pub fn decode_text_file(bytes: &[u8]) -> Option<(String, FileEncoding)> {
+    let (encoding, bom_len) = match Encoding::for_bom(bytes) {
+        Some((encoding, bom_len)) => (encoding, bom_len),
+        None if bytes.contains(&0) => return None,
+        None if std::str::from_utf8(bytes).is_ok() => (UTF_8, 0),
+        None => (detect_encoding(bytes), 0),
+    };
+    let (decoded, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+    if had_errors {
+        return None;
+    }
+    let file_encoding = FileEncoding {
+        encoding,
+        bom: bom_len > 0,
+    };
+    let decoded = decoded.into_owned();
+    if file_encoding.encode(&decoded).as_deref() != Some(bytes) {
+        return None;
+    }
+    Some((decoded, file_encoding))
+}
+
 // Windows-1252 reassigns a handful of 0x80-0x9F slots to smart punctuation (curly quotes, dashes,
 // ™). CP866 uses those *same byte values* for uppercase Cyrillic letters. When chardetng sees shell
 // snippets that mix these bytes with ASCII it sometimes guesses IBM866, so “smart quotes” render as
@@ -458,4 +545,49 @@ mod tests {
         let result = bytes_to_string_smart(&invalid_bytes);
         assert_eq!(result, String::from_utf8_lossy(&invalid_bytes));
     }
+
+    #[test]
+    fn test_decode_text_file_round_trips_shift_jis() {
+        let text = "こんにちは、世界。これは文字コードのテストです。\r\n日本語のファイルを編集します。\r\n";
+        let (encoded, _, had_errors) = SHIFT_JIS.encode(text);
+        assert!(!had_errors, "failed to encode Shift-JIS sample");
+
+        let (decoded, encoding) = decode_text_file(&encoded).expect("decodes");
+        assert_eq!(decoded, text);
+        assert_eq!(encoding.name(), "Shift_JIS");
+        assert_eq!(encoding.encode(&decoded).as_deref(), Some(encoded.as_ref()));
+    }
+
+    #[test]
+    fn test_decode_text_file_keeps_byte_order_marks() {
+        let (decoded, encoding) = decode_text_file(b"\xEF\xBB\xBFhello\n").expect("decodes");
+        assert_eq!(decoded, "hello\n");
+        assert!(encoding.has_bom());
+        assert_eq!(
+            encoding.encode("bye\n"),
+            Some(b"\xEF\xBB\xBFbye\n".to_vec())
+        );
+
+        let utf16 = b"\xFF\xFEh\0i\0\n\0";
+        let (decoded, encoding) = decode_text_file(utf16).expect("decodes");
+        assert_eq!(decoded, "hi\n");
+        assert_eq!(encoding.name(), "UTF-16LE");
+        assert_eq!(encoding.encode("hi\n"), Some(utf16.to_vec()));
+    }
+
+    #[test]
+    fn test_decode_text_file_rejects_binary_content() {
+        assert_eq!(decode_text_file(b"\x7FELF\x02\x01\x01\0\0\0"), None);
+    }
+
+    #[test]
+    fn test_file_encoding_rejects_unmappable_characters() {
+        let (encoded, _, _) = SHIFT_JIS.encode("日本語のテキストファイルです。");
+        let (_, encoding) = decode_text_file(&encoded).expect("decodes");
+        assert_eq!(encoding.encode("emoji 🎉"), None);
+        assert_eq!(
+            FileEncoding::UTF8.encode("emoji 🎉"),
+            Some("emoji 🎉".as_bytes().to_vec())
+        );
+    }
 }
@@ -0,0 +1,156 @@
+//! BPE tokenizers used for context-window accounting.
+//!
+//! The vocabularies are embedded in the binary (via `tiktoken-rs`) and loaded
+//! lazily on first use, so callers that never ask for a tokenizer do not pay
+//! the initialization cost.
+
+use tiktoken_rs::CoreBPE;
+use tiktoken_rs::cl100k_base_singleton;
+use tiktoken_rs::o200k_base_singleton;
+
+/// Supported BPE vocabularies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingKind {
+    /// Vocabulary used by GPT-4o, o-series and GPT-5 family models.
+    O200kBase,
+    /// Vocabulary used by GPT-4 and GPT-3.5 family models.
+    Cl100kBase,
+}
+
+/// Result of fitting a string into a token budget while keeping its head and
+/// tail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenSplit {
+    /// Token count of the full input.
+    pub total_tokens: usize,
+    /// Byte offset where the kept prefix ends (always a char boundary).
+    pub head_end: usize,
+    /// Byte offset where the kept suffix starts (always a char boundary).
+    pub tail_start: usize,
+}
+
+/// A BPE tokenizer for a specific vocabulary.
+#[derive(Clone, Copy)]
+pub struct Tokenizer {
+    kind: EncodingKind,
+    bpe: &'static CoreBPE,
+}
+
+impl std::fmt::Debug for Tokenizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tokenizer")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Tokenizer {
+    /// Returns the shared tokenizer for `kind`, loading its vocabulary on the
+    /// first call.
+    pub fn get(kind: EncodingKind) -> Self {
+        let bpe = match kind {
+            EncodingKind::O200kBase => o200k_base_singleton(),
+            EncodingKind::Cl100kBase => cl100k_base_singleton(),
+        };
+        Self { kind, bpe }
+    }
+
+    pub fn kind(&self) -> EncodingKind {
+        self.kind
+    }
+
+    /// Encodes `text` as ordinary text (special tokens are not recognized).
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        self.bpe.encode_ordinary(text)
+    }
+
+    /// Returns the number of tokens in `text`.
+    pub fn count(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        self.encode(text).len()
+    }
+
+    /// Computes how to keep the beginning and end of `text` within
+    /// `max_tokens`, splitting the budget evenly between head and tail.
+    ///
+    /// Returns `None` when `text` already fits. Offsets are snapped inward to
+    /// UTF-8 boundaries because a single character may span several tokens.
+    pub fn split_to_budget(&self, text: &str, max_tokens: usize) -> Option<TokenSplit> {
+        let tokens = self.encode(text);
+        let total_tokens = tokens.len();
+        if total_tokens <= max_tokens {
+            return None;
+        }
+
+        let head_tokens = max_tokens / 2;
+        let tail_tokens = max_tokens - head_tokens;
+        let head_bytes = self.byte_len(&tokens[..head_tokens]);
+        let tail_bytes = self.byte_len(&tokens[total_tokens - tail_tokens..]);
+
+        let mut head_end = head_bytes.min(text.len());
+        while !text.is_char_boundary(head_end) {
+            head_end -= 1;
+        }
+        let mut tail_start = text.len().saturating_sub(tail_bytes).max(head_end);
+        while !text.is_char_boundary(tail_start) {
+            tail_start += 1;
+        }
+
+        Some(TokenSplit {
+            total_tokens,
+            head_end,
+            tail_start,
+        })
+    }
+
+    fn byte_len(&self, tokens: &[u32]) -> usize {
+        self.bpe
+            .decode_bytes(tokens)
+            .map(|bytes| bytes.len())
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn counts_ascii_and_cjk_text() {
+        let tokenizer = Tokenizer::get(EncodingKind::O200kBase);
+        assert_eq!(tokenizer.count(""), 0);
+        assert_eq!(tokenizer.count("hello world"), 2);
+        // 27 bytes, which the 4-bytes-per-token heuristic would count as 7 tokens.
+        assert_eq!(tokenizer.count("上下文窗口剩余容量"), 6);
+        assert_eq!(
+            Tokenizer::get(EncodingKind::Cl100kBase).count("上下文窗口剩余容量"),
+            11
+        );
+    }
+
+    #[test]
+    fn split_to_budget_returns_none_when_text_fits() {
+        let tokenizer = Tokenizer::get(EncodingKind::Cl100kBase);
+        assert_eq!(tokenizer.split_to_budget("short text", 10), None);
+    }
+
+    #[test]
+    fn split_to_budget_keeps_head_and_tail_on_char_boundaries() {
+        let tokenizer = Tokenizer::get(EncodingKind::O200kBase);
+        let text = "αβγ ".repeat(50);
+        let split = tokenizer
+            .split_to_budget(&text, 10)
+            .expect("text should exceed budget");
+
+        assert_eq!(split.total_tokens, tokenizer.count(&text));
+        assert!(text.is_char_boundary(split.head_end));
+        assert!(text.is_char_boundary(split.tail_start));
+        assert!(split.head_end <= split.tail_start);
+        let kept =
+            tokenizer.count(&text[..split.head_end]) + tokenizer.count(&text[split.tail_start..]);
+        assert!(kept <= 12, "kept {kept} tokens");
+    }
+}
@@ -37,8 +37,17 @@ export type TurnCompletedEvent = {
 export type TurnFailedEvent = {
   type: "turn.failed";
   error: ThreadError;
+  /** Set when the turn was stopped by a configured limit. */
+  reason?: TurnFailedReason;
 };
 
+/** The limit that stopped a turn. */
+export type TurnFailedReason =
+  | "max_turns"
+  | "max_tool_calls"
+  | "timeout"
+  | "max_consecutive_failures";
+
 /** Emitted when a new item is added to the thread. Typically the item is initially "in progress". */
 export type ItemStartedEvent = {
   type: "item.started";
@@ -4,6 +4,7 @@ export type {
   TurnStartedEvent,
   TurnCompletedEvent,
   TurnFailedEvent,
+  TurnFailedReason,
   ItemStartedEvent,
   ItemUpdatedEvent,
   ItemCompletedEvent,